@@ -0,0 +1,1027 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+#[cfg(feature = "at_rules")]
+use crate::at_rules::at_rule::{AtRule, KeyFrame};
+use crate::calc::CalcNode;
+#[cfg(not(feature = "std"))]
+use crate::float_funcs::FloatFuncs;
+use crate::value::{ComponentValue, Function, Value};
+use crate::Declaration;
+#[cfg(feature = "at_rules")]
+use crate::StyleSheet;
+
+/// An `<easing-function>` value.
+///
+/// <https://www.w3.org/TR/css-easing-2/>
+///
+/// # Example
+///
+/// ```
+/// use simplecss::EasingFunction;
+///
+/// let ease = EasingFunction::parse("ease-in-out").unwrap();
+/// assert!((ease.evaluate(0.5) - 0.5).abs() < 1e-6);
+///
+/// let steps = EasingFunction::parse("steps(4, jump-none)").unwrap();
+/// assert_eq!(steps.evaluate(0.5), 2.0 / 3.0);
+///
+/// let bounce = EasingFunction::parse("linear(0, 1.2 60%, 1)").unwrap();
+/// assert_eq!(bounce.evaluate(0.3), 0.6);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub enum EasingFunction {
+    /// `linear`
+    Linear,
+    /// `cubic-bezier(x1, y1, x2, y2)`, including the `ease` keywords.
+    CubicBezier(f64, f64, f64, f64),
+    /// `steps(count, position)`, including `step-start` and `step-end`.
+    Steps(u32, StepPosition),
+    /// `linear()` with control points.
+    ///
+    /// Points are sorted by input and there are at least two of them.
+    Points(Vec<LinearPoint>),
+}
+
+impl EasingFunction {
+    /// `ease`
+    pub const EASE: EasingFunction = EasingFunction::CubicBezier(0.25, 0.1, 0.25, 1.0);
+
+    /// `ease-in`
+    pub const EASE_IN: EasingFunction = EasingFunction::CubicBezier(0.42, 0.0, 1.0, 1.0);
+
+    /// `ease-out`
+    pub const EASE_OUT: EasingFunction = EasingFunction::CubicBezier(0.0, 0.0, 0.58, 1.0);
+
+    /// `ease-in-out`
+    pub const EASE_IN_OUT: EasingFunction = EasingFunction::CubicBezier(0.42, 0.0, 0.58, 1.0);
+
+    /// Parses an easing function from a property value.
+    pub fn parse(text: &str) -> Option<Self> {
+        let value = Value::parse(text).ok()?;
+        match value.components.as_slice() {
+            [component] => EasingFunction::from_component(component),
+            _ => None,
+        }
+    }
+
+    /// Converts a component value into an easing function.
+    pub fn from_component(component: &ComponentValue<'_>) -> Option<Self> {
+        match component {
+            ComponentValue::Ident(_) => keyword(EASING_KEYWORDS, component),
+            ComponentValue::Function(func) => EasingFunction::from_function(func),
+            _ => None,
+        }
+    }
+
+    fn from_function(func: &Function<'_>) -> Option<Self> {
+        let args: Vec<_> = func.split_commas().collect();
+        if func.name.eq_ignore_ascii_case("cubic-bezier") {
+            let [[x1], [y1], [x2], [y2]] = args.as_slice() else {
+                return None;
+            };
+
+            let (x1, y1, x2, y2) = (number(x1)?, number(y1)?, number(x2)?, number(y2)?);
+            // The curve must be a function of time.
+            ((0.0..=1.0).contains(&x1) && (0.0..=1.0).contains(&x2))
+                .then_some(EasingFunction::CubicBezier(x1, y1, x2, y2))
+        } else if func.name.eq_ignore_ascii_case("steps") {
+            let (count, position) = match args.as_slice() {
+                [[count]] => (count, StepPosition::JumpEnd),
+                [[count], [position]] => (count, keyword(STEP_POSITIONS, position)?),
+                _ => return None,
+            };
+
+            let count = integer(count)?;
+            let min = if position == StepPosition::JumpNone {
+                2
+            } else {
+                1
+            };
+            (count >= min).then_some(EasingFunction::Steps(count, position))
+        } else if func.name.eq_ignore_ascii_case("linear") {
+            linear_points(&args).map(EasingFunction::Points)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the output progress for the input progress `t`.
+    ///
+    /// `t` is usually in the 0..=1 range, but can be outside of it,
+    /// for example while a `cubic-bezier()` easing overshoots.
+    pub fn evaluate(&self, t: f64) -> f64 {
+        match self {
+            EasingFunction::Linear => t,
+            EasingFunction::CubicBezier(x1, y1, x2, y2) => cubic_bezier(*x1, *y1, *x2, *y2, t),
+            EasingFunction::Steps(count, position) => steps(*count, *position, t),
+            EasingFunction::Points(points) => interpolate_points(points, t),
+        }
+    }
+}
+
+impl Default for EasingFunction {
+    fn default() -> Self {
+        EasingFunction::EASE
+    }
+}
+
+static EASING_KEYWORDS: &[(&str, EasingFunction)] = &[
+    ("linear", EasingFunction::Linear),
+    ("ease", EasingFunction::EASE),
+    ("ease-in", EasingFunction::EASE_IN),
+    ("ease-out", EasingFunction::EASE_OUT),
+    ("ease-in-out", EasingFunction::EASE_IN_OUT),
+    (
+        "step-start",
+        EasingFunction::Steps(1, StepPosition::JumpStart),
+    ),
+    ("step-end", EasingFunction::Steps(1, StepPosition::JumpEnd)),
+];
+
+/// A `steps()` jump position.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum StepPosition {
+    /// `jump-start` or `start`
+    JumpStart,
+    /// `jump-end` or `end`
+    #[default]
+    JumpEnd,
+    /// `jump-none`
+    JumpNone,
+    /// `jump-both`
+    JumpBoth,
+}
+
+static STEP_POSITIONS: &[(&str, StepPosition)] = &[
+    ("jump-start", StepPosition::JumpStart),
+    ("jump-end", StepPosition::JumpEnd),
+    ("jump-none", StepPosition::JumpNone),
+    ("jump-both", StepPosition::JumpBoth),
+    ("start", StepPosition::JumpStart),
+    ("end", StepPosition::JumpEnd),
+];
+
+/// A `linear()` control point.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct LinearPoint {
+    /// The input progress, where `1` is `100%`.
+    pub input: f64,
+    /// The output progress.
+    pub output: f64,
+}
+
+/// Parses `linear()` stops into control points.
+///
+/// <https://www.w3.org/TR/css-easing-2/#linear-easing-function-parsing>
+fn linear_points(stops: &[&[ComponentValue<'_>]]) -> Option<Vec<LinearPoint>> {
+    let mut points: Vec<(f64, Option<f64>)> = Vec::new();
+    for stop in stops {
+        // `<number> && <percentage>{0,2}`
+        let (output, inputs) = match stop {
+            [ComponentValue::Percentage(_), ..] => stop.split_last()?,
+            _ => stop.split_first()?,
+        };
+
+        let output = number(output)?;
+        match inputs {
+            [] => points.push((output, None)),
+            [ComponentValue::Percentage(input)] => points.push((output, Some(input / 100.0))),
+            [ComponentValue::Percentage(start), ComponentValue::Percentage(end)] => {
+                points.push((output, Some(start / 100.0)));
+                points.push((output, Some(end / 100.0)));
+            }
+            _ => return None,
+        }
+    }
+
+    if points.len() < 2 {
+        return None;
+    }
+
+    // The first and the last points default to 0% and 100%.
+    if let Some((_, input @ None)) = points.first_mut() {
+        *input = Some(0.0);
+    }
+    if let Some((_, input @ None)) = points.last_mut() {
+        *input = Some(1.0);
+    }
+
+    // Inputs cannot decrease.
+    let mut largest = f64::NEG_INFINITY;
+    for (_, input) in &mut points {
+        if let Some(input) = input {
+            largest = largest.max(*input);
+            *input = largest;
+        }
+    }
+
+    // Missing inputs are spread evenly between the neighbours.
+    let mut start = 0;
+    for end in 1..points.len() {
+        let (Some(from), Some(to)) = (points[start].1, points[end].1) else {
+            continue;
+        };
+
+        let count = (end - start) as f64;
+        for (i, (_, input)) in points[start + 1..end].iter_mut().enumerate() {
+            *input = Some(from + (to - from) * (i + 1) as f64 / count);
+        }
+        start = end;
+    }
+
+    Some(
+        points
+            .into_iter()
+            .map(|(output, input)| LinearPoint {
+                input: input.unwrap_or_default(),
+                output,
+            })
+            .collect(),
+    )
+}
+
+fn interpolate_points(points: &[LinearPoint], t: f64) -> f64 {
+    // Progress outside of the points is extrapolated from the first or the last two points.
+    let (a, b) = match points {
+        [] => return t,
+        [point] => return point.output,
+        [first, second, ..] if t < first.input => (first, second),
+        [.., a, b] if t >= b.input => (a, b),
+        _ => match points.windows(2).rev().find(|pair| pair[0].input <= t) {
+            Some([a, b]) => (a, b),
+            _ => return t,
+        },
+    };
+
+    if b.input <= a.input {
+        return b.output;
+    }
+
+    a.output + (b.output - a.output) * (t - a.input) / (b.input - a.input)
+}
+
+/// <https://www.w3.org/TR/css-easing-2/#cubic-bezier-algo>
+fn cubic_bezier(x1: f64, y1: f64, x2: f64, y2: f64, t: f64) -> f64 {
+    // Outside of the 0..=1 range the curve continues along its end tangents.
+    if t < 0.0 {
+        let slope = if x1 > 0.0 {
+            y1 / x1
+        } else if y1 == 0.0 && x2 > 0.0 {
+            y2 / x2
+        } else {
+            0.0
+        };
+        return slope * t;
+    }
+
+    if t > 1.0 {
+        let slope = if x2 < 1.0 {
+            (y2 - 1.0) / (x2 - 1.0)
+        } else if y2 == 1.0 && x1 < 1.0 {
+            (y1 - 1.0) / (x1 - 1.0)
+        } else {
+            0.0
+        };
+        return 1.0 + slope * (t - 1.0);
+    }
+
+    bezier(y1, y2, solve_bezier(x1, x2, t))
+}
+
+/// Evaluates a 1D cubic Bézier curve from `0` to `1` with the control points `p1` and `p2`.
+fn bezier(p1: f64, p2: f64, s: f64) -> f64 {
+    let r = 1.0 - s;
+    3.0 * r * r * s * p1 + 3.0 * r * s * s * p2 + s * s * s
+}
+
+fn bezier_derivative(p1: f64, p2: f64, s: f64) -> f64 {
+    let r = 1.0 - s;
+    3.0 * r * r * p1 + 6.0 * r * s * (p2 - p1) + 3.0 * s * s * (1.0 - p2)
+}
+
+/// Finds the curve parameter for `x`, using the Newton's method with a bisection fallback.
+fn solve_bezier(p1: f64, p2: f64, x: f64) -> f64 {
+    const EPSILON: f64 = 1e-9;
+
+    let mut s = x;
+    for _ in 0..8 {
+        let error = bezier(p1, p2, s) - x;
+        if abs(error) < EPSILON {
+            return s;
+        }
+
+        let derivative = bezier_derivative(p1, p2, s);
+        if abs(derivative) < 1e-6 {
+            break;
+        }
+        s -= error / derivative;
+    }
+
+    let (mut low, mut high) = (0.0, 1.0);
+    s = x;
+    for _ in 0..64 {
+        let value = bezier(p1, p2, s);
+        if abs(value - x) < EPSILON {
+            break;
+        }
+
+        if value < x {
+            low = s;
+        } else {
+            high = s;
+        }
+        s = (low + high) / 2.0;
+    }
+
+    s
+}
+
+/// <https://www.w3.org/TR/css-easing-2/#step-easing-algo>
+fn steps(count: u32, position: StepPosition, t: f64) -> f64 {
+    let count = f64::from(count);
+    let mut step = (t * count).floor();
+    if matches!(position, StepPosition::JumpStart | StepPosition::JumpBoth) {
+        step += 1.0;
+    }
+
+    let jumps = match position {
+        StepPosition::JumpBoth => count + 1.0,
+        StepPosition::JumpNone => count - 1.0,
+        StepPosition::JumpStart | StepPosition::JumpEnd => count,
+    };
+
+    if t >= 0.0 && step < 0.0 {
+        step = 0.0;
+    }
+    if t <= 1.0 && step > jumps {
+        step = jumps;
+    }
+
+    step / jumps
+}
+
+/// An `animation-iteration-count` value.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum IterationCount {
+    /// A non-negative number of iterations.
+    Count(f64),
+    /// `infinite`
+    Infinite,
+}
+
+impl IterationCount {
+    /// Converts a component value into an iteration count.
+    pub fn from_component(component: &ComponentValue<'_>) -> Option<Self> {
+        if component.is_ident("infinite") {
+            return Some(IterationCount::Infinite);
+        }
+
+        let count = number(component)?;
+        (count >= 0.0).then_some(IterationCount::Count(count))
+    }
+}
+
+impl Default for IterationCount {
+    fn default() -> Self {
+        IterationCount::Count(1.0)
+    }
+}
+
+/// An `animation-direction` value.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum AnimationDirection {
+    /// `normal`
+    #[default]
+    Normal,
+    /// `reverse`
+    Reverse,
+    /// `alternate`
+    Alternate,
+    /// `alternate-reverse`
+    AlternateReverse,
+}
+
+impl AnimationDirection {
+    /// Converts a component value into an animation direction.
+    pub fn from_component(component: &ComponentValue<'_>) -> Option<Self> {
+        keyword(DIRECTIONS, component)
+    }
+}
+
+static DIRECTIONS: &[(&str, AnimationDirection)] = &[
+    ("normal", AnimationDirection::Normal),
+    ("reverse", AnimationDirection::Reverse),
+    ("alternate", AnimationDirection::Alternate),
+    ("alternate-reverse", AnimationDirection::AlternateReverse),
+];
+
+/// An `animation-fill-mode` value.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum FillMode {
+    /// `none`
+    #[default]
+    None,
+    /// `forwards`
+    Forwards,
+    /// `backwards`
+    Backwards,
+    /// `both`
+    Both,
+}
+
+impl FillMode {
+    /// Converts a component value into a fill mode.
+    pub fn from_component(component: &ComponentValue<'_>) -> Option<Self> {
+        keyword(FILL_MODES, component)
+    }
+}
+
+static FILL_MODES: &[(&str, FillMode)] = &[
+    ("none", FillMode::None),
+    ("forwards", FillMode::Forwards),
+    ("backwards", FillMode::Backwards),
+    ("both", FillMode::Both),
+];
+
+/// An `animation-play-state` value.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum PlayState {
+    /// `running`
+    #[default]
+    Running,
+    /// `paused`
+    Paused,
+}
+
+impl PlayState {
+    /// Converts a component value into a play state.
+    pub fn from_component(component: &ComponentValue<'_>) -> Option<Self> {
+        keyword(PLAY_STATES, component)
+    }
+}
+
+static PLAY_STATES: &[(&str, PlayState)] = &[
+    ("running", PlayState::Running),
+    ("paused", PlayState::Paused),
+];
+
+/// A single animation, ie. one layer of the `animation` property.
+///
+/// # Example
+///
+/// ```
+/// use simplecss::{Animation, EasingFunction, FillMode, IterationCount};
+///
+/// let list = Animation::parse_list("spin 1s linear infinite, fade 200ms both").unwrap();
+/// assert_eq!(list[0].name.as_deref(), Some("spin"));
+/// assert_eq!(list[0].timing_function, EasingFunction::Linear);
+/// assert_eq!(list[0].iteration_count, IterationCount::Infinite);
+/// assert_eq!(list[1].duration, 0.2);
+/// assert_eq!(list[1].fill_mode, FillMode::Both);
+/// ```
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Animation<'a> {
+    /// The `@keyframes` name, `None` for `none`.
+    pub name: Option<Cow<'a, str>>,
+    /// The duration, in seconds. `auto` is `0`.
+    pub duration: f64,
+    /// The easing function.
+    pub timing_function: EasingFunction,
+    /// The delay, in seconds. Can be negative.
+    pub delay: f64,
+    /// The number of iterations.
+    pub iteration_count: IterationCount,
+    /// The playback direction.
+    pub direction: AnimationDirection,
+    /// The fill mode.
+    pub fill_mode: FillMode,
+    /// The play state.
+    pub play_state: PlayState,
+}
+
+impl<'a> Animation<'a> {
+    /// Parses an `animation` shorthand value.
+    ///
+    /// CSS-wide keywords, like `inherit`, are not supported.
+    pub fn parse_list(text: &'a str) -> Option<Vec<Self>> {
+        let value = Value::parse(text).ok()?;
+        Animation::from_components(&value.components)
+    }
+
+    /// Converts component values into an `animation` shorthand value.
+    pub fn from_components(components: &[ComponentValue<'a>]) -> Option<Vec<Self>> {
+        components
+            .split(|c| *c == ComponentValue::Comma)
+            .map(Animation::from_layer)
+            .collect()
+    }
+
+    fn from_layer(layer: &[ComponentValue<'a>]) -> Option<Self> {
+        if layer.is_empty() {
+            return None;
+        }
+
+        let mut name = None;
+        let mut duration = None;
+        let mut timing_function = None;
+        let mut delay = None;
+        let mut iteration_count = None;
+        let mut direction = None;
+        let mut fill_mode = None;
+        let mut play_state = None;
+
+        // Keywords of other properties take precedence over the name.
+        for c in layer {
+            if let Some(seconds) = time(c).filter(|_| duration.is_none() || delay.is_none()) {
+                if duration.is_none() {
+                    duration = Some(seconds).filter(|s| *s >= 0.0);
+                    duration?;
+                } else {
+                    delay = Some(seconds);
+                }
+            } else if duration.is_none() && c.is_ident("auto") {
+                duration = Some(0.0);
+            } else if let Some(easing) =
+                EasingFunction::from_component(c).filter(|_| timing_function.is_none())
+            {
+                timing_function = Some(easing);
+            } else if let Some(count) =
+                IterationCount::from_component(c).filter(|_| iteration_count.is_none())
+            {
+                iteration_count = Some(count);
+            } else if let Some(value) =
+                AnimationDirection::from_component(c).filter(|_| direction.is_none())
+            {
+                direction = Some(value);
+            } else if let Some(value) = FillMode::from_component(c).filter(|_| fill_mode.is_none())
+            {
+                fill_mode = Some(value);
+            } else if let Some(value) =
+                PlayState::from_component(c).filter(|_| play_state.is_none())
+            {
+                play_state = Some(value);
+            } else if name.is_none() {
+                name = Some(keyframes_name(c)?);
+            } else {
+                return None;
+            }
+        }
+
+        Some(Animation {
+            name: name.unwrap_or_default(),
+            duration: duration.unwrap_or_default(),
+            timing_function: timing_function.unwrap_or_default(),
+            delay: delay.unwrap_or_default(),
+            iteration_count: iteration_count.unwrap_or_default(),
+            direction: direction.unwrap_or_default(),
+            fill_mode: fill_mode.unwrap_or_default(),
+            play_state: play_state.unwrap_or_default(),
+        })
+    }
+
+    /// Collects animations from the `animation` and `animation-*` declarations of a rule.
+    ///
+    /// Declarations are applied in order, with `!important` ones last,
+    /// and invalid values are ignored. The number of animations is defined by `animation-name`,
+    /// other longhand lists are repeated or truncated to match it.
+    /// Animations named `none` are kept, so that indices match the declared lists.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use simplecss::{Animation, Declaration, DeclarationTokenizer, PlayState};
+    ///
+    /// let declarations: Vec<Declaration> =
+    ///     DeclarationTokenizer::from("animation: a 1s, b 2s; animation-play-state: paused").collect();
+    /// let list = Animation::from_declarations(&declarations);
+    /// assert_eq!(list.len(), 2);
+    /// assert_eq!(list[1].duration, 2.0);
+    /// assert_eq!(list[1].play_state, PlayState::Paused);
+    /// ```
+    pub fn from_declarations(declarations: &[Declaration<'a>]) -> Vec<Self> {
+        let mut lists = AnimationLists::default();
+        for important in [false, true] {
+            for declaration in declarations.iter().filter(|d| d.important == important) {
+                // Invalid declarations are ignored.
+                let _ = lists.apply(&declaration.name, declaration.value);
+            }
+        }
+
+        lists
+            .names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| Animation {
+                name: name.clone(),
+                duration: repeat(&lists.durations, i),
+                timing_function: repeat(&lists.timing_functions, i),
+                delay: repeat(&lists.delays, i),
+                iteration_count: repeat(&lists.iteration_counts, i),
+                direction: repeat(&lists.directions, i),
+                fill_mode: repeat(&lists.fill_modes, i),
+                play_state: repeat(&lists.play_states, i),
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "at_rules")]
+impl Animation<'_> {
+    /// Finds the `@keyframes` rule referenced by the animation name.
+    ///
+    /// Names are case-sensitive. When there are multiple rules with the same name,
+    /// the last one is used.
+    pub fn keyframes<'s, 'b>(&self, style: &'s StyleSheet<'b>) -> Option<&'s [KeyFrame<'b>]> {
+        let name = self.name.as_deref()?;
+        style.at_rules.iter().rev().find_map(|rule| match rule {
+            AtRule::Keyframes {
+                name: rule_name,
+                frames,
+                ..
+            } if rule_name == name => Some(frames.as_slice()),
+            _ => None,
+        })
+    }
+}
+
+/// `animation-*` longhand lists, before they are combined.
+struct AnimationLists<'a> {
+    names: Vec<Option<Cow<'a, str>>>,
+    durations: Vec<f64>,
+    timing_functions: Vec<EasingFunction>,
+    delays: Vec<f64>,
+    iteration_counts: Vec<IterationCount>,
+    directions: Vec<AnimationDirection>,
+    fill_modes: Vec<FillMode>,
+    play_states: Vec<PlayState>,
+}
+
+impl Default for AnimationLists<'_> {
+    fn default() -> Self {
+        AnimationLists::from_animations(&[Animation::default()])
+    }
+}
+
+impl<'a> AnimationLists<'a> {
+    fn from_animations(list: &[Animation<'a>]) -> Self {
+        AnimationLists {
+            names: list.iter().map(|a| a.name.clone()).collect(),
+            durations: list.iter().map(|a| a.duration).collect(),
+            timing_functions: list.iter().map(|a| a.timing_function.clone()).collect(),
+            delays: list.iter().map(|a| a.delay).collect(),
+            iteration_counts: list.iter().map(|a| a.iteration_count).collect(),
+            directions: list.iter().map(|a| a.direction).collect(),
+            fill_modes: list.iter().map(|a| a.fill_mode).collect(),
+            play_states: list.iter().map(|a| a.play_state).collect(),
+        }
+    }
+
+    fn apply(&mut self, name: &str, value: &'a str) -> Option<()> {
+        match name.to_ascii_lowercase().as_str() {
+            "animation" => *self = AnimationLists::from_animations(&Animation::parse_list(value)?),
+            "animation-name" => self.names = parse_list(value, keyframes_name)?,
+            "animation-duration" => {
+                self.durations = parse_list(value, |c| {
+                    if c.is_ident("auto") {
+                        Some(0.0)
+                    } else {
+                        time(c).filter(|seconds| *seconds >= 0.0)
+                    }
+                })?;
+            }
+            "animation-timing-function" => {
+                self.timing_functions = parse_list(value, EasingFunction::from_component)?;
+            }
+            "animation-delay" => self.delays = parse_list(value, time)?,
+            "animation-iteration-count" => {
+                self.iteration_counts = parse_list(value, IterationCount::from_component)?;
+            }
+            "animation-direction" => {
+                self.directions = parse_list(value, AnimationDirection::from_component)?;
+            }
+            "animation-fill-mode" => {
+                self.fill_modes = parse_list(value, FillMode::from_component)?;
+            }
+            "animation-play-state" => {
+                self.play_states = parse_list(value, PlayState::from_component)?;
+            }
+            _ => {}
+        }
+
+        Some(())
+    }
+}
+
+/// A `transition-property` value.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub enum TransitionProperty<'a> {
+    /// `all`
+    #[default]
+    All,
+    /// `none`
+    None,
+    /// A property name.
+    Name(Cow<'a, str>),
+}
+
+impl TransitionProperty<'_> {
+    /// Checks that the transition applies to a property, ignoring ASCII case.
+    pub fn matches(&self, property: &str) -> bool {
+        match self {
+            TransitionProperty::All => true,
+            TransitionProperty::None => false,
+            TransitionProperty::Name(name) => name.eq_ignore_ascii_case(property),
+        }
+    }
+}
+
+/// A single transition, ie. one layer of the `transition` property.
+///
+/// # Example
+///
+/// ```
+/// use simplecss::{EasingFunction, Transition, TransitionProperty};
+///
+/// let list = Transition::parse_list("opacity 0.3s ease-in, transform 1s 50ms").unwrap();
+/// assert_eq!(list[0].property, TransitionProperty::Name("opacity".into()));
+/// assert_eq!(list[0].timing_function, EasingFunction::EASE_IN);
+/// assert_eq!(list[1].delay, 0.05);
+/// ```
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Transition<'a> {
+    /// The transitioned property.
+    pub property: TransitionProperty<'a>,
+    /// The duration, in seconds.
+    pub duration: f64,
+    /// The easing function.
+    pub timing_function: EasingFunction,
+    /// The delay, in seconds. Can be negative.
+    pub delay: f64,
+    /// Checks that `transition-behavior` is `allow-discrete`.
+    pub allow_discrete: bool,
+}
+
+impl<'a> Transition<'a> {
+    /// Parses a `transition` shorthand value.
+    ///
+    /// CSS-wide keywords, like `inherit`, are not supported.
+    pub fn parse_list(text: &'a str) -> Option<Vec<Self>> {
+        let value = Value::parse(text).ok()?;
+        Transition::from_components(&value.components)
+    }
+
+    /// Converts component values into a `transition` shorthand value.
+    pub fn from_components(components: &[ComponentValue<'a>]) -> Option<Vec<Self>> {
+        let list: Vec<_> = components
+            .split(|c| *c == ComponentValue::Comma)
+            .map(Transition::from_layer)
+            .collect::<Option<_>>()?;
+
+        // `none` is allowed only on its own.
+        if list.len() > 1 && list.iter().any(|t| t.property == TransitionProperty::None) {
+            return None;
+        }
+
+        Some(list)
+    }
+
+    fn from_layer(layer: &[ComponentValue<'a>]) -> Option<Self> {
+        if layer.is_empty() {
+            return None;
+        }
+
+        let mut property = None;
+        let mut duration = None;
+        let mut timing_function = None;
+        let mut delay = None;
+        let mut allow_discrete = None;
+        for c in layer {
+            if let Some(seconds) = time(c).filter(|_| duration.is_none() || delay.is_none()) {
+                if duration.is_none() {
+                    duration = Some(seconds).filter(|s| *s >= 0.0);
+                    duration?;
+                } else {
+                    delay = Some(seconds);
+                }
+            } else if let Some(easing) =
+                EasingFunction::from_component(c).filter(|_| timing_function.is_none())
+            {
+                timing_function = Some(easing);
+            } else if let Some(value) = transition_behavior(c).filter(|_| allow_discrete.is_none())
+            {
+                allow_discrete = Some(value);
+            } else if property.is_none() {
+                property = Some(transition_property(c)?);
+            } else {
+                return None;
+            }
+        }
+
+        Some(Transition {
+            property: property.unwrap_or_default(),
+            duration: duration.unwrap_or_default(),
+            timing_function: timing_function.unwrap_or_default(),
+            delay: delay.unwrap_or_default(),
+            allow_discrete: allow_discrete.unwrap_or_default(),
+        })
+    }
+
+    /// Collects transitions from the `transition` and `transition-*` declarations of a rule.
+    ///
+    /// Declarations are applied in order, with `!important` ones last,
+    /// and invalid values are ignored. The number of transitions is defined
+    /// by `transition-property`, other longhand lists are repeated or truncated to match it.
+    /// Transitions with the `none` property are skipped.
+    pub fn from_declarations(declarations: &[Declaration<'a>]) -> Vec<Self> {
+        let mut lists = TransitionLists::default();
+        for important in [false, true] {
+            for declaration in declarations.iter().filter(|d| d.important == important) {
+                // Invalid declarations are ignored.
+                let _ = lists.apply(&declaration.name, declaration.value);
+            }
+        }
+
+        lists
+            .properties
+            .iter()
+            .enumerate()
+            .filter(|(_, property)| **property != TransitionProperty::None)
+            .map(|(i, property)| Transition {
+                property: property.clone(),
+                duration: repeat(&lists.durations, i),
+                timing_function: repeat(&lists.timing_functions, i),
+                delay: repeat(&lists.delays, i),
+                allow_discrete: repeat(&lists.behaviors, i),
+            })
+            .collect()
+    }
+}
+
+/// `transition-*` longhand lists, before they are combined.
+struct TransitionLists<'a> {
+    properties: Vec<TransitionProperty<'a>>,
+    durations: Vec<f64>,
+    timing_functions: Vec<EasingFunction>,
+    delays: Vec<f64>,
+    behaviors: Vec<bool>,
+}
+
+impl Default for TransitionLists<'_> {
+    fn default() -> Self {
+        TransitionLists::from_transitions(&[Transition::default()])
+    }
+}
+
+impl<'a> TransitionLists<'a> {
+    fn from_transitions(list: &[Transition<'a>]) -> Self {
+        TransitionLists {
+            properties: list.iter().map(|t| t.property.clone()).collect(),
+            durations: list.iter().map(|t| t.duration).collect(),
+            timing_functions: list.iter().map(|t| t.timing_function.clone()).collect(),
+            delays: list.iter().map(|t| t.delay).collect(),
+            behaviors: list.iter().map(|t| t.allow_discrete).collect(),
+        }
+    }
+
+    fn apply(&mut self, name: &str, value: &'a str) -> Option<()> {
+        match name.to_ascii_lowercase().as_str() {
+            "transition" => {
+                *self = TransitionLists::from_transitions(&Transition::parse_list(value)?);
+            }
+            "transition-property" => {
+                let list = parse_list(value, transition_property)?;
+                if list.len() > 1 && list.contains(&TransitionProperty::None) {
+                    return None;
+                }
+                self.properties = list;
+            }
+            "transition-duration" => {
+                self.durations = parse_list(value, |c| time(c).filter(|seconds| *seconds >= 0.0))?;
+            }
+            "transition-timing-function" => {
+                self.timing_functions = parse_list(value, EasingFunction::from_component)?;
+            }
+            "transition-delay" => self.delays = parse_list(value, time)?,
+            "transition-behavior" => self.behaviors = parse_list(value, transition_behavior)?,
+            _ => {}
+        }
+
+        Some(())
+    }
+}
+
+fn transition_property<'a>(component: &ComponentValue<'a>) -> Option<TransitionProperty<'a>> {
+    let ComponentValue::Ident(name) = component else {
+        return None;
+    };
+
+    if name.eq_ignore_ascii_case("all") {
+        Some(TransitionProperty::All)
+    } else if name.eq_ignore_ascii_case("none") {
+        Some(TransitionProperty::None)
+    } else if is_keyword(name, RESERVED_NAMES) {
+        None
+    } else {
+        Some(TransitionProperty::Name(name.clone()))
+    }
+}
+
+/// Parses `transition-behavior` into the `allow-discrete` flag.
+fn transition_behavior(component: &ComponentValue<'_>) -> Option<bool> {
+    if component.is_ident("normal") {
+        Some(false)
+    } else if component.is_ident("allow-discrete") {
+        Some(true)
+    } else {
+        None
+    }
+}
+
+/// Parses `<keyframes-name> | none`.
+fn keyframes_name<'a>(component: &ComponentValue<'a>) -> Option<Option<Cow<'a, str>>> {
+    match component {
+        ComponentValue::Ident(name) if name.eq_ignore_ascii_case("none") => Some(None),
+        ComponentValue::Ident(name) if is_keyword(name, RESERVED_NAMES) => None,
+        ComponentValue::Ident(name) | ComponentValue::String(name) => Some(Some(name.clone())),
+        _ => None,
+    }
+}
+
+/// CSS-wide keywords, which cannot be used as names.
+const RESERVED_NAMES: &[&str] = &[
+    "inherit",
+    "initial",
+    "unset",
+    "revert",
+    "revert-layer",
+    "default",
+];
+
+/// Parses a comma separated list, where each item is a single component.
+fn parse_list<'a, T, F>(text: &'a str, parse: F) -> Option<Vec<T>>
+where
+    F: Fn(&ComponentValue<'a>) -> Option<T>,
+{
+    let value = Value::parse(text).ok()?;
+    value
+        .split_commas()
+        .map(|item| match item {
+            [component] => parse(component),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the list item for the `index`-th layer, repeating the list when needed.
+fn repeat<T: Clone + Default>(list: &[T], index: usize) -> T {
+    list.iter().cycle().nth(index).cloned().unwrap_or_default()
+}
+
+/// Parses a `<time>`, in seconds.
+fn time(component: &ComponentValue<'_>) -> Option<f64> {
+    match component {
+        ComponentValue::Dimension(n, unit) if unit.eq_ignore_ascii_case("s") => Some(*n),
+        ComponentValue::Dimension(n, unit) if unit.eq_ignore_ascii_case("ms") => Some(n / 1000.0),
+        ComponentValue::Function(func) => CalcNode::from_function(func)?.to_seconds(),
+        _ => None,
+    }
+}
+
+fn number(component: &ComponentValue<'_>) -> Option<f64> {
+    match component {
+        ComponentValue::Number(n) => Some(*n),
+        ComponentValue::Function(func) => CalcNode::from_function(func)?.to_number(),
+        _ => None,
+    }
+}
+
+/// Parses a non-negative integer.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn integer(component: &ComponentValue<'_>) -> Option<u32> {
+    let n = number(component)?;
+    (n >= 0.0 && n <= f64::from(u32::MAX) && n == (n as u32).into()).then_some(n as u32)
+}
+
+fn keyword<T: Clone>(table: &[(&str, T)], component: &ComponentValue<'_>) -> Option<T> {
+    let name = component.as_ident()?;
+    table
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.clone())
+}
+
+fn is_keyword(name: &str, keywords: &[&str]) -> bool {
+    keywords.iter().any(|k| k.eq_ignore_ascii_case(name))
+}
+
+fn abs(value: f64) -> f64 {
+    if value < 0.0 {
+        -value
+    } else {
+        value
+    }
+}
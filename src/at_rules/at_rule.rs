@@ -1,23 +1,26 @@
-use crate::{
-    Declaration, Error,
-    Rule, Stream,
-};
+use super::{container::Container, import::Import, media::Media, supports::Supports};
+use crate::line_index::LazyLineIndex;
+use crate::stream::{write_ident, write_string};
+use crate::{Declaration, Error, Rule, Span, Stream};
+use alloc::borrow::Cow;
+use alloc::string::String;
 use alloc::vec::Vec;
-use super::{
-    container::Container,
-    import::Import,
-    media::Media,
-    supports::Supports,
-};
+use core::fmt::{self, Write};
 
 /// An @ rule
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 pub enum AtRule<'a> {
     /// An @container rule
     Container(Container<'a>),
 
     /// An @font-face rule
-    FontFace(Vec<Declaration<'a>>),
+    FontFace {
+        /// The list of declarations inside the body
+        declarations: Vec<Declaration<'a>>,
+
+        /// The source location
+        span: AtRuleSpan,
+    },
 
     /// An @import rule.
     Import(Import<'a>),
@@ -25,14 +28,23 @@ pub enum AtRule<'a> {
     /// An @keyframes rule.
     Keyframes {
         /// Animation name
-        name: &'a str,
+        name: Cow<'a, str>,
 
         /// Frames in the animation
         frames: Vec<KeyFrame<'a>>,
+
+        /// The source location
+        span: AtRuleSpan,
     },
 
     /// An @layer rule
-    Layer(LayerType<'a>),
+    Layer {
+        /// The layer statement or block
+        layer: LayerType<'a>,
+
+        /// The source location
+        span: AtRuleSpan,
+    },
 
     /// An @media rule
     Media(Media<'a>),
@@ -40,10 +52,13 @@ pub enum AtRule<'a> {
     /// An @namespace rule
     Namespace {
         /// The name of the namespace, None if default
-        name: Option<&'a str>,
+        name: Option<Cow<'a, str>>,
 
         /// The source of the namespace
-        value: &'a str,
+        value: Cow<'a, str>,
+
+        /// The source location
+        span: AtRuleSpan,
     },
 
     /// An @supports rule
@@ -52,27 +67,241 @@ pub enum AtRule<'a> {
     /// Some other, unparsed at rule
     Other {
         /// The identity of the @rule
-        /// 
+        ///
         /// ie. "keyframes" in "@keyframes anim-name {...}"
-        ident: &'a str,
+        ident: Cow<'a, str>,
 
         /// Any data before a block begins
-        /// 
+        ///
         /// ie the "anim-name" in "@keyframes anim-name {...}"
         pre_block: &'a str,
 
         /// The body of the @ rule if it was a block
-        /// 
+        ///
         /// ie. "..." in "@keyframes anim-name {...}"
         block: &'a str,
+
+        /// The source location
+        span: AtRuleSpan,
     },
 }
+
+impl PartialEq for AtRule<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        // Spans are ignored.
+        match (self, other) {
+            (Self::Container(a), Self::Container(b)) => a == b,
+            (
+                Self::FontFace {
+                    declarations: a, ..
+                },
+                Self::FontFace {
+                    declarations: b, ..
+                },
+            ) => a == b,
+            (Self::Import(a), Self::Import(b)) => a == b,
+            (
+                Self::Keyframes {
+                    name: name1,
+                    frames: frames1,
+                    ..
+                },
+                Self::Keyframes {
+                    name: name2,
+                    frames: frames2,
+                    ..
+                },
+            ) => name1 == name2 && frames1 == frames2,
+            (Self::Layer { layer: a, .. }, Self::Layer { layer: b, .. }) => a == b,
+            (Self::Media(a), Self::Media(b)) => a == b,
+            (
+                Self::Namespace {
+                    name: name1,
+                    value: value1,
+                    ..
+                },
+                Self::Namespace {
+                    name: name2,
+                    value: value2,
+                    ..
+                },
+            ) => name1 == name2 && value1 == value2,
+            (Self::Supports(a), Self::Supports(b)) => a == b,
+            (
+                Self::Other {
+                    ident: ident1,
+                    pre_block: pre_block1,
+                    block: block1,
+                    ..
+                },
+                Self::Other {
+                    ident: ident2,
+                    pre_block: pre_block2,
+                    block: block2,
+                    ..
+                },
+            ) => ident1 == ident2 && pre_block1 == pre_block2 && block1 == block2,
+            _ => false,
+        }
+    }
+}
+
 impl<'a> AtRule<'a> {
-    pub(crate) fn consume(s: &mut Stream<'a>) -> Result<Self, Error> {
+    /// Returns the source location of the rule.
+    pub fn span(&self) -> AtRuleSpan {
+        match self {
+            Self::Container(Container { span, .. })
+            | Self::FontFace { span, .. }
+            | Self::Import(Import { span, .. })
+            | Self::Keyframes { span, .. }
+            | Self::Layer { span, .. }
+            | Self::Media(Media { span, .. })
+            | Self::Namespace { span, .. }
+            | Self::Supports(Supports { span, .. })
+            | Self::Other { span, .. } => *span,
+        }
+    }
+
+    fn span_mut(&mut self) -> &mut AtRuleSpan {
+        match self {
+            Self::Container(Container { span, .. })
+            | Self::FontFace { span, .. }
+            | Self::Import(Import { span, .. })
+            | Self::Keyframes { span, .. }
+            | Self::Layer { span, .. }
+            | Self::Media(Media { span, .. })
+            | Self::Namespace { span, .. }
+            | Self::Supports(Supports { span, .. })
+            | Self::Other { span, .. } => span,
+        }
+    }
+
+    /// Writes the rule without whitespace, see [`StyleSheet::to_minified_string`](crate::StyleSheet::to_minified_string).
+    pub(crate) fn write_minified(&self, text: &mut String) {
+        let _ = self.write_minified_impl(text);
+    }
+
+    fn write_minified_impl(&self, text: &mut String) -> fmt::Result {
+        match self {
+            Self::Container(container) => {
+                text.push_str("@container ");
+                write_separated(text, &container.conditions, ",")?;
+                text.push('{');
+                container.contents.write_minified(text);
+                text.push('}');
+            }
+            Self::FontFace { declarations, .. } => {
+                text.push_str("@font-face{");
+                write_separated(text, declarations, ";")?;
+                text.push('}');
+            }
+            Self::Import(import) => write!(text, "{}", import)?,
+            Self::Keyframes { name, frames, .. } => {
+                text.push_str("@keyframes ");
+                write_ident(text, name)?;
+                text.push('{');
+                for frame in frames {
+                    write!(text, "{}{{", frame.key)?;
+                    write_separated(text, &frame.declarations, ";")?;
+                    text.push('}');
+                }
+                text.push('}');
+            }
+            Self::Layer {
+                layer: LayerType::Statement(names),
+                ..
+            } => {
+                text.push_str("@layer ");
+                for (i, name) in names.iter().enumerate() {
+                    if i != 0 {
+                        text.push(',');
+                    }
+                    write_ident(text, name)?;
+                }
+                text.push(';');
+            }
+            Self::Layer {
+                layer: LayerType::Block { name, rules },
+                ..
+            } => {
+                text.push_str("@layer");
+                if let Some(name) = name {
+                    text.push(' ');
+                    write_ident(text, name)?;
+                }
+                text.push('{');
+                crate::write_minified_rules(text, rules);
+                text.push('}');
+            }
+            Self::Media(media) => {
+                text.push_str("@media ");
+                write_separated(text, &media.query, ",")?;
+                text.push('{');
+                crate::write_minified_rules(text, &media.rules);
+                text.push('}');
+            }
+            Self::Namespace { name, value, .. } => {
+                text.push_str("@namespace ");
+                let is_url = value
+                    .get(..4)
+                    .map_or(false, |v| v.eq_ignore_ascii_case("url("));
+                match name {
+                    Some(name) => {
+                        write_ident(text, name)?;
+                        write!(text, " {}", value)?;
+                    }
+                    // A string without a prefix is stored unquoted.
+                    None if !is_url => write_string(text, value)?,
+                    None => text.push_str(value),
+                }
+                text.push(';');
+            }
+            Self::Supports(supports) => {
+                write!(text, "@supports {}{{", supports.condition)?;
+                crate::write_minified_rules(text, &supports.rules);
+                text.push('}');
+            }
+            Self::Other {
+                ident,
+                pre_block,
+                block,
+                ..
+            } => {
+                text.push('@');
+                write_ident(text, ident)?;
+                if !pre_block.is_empty() {
+                    write!(text, " {}", pre_block)?;
+                }
+
+                if block.is_empty() {
+                    text.push(';');
+                } else {
+                    write!(text, "{{{}}}", block)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Consumes an at-rule. The stream must be right after the `@`.
+    pub(crate) fn consume(s: &mut Stream<'a>, lines: &LazyLineIndex<'_>) -> Result<Self, Error> {
+        let start = s.pos() - 1;
         let ident = s.consume_ident()?;
+        let prelude_start = s.pos();
+
+        let mut rule = Self::consume_impl(s, ident, lines)?;
+        *rule.span_mut() = AtRuleSpan::scan(s, start, prelude_start);
+        Ok(rule)
+    }
 
-        match ident {
-            "container" => Ok(Self::Container(Container::consume(s)?)),
+    fn consume_impl(
+        s: &mut Stream<'a>,
+        ident: Cow<'a, str>,
+        lines: &LazyLineIndex<'_>,
+    ) -> Result<Self, Error> {
+        match ident.to_ascii_lowercase().as_str() {
+            "container" => Ok(Self::Container(Container::consume(s, lines)?)),
 
             "font-face" => {
                 s.skip_spaces();
@@ -80,7 +309,10 @@ impl<'a> AtRule<'a> {
                 let declarations = crate::consume_declarations(s)?;
                 s.skip_spaces();
                 s.try_consume_byte(b'}');
-                Ok(Self::FontFace(declarations))
+                Ok(Self::FontFace {
+                    declarations,
+                    span: AtRuleSpan::default(),
+                })
             }
 
             "import" => Ok(Self::Import(Import::consume(s)?)),
@@ -96,7 +328,7 @@ impl<'a> AtRule<'a> {
                 loop {
                     // read the keyframe ident
                     s.skip_spaces_and_comments()?;
-                    let key = s.consume_ident_special()?.trim();
+                    let key = s.consume_ident_special()?;
 
                     // read the declarations
                     s.skip_spaces_and_comments()?;
@@ -113,7 +345,11 @@ impl<'a> AtRule<'a> {
                     }
                 }
 
-                Ok(Self::Keyframes { name, frames })
+                Ok(Self::Keyframes {
+                    name,
+                    frames,
+                    span: AtRuleSpan::default(),
+                })
             }
 
             "layer" => {
@@ -126,46 +362,51 @@ impl<'a> AtRule<'a> {
                     b'{' => {
                         s.advance(1);
                         s.skip_spaces_and_comments()?;
-                        
-                        let name = value.trim();
+
+                        let name = value;
                         let mut rules = Vec::new();
-                        crate::consume_rule_set(s, &mut rules)?;
+                        crate::consume_rule_set(s, &mut rules, lines)?;
                         s.skip_spaces_and_comments()?;
                         s.try_consume_byte(b'}');
                         s.skip_spaces_and_comments()?;
 
                         let name = Some(name).filter(|n| !n.is_empty());
-                        Ok(Self::Layer(LayerType::Block { name, rules }))
+                        Ok(Self::Layer {
+                            layer: LayerType::Block { name, rules },
+                            span: AtRuleSpan::default(),
+                        })
                     }
                     // list
                     b',' => {
-                        let mut names = alloc::vec![value.trim()];
+                        let mut names = alloc::vec![value];
                         s.skip_spaces_and_comments()?;
 
                         // layer list (probably)
                         while s.curr_byte()? == b',' {
                             s.advance(1);
                             s.skip_spaces_and_comments()?;
-                            names.push(s.consume_ident()?.trim());
+                            names.push(s.consume_ident()?);
                             s.skip_spaces_and_comments()?;
                         }
                         s.consume_byte(b';')?;
 
-                        Ok(Self::Layer(LayerType::Statement(names)))
+                        Ok(Self::Layer {
+                            layer: LayerType::Statement(names),
+                            span: AtRuleSpan::default(),
+                        })
                     }
 
                     // single item list
-                    b';' => {
-                        Ok(Self::Layer(LayerType::Statement(alloc::vec![value])))
-                    }
+                    b';' => Ok(Self::Layer {
+                        layer: LayerType::Statement(alloc::vec![value]),
+                        span: AtRuleSpan::default(),
+                    }),
 
                     _ => Err(Error::InvalidIdent(s.gen_text_pos())),
                 }
             }
 
-            "media" => {
-                Ok(Self::Media(Media::consume(s)?))
-            }
+            "media" => Ok(Self::Media(Media::consume(s, lines)?)),
 
             // TODO: this is inconsistent
             "namespace" => {
@@ -175,7 +416,11 @@ impl<'a> AtRule<'a> {
                     s.skip_spaces_and_comments()?;
                     s.consume_byte(b';')?;
 
-                    Ok(Self::Namespace { name: None, value })
+                    Ok(Self::Namespace {
+                        name: None,
+                        value,
+                        span: AtRuleSpan::default(),
+                    })
                 } else {
                     let start = s.pos();
                     let ident2 = s.consume_ident()?;
@@ -184,7 +429,7 @@ impl<'a> AtRule<'a> {
                     if s.curr_byte()? == b'(' {
                         s.advance(1);
                         s.skip_spaces_and_comments()?;
-                        
+
                         if s.curr_byte()? == b'"' {
                             s.skip_spaces_and_comments()?;
                             s.consume_byte(b';')?;
@@ -194,32 +439,43 @@ impl<'a> AtRule<'a> {
                         s.skip_spaces_and_comments()?;
                         s.consume_byte(b')')?;
 
-                        let value = s.slice_range(start, s.pos());
+                        let value = Cow::Borrowed(s.slice_range(start, s.pos()));
                         s.skip_spaces_and_comments()?;
                         s.consume_byte(b';')?;
                         s.skip_spaces_and_comments()?;
-                        
-                        Ok(Self::Namespace { name: None, value })
+
+                        Ok(Self::Namespace {
+                            name: None,
+                            value,
+                            span: AtRuleSpan::default(),
+                        })
                     } else {
                         s.skip_spaces_and_comments()?;
-                        let value = crate::consume_value(s)?;
+                        let value = Cow::Borrowed(crate::consume_value(s)?);
                         s.skip_spaces_and_comments()?;
                         s.consume_byte(b';')?;
 
-                        Ok(Self::Namespace { name: Some(ident2), value })
+                        Ok(Self::Namespace {
+                            name: Some(ident2),
+                            value,
+                            span: AtRuleSpan::default(),
+                        })
                     }
                 }
             }
 
-            "supports" => Ok(Self::Supports(Supports::consume(s)?)),
+            "supports" => Ok(Self::Supports(Supports::consume(s, lines)?)),
 
             _ => {
-                let pre_block = s.consume_bytes(|c| c != b';' && c != b'{').trim();
+                let start = s.pos();
+                crate::skip_component_values(s, |c| c == b';' || c == b'{');
+                let pre_block = s.slice_back(start).trim();
                 s.skip_spaces_and_comments()?;
 
                 let block = if s.curr_byte()? == b'{' {
                     crate::read_block(s, false).trim()
                 } else {
+                    s.try_consume_byte(b';');
                     ""
                 };
 
@@ -227,18 +483,61 @@ impl<'a> AtRule<'a> {
                     ident,
                     pre_block,
                     block,
+                    span: AtRuleSpan::default(),
                 })
             }
         }
     }
 }
 
+/// An at-rule source location.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct AtRuleSpan {
+    /// The whole rule, from the `@` to the closing `}` or `;`.
+    pub rule: Span,
+
+    /// The prelude, ie. everything between the rule name and the block or `;`.
+    ///
+    /// Surrounding whitespace is not included.
+    pub prelude: Span,
+
+    /// The block content, without braces. `None` for statement at-rules.
+    pub block: Option<Span>,
+}
+impl AtRuleSpan {
+    /// Rescans an already consumed at-rule for its prelude and block boundaries.
+    pub(crate) fn scan(s: &Stream<'_>, start: usize, prelude_start: usize) -> Self {
+        let mut scan = s.sub_stream(prelude_start, s.pos());
+        crate::skip_component_values(&mut scan, |c| c == b'{' || c == b';');
+
+        let prelude = s.slice_range(prelude_start, scan.pos());
+        let prelude_start = prelude_start + prelude.len() - prelude.trim_start().len();
+        let prelude_end = prelude_start + prelude.trim().len();
+
+        let mut block = None;
+        if scan.curr_byte() == Ok(b'{') {
+            scan.advance(1);
+            let block_start = scan.pos();
+            crate::skip_component_values(&mut scan, |c| c == b'}');
+            block = Some(Span::new(block_start, scan.pos()));
+            scan.try_consume_byte(b'}');
+        } else {
+            scan.try_consume_byte(b';');
+        }
+
+        Self {
+            rule: Span::new(start, scan.pos()),
+            prelude: Span::new(prelude_start, prelude_end),
+            block,
+        }
+    }
+}
 
 /// An @keyframes entry
 #[derive(Clone, Debug, PartialEq)]
 pub struct KeyFrame<'a> {
     /// The key in the keyframe, ie "100%"
-    pub key: &'a str,
+    pub key: Cow<'a, str>,
 
     /// the list of declarations inside the body
     pub declarations: Vec<Declaration<'a>>,
@@ -248,14 +547,14 @@ pub struct KeyFrame<'a> {
 #[derive(Clone, Debug, PartialEq)]
 pub enum LayerType<'a> {
     /// @layer layer1, layer2
-    Statement(Vec<&'a str>),
+    Statement(Vec<Cow<'a, str>>),
 
     /// @layer name { ..rules.. }
     Block {
         /// The name of the layer.
-        /// 
+        ///
         /// None if anonymous
-        name: Option<&'a str>,
+        name: Option<Cow<'a, str>>,
 
         /// The list of rules in the @layer body
         rules: Vec<Rule<'a>>,
@@ -265,7 +564,7 @@ pub enum LayerType<'a> {
 /// A comparison operator
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Comparison<'a> {
-    /// == 
+    /// ==
     Equal,
 
     /// !=
@@ -286,6 +585,20 @@ pub enum Comparison<'a> {
     /// Some other, likely custom operator
     Other(&'a str),
 }
+impl fmt::Display for Comparison<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Equal => write!(f, "="),
+            Self::NotEqual => write!(f, "!="),
+            Self::Less => write!(f, "<"),
+            Self::Greater => write!(f, ">"),
+            Self::LessEq => write!(f, "<="),
+            Self::GreaterEq => write!(f, ">="),
+            Self::Other(other) => write!(f, "{}", other),
+        }
+    }
+}
+
 impl<'a> Comparison<'a> {
     pub(crate) fn consume(s: &mut Stream<'a>) -> Result<Self, Error> {
         s.skip_spaces_and_comments()?;
@@ -301,11 +614,9 @@ impl<'a> Comparison<'a> {
             "!=" => Self::NotEqual,
             _ => Self::Other(bytes),
         })
-
     }
 }
 
-
 /// A combinator/modifier
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub enum Combinator<'a> {
@@ -322,11 +633,27 @@ pub enum Combinator<'a> {
     Other(&'a str),
 }
 
+/// Writes items separated by `separator`.
+pub(crate) fn write_separated<W: Write, T: fmt::Display>(
+    w: &mut W,
+    items: &[T],
+    separator: &str,
+) -> fmt::Result {
+    for (i, item) in items.iter().enumerate() {
+        if i != 0 {
+            w.write_str(separator)?;
+        }
+        write!(w, "{}", item)?;
+    }
+
+    Ok(())
+}
+
 pub(crate) fn is_keyword(s: &mut Stream<'_>, keyword: &str) -> bool {
     let tail = s.slice_tail();
-    // if the tail doesnt start with our keyword then its obviously not the keyword 
-    if !tail.starts_with(keyword) { 
-        return false 
+    // if the tail doesnt start with our keyword then its obviously not the keyword
+    if !s.starts_with_ignore_case(keyword) {
+        return false;
     }
 
     // if the tail and the keyword are the same length, then all thats left in the stream is the keyword
@@ -342,4 +669,3 @@ pub(crate) fn is_keyword(s: &mut Stream<'_>, keyword: &str) -> bool {
         _ => false,
     }
 }
-
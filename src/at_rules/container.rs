@@ -1,26 +1,39 @@
-use crate::{StyleSheet, Stream, Error};
-use super::at_rule::{Comparison, is_keyword};
-use alloc::vec::Vec;
+use super::at_rule::{is_keyword, AtRuleSpan, Comparison};
+use crate::{Error, Stream, StyleSheet};
+use alloc::borrow::Cow;
 use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
 
+use crate::line_index::LazyLineIndex;
+use crate::stream::write_ident;
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 /// An @container rule
 pub struct Container<'a> {
     /// the rule conditions
     pub conditions: Vec<ContainerCondition<'a>>,
     /// the contents of the rule's block
     pub contents: StyleSheet<'a>,
+    /// the source location
+    pub span: AtRuleSpan,
+}
+impl PartialEq for Container<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.conditions == other.conditions && self.contents == other.contents
+    }
 }
 impl<'a> Container<'a> {
-    pub(crate) fn consume(s: &mut Stream<'a>) -> Result<Self, Error> {
+    pub(crate) fn consume(s: &mut Stream<'a>, lines: &LazyLineIndex<'_>) -> Result<Self, Error> {
         s.skip_spaces_and_comments()?;
 
         let mut conditions = Vec::new();
         loop {
             conditions.push(ContainerCondition::consume(s)?);
             s.skip_spaces_and_comments()?;
-            if s.curr_byte()? != b',' { break }
+            if s.curr_byte()? != b',' {
+                break;
+            }
             s.advance(1);
             s.skip_spaces_and_comments()?;
         }
@@ -29,32 +42,35 @@ impl<'a> Container<'a> {
         s.consume_byte(b'{')?;
 
         let start = s.pos();
-        crate::consume_until_block_end(s);
+        crate::skip_component_values(s, |c| c == b'}');
 
-        let sheet = StyleSheet::parse(s.slice_range(start, s.pos()));
+        // Parse in place, so the nested spans are relative to the whole source.
+        let mut sheet = StyleSheet::new();
+        sheet.consume(s.sub_stream(start, s.pos()), lines);
+        s.try_consume_byte(b'}');
 
         Ok(Self {
             conditions,
-            contents: sheet
+            contents: sheet,
+            span: AtRuleSpan::default(),
         })
     }
 }
 
-
 /// an @container rule condition
 #[derive(Clone, Debug, PartialEq)]
 pub enum ContainerCondition<'a> {
     /// the condition is just the name
-    NameOnly(&'a str),
+    NameOnly(Cow<'a, str>),
     /// the condition is just the query
     QueryOnly(ContainerQuery<'a>),
     /// the condition has a name and a query
     NameAndQuery {
         /// the name of the container
-        name: &'a str,
+        name: Cow<'a, str>,
         /// the query
         query: ContainerQuery<'a>,
-    }
+    },
 }
 impl<'a> ContainerCondition<'a> {
     fn consume(s: &mut Stream<'a>) -> Result<Self, Error> {
@@ -76,11 +92,23 @@ impl<'a> ContainerCondition<'a> {
             (Some(name), None) => Ok(Self::NameOnly(name)),
             (None, Some(query)) => Ok(Self::QueryOnly(query)),
             (Some(name), Some(query)) => Ok(Self::NameAndQuery { name, query }),
-            (None, None) => Err(Error::InvalidValue(text_pos))
+            (None, None) => Err(Error::InvalidValue(text_pos)),
         }
     }
 }
 
+impl fmt::Display for ContainerCondition<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NameOnly(name) => write_ident(f, name),
+            Self::QueryOnly(query) => write!(f, "{}", query),
+            Self::NameAndQuery { name, query } => {
+                write_ident(f, name)?;
+                write!(f, " {}", query)
+            }
+        }
+    }
+}
 
 /// a container query
 #[derive(Clone, Debug, PartialEq)]
@@ -91,9 +119,9 @@ pub enum ContainerQuery<'a> {
     List {
         /// the first query
         first: ContainerQueryInParens<'a>,
-        /// any following queryies 
-        rest: Vec<ContainerQueryAndOr<'a>>
-    }
+        /// any following queryies
+        rest: Vec<ContainerQueryAndOr<'a>>,
+    },
 }
 impl<'a> ContainerQuery<'a> {
     fn try_consume(s: &mut Stream<'a>) -> Result<Option<Self>, Error> {
@@ -128,6 +156,25 @@ impl<'a> ContainerQuery<'a> {
     }
 }
 
+impl fmt::Display for ContainerQuery<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Not(query) => write!(f, "not {}", query),
+            Self::List { first, rest } => {
+                write!(f, "{}", first)?;
+                for item in rest {
+                    match item {
+                        ContainerQueryAndOr::And(query) => write!(f, " and {}", query)?,
+                        ContainerQueryAndOr::Or(query) => write!(f, " or {}", query)?,
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
 /// a container query prefixed with either 'and' or 'or'
 #[derive(Clone, Debug, PartialEq)]
 pub enum ContainerQueryAndOr<'a> {
@@ -135,7 +182,7 @@ pub enum ContainerQueryAndOr<'a> {
     And(ContainerQueryInParens<'a>),
 
     /// a query prefixed with 'or'
-    Or(ContainerQueryInParens<'a>)
+    Or(ContainerQueryInParens<'a>),
 }
 impl<'a> ContainerQueryAndOr<'a> {
     fn try_consume(s: &mut Stream<'a>) -> Result<Option<Self>, Error> {
@@ -196,11 +243,21 @@ impl<'a> ContainerQueryInParens<'a> {
     }
 }
 
+impl fmt::Display for ContainerQueryInParens<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Query(query) => write!(f, "({})", query),
+            Self::Feature(feature) => write!(f, "({})", feature),
+            Self::Function(function) => write!(f, "{}", function),
+        }
+    }
+}
+
 /// A field should be compared to a value
 #[derive(Clone, Debug, PartialEq)]
 pub struct Feature<'a> {
     /// The field to be compared
-    pub key: &'a str,
+    pub key: Cow<'a, str>,
     /// What comparison should be made
     pub comparison: Comparison<'a>,
 
@@ -210,39 +267,52 @@ pub struct Feature<'a> {
 impl<'a> Feature<'a> {
     fn consume(s: &mut Stream<'a>) -> Result<Self, Error> {
         s.skip_spaces_and_comments()?;
-        let key = s.consume_ident()?.trim();
+        let key = s.consume_ident()?;
         s.skip_spaces_and_comments()?;
         let comparison = Comparison::consume(s)?;
         s.skip_spaces_and_comments()?;
         let value = crate::consume_value(s)?;
         Ok(Self {
-            key, 
+            key,
             comparison,
-            value
+            value,
         })
     }
 }
 
+impl fmt::Display for Feature<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The comparison must be followed by a space.
+        write_ident(f, &self.key)?;
+        write!(f, " {} {}", self.comparison, self.value)
+    }
+}
 
 /// TODO: rename? again, not really a "function"
 #[derive(Clone, Debug, PartialEq)]
 pub struct ContainerFunction<'a> {
     /// the name of the "function", ie "style"
-    pub name: &'a str,
+    pub name: Cow<'a, str>,
 
     /// the query
-    pub query: FunctionQuery<'a>
+    pub query: FunctionQuery<'a>,
 }
 impl<'a> ContainerFunction<'a> {
     fn consume(s: &mut Stream<'a>) -> Result<Self, Error> {
         s.skip_spaces_and_comments()?;
         Ok(Self {
-            name: s.consume_ident_special()?.trim(),
+            name: s.consume_ident_special()?,
             query: FunctionQuery::consume(s)?,
         })
     }
 }
 
+impl fmt::Display for ContainerFunction<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_ident(f, &self.name)?;
+        write!(f, "{}", self.query)
+    }
+}
 
 /// A function query
 #[derive(Clone, Debug, PartialEq)]
@@ -254,8 +324,8 @@ pub enum FunctionQuery<'a> {
         /// The first in the list
         first: FunctionInParens<'a>,
         /// The following to be compared as well
-        rest: Vec<FunctionAndOr<'a>>
-    }
+        rest: Vec<FunctionAndOr<'a>>,
+    },
 }
 impl<'a> FunctionQuery<'a> {
     fn consume(s: &mut Stream<'a>) -> Result<Self, Error> {
@@ -270,6 +340,24 @@ impl<'a> FunctionQuery<'a> {
     }
 }
 
+impl fmt::Display for FunctionQuery<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Not(function) => write!(f, "not {}", function),
+            Self::List { first, rest } => {
+                write!(f, "{}", first)?;
+                for item in rest {
+                    match item {
+                        FunctionAndOr::And(function) => write!(f, " and {}", function)?,
+                        FunctionAndOr::Or(function) => write!(f, " or {}", function)?,
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
 
 /// An inner function query
 #[derive(Clone, Debug, PartialEq)]
@@ -289,18 +377,19 @@ impl<'a> FunctionInParens<'a> {
         let r = if s.curr_byte()? == b':' {
             s.advance(1);
             let value = s.consume_bytes(|b| b != b')').trim();
-            Self::Feature(Feature { key: ident, comparison: Comparison::Equal, value })
+            Self::Feature(Feature {
+                key: ident,
+                comparison: Comparison::Equal,
+                value,
+            })
         } else {
             s.consume_byte(b'(')?;
             let query = FunctionQuery::consume(s)?;
             s.skip_spaces_and_comments()?;
             s.consume_byte(b')')?;
-            Self::Query(Box::new(ContainerFunction {
-                name: ident,
-                query,
-            }))
+            Self::Query(Box::new(ContainerFunction { name: ident, query }))
         };
-        
+
         s.skip_spaces_and_comments()?;
         s.consume_byte(b')')?;
 
@@ -308,6 +397,23 @@ impl<'a> FunctionInParens<'a> {
     }
 }
 
+impl fmt::Display for FunctionInParens<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        match self {
+            Self::Query(function) => {
+                write_ident(f, &function.name)?;
+                write!(f, "({})", function.query)?;
+            }
+            Self::Feature(feature) => {
+                write_ident(f, &feature.key)?;
+                write!(f, ":{}", feature.value)?;
+            }
+        }
+        write!(f, ")")
+    }
+}
+
 /// A function preceeded by 'and' or 'or'
 #[derive(Clone, Debug, PartialEq)]
 pub enum FunctionAndOr<'a> {
@@ -1,13 +1,13 @@
-use alloc::vec::Vec;
-use crate::{
-    Stream,
-    Error,
-};
+use super::at_rule::{is_keyword, write_separated, AtRuleSpan};
 use super::media::MediaQuery;
-use super::at_rule::is_keyword;
+use crate::stream::{write_ident, write_string};
+use crate::{Error, Span, Stream};
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::fmt;
 
 /// An @import rule
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 pub struct Import<'a> {
     /// The url to import from
     pub url: ImportUrl<'a>,
@@ -20,6 +20,17 @@ pub struct Import<'a> {
 
     /// A list of media queries
     pub media_queries: Vec<MediaQuery<'a>>,
+
+    /// The source location
+    pub span: AtRuleSpan,
+}
+impl PartialEq for Import<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.url == other.url
+            && self.layer == other.layer
+            && self.supports == other.supports
+            && self.media_queries == other.media_queries
+    }
 }
 impl<'a> Import<'a> {
     pub(crate) fn consume(s: &mut Stream<'a>) -> Result<Self, Error> {
@@ -29,7 +40,7 @@ impl<'a> Import<'a> {
         // read layer maybe
         let mut layer = None;
         s.skip_spaces_and_comments()?;
-        if s.slice_tail().starts_with("layer") {
+        if s.starts_with_ignore_case("layer") {
             s.advance(5);
             if s.curr_byte()? == b'(' {
                 s.advance(1);
@@ -46,14 +57,14 @@ impl<'a> Import<'a> {
         // supports
         let mut supports = None;
         s.skip_spaces_and_comments()?;
-        if s.slice_tail().starts_with("supports") {
+        if s.starts_with_ignore_case("supports") {
             s.advance(8);
             s.consume_byte(b'(')?;
             s.skip_spaces_and_comments()?;
 
             if is_keyword(s, "not") {
                 supports = Some(ImportConditionSupports::SupportsCondition(
-                    super::supports::SupportsCondition::consume(s)?
+                    super::supports::SupportsCondition::consume(s)?,
                 ));
             } else {
                 // try declaration first
@@ -61,19 +72,30 @@ impl<'a> Import<'a> {
                 let pos = s.pos();
                 match s.consume_ident() {
                     Ok(name) => {
+                        let name_end = s.pos();
                         s.skip_spaces_and_comments()?;
                         if s.curr_byte()? == b':' {
                             s.advance(1);
                             s.skip_spaces_and_comments()?;
+                            let value_start = s.pos();
                             let value = crate::consume_value(s)?;
+                            let span = crate::DeclarationSpan {
+                                name: Span::new(pos, name_end),
+                                value: Span::new(value_start, value_start + value.len()),
+                                important: None,
+                            };
 
-                            supports = Some(ImportConditionSupports::Declaration(
-                                crate::Declaration { name, value, important: false }
-                            ));
+                            supports =
+                                Some(ImportConditionSupports::Declaration(crate::Declaration {
+                                    name,
+                                    value,
+                                    important: false,
+                                    span,
+                                }));
                         } else {
                             s.reset_pos(pos);
                             supports = Some(ImportConditionSupports::SupportsCondition(
-                                super::supports::SupportsCondition::consume(s)?
+                                super::supports::SupportsCondition::consume(s)?,
                             ));
                         }
                     }
@@ -81,7 +103,7 @@ impl<'a> Import<'a> {
                     Err(_) => {
                         s.reset_pos(pos);
                         supports = Some(ImportConditionSupports::SupportsCondition(
-                            super::supports::SupportsCondition::consume(s)?
+                            super::supports::SupportsCondition::consume(s)?,
                         ));
                     }
                 }
@@ -107,26 +129,59 @@ impl<'a> Import<'a> {
             url,
             layer,
             supports,
-            media_queries
+            media_queries,
+            span: AtRuleSpan::default(),
         })
-    }   
+    }
+}
+
+impl fmt::Display for Import<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "@import {}", self.url)?;
+        match &self.layer {
+            Some(ImportLayer::Layer) => write!(f, " layer")?,
+            Some(ImportLayer::Named(name)) => {
+                write!(f, " layer(")?;
+                write_ident(f, name)?;
+                write!(f, ")")?;
+            }
+            None => {}
+        }
+
+        match &self.supports {
+            Some(ImportConditionSupports::SupportsCondition(condition)) => {
+                write!(f, " supports({})", condition)?;
+            }
+            Some(ImportConditionSupports::Declaration(declaration)) => {
+                write!(f, " supports({})", declaration)?;
+            }
+            None => {}
+        }
+
+        if !self.media_queries.is_empty() {
+            write!(f, " ")?;
+            write_separated(f, &self.media_queries, ",")?;
+        }
+
+        write!(f, ";")
+    }
 }
 
 /// The url for an @import rule
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum ImportUrl<'a> {
     /// A url(...)
-    Url(&'a str),
+    Url(Cow<'a, str>),
     /// An src(...)
-    Src(&'a str),
-    /// A raw string 
-    String(&'a str),
+    Src(Cow<'a, str>),
+    /// A raw string
+    String(Cow<'a, str>),
 }
 impl<'a> ImportUrl<'a> {
     fn consume(s: &mut Stream<'a>) -> Result<Self, Error> {
         s.skip_spaces_and_comments()?;
 
-        if s.slice_tail().starts_with("url") {
+        if s.starts_with_ignore_case("url") {
             // url
             s.advance(3);
             s.skip_spaces_and_comments()?;
@@ -136,7 +191,7 @@ impl<'a> ImportUrl<'a> {
             s.skip_spaces_and_comments()?;
             s.consume_byte(b')')?;
             Ok(Self::Url(url))
-        } else if s.slice_tail().starts_with("src") {
+        } else if s.starts_with_ignore_case("src") {
             // src
             s.advance(3);
             s.skip_spaces_and_comments()?;
@@ -153,6 +208,23 @@ impl<'a> ImportUrl<'a> {
     }
 }
 
+impl fmt::Display for ImportUrl<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Url(url) => {
+                write!(f, "url(")?;
+                write_string(f, url)?;
+                write!(f, ")")
+            }
+            Self::Src(src) => {
+                write!(f, "src(")?;
+                write_string(f, src)?;
+                write!(f, ")")
+            }
+            Self::String(string) => write_string(f, string),
+        }
+    }
+}
 
 /// An @import supports condition
 #[derive(Clone, Debug, PartialEq)]
@@ -163,13 +235,23 @@ pub enum ImportConditionSupports<'a> {
     /// A key: value declaration
     Declaration(crate::Declaration<'a>),
 }
+impl ImportConditionSupports<'_> {
+    /// Checks that the condition is supported, see [`SupportsCondition::evaluate`](super::supports::SupportsCondition::evaluate).
+    #[cfg(any(feature = "std", feature = "libm"))]
+    pub fn evaluate(&self) -> bool {
+        match self {
+            Self::SupportsCondition(condition) => condition.evaluate(),
+            Self::Declaration(declaration) => declaration.validate().is_ok(),
+        }
+    }
+}
 
 /// Layer info in an @import rule
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum ImportLayer<'a> {
     /// The `layer` keyword was specified
     Layer,
 
     /// A layer was named
-    Named(&'a str),
-} 
+    Named(Cow<'a, str>),
+}
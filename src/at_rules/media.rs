@@ -1,25 +1,31 @@
-use crate::{
-    Stream,
-    Error,
-    Rule,
-};
+use crate::{Error, Rule, Stream};
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
 use alloc::vec;
 use alloc::vec::Vec;
-use alloc::boxed::Box;
-
-use super::at_rule::{is_keyword, Comparison};
+use core::fmt;
 
+use super::at_rule::{is_keyword, AtRuleSpan, Comparison};
+use crate::line_index::LazyLineIndex;
+use crate::stream::write_ident;
 
 /// an @media rule
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 pub struct Media<'a> {
     /// the query params
     pub query: Vec<MediaQuery<'a>>,
     /// the rules inside the body
-    pub rules: Vec<Rule<'a>>
+    pub rules: Vec<Rule<'a>>,
+    /// the source location
+    pub span: AtRuleSpan,
+}
+impl PartialEq for Media<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.query == other.query && self.rules == other.rules
+    }
 }
 impl<'a> Media<'a> {
-    pub(crate) fn consume(s: &mut Stream<'a>) -> Result<Self, Error> {
+    pub(crate) fn consume(s: &mut Stream<'a>, lines: &LazyLineIndex<'_>) -> Result<Self, Error> {
         let first = MediaQuery::consume(s)?;
         let mut list = vec![first];
 
@@ -34,13 +40,14 @@ impl<'a> Media<'a> {
         s.skip_spaces_and_comments()?;
         s.consume_byte(b'{')?;
         let mut rules = Vec::new();
-        crate::consume_rule_set(s, &mut rules)?;
+        crate::consume_rule_set(s, &mut rules, lines)?;
         s.skip_spaces_and_comments()?;
         s.consume_byte(b'}')?;
 
         Ok(Self {
             query: list,
             rules,
+            span: AtRuleSpan::default(),
         })
     }
 }
@@ -59,10 +66,10 @@ pub enum MediaNotOnly {
 pub enum MediaQuery<'a> {
     Condition(MediaCondition<'a>),
     OtherThing {
-        not_only: Option<MediaNotOnly>, 
-        media_type: &'a str,
+        not_only: Option<MediaNotOnly>,
+        media_type: Cow<'a, str>,
         conditions: Vec<MediaConditionWithoutOr<'a>>,
-    }
+    },
 }
 impl<'a> MediaQuery<'a> {
     pub(crate) fn consume(s: &mut Stream<'a>) -> Result<Self, Error> {
@@ -89,9 +96,9 @@ impl<'a> MediaQuery<'a> {
                     s.skip_spaces_and_comments()?;
                 }
 
-                Ok(Self::OtherThing { 
-                    not_only: Some(MediaNotOnly::Not), 
-                    media_type, 
+                Ok(Self::OtherThing {
+                    not_only: Some(MediaNotOnly::Not),
+                    media_type,
                     conditions,
                 })
             }
@@ -110,17 +117,17 @@ impl<'a> MediaQuery<'a> {
                 s.skip_spaces_and_comments()?;
             }
 
-            Ok(Self::OtherThing { 
-                not_only: Some(MediaNotOnly::Only), 
-                media_type, 
+            Ok(Self::OtherThing {
+                not_only: Some(MediaNotOnly::Only),
+                media_type,
                 conditions,
             })
         } else if s.curr_byte()? == b'(' {
             let media = MediaInParens::consume(s)?;
             s.skip_spaces_and_comments()?;
             let conditions = MediaAndOr::consume_many(s)?;
-            Ok(Self::Condition(MediaCondition::List { 
-                first: Box::new(media), 
+            Ok(Self::Condition(MediaCondition::List {
+                first: Box::new(media),
                 conditions,
             }))
         } else {
@@ -137,13 +144,38 @@ impl<'a> MediaQuery<'a> {
                 s.skip_spaces_and_comments()?;
             }
 
-            Ok(Self::OtherThing { 
-                not_only: None, 
-                media_type, 
+            Ok(Self::OtherThing {
+                not_only: None,
+                media_type,
                 conditions,
             })
         }
+    }
+}
 
+impl fmt::Display for MediaQuery<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Condition(condition) => write!(f, "{}", condition),
+            Self::OtherThing {
+                not_only,
+                media_type,
+                conditions,
+            } => {
+                match not_only {
+                    Some(MediaNotOnly::Not) => write!(f, "not ")?,
+                    Some(MediaNotOnly::Only) => write!(f, "only ")?,
+                    None => {}
+                }
+
+                write_ident(f, media_type)?;
+                for condition in conditions {
+                    write!(f, " and {}", condition)?;
+                }
+
+                Ok(())
+            }
+        }
     }
 }
 
@@ -157,7 +189,7 @@ pub enum MediaCondition<'a> {
         /// The first condition in the list
         first: Box<MediaInParens<'a>>,
         /// All subsequent conditions in the list
-        conditions: Vec<MediaAndOr<'a>>
+        conditions: Vec<MediaAndOr<'a>>,
     },
 }
 impl<'a> MediaCondition<'a> {
@@ -178,6 +210,25 @@ impl<'a> MediaCondition<'a> {
     }
 }
 
+impl fmt::Display for MediaCondition<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Not(media) => write!(f, "not {}", media),
+            Self::List { first, conditions } => {
+                write!(f, "{}", first)?;
+                for condition in conditions {
+                    match condition {
+                        MediaAndOr::And(media) => write!(f, " and {}", media)?,
+                        MediaAndOr::Or(media) => write!(f, " or {}", media)?,
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
 /// A media condition without the ability to 'or'
 #[derive(Clone, Debug, PartialEq)]
 pub enum MediaConditionWithoutOr<'a> {
@@ -188,7 +239,7 @@ pub enum MediaConditionWithoutOr<'a> {
         /// The first in the list
         media: MediaInParens<'a>,
         /// Any following conditions
-        conditions: Vec<MediaAnd<'a>>
+        conditions: Vec<MediaAnd<'a>>,
     },
 }
 impl<'a> MediaConditionWithoutOr<'a> {
@@ -214,6 +265,22 @@ impl<'a> MediaConditionWithoutOr<'a> {
     }
 }
 
+impl fmt::Display for MediaConditionWithoutOr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Not(media) => write!(f, "not {}", media),
+            Self::Media { media, conditions } => {
+                write!(f, "{}", media)?;
+                for condition in conditions {
+                    write!(f, " and {}", condition.0)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
 /// Wrapper for when `MediaInParens` can only be preceeded by an "and"
 #[derive(Clone, Debug, PartialEq)]
 pub struct MediaAnd<'a>(pub MediaInParens<'a>);
@@ -229,7 +296,7 @@ pub enum MediaAndOr<'a> {
 impl<'a> MediaAndOr<'a> {
     fn consume(s: &mut Stream<'a>) -> Result<Self, Error> {
         s.skip_spaces_and_comments()?;
-        
+
         if is_keyword(s, "and") {
             s.advance(3);
             s.skip_spaces_and_comments()?;
@@ -258,7 +325,6 @@ impl<'a> MediaAndOr<'a> {
     }
 }
 
-
 /// a media condition inside of parenthesis
 #[derive(Clone, Debug, PartialEq)]
 pub enum MediaInParens<'a> {
@@ -286,6 +352,15 @@ impl<'a> MediaInParens<'a> {
     }
 }
 
+impl fmt::Display for MediaInParens<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Condition(condition) => write!(f, "({})", condition),
+            Self::Feature(feature) => write!(f, "{}", feature),
+        }
+    }
+}
+
 /// An @media feature
 #[derive(Clone, Debug, PartialEq)]
 pub enum MediaFeature<'a> {
@@ -296,12 +371,12 @@ pub enum MediaFeature<'a> {
         /// The value
         val: &'a str,
     },
-    
+
     /// An ident
     Name(&'a str),
 
     /// A range
-    Range(MediaRange<'a>)
+    Range(MediaRange<'a>),
 }
 impl<'a> MediaFeature<'a> {
     fn consume(s: &mut Stream<'a>) -> Result<Self, Error> {
@@ -319,7 +394,10 @@ impl<'a> MediaFeature<'a> {
             s.skip_spaces_and_comments()?;
             s.consume_byte(b')')?;
             s.skip_spaces_and_comments()?;
-            Ok(Self::KeyVal { key: first, val: second })
+            Ok(Self::KeyVal {
+                key: first,
+                val: second,
+            })
         } else if let Some(range) = MediaRange::try_consume(s)? {
             s.skip_spaces_and_comments()?;
             s.consume_byte(b')')?;
@@ -332,11 +410,21 @@ impl<'a> MediaFeature<'a> {
     }
 }
 
+impl fmt::Display for MediaFeature<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::KeyVal { key, val } => write!(f, "({}:{})", key, val),
+            Self::Name(name) => write!(f, "({})", name),
+            Self::Range(range) => write!(f, "({})", range),
+        }
+    }
+}
+
 /// A range comparison
 #[derive(Clone, Debug, PartialEq)]
 pub enum MediaRange<'a> {
     /// A typical comparison
-    /// 
+    ///
     /// ie lhs > rhs
     Regular {
         /// Left hand side
@@ -351,7 +439,7 @@ pub enum MediaRange<'a> {
     LessThanChain {
         /// Left hand side
         lhs: &'a str,
-        /// Middle 
+        /// Middle
         ident: &'a str,
         /// Right hand side
         rhs: &'a str,
@@ -361,7 +449,7 @@ pub enum MediaRange<'a> {
     LessEqChain {
         /// Left hand side
         lhs: &'a str,
-        /// Middle 
+        /// Middle
         ident: &'a str,
         /// Right hand side
         rhs: &'a str,
@@ -371,7 +459,7 @@ pub enum MediaRange<'a> {
     GreaterThanChain {
         /// Left hand side
         lhs: &'a str,
-        /// Middle 
+        /// Middle
         ident: &'a str,
         /// Right hand side
         rhs: &'a str,
@@ -381,7 +469,7 @@ pub enum MediaRange<'a> {
     GreaterEqChain {
         /// Left hand side
         lhs: &'a str,
-        /// Middle 
+        /// Middle
         ident: &'a str,
         /// Right hand side
         rhs: &'a str,
@@ -397,7 +485,7 @@ impl<'a> MediaRange<'a> {
         let tail = s.slice_tail();
 
         let comp_str;
-        let comp  = if tail.starts_with("<") {
+        let comp = if tail.starts_with("<") {
             comp_str = "<";
             Comparison::Less
         } else if tail.starts_with("<=") {
@@ -414,7 +502,7 @@ impl<'a> MediaRange<'a> {
             Comparison::Equal
         } else {
             s.reset_pos(start_pos);
-            return Ok(None)
+            return Ok(None);
         };
 
         s.advance(comp_str.len());
@@ -427,29 +515,52 @@ impl<'a> MediaRange<'a> {
             s.advance(comp_str.len());
             let rhs = consume_value2(s);
             let a = match comp {
-                Comparison::Greater => {
-                    Self::GreaterThanChain { lhs, ident: value, rhs }
-                }
-                Comparison::GreaterEq => {
-                    Self::GreaterEqChain { lhs, ident: value, rhs }
-                }
-                Comparison::Less => {
-                    Self::LessThanChain { lhs, ident: value, rhs }
-                }
-                Comparison::LessEq => {
-                    Self::LessEqChain { lhs, ident: value, rhs }
-                }
+                Comparison::Greater => Self::GreaterThanChain {
+                    lhs,
+                    ident: value,
+                    rhs,
+                },
+                Comparison::GreaterEq => Self::GreaterEqChain {
+                    lhs,
+                    ident: value,
+                    rhs,
+                },
+                Comparison::Less => Self::LessThanChain {
+                    lhs,
+                    ident: value,
+                    rhs,
+                },
+                Comparison::LessEq => Self::LessEqChain {
+                    lhs,
+                    ident: value,
+                    rhs,
+                },
                 // TODO: proper error for this?
                 _ => return Err(Error::UnexpectedCombinator),
             };
 
             Ok(Some(a))
         } else {
-            Ok(Some(Self::Regular { lhs, comp, rhs: value }))
+            Ok(Some(Self::Regular {
+                lhs,
+                comp,
+                rhs: value,
+            }))
         }
     }
 }
 
+impl fmt::Display for MediaRange<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Regular { lhs, comp, rhs } => write!(f, "{}{}{}", lhs, comp, rhs),
+            Self::LessThanChain { lhs, ident, rhs } => write!(f, "{}<{}<{}", lhs, ident, rhs),
+            Self::LessEqChain { lhs, ident, rhs } => write!(f, "{}<={}<={}", lhs, ident, rhs),
+            Self::GreaterThanChain { lhs, ident, rhs } => write!(f, "{}>{}>{}", lhs, ident, rhs),
+            Self::GreaterEqChain { lhs, ident, rhs } => write!(f, "{}>={}>={}", lhs, ident, rhs),
+        }
+    }
+}
 
 fn consume_value2<'a>(s: &mut Stream<'a>) -> &'a str {
     s.consume_bytes(|b| ![b'>', b'<', b'=', b')', b':'].contains(&b))
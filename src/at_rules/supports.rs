@@ -1,40 +1,51 @@
-use crate::{
-    Stream,
-    Error,
-    Rule,
-    Declaration
-};
-use super::at_rule::is_keyword;
-use alloc::vec::Vec;
+use super::at_rule::{is_keyword, AtRuleSpan};
+use crate::line_index::LazyLineIndex;
+use crate::{Declaration, Error, Rule, Stream};
 use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
 
 /// An @supports rule
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 pub struct Supports<'a> {
     /// The condition
     pub condition: SupportsCondition<'a>,
 
     /// the rules in the block body
     pub rules: Vec<Rule<'a>>,
+
+    /// The source location
+    pub span: AtRuleSpan,
+}
+impl PartialEq for Supports<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.condition == other.condition && self.rules == other.rules
+    }
 }
 impl<'a> Supports<'a> {
-    pub(crate) fn consume(s: &mut Stream<'a>) -> Result<Self, Error> {
+    pub(crate) fn consume(s: &mut Stream<'a>, lines: &LazyLineIndex<'_>) -> Result<Self, Error> {
         let condition = SupportsCondition::consume(s)?;
         s.skip_spaces_and_comments()?;
         s.consume_byte(b'{')?;
-        
+
         let mut rules = Vec::new();
-        crate::consume_rule_set(s, &mut rules)?;
+        crate::consume_rule_set(s, &mut rules, lines)?;
         s.skip_spaces_and_comments()?;
         s.consume_byte(b'}')?;
 
         Ok(Self {
             condition,
             rules,
+            span: AtRuleSpan::default(),
         })
     }
-}
 
+    /// Checks that the condition is supported, see [`SupportsCondition::evaluate`].
+    #[cfg(any(feature = "std", feature = "libm"))]
+    pub fn evaluate(&self) -> bool {
+        self.condition.evaluate()
+    }
+}
 
 /// The type of condition
 #[derive(Clone, Debug, PartialEq)]
@@ -47,7 +58,7 @@ pub enum SupportsCondition<'a> {
         first: SupportsInParens<'a>,
         /// The rest of the conditions
         list: Vec<SupportsAndOr<'a>>,
-    }
+    },
 }
 impl<'a> SupportsCondition<'a> {
     pub(crate) fn consume(s: &mut Stream<'a>) -> Result<Self, Error> {
@@ -57,16 +68,34 @@ impl<'a> SupportsCondition<'a> {
             s.advance(3);
             Ok(Self::Not(SupportsInParens::consume(s)?))
         } else {
-            Ok(Self::List { 
-                first: SupportsInParens::consume(s)?, 
+            Ok(Self::List {
+                first: SupportsInParens::consume(s)?,
                 list: SupportsAndOr::consume_many(s)?,
             })
         }
     }
 
+    /// Checks that the condition is supported.
+    ///
+    /// A declaration is supported when it's valid according to the property registry,
+    /// see [`Declaration::validate`].
+    #[cfg(any(feature = "std", feature = "libm"))]
+    pub fn evaluate(&self) -> bool {
+        match self {
+            Self::Not(inner) => !inner.evaluate(),
+            Self::List { first, list } => {
+                list.iter()
+                    .fold(first.evaluate(), |result, item| match item {
+                        SupportsAndOr::And(inner) => result && inner.evaluate(),
+                        SupportsAndOr::Or(inner) => result || inner.evaluate(),
+                    })
+            }
+        }
+    }
+
     fn try_consume(s: &mut Stream<'a>) -> Result<Option<Self>, Error> {
         s.skip_spaces_and_comments()?;
-        
+
         if is_keyword(s, "not") || s.curr_byte()? == b'(' {
             Ok(Some(Self::consume(s)?))
         } else {
@@ -75,6 +104,25 @@ impl<'a> SupportsCondition<'a> {
     }
 }
 
+impl fmt::Display for SupportsCondition<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Not(inner) => write!(f, "not {}", inner),
+            Self::List { first, list } => {
+                write!(f, "{}", first)?;
+                for item in list {
+                    match item {
+                        SupportsAndOr::And(inner) => write!(f, " and {}", inner)?,
+                        SupportsAndOr::Or(inner) => write!(f, " or {}", inner)?,
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
 /// A support condition prefixed with 'and' or 'or'
 #[derive(Clone, Debug, PartialEq)]
 pub enum SupportsAndOr<'a> {
@@ -116,6 +164,15 @@ pub enum SupportsInParens<'a> {
     Feature(Declaration<'a>),
 }
 impl<'a> SupportsInParens<'a> {
+    /// Checks that the condition or the declaration is supported.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    pub fn evaluate(&self) -> bool {
+        match self {
+            Self::Condition(condition) => condition.evaluate(),
+            Self::Feature(declaration) => declaration.validate().is_ok(),
+        }
+    }
+
     fn consume(s: &mut Stream<'a>) -> Result<Self, Error> {
         s.skip_spaces_and_comments()?;
         s.consume_byte(b'(')?;
@@ -128,7 +185,16 @@ impl<'a> SupportsInParens<'a> {
 
         s.skip_spaces_and_comments()?;
         s.consume_byte(b')')?;
-        
+
         Ok(out)
     }
 }
+
+impl fmt::Display for SupportsInParens<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Condition(condition) => write!(f, "({})", condition),
+            Self::Feature(declaration) => write!(f, "({})", declaration),
+        }
+    }
+}
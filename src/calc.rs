@@ -0,0 +1,746 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::f64::consts::{E, PI};
+
+use crate::color::angle_to_degrees;
+#[cfg(not(feature = "std"))]
+use crate::float_funcs::FloatFuncs;
+use crate::length::{Length, LengthUnit, ResolveContext};
+use crate::value::{Bracket, ComponentValue, Function, Value};
+
+/// A math function.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum MathFunction {
+    /// `min(A, B, ...)`
+    Min,
+    /// `max(A, B, ...)`
+    Max,
+    /// `clamp(MIN, VAL, MAX)`
+    Clamp,
+    /// `round(<strategy>, A, B)`
+    Round(RoundingStrategy),
+    /// `mod(A, B)`, with the sign of `B`.
+    Mod,
+    /// `rem(A, B)`, with the sign of `A`.
+    Rem,
+    /// `abs(A)`
+    Abs,
+    /// `sign(A)`
+    Sign,
+    /// `sin(A)`
+    Sin,
+    /// `cos(A)`
+    Cos,
+    /// `tan(A)`
+    Tan,
+    /// `asin(A)`
+    Asin,
+    /// `acos(A)`
+    Acos,
+    /// `atan(A)`
+    Atan,
+    /// `atan2(A, B)`
+    Atan2,
+}
+
+impl MathFunction {
+    /// Returns a function by its name, ignoring ASCII case.
+    ///
+    /// `calc` is not a function of its own and returns `None`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let functions = [
+            ("min", MathFunction::Min),
+            ("max", MathFunction::Max),
+            ("clamp", MathFunction::Clamp),
+            ("round", MathFunction::Round(RoundingStrategy::Nearest)),
+            ("mod", MathFunction::Mod),
+            ("rem", MathFunction::Rem),
+            ("abs", MathFunction::Abs),
+            ("sign", MathFunction::Sign),
+            ("sin", MathFunction::Sin),
+            ("cos", MathFunction::Cos),
+            ("tan", MathFunction::Tan),
+            ("asin", MathFunction::Asin),
+            ("acos", MathFunction::Acos),
+            ("atan", MathFunction::Atan),
+            ("atan2", MathFunction::Atan2),
+        ];
+
+        functions
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, f)| *f)
+    }
+}
+
+/// A `round()` strategy.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum RoundingStrategy {
+    /// `nearest`, the default. Ties are rounded up.
+    Nearest,
+    /// `up`, towards positive infinity.
+    Up,
+    /// `down`, towards negative infinity.
+    Down,
+    /// `to-zero`
+    ToZero,
+}
+
+impl RoundingStrategy {
+    fn from_name(name: &str) -> Option<Self> {
+        let strategies = [
+            ("nearest", RoundingStrategy::Nearest),
+            ("up", RoundingStrategy::Up),
+            ("down", RoundingStrategy::Down),
+            ("to-zero", RoundingStrategy::ToZero),
+        ];
+
+        strategies
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, s)| *s)
+    }
+}
+
+/// A math expression type.
+///
+/// <https://www.w3.org/TR/css-values-4/#calc-type-checking>
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum CalcType {
+    /// `<number>`
+    Number,
+    /// `<length>`
+    Length,
+    /// `<percentage>`
+    Percentage,
+    /// `<length-percentage>`, ie. a mix of lengths and percentages.
+    LengthPercentage,
+    /// `<angle>`
+    Angle,
+    /// `<time>`
+    Time,
+}
+
+impl CalcType {
+    /// Returns the type of a sum, or of other functions that require the same types.
+    fn add(self, other: Self) -> Option<Self> {
+        use CalcType::{Length, LengthPercentage, Percentage};
+
+        match (self, other) {
+            (a, b) if a == b => Some(a),
+            (Length | Percentage | LengthPercentage, Length | Percentage | LengthPercentage) => {
+                Some(LengthPercentage)
+            }
+            _ => None,
+        }
+    }
+
+    fn multiply(self, other: Self) -> Option<Self> {
+        match (self, other) {
+            (CalcType::Number, t) | (t, CalcType::Number) => Some(t),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_length(self) -> bool {
+        matches!(
+            self,
+            CalcType::Length | CalcType::Percentage | CalcType::LengthPercentage
+        )
+    }
+}
+
+/// A math expression tree.
+///
+/// Built from `calc()`, `min()`, `max()`, `clamp()`, `round()`, `mod()`, `rem()`,
+/// `abs()`, `sign()` and trigonometric functions. The expression is type checked
+/// and simplified, so `calc(1px + 2px * 3)` becomes a single `7px` length.
+/// Relative lengths are kept and resolved by [`to_px`](Self::to_px).
+///
+/// # Example
+///
+/// ```
+/// use simplecss::{CalcNode, CalcType, Length, LengthUnit, ResolveContext};
+///
+/// let calc = CalcNode::parse("calc((100% - 2em) / 2)").unwrap();
+/// assert_eq!(calc.calc_type(), Some(CalcType::LengthPercentage));
+///
+/// let context = ResolveContext {
+///     percentage_base: 320.0,
+///     ..ResolveContext::default()
+/// };
+/// assert_eq!(calc.to_px(&context), Some(144.0));
+///
+/// let calc = CalcNode::parse("max(1px + 2px * 3, 5px)").unwrap();
+/// assert_eq!(calc, CalcNode::Length(Length::new(7.0, LengthUnit::Px)));
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub enum CalcNode {
+    /// A number.
+    Number(f64),
+    /// A percentage.
+    ///
+    /// The value is not divided by 100.
+    Percentage(f64),
+    /// A length, other than a percentage.
+    Length(Length),
+    /// An angle, in degrees.
+    Angle(f64),
+    /// A time, in seconds.
+    Time(f64),
+    /// A sum.
+    Sum(Vec<CalcNode>),
+    /// A product.
+    Product(Vec<CalcNode>),
+    /// A negation, ie. `-A`.
+    Negate(Box<CalcNode>),
+    /// An inversion, ie. `1 / A`.
+    Invert(Box<CalcNode>),
+    /// A math function with its arguments.
+    Function(MathFunction, Vec<CalcNode>),
+}
+
+impl CalcNode {
+    /// Parses a math function, like `calc(100% - 2em)`.
+    ///
+    /// The text must consist of a single function.
+    /// Returns `None` on syntax and type errors.
+    pub fn parse(text: &str) -> Option<Self> {
+        let value = Value::parse(text).ok()?;
+        match value.components.as_slice() {
+            [ComponentValue::Function(func)] => CalcNode::from_function(func),
+            _ => None,
+        }
+    }
+
+    /// Parses a math function component value.
+    ///
+    /// Returns `None` when the function is not a math function
+    /// or on syntax and type errors.
+    pub fn from_function(func: &Function<'_>) -> Option<Self> {
+        parse_function(func, &|_| None)
+    }
+
+    /// Returns the expression type.
+    ///
+    /// Expressions built by the parser always have a valid type.
+    pub fn calc_type(&self) -> Option<CalcType> {
+        match self {
+            CalcNode::Number(_) => Some(CalcType::Number),
+            CalcNode::Percentage(_) => Some(CalcType::Percentage),
+            CalcNode::Length(_) => Some(CalcType::Length),
+            CalcNode::Angle(_) => Some(CalcType::Angle),
+            CalcNode::Time(_) => Some(CalcType::Time),
+            CalcNode::Sum(nodes) => fold_types(nodes, CalcType::add),
+            CalcNode::Product(nodes) => fold_types(nodes, CalcType::multiply),
+            CalcNode::Negate(node) => node.calc_type(),
+            CalcNode::Invert(node) => match node.calc_type()? {
+                CalcType::Number => Some(CalcType::Number),
+                _ => None,
+            },
+            CalcNode::Function(f, args) => function_type(*f, args),
+        }
+    }
+
+    /// Evaluates a length or percentage expression, in pixels.
+    ///
+    /// Percentages are resolved against [`ResolveContext::percentage_base`].
+    pub fn to_px(&self, context: &ResolveContext) -> Option<f64> {
+        if !self.calc_type()?.is_length() {
+            return None;
+        }
+
+        Some(self.eval(context))
+    }
+
+    /// Evaluates a number expression.
+    ///
+    /// Lengths inside of functions like `sign()` are resolved using the default [`ResolveContext`].
+    pub fn to_number(&self) -> Option<f64> {
+        self.eval_typed(CalcType::Number)
+    }
+
+    /// Evaluates an angle expression, in degrees.
+    pub fn to_degrees(&self) -> Option<f64> {
+        self.eval_typed(CalcType::Angle)
+    }
+
+    /// Evaluates a time expression, in seconds.
+    pub fn to_seconds(&self) -> Option<f64> {
+        self.eval_typed(CalcType::Time)
+    }
+
+    fn eval_typed(&self, calc_type: CalcType) -> Option<f64> {
+        if self.calc_type()? != calc_type {
+            return None;
+        }
+
+        Some(self.eval(&ResolveContext::default()))
+    }
+
+    /// Evaluates the expression in canonical units, ie. pixels, degrees and seconds.
+    fn eval(&self, context: &ResolveContext) -> f64 {
+        match self {
+            CalcNode::Number(n) | CalcNode::Angle(n) | CalcNode::Time(n) => *n,
+            CalcNode::Percentage(n) => n / 100.0 * context.percentage_base,
+            CalcNode::Length(length) => length.to_px(context),
+            CalcNode::Sum(nodes) => nodes.iter().map(|n| n.eval(context)).sum(),
+            CalcNode::Product(nodes) => nodes.iter().map(|n| n.eval(context)).product(),
+            CalcNode::Negate(node) => -node.eval(context),
+            CalcNode::Invert(node) => 1.0 / node.eval(context),
+            CalcNode::Function(f, args) => {
+                let mut values = [0.0, 1.0, 0.0];
+                for (value, arg) in values.iter_mut().zip(args) {
+                    *value = arg.eval(context);
+                }
+
+                match f {
+                    MathFunction::Min => args
+                        .iter()
+                        .map(|n| n.eval(context))
+                        .fold(f64::NAN, f64::min),
+                    MathFunction::Max => args
+                        .iter()
+                        .map(|n| n.eval(context))
+                        .fold(f64::NAN, f64::max),
+                    _ => {
+                        let is_angle =
+                            args.first().and_then(|n| n.calc_type()) == Some(CalcType::Angle);
+                        apply(*f, values, is_angle)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Checks that a function is a math function, including `calc()`.
+pub(crate) fn is_math_function(name: &str) -> bool {
+    name.eq_ignore_ascii_case("calc") || MathFunction::from_name(name).is_some()
+}
+
+/// Parses a math function.
+///
+/// `keywords` resolves idents, like channel keywords of relative colors.
+pub(crate) fn parse_function(
+    func: &Function<'_>,
+    keywords: &dyn Fn(&str) -> Option<CalcNode>,
+) -> Option<CalcNode> {
+    let node = parse_math_function(func, keywords)?;
+    node.calc_type()?;
+    Some(simplify(node))
+}
+
+fn parse_math_function(
+    func: &Function<'_>,
+    keywords: &dyn Fn(&str) -> Option<CalcNode>,
+) -> Option<CalcNode> {
+    if func.name.eq_ignore_ascii_case("calc") {
+        return parse_sum(&func.arguments, keywords);
+    }
+
+    let mut f = MathFunction::from_name(&func.name)?;
+    let mut items: Vec<_> = func.split_commas().collect();
+
+    if let MathFunction::Round(_) = f {
+        if let Some([ComponentValue::Ident(name)]) = items.first() {
+            if let Some(strategy) = RoundingStrategy::from_name(name) {
+                f = MathFunction::Round(strategy);
+                items.remove(0);
+            }
+        }
+    }
+
+    let mut args = Vec::with_capacity(items.len());
+    for item in items {
+        args.push(parse_sum(item, keywords)?);
+    }
+
+    let count_ok = match f {
+        MathFunction::Min | MathFunction::Max => !args.is_empty(),
+        MathFunction::Clamp => args.len() == 3,
+        MathFunction::Round(_) => args.len() == 1 || args.len() == 2,
+        MathFunction::Mod | MathFunction::Rem | MathFunction::Atan2 => args.len() == 2,
+        _ => args.len() == 1,
+    };
+
+    if !count_ok {
+        return None;
+    }
+
+    Some(CalcNode::Function(f, args))
+}
+
+/// Parses `<calc-product> [ [ '+' | '-' ] <calc-product> ]*`.
+fn parse_sum(
+    components: &[ComponentValue<'_>],
+    keywords: &dyn Fn(&str) -> Option<CalcNode>,
+) -> Option<CalcNode> {
+    let mut nodes = Vec::new();
+    let mut start = 0;
+    let mut negate = false;
+    for (i, c) in components.iter().enumerate() {
+        let is_negative = match c {
+            ComponentValue::Delim('+') => false,
+            ComponentValue::Delim('-') => true,
+            _ => continue,
+        };
+
+        let node = parse_product(&components[start..i], keywords)?;
+        nodes.push(negate_if(node, negate));
+        negate = is_negative;
+        start = i + 1;
+    }
+
+    let node = parse_product(&components[start..], keywords)?;
+    nodes.push(negate_if(node, negate));
+
+    if nodes.len() == 1 {
+        nodes.pop()
+    } else {
+        Some(CalcNode::Sum(nodes))
+    }
+}
+
+fn negate_if(node: CalcNode, negate: bool) -> CalcNode {
+    if negate {
+        CalcNode::Negate(Box::new(node))
+    } else {
+        node
+    }
+}
+
+/// Parses `<calc-value> [ [ '*' | '/' ] <calc-value> ]*`.
+fn parse_product(
+    components: &[ComponentValue<'_>],
+    keywords: &dyn Fn(&str) -> Option<CalcNode>,
+) -> Option<CalcNode> {
+    let (first, mut rest) = components.split_first()?;
+    let mut nodes = Vec::new();
+    nodes.push(parse_value(first, keywords)?);
+
+    while let [op, value, tail @ ..] = rest {
+        let node = parse_value(value, keywords)?;
+        match op {
+            ComponentValue::Delim('*') => nodes.push(node),
+            ComponentValue::Slash => nodes.push(CalcNode::Invert(Box::new(node))),
+            _ => return None,
+        }
+
+        rest = tail;
+    }
+
+    if !rest.is_empty() {
+        return None;
+    }
+
+    if nodes.len() == 1 {
+        nodes.pop()
+    } else {
+        Some(CalcNode::Product(nodes))
+    }
+}
+
+fn parse_value(
+    component: &ComponentValue<'_>,
+    keywords: &dyn Fn(&str) -> Option<CalcNode>,
+) -> Option<CalcNode> {
+    match component {
+        ComponentValue::Number(n) => Some(CalcNode::Number(*n)),
+        ComponentValue::Percentage(n) => Some(CalcNode::Percentage(*n)),
+        ComponentValue::Dimension(n, unit) => dimension(*n, unit),
+        ComponentValue::Ident(name) => constant(name).or_else(|| keywords(name)),
+        ComponentValue::Block(Bracket::Parenthesis, content) => parse_sum(content, keywords),
+        ComponentValue::Function(func) => parse_math_function(func, keywords),
+        _ => None,
+    }
+}
+
+fn dimension(n: f64, unit: &str) -> Option<CalcNode> {
+    if let Some(unit) = LengthUnit::from_name(unit) {
+        Some(CalcNode::Length(Length::new(n, unit)))
+    } else if let Some(degrees) = angle_to_degrees(n, unit) {
+        Some(CalcNode::Angle(degrees))
+    } else if unit.eq_ignore_ascii_case("s") {
+        Some(CalcNode::Time(n))
+    } else if unit.eq_ignore_ascii_case("ms") {
+        Some(CalcNode::Time(n / 1000.0))
+    } else {
+        None
+    }
+}
+
+fn constant(name: &str) -> Option<CalcNode> {
+    let constants = [
+        ("e", E),
+        ("pi", PI),
+        ("infinity", f64::INFINITY),
+        ("-infinity", f64::NEG_INFINITY),
+        ("nan", f64::NAN),
+    ];
+
+    constants
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, value)| CalcNode::Number(*value))
+}
+
+fn fold_types(
+    nodes: &[CalcNode],
+    f: fn(CalcType, CalcType) -> Option<CalcType>,
+) -> Option<CalcType> {
+    let (first, rest) = nodes.split_first()?;
+    let mut calc_type = first.calc_type()?;
+    for node in rest {
+        calc_type = f(calc_type, node.calc_type()?)?;
+    }
+
+    Some(calc_type)
+}
+
+fn function_type(f: MathFunction, args: &[CalcNode]) -> Option<CalcType> {
+    let calc_type = fold_types(args, CalcType::add)?;
+    match f {
+        // Without a step, only numbers can be rounded.
+        MathFunction::Round(_) if args.len() == 1 && calc_type != CalcType::Number => None,
+        MathFunction::Sign => Some(CalcType::Number),
+        MathFunction::Sin | MathFunction::Cos | MathFunction::Tan => match calc_type {
+            CalcType::Number | CalcType::Angle => Some(CalcType::Number),
+            _ => None,
+        },
+        MathFunction::Asin | MathFunction::Acos | MathFunction::Atan => match calc_type {
+            CalcType::Number => Some(CalcType::Angle),
+            _ => None,
+        },
+        MathFunction::Atan2 => Some(CalcType::Angle),
+        _ => Some(calc_type),
+    }
+}
+
+/// Applies a function with up to three arguments.
+///
+/// `min()` and `max()` use only two of them. `is_angle` tells whether the arguments are angles, in degrees.
+fn apply(f: MathFunction, [a, b, c]: [f64; 3], is_angle: bool) -> f64 {
+    let radians = |v: f64| if is_angle { v.to_radians() } else { v };
+    match f {
+        MathFunction::Min => a.min(b),
+        MathFunction::Max => a.max(b),
+        MathFunction::Clamp => b.min(c).max(a),
+        MathFunction::Round(strategy) => round(strategy, a, b),
+        MathFunction::Mod => a - b * (a / b).floor(),
+        MathFunction::Rem => a % b,
+        MathFunction::Abs => {
+            if a < 0.0 {
+                -a
+            } else {
+                a
+            }
+        }
+        MathFunction::Sign => {
+            if a > 0.0 {
+                1.0
+            } else if a < 0.0 {
+                -1.0
+            } else {
+                a
+            }
+        }
+        MathFunction::Sin => radians(a).sin(),
+        MathFunction::Cos => radians(a).cos(),
+        MathFunction::Tan => radians(a).tan(),
+        MathFunction::Asin => a.asin().to_degrees(),
+        MathFunction::Acos => a.acos().to_degrees(),
+        MathFunction::Atan => a.atan().to_degrees(),
+        MathFunction::Atan2 => a.atan2(b).to_degrees(),
+    }
+}
+
+/// Applies `round()`. The step defaults to 1.
+fn round(strategy: RoundingStrategy, value: f64, step: f64) -> f64 {
+    if step == 0.0 {
+        return f64::NAN;
+    }
+
+    let step = if step < 0.0 { -step } else { step };
+    let q = value / step;
+    let rounded = match strategy {
+        RoundingStrategy::Nearest => (q + 0.5).floor(),
+        RoundingStrategy::Up => q.ceil(),
+        RoundingStrategy::ToZero if q < 0.0 => q.ceil(),
+        RoundingStrategy::Down | RoundingStrategy::ToZero => q.floor(),
+    };
+
+    rounded * step
+}
+
+/// Simplifies a type checked expression.
+///
+/// <https://www.w3.org/TR/css-values-4/#calc-simplification>
+fn simplify(node: CalcNode) -> CalcNode {
+    match node {
+        CalcNode::Negate(node) => match simplify(*node) {
+            CalcNode::Negate(node) => *node,
+            node => scale(node, -1.0).unwrap_or_else(|node| CalcNode::Negate(Box::new(node))),
+        },
+        CalcNode::Invert(node) => match simplify(*node) {
+            CalcNode::Number(n) => CalcNode::Number(1.0 / n),
+            CalcNode::Invert(node) => *node,
+            node => CalcNode::Invert(Box::new(node)),
+        },
+        CalcNode::Sum(nodes) => simplify_sum(nodes),
+        CalcNode::Product(nodes) => simplify_product(nodes),
+        CalcNode::Function(f, args) => {
+            let args: Vec<_> = args.into_iter().map(simplify).collect();
+            fold_function(f, &args).unwrap_or(CalcNode::Function(f, args))
+        }
+        leaf => leaf,
+    }
+}
+
+fn simplify_sum(nodes: Vec<CalcNode>) -> CalcNode {
+    let mut result: Vec<CalcNode> = Vec::new();
+    for node in nodes {
+        let children = match simplify(node) {
+            CalcNode::Sum(children) => children,
+            node => alloc::vec![node],
+        };
+
+        for child in children {
+            // Combine leaves of the same unit.
+            let same = result.iter_mut().find_map(|n| match (n, &child) {
+                (CalcNode::Number(a), CalcNode::Number(b))
+                | (CalcNode::Percentage(a), CalcNode::Percentage(b))
+                | (CalcNode::Angle(a), CalcNode::Angle(b))
+                | (CalcNode::Time(a), CalcNode::Time(b)) => Some((a, *b)),
+                (CalcNode::Length(a), CalcNode::Length(b)) if a.unit == b.unit => {
+                    Some((&mut a.value, b.value))
+                }
+                _ => None,
+            });
+
+            match same {
+                Some((a, b)) => *a += b,
+                None => result.push(child),
+            }
+        }
+    }
+
+    if result.len() == 1 {
+        result.remove(0)
+    } else {
+        CalcNode::Sum(result)
+    }
+}
+
+fn simplify_product(nodes: Vec<CalcNode>) -> CalcNode {
+    let mut factor = 1.0;
+    let mut result = Vec::new();
+    for node in nodes {
+        let children = match simplify(node) {
+            CalcNode::Product(children) => children,
+            node => alloc::vec![node],
+        };
+
+        for child in children {
+            match child {
+                CalcNode::Number(n) => factor *= n,
+                child => result.push(child),
+            }
+        }
+    }
+
+    match result.len() {
+        0 => CalcNode::Number(factor),
+        1 => {
+            let node = result.remove(0);
+            scale(node, factor).unwrap_or_else(|node| {
+                CalcNode::Product(alloc::vec![CalcNode::Number(factor), node])
+            })
+        }
+        _ => {
+            result.insert(0, CalcNode::Number(factor));
+            CalcNode::Product(result)
+        }
+    }
+}
+
+/// Multiplies a leaf or a sum of leaves by a number.
+///
+/// Returns the node back when it can't be scaled.
+fn scale(node: CalcNode, factor: f64) -> Result<CalcNode, CalcNode> {
+    match node {
+        CalcNode::Number(n) => Ok(CalcNode::Number(n * factor)),
+        CalcNode::Percentage(n) => Ok(CalcNode::Percentage(n * factor)),
+        CalcNode::Length(l) => Ok(CalcNode::Length(Length::new(l.value * factor, l.unit))),
+        CalcNode::Angle(n) => Ok(CalcNode::Angle(n * factor)),
+        CalcNode::Time(n) => Ok(CalcNode::Time(n * factor)),
+        CalcNode::Sum(nodes) if nodes.iter().all(is_leaf) => {
+            let nodes = nodes
+                .into_iter()
+                .filter_map(|n| scale(n, factor).ok())
+                .collect();
+            Ok(CalcNode::Sum(nodes))
+        }
+        node => Err(node),
+    }
+}
+
+fn is_leaf(node: &CalcNode) -> bool {
+    matches!(
+        node,
+        CalcNode::Number(_)
+            | CalcNode::Percentage(_)
+            | CalcNode::Length(_)
+            | CalcNode::Angle(_)
+            | CalcNode::Time(_)
+    )
+}
+
+/// Evaluates a function when all arguments are leaves of the same unit.
+fn fold_function(f: MathFunction, args: &[CalcNode]) -> Option<CalcNode> {
+    let (first, rest) = args.split_first()?;
+    let same_unit = rest.iter().all(|n| match (first, n) {
+        (CalcNode::Length(a), CalcNode::Length(b)) => a.unit == b.unit,
+        (a, b) => is_leaf(a) && core::mem::discriminant(a) == core::mem::discriminant(b),
+    });
+
+    if !same_unit || !is_leaf(first) {
+        return None;
+    }
+
+    let value = |node: &CalcNode| match node {
+        CalcNode::Length(l) => l.value,
+        CalcNode::Number(n) | CalcNode::Percentage(n) | CalcNode::Angle(n) | CalcNode::Time(n) => {
+            *n
+        }
+        _ => 0.0,
+    };
+
+    let result = match f {
+        MathFunction::Min => args.iter().map(value).fold(f64::NAN, f64::min),
+        MathFunction::Max => args.iter().map(value).fold(f64::NAN, f64::max),
+        _ => {
+            let mut values = [0.0, 1.0, 0.0];
+            for (v, arg) in values.iter_mut().zip(args) {
+                *v = value(arg);
+            }
+
+            apply(f, values, matches!(first, CalcNode::Angle(_)))
+        }
+    };
+
+    let node = match (function_type(f, args)?, first) {
+        (CalcType::Number, _) => CalcNode::Number(result),
+        // Including inverse trigonometric functions of numbers.
+        (CalcType::Angle, _) => CalcNode::Angle(result),
+        (_, CalcNode::Length(l)) => CalcNode::Length(Length::new(result, l.unit)),
+        (_, CalcNode::Percentage(_)) => CalcNode::Percentage(result),
+        (_, CalcNode::Time(_)) => CalcNode::Time(result),
+        _ => return None,
+    };
+
+    Some(node)
+}
@@ -0,0 +1,258 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::str;
+
+use crate::stream::Stream;
+use crate::StyleSheet;
+
+/// A push-based style sheet parser.
+///
+/// Accepts the input in chunks of any size and returns parsed top-level statements,
+/// ie. rule sets and at-rules, as soon as they are complete.
+/// Statements that span chunk boundaries are kept until their end arrives.
+///
+/// # Example
+///
+/// ```
+/// use simplecss::ChunkedParser;
+///
+/// let mut parser = ChunkedParser::new();
+/// parser.feed("a { color: red } b { col");
+///
+/// let a = parser.next_statement().unwrap();
+/// assert_eq!(a.text(), "a { color: red }");
+/// assert!(parser.next_statement().is_none());
+///
+/// parser.feed("or: green }");
+/// parser.finish();
+///
+/// let b = parser.next_statement().unwrap();
+/// assert_eq!(b.offset(), 17);
+/// assert!(parser.next_statement().is_none());
+///
+/// // Statements are owned, so they can be parsed at any time.
+/// assert_eq!(a.parse().rules[0].declarations[0].value, "red");
+/// assert_eq!(b.parse().rules[0].selector.to_string(), "b");
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct ChunkedParser {
+    buffer: String,
+    /// The start of the next statement in the buffer.
+    pos: usize,
+    /// The offset of the buffer start in the whole input.
+    offset: usize,
+    /// The scanning progress of the next statement, once its start is found.
+    scan: Option<Scan>,
+    /// An incomplete UTF-8 sequence at the end of the last byte chunk.
+    utf8_tail: Vec<u8>,
+    finished: bool,
+}
+
+impl ChunkedParser {
+    /// Creates a new parser.
+    pub fn new() -> Self {
+        ChunkedParser::default()
+    }
+
+    /// Appends a text chunk.
+    pub fn feed(&mut self, chunk: &str) {
+        self.flush_utf8_tail();
+        self.buffer.push_str(chunk);
+    }
+
+    /// Appends a UTF-8 chunk.
+    ///
+    /// Chars split between chunks are handled.
+    /// Invalid sequences are replaced with U+FFFD.
+    /// Offsets are relative to the decoded text.
+    pub fn feed_bytes(&mut self, chunk: &[u8]) {
+        let mut bytes = core::mem::take(&mut self.utf8_tail);
+        bytes.extend_from_slice(chunk);
+
+        let mut rest = bytes.as_slice();
+        loop {
+            match str::from_utf8(rest) {
+                Ok(text) => {
+                    self.buffer.push_str(text);
+                    break;
+                }
+                Err(e) => {
+                    let (valid, tail) = rest.split_at(e.valid_up_to());
+                    // Checked by `from_utf8` already.
+                    self.buffer
+                        .push_str(str::from_utf8(valid).unwrap_or_default());
+
+                    match e.error_len() {
+                        Some(len) => {
+                            self.buffer.push('\u{FFFD}');
+                            rest = &tail[len..];
+                        }
+                        None => {
+                            // Wait for the rest of the char.
+                            self.utf8_tail = tail.to_vec();
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Marks the end of the input.
+    ///
+    /// After this, [`next_statement`](Self::next_statement) returns
+    /// the remaining statements even when they are not closed.
+    pub fn finish(&mut self) {
+        self.flush_utf8_tail();
+        self.finished = true;
+    }
+
+    /// Returns the next complete statement, if any.
+    pub fn next_statement(&mut self) -> Option<OwnedStatement> {
+        self.compact();
+
+        let end = self.scan()?;
+        let start = self.pos;
+        self.pos = end;
+        self.scan = None;
+
+        Some(OwnedStatement {
+            text: String::from(&self.buffer[start..end]),
+            offset: self.offset + start,
+        })
+    }
+
+    /// Scans the next statement and returns its end when it's complete.
+    ///
+    /// The scanning continues from where it stopped the last time,
+    /// so each byte is scanned only once, except for a trailing string or comment.
+    fn scan(&mut self) -> Option<usize> {
+        if self.scan.is_none() {
+            let mut s = Stream::from(self.buffer.as_str());
+            s.advance(self.pos);
+            if crate::skip_statement_separators(&mut s).is_err() {
+                // An unclosed comment.
+                if self.finished {
+                    self.pos = self.buffer.len();
+                }
+
+                return None;
+            }
+
+            self.pos = s.pos();
+
+            // Wait for the rest of a separator.
+            let tail = s.slice_tail();
+            let is_separator_start = ["<!--", "-->", "/*"].iter().any(|t| t.starts_with(tail));
+            if tail.is_empty() || (is_separator_start && !self.finished) {
+                return None;
+            }
+
+            self.scan = Some(Scan {
+                pos: self.pos,
+                ..Scan::default()
+            });
+        }
+
+        let scan = self.scan.as_mut()?;
+        let mut s = Stream::from(self.buffer.as_str());
+        s.advance(scan.pos);
+
+        let is_at_rule = self.buffer.as_bytes()[self.pos] == b'@';
+        loop {
+            let complete = if scan.in_block {
+                crate::skip_component_values_in(&mut s, &mut scan.closing, |c| c == b'}')
+            } else {
+                crate::skip_component_values_in(&mut s, &mut scan.closing, |c| {
+                    c == b'{' || (is_at_rule && c == b';')
+                })
+            };
+
+            match s.curr_byte() {
+                Ok(b'{') if !scan.in_block => {
+                    s.advance(1);
+                    scan.in_block = true;
+                }
+                Ok(_) => return Some(s.pos() + 1),
+                Err(_) if self.finished => return Some(s.pos()),
+                Err(_) => {
+                    scan.pos = complete;
+                    return None;
+                }
+            }
+        }
+    }
+
+    /// Removes the already returned text from the buffer.
+    ///
+    /// Done only when it takes most of the buffer,
+    /// so the remaining text is moved a constant number of times on average.
+    fn compact(&mut self) {
+        if self.pos > self.buffer.len() / 2 {
+            self.buffer.drain(..self.pos);
+            self.offset += self.pos;
+            if let Some(scan) = &mut self.scan {
+                scan.pos -= self.pos;
+            }
+            self.pos = 0;
+        }
+    }
+
+    fn flush_utf8_tail(&mut self) {
+        if !self.utf8_tail.is_empty() {
+            self.utf8_tail.clear();
+            self.buffer.push('\u{FFFD}');
+        }
+    }
+}
+
+/// The scanning progress of an incomplete statement.
+#[derive(Clone, Default, Debug)]
+struct Scan {
+    /// The position where the scanning continues.
+    pos: usize,
+    /// A stack of expected closing brackets.
+    closing: Vec<u8>,
+    /// Whether the statement block is reached.
+    in_block: bool,
+}
+
+/// A top-level statement returned by [`ChunkedParser`].
+///
+/// Owns its source, so it can be kept while the parser is fed with more input.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct OwnedStatement {
+    text: String,
+    offset: usize,
+}
+
+impl OwnedStatement {
+    /// Returns the statement source.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the statement offset in the whole input.
+    ///
+    /// Add it to the spans of the parsed nodes to make them absolute.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Checks that the statement is an at-rule.
+    pub fn is_at_rule(&self) -> bool {
+        self.text.starts_with('@')
+    }
+
+    /// Parses the statement.
+    ///
+    /// A rule set with a selector list produces multiple rules,
+    /// and a malformed at-rule produces none.
+    /// Spans are relative to [`text`](Self::text).
+    pub fn parse(&self) -> StyleSheet<'_> {
+        StyleSheet::parse(&self.text)
+    }
+}
@@ -0,0 +1,730 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use core::cmp::Ordering;
+
+use crate::calc::{self, is_math_function, CalcNode};
+use crate::color_space::{HueInterpolation, Space, SpaceColor};
+use crate::value::{ComponentValue, Function, Value};
+
+/// A straight, ie. not premultiplied, sRGB color.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Rgba {
+    /// The red channel.
+    pub red: u8,
+    /// The green channel.
+    pub green: u8,
+    /// The blue channel.
+    pub blue: u8,
+    /// The alpha channel. 255 is opaque.
+    pub alpha: u8,
+}
+
+impl Rgba {
+    /// A fully transparent black.
+    pub const TRANSPARENT: Rgba = Rgba::new(0, 0, 0, 0);
+
+    /// Constructs a new color.
+    pub const fn new(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        Rgba {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+}
+
+/// A color value.
+///
+/// Supports [CSS Color 4](https://www.w3.org/TR/css-color-4/) named colors,
+/// hex colors and the `rgb()`, `rgba()`, `hsl()`, `hsla()` and `hwb()` functions,
+/// in both legacy comma-separated and modern space-separated syntax.
+///
+/// The `lab()`, `lch()`, `oklab()`, `oklch()` and `color()` functions,
+/// [CSS Color 5](https://www.w3.org/TR/css-color-5/) `color-mix()` and relative colors,
+/// like `rgb(from red r g b / 50%)`, are supported as well.
+/// Colors outside of the sRGB gamut are gamut mapped by reducing their `OKLCh` chroma.
+///
+/// # Example
+///
+/// ```
+/// use simplecss::{Color, Rgba};
+///
+/// assert_eq!(Color::parse("#ff000080"), Some(Color::Rgba(Rgba::new(255, 0, 0, 128))));
+/// assert_eq!(Color::parse("rgb(0 128 255 / 50%)"), Some(Color::Rgba(Rgba::new(0, 128, 255, 128))));
+/// assert_eq!(Color::parse("hsl(120, 100%, 25%)"), Some(Color::Rgba(Rgba::new(0, 128, 0, 255))));
+/// assert_eq!(Color::parse("oklch(62.8% 0.2577 29.23)"), Some(Color::Rgba(Rgba::new(255, 0, 0, 255))));
+/// assert_eq!(Color::parse("color-mix(in srgb, red, blue)"), Some(Color::Rgba(Rgba::new(128, 0, 128, 255))));
+/// assert_eq!(Color::parse("currentColor"), Some(Color::CurrentColor));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Color {
+    /// An sRGB color.
+    Rgba(Rgba),
+    /// The `currentColor` keyword.
+    ///
+    /// Resolves to the value of the `color` property.
+    CurrentColor,
+}
+
+impl Color {
+    /// Parses a color from a property value.
+    ///
+    /// The value must consist of a single color.
+    /// Returns `None` on invalid or unsupported colors.
+    pub fn parse(text: &str) -> Option<Self> {
+        let value = Value::parse(text).ok()?;
+        match value.components.as_slice() {
+            [component] => Color::from_component(component),
+            _ => None,
+        }
+    }
+
+    /// Converts a component value into a color.
+    pub fn from_component(component: &ComponentValue<'_>) -> Option<Self> {
+        if component.is_ident("currentcolor") {
+            return Some(Color::CurrentColor);
+        }
+
+        let (rgb, alpha) = parse_color(component)?.to_srgb();
+        let [red, green, blue] = rgb.map(|v| to_u8(v * 255.0));
+        Some(Color::Rgba(Rgba::new(
+            red,
+            green,
+            blue,
+            to_u8(alpha * 255.0),
+        )))
+    }
+}
+
+/// Parses `rgb`, `rrggbb`, `rgba` and `rrggbbaa`.
+fn parse_hex(hex: &str) -> Option<Rgba> {
+    let mut digits = [0; 8];
+    if !matches!(hex.len(), 3 | 4 | 6 | 8) {
+        return None;
+    }
+
+    for (digit, c) in digits.iter_mut().zip(hex.bytes()) {
+        *digit = match c {
+            b'0'..=b'9' => c - b'0',
+            b'a'..=b'f' => c - b'a' + 10,
+            b'A'..=b'F' => c - b'A' + 10,
+            _ => return None,
+        };
+    }
+
+    let short = |i: usize| digits[i] * 17;
+    let long = |i: usize| digits[i * 2] * 16 + digits[i * 2 + 1];
+    let color = match hex.len() {
+        3 => Rgba::new(short(0), short(1), short(2), 255),
+        4 => Rgba::new(short(0), short(1), short(2), short(3)),
+        6 => Rgba::new(long(0), long(1), long(2), 255),
+        _ => Rgba::new(long(0), long(1), long(2), long(3)),
+    };
+
+    Some(color)
+}
+
+fn parse_color(component: &ComponentValue<'_>) -> Option<SpaceColor> {
+    match component {
+        ComponentValue::Ident(name) if name.eq_ignore_ascii_case("transparent") => {
+            Some(rgba_to_space_color(Rgba::TRANSPARENT))
+        }
+        ComponentValue::Ident(name) => named_color(name).map(rgba_to_space_color),
+        ComponentValue::Hash(hex) => parse_hex(hex).map(rgba_to_space_color),
+        ComponentValue::Function(func) if func.name.eq_ignore_ascii_case("color-mix") => {
+            parse_color_mix(func)
+        }
+        ComponentValue::Function(func) => parse_function(func),
+        _ => None,
+    }
+}
+
+fn rgba_to_space_color(color: Rgba) -> SpaceColor {
+    let channel = |v: u8| f64::from(v) / 255.0;
+    SpaceColor {
+        space: Space::Srgb,
+        channels: [
+            channel(color.red),
+            channel(color.green),
+            channel(color.blue),
+        ],
+        alpha: channel(color.alpha),
+    }
+}
+
+/// A color function channel.
+#[derive(Clone, Copy)]
+enum Channel {
+    /// A hue, in degrees.
+    Hue(&'static str),
+    /// A number and the number that `100%` stands for.
+    Number(&'static str, f64),
+}
+
+impl Channel {
+    fn name(self) -> &'static str {
+        match self {
+            Channel::Hue(name) | Channel::Number(name, _) => name,
+        }
+    }
+}
+
+/// A color function syntax.
+#[derive(Clone, Copy)]
+struct Syntax {
+    space: Space,
+    /// Channels, with their relative color keywords.
+    channels: [Channel; 3],
+    /// The number of function units per color space unit.
+    scale: f64,
+    /// Whether the legacy comma-separated syntax is allowed.
+    legacy: bool,
+}
+
+impl Syntax {
+    fn from_function(name: &str) -> Option<Self> {
+        let syntax = |space, channels, scale, legacy| {
+            Some(Syntax {
+                space,
+                channels,
+                scale,
+                legacy,
+            })
+        };
+
+        let is = |n: &str| name.eq_ignore_ascii_case(n);
+        if is("rgb") || is("rgba") {
+            let channels = [
+                Channel::Number("r", 255.0),
+                Channel::Number("g", 255.0),
+                Channel::Number("b", 255.0),
+            ];
+            syntax(Space::Srgb, channels, 255.0, true)
+        } else if is("hsl") || is("hsla") {
+            let channels = [
+                Channel::Hue("h"),
+                Channel::Number("s", 100.0),
+                Channel::Number("l", 100.0),
+            ];
+            syntax(Space::Hsl, channels, 1.0, true)
+        } else if is("hwb") {
+            let channels = [
+                Channel::Hue("h"),
+                Channel::Number("w", 100.0),
+                Channel::Number("b", 100.0),
+            ];
+            syntax(Space::Hwb, channels, 1.0, false)
+        } else if is("lab") {
+            let channels = [
+                Channel::Number("l", 100.0),
+                Channel::Number("a", 125.0),
+                Channel::Number("b", 125.0),
+            ];
+            syntax(Space::Lab, channels, 1.0, false)
+        } else if is("lch") {
+            let channels = [
+                Channel::Number("l", 100.0),
+                Channel::Number("c", 150.0),
+                Channel::Hue("h"),
+            ];
+            syntax(Space::Lch, channels, 1.0, false)
+        } else if is("oklab") {
+            let channels = [
+                Channel::Number("l", 1.0),
+                Channel::Number("a", 0.4),
+                Channel::Number("b", 0.4),
+            ];
+            syntax(Space::Oklab, channels, 1.0, false)
+        } else if is("oklch") {
+            let channels = [
+                Channel::Number("l", 1.0),
+                Channel::Number("c", 0.4),
+                Channel::Hue("h"),
+            ];
+            syntax(Space::Oklch, channels, 1.0, false)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the syntax of a `color()` function color space.
+    fn predefined(space: Space) -> Self {
+        let channels = match space {
+            Space::XyzD50 | Space::XyzD65 => [
+                Channel::Number("x", 1.0),
+                Channel::Number("y", 1.0),
+                Channel::Number("z", 1.0),
+            ],
+            _ => [
+                Channel::Number("r", 1.0),
+                Channel::Number("g", 1.0),
+                Channel::Number("b", 1.0),
+            ],
+        };
+
+        Syntax {
+            space,
+            channels,
+            scale: 1.0,
+            legacy: false,
+        }
+    }
+
+    /// Returns a relative color keyword value.
+    fn keyword(&self, name: &str, origin: &SpaceColor) -> Option<f64> {
+        let or_zero = |v: f64| if v.is_nan() { 0.0 } else { v };
+        if name.eq_ignore_ascii_case("alpha") {
+            return Some(or_zero(origin.alpha));
+        }
+
+        let idx = self
+            .channels
+            .iter()
+            .position(|c| c.name().eq_ignore_ascii_case(name))?;
+        let value = or_zero(origin.channels[idx]);
+        match self.channels[idx] {
+            Channel::Hue(_) => Some(value),
+            Channel::Number(..) => Some(value * self.scale),
+        }
+    }
+
+    /// Returns a channel value in color space units.
+    fn channel(
+        &self,
+        idx: usize,
+        component: &ComponentValue<'_>,
+        origin: Option<&SpaceColor>,
+    ) -> Option<f64> {
+        if let Some(value) = self.calc(component, origin) {
+            return self.channel(idx, &value?, None);
+        }
+
+        let value = match (self.channels[idx], component) {
+            (_, c) if c.is_ident("none") => return Some(f64::NAN),
+            (_, ComponentValue::Ident(name)) => self.keyword(name, origin?)?,
+            (_, ComponentValue::Number(n)) => *n,
+            (Channel::Number(_, full), ComponentValue::Percentage(n)) => n / 100.0 * full,
+            (Channel::Hue(_), ComponentValue::Dimension(n, unit)) => angle_to_degrees(*n, unit)?,
+            _ => return None,
+        };
+
+        match self.channels[idx] {
+            Channel::Hue(_) => Some(value),
+            Channel::Number(..) => Some(value / self.scale),
+        }
+    }
+
+    fn alpha(&self, component: &ComponentValue<'_>, origin: Option<&SpaceColor>) -> Option<f64> {
+        if let Some(value) = self.calc(component, origin) {
+            return self.alpha(&value?, None);
+        }
+
+        match component {
+            c if c.is_ident("none") => Some(f64::NAN),
+            ComponentValue::Ident(name) => self.keyword(name, origin?),
+            ComponentValue::Number(n) => Some(*n),
+            ComponentValue::Percentage(n) => Some(n / 100.0),
+            _ => None,
+        }
+    }
+
+    /// Evaluates a math function, with relative color keywords as numbers.
+    ///
+    /// Returns `None` when the component is not a math function
+    /// and `Some(None)` when it's invalid.
+    fn calc(
+        &self,
+        component: &ComponentValue<'_>,
+        origin: Option<&SpaceColor>,
+    ) -> Option<Option<ComponentValue<'static>>> {
+        let func = match component {
+            ComponentValue::Function(func) if is_math_function(&func.name) => func,
+            _ => return None,
+        };
+
+        let keywords = |name: &str| self.keyword(name, origin?).map(CalcNode::Number);
+        let value = match calc::parse_function(func, &keywords) {
+            Some(CalcNode::Number(n)) => Some(ComponentValue::Number(n)),
+            Some(CalcNode::Percentage(n)) => Some(ComponentValue::Percentage(n)),
+            Some(CalcNode::Angle(n)) => Some(ComponentValue::Dimension(n, "deg".into())),
+            _ => None,
+        };
+
+        Some(value)
+    }
+
+    /// Clamps channels to their valid ranges.
+    fn finish(&self, mut channels: [f64; 3], alpha: f64) -> SpaceColor {
+        let [lightness, chroma, _] = &mut channels;
+        match self.space {
+            Space::Lab | Space::Lch => *lightness = lightness.clamp(0.0, 100.0),
+            Space::Oklab | Space::Oklch => *lightness = lightness.clamp(0.0, 1.0),
+            _ => {}
+        }
+
+        if matches!(self.space, Space::Lch | Space::Oklch) && *chroma < 0.0 {
+            *chroma = 0.0;
+        }
+
+        SpaceColor {
+            space: self.space,
+            channels,
+            alpha: alpha.clamp(0.0, 1.0),
+        }
+    }
+}
+
+/// Parses a color function, including relative colors.
+fn parse_function(func: &Function<'_>) -> Option<SpaceColor> {
+    let mut args = func.arguments.as_slice();
+
+    let mut origin = None;
+    if let [from, color, rest @ ..] = args {
+        if from.is_ident("from") {
+            origin = Some(parse_color(color)?);
+            args = rest;
+        }
+    }
+
+    let syntax = if func.name.eq_ignore_ascii_case("color") {
+        let (space, rest) = args.split_first()?;
+        args = rest;
+        Syntax::predefined(Space::from_predefined(space.as_ident()?)?)
+    } else {
+        Syntax::from_function(&func.name)?
+    };
+
+    if args.contains(&ComponentValue::Comma) {
+        if origin.is_some() || !syntax.legacy {
+            return None;
+        }
+
+        return parse_legacy_function(&syntax, func);
+    }
+
+    let origin = origin.map(|c| c.convert(syntax.space));
+    let origin = origin.as_ref();
+    let (a, b, c, alpha) = match args {
+        [a, b, c] => (a, b, c, None),
+        [a, b, c, ComponentValue::Slash, alpha] => (a, b, c, Some(alpha)),
+        _ => return None,
+    };
+
+    let channels = [
+        syntax.channel(0, a, origin)?,
+        syntax.channel(1, b, origin)?,
+        syntax.channel(2, c, origin)?,
+    ];
+
+    let alpha = match alpha {
+        Some(alpha) => syntax.alpha(alpha, origin)?,
+        None => origin.map_or(1.0, |origin| origin.alpha),
+    };
+
+    Some(syntax.finish(channels, alpha))
+}
+
+/// Parses `a, b, c[, alpha]`.
+fn parse_legacy_function(syntax: &Syntax, func: &Function<'_>) -> Option<SpaceColor> {
+    let mut items: [Option<ComponentValue<'_>>; 4] = Default::default();
+    let mut count = 0;
+    for item in func.split_commas() {
+        match item {
+            [component] if !component.is_ident("none") => {
+                // Math functions are resolved first, so their results pass the type checks.
+                let component = match syntax.calc(component, None) {
+                    Some(value) => value?,
+                    None => component.clone(),
+                };
+
+                *items.get_mut(count)? = Some(component);
+                count += 1;
+            }
+            _ => return None,
+        }
+    }
+
+    let (channels, alpha) = match items {
+        [Some(a), Some(b), Some(c), alpha] => ([a, b, c], alpha),
+        _ => return None,
+    };
+
+    let is_number = |c: &ComponentValue<'_>| matches!(c, ComponentValue::Number(_));
+    let is_percentage = |c: &ComponentValue<'_>| matches!(c, ComponentValue::Percentage(_));
+    let valid = match syntax.space {
+        // Either all numbers or all percentages.
+        Space::Srgb => channels.iter().all(is_number) || channels.iter().all(is_percentage),
+        // Percentages only, except the hue.
+        _ => channels[1..].iter().all(is_percentage),
+    };
+
+    if !valid {
+        return None;
+    }
+
+    let channels = [
+        syntax.channel(0, &channels[0], None)?,
+        syntax.channel(1, &channels[1], None)?,
+        syntax.channel(2, &channels[2], None)?,
+    ];
+
+    let alpha = match alpha {
+        Some(alpha) => syntax.alpha(&alpha, None)?,
+        None => 1.0,
+    };
+
+    Some(syntax.finish(channels, alpha))
+}
+
+/// Parses `color-mix(in <space> [<method> hue], <color> [<percentage>], <color> [<percentage>])`.
+///
+/// <https://www.w3.org/TR/css-color-5/#color-mix>
+fn parse_color_mix(func: &Function<'_>) -> Option<SpaceColor> {
+    let mut items = func.split_commas();
+    let (space, hue) = match items.next()? {
+        [keyword, space] if keyword.is_ident("in") => (
+            Space::from_interpolation(space.as_ident()?)?,
+            HueInterpolation::Shorter,
+        ),
+        [keyword, space, method, hue] if keyword.is_ident("in") && hue.is_ident("hue") => {
+            let space = Space::from_interpolation(space.as_ident()?)?;
+            space.hue_index()?;
+            (space, HueInterpolation::from_name(method.as_ident()?)?)
+        }
+        _ => return None,
+    };
+
+    let (first, p1) = parse_mix_item(items.next()?)?;
+    let (second, p2) = parse_mix_item(items.next()?)?;
+    if items.next().is_some() {
+        return None;
+    }
+
+    let (p1, p2) = match (p1, p2) {
+        (None, None) => (50.0, 50.0),
+        (Some(p1), None) => (p1, 100.0 - p1),
+        (None, Some(p2)) => (100.0 - p2, p2),
+        (Some(p1), Some(p2)) => (p1, p2),
+    };
+
+    let sum = p1 + p2;
+    if sum <= 0.0 {
+        return None;
+    }
+
+    let mut color = first
+        .convert(space)
+        .mix(second.convert(space), p2 / sum, hue);
+
+    // Percentages that sum to less than 100% make the result transparent.
+    if sum < 100.0 {
+        color.alpha *= sum / 100.0;
+    }
+
+    Some(color)
+}
+
+fn parse_mix_item(item: &[ComponentValue<'_>]) -> Option<(SpaceColor, Option<f64>)> {
+    let (color, percentage) = match item {
+        [color] => return Some((parse_color(color)?, None)),
+        [color, ComponentValue::Percentage(p)] | [ComponentValue::Percentage(p), color] => {
+            (color, *p)
+        }
+        _ => return None,
+    };
+
+    if !(0.0..=100.0).contains(&percentage) {
+        return None;
+    }
+
+    Some((parse_color(color)?, Some(percentage)))
+}
+
+/// Returns an angle in degrees.
+pub(crate) fn angle_to_degrees(n: f64, unit: &str) -> Option<f64> {
+    if unit.eq_ignore_ascii_case("deg") {
+        Some(n)
+    } else if unit.eq_ignore_ascii_case("grad") {
+        Some(n * 0.9)
+    } else if unit.eq_ignore_ascii_case("rad") {
+        Some(n.to_degrees())
+    } else if unit.eq_ignore_ascii_case("turn") {
+        Some(n * 360.0)
+    } else {
+        None
+    }
+}
+
+/// Rounds and clamps a channel value.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn to_u8(v: f64) -> u8 {
+    // NaN becomes 0.
+    (v.clamp(0.0, 255.0) + 0.5) as u8
+}
+
+fn named_color(name: &str) -> Option<Rgba> {
+    let idx = NAMED_COLORS
+        .binary_search_by(|(n, _)| cmp_ignore_ascii_case(n, name))
+        .ok()?;
+    let [red, green, blue] = NAMED_COLORS[idx].1;
+    Some(Rgba::new(red, green, blue, 255))
+}
+
+/// Compares a lowercase `a` with `b`, ignoring ASCII case.
+pub(crate) fn cmp_ignore_ascii_case(a: &str, b: &str) -> Ordering {
+    a.bytes().cmp(b.bytes().map(|c| c.to_ascii_lowercase()))
+}
+
+/// <https://www.w3.org/TR/css-color-4/#named-colors>
+static NAMED_COLORS: &[(&str, [u8; 3])] = &[
+    ("aliceblue", [240, 248, 255]),
+    ("antiquewhite", [250, 235, 215]),
+    ("aqua", [0, 255, 255]),
+    ("aquamarine", [127, 255, 212]),
+    ("azure", [240, 255, 255]),
+    ("beige", [245, 245, 220]),
+    ("bisque", [255, 228, 196]),
+    ("black", [0, 0, 0]),
+    ("blanchedalmond", [255, 235, 205]),
+    ("blue", [0, 0, 255]),
+    ("blueviolet", [138, 43, 226]),
+    ("brown", [165, 42, 42]),
+    ("burlywood", [222, 184, 135]),
+    ("cadetblue", [95, 158, 160]),
+    ("chartreuse", [127, 255, 0]),
+    ("chocolate", [210, 105, 30]),
+    ("coral", [255, 127, 80]),
+    ("cornflowerblue", [100, 149, 237]),
+    ("cornsilk", [255, 248, 220]),
+    ("crimson", [220, 20, 60]),
+    ("cyan", [0, 255, 255]),
+    ("darkblue", [0, 0, 139]),
+    ("darkcyan", [0, 139, 139]),
+    ("darkgoldenrod", [184, 134, 11]),
+    ("darkgray", [169, 169, 169]),
+    ("darkgreen", [0, 100, 0]),
+    ("darkgrey", [169, 169, 169]),
+    ("darkkhaki", [189, 183, 107]),
+    ("darkmagenta", [139, 0, 139]),
+    ("darkolivegreen", [85, 107, 47]),
+    ("darkorange", [255, 140, 0]),
+    ("darkorchid", [153, 50, 204]),
+    ("darkred", [139, 0, 0]),
+    ("darksalmon", [233, 150, 122]),
+    ("darkseagreen", [143, 188, 143]),
+    ("darkslateblue", [72, 61, 139]),
+    ("darkslategray", [47, 79, 79]),
+    ("darkslategrey", [47, 79, 79]),
+    ("darkturquoise", [0, 206, 209]),
+    ("darkviolet", [148, 0, 211]),
+    ("deeppink", [255, 20, 147]),
+    ("deepskyblue", [0, 191, 255]),
+    ("dimgray", [105, 105, 105]),
+    ("dimgrey", [105, 105, 105]),
+    ("dodgerblue", [30, 144, 255]),
+    ("firebrick", [178, 34, 34]),
+    ("floralwhite", [255, 250, 240]),
+    ("forestgreen", [34, 139, 34]),
+    ("fuchsia", [255, 0, 255]),
+    ("gainsboro", [220, 220, 220]),
+    ("ghostwhite", [248, 248, 255]),
+    ("gold", [255, 215, 0]),
+    ("goldenrod", [218, 165, 32]),
+    ("gray", [128, 128, 128]),
+    ("green", [0, 128, 0]),
+    ("greenyellow", [173, 255, 47]),
+    ("grey", [128, 128, 128]),
+    ("honeydew", [240, 255, 240]),
+    ("hotpink", [255, 105, 180]),
+    ("indianred", [205, 92, 92]),
+    ("indigo", [75, 0, 130]),
+    ("ivory", [255, 255, 240]),
+    ("khaki", [240, 230, 140]),
+    ("lavender", [230, 230, 250]),
+    ("lavenderblush", [255, 240, 245]),
+    ("lawngreen", [124, 252, 0]),
+    ("lemonchiffon", [255, 250, 205]),
+    ("lightblue", [173, 216, 230]),
+    ("lightcoral", [240, 128, 128]),
+    ("lightcyan", [224, 255, 255]),
+    ("lightgoldenrodyellow", [250, 250, 210]),
+    ("lightgray", [211, 211, 211]),
+    ("lightgreen", [144, 238, 144]),
+    ("lightgrey", [211, 211, 211]),
+    ("lightpink", [255, 182, 193]),
+    ("lightsalmon", [255, 160, 122]),
+    ("lightseagreen", [32, 178, 170]),
+    ("lightskyblue", [135, 206, 250]),
+    ("lightslategray", [119, 136, 153]),
+    ("lightslategrey", [119, 136, 153]),
+    ("lightsteelblue", [176, 196, 222]),
+    ("lightyellow", [255, 255, 224]),
+    ("lime", [0, 255, 0]),
+    ("limegreen", [50, 205, 50]),
+    ("linen", [250, 240, 230]),
+    ("magenta", [255, 0, 255]),
+    ("maroon", [128, 0, 0]),
+    ("mediumaquamarine", [102, 205, 170]),
+    ("mediumblue", [0, 0, 205]),
+    ("mediumorchid", [186, 85, 211]),
+    ("mediumpurple", [147, 112, 219]),
+    ("mediumseagreen", [60, 179, 113]),
+    ("mediumslateblue", [123, 104, 238]),
+    ("mediumspringgreen", [0, 250, 154]),
+    ("mediumturquoise", [72, 209, 204]),
+    ("mediumvioletred", [199, 21, 133]),
+    ("midnightblue", [25, 25, 112]),
+    ("mintcream", [245, 255, 250]),
+    ("mistyrose", [255, 228, 225]),
+    ("moccasin", [255, 228, 181]),
+    ("navajowhite", [255, 222, 173]),
+    ("navy", [0, 0, 128]),
+    ("oldlace", [253, 245, 230]),
+    ("olive", [128, 128, 0]),
+    ("olivedrab", [107, 142, 35]),
+    ("orange", [255, 165, 0]),
+    ("orangered", [255, 69, 0]),
+    ("orchid", [218, 112, 214]),
+    ("palegoldenrod", [238, 232, 170]),
+    ("palegreen", [152, 251, 152]),
+    ("paleturquoise", [175, 238, 238]),
+    ("palevioletred", [219, 112, 147]),
+    ("papayawhip", [255, 239, 213]),
+    ("peachpuff", [255, 218, 185]),
+    ("peru", [205, 133, 63]),
+    ("pink", [255, 192, 203]),
+    ("plum", [221, 160, 221]),
+    ("powderblue", [176, 224, 230]),
+    ("purple", [128, 0, 128]),
+    ("rebeccapurple", [102, 51, 153]),
+    ("red", [255, 0, 0]),
+    ("rosybrown", [188, 143, 143]),
+    ("royalblue", [65, 105, 225]),
+    ("saddlebrown", [139, 69, 19]),
+    ("salmon", [250, 128, 114]),
+    ("sandybrown", [244, 164, 96]),
+    ("seagreen", [46, 139, 87]),
+    ("seashell", [255, 245, 238]),
+    ("sienna", [160, 82, 45]),
+    ("silver", [192, 192, 192]),
+    ("skyblue", [135, 206, 235]),
+    ("slateblue", [106, 90, 205]),
+    ("slategray", [112, 128, 144]),
+    ("slategrey", [112, 128, 144]),
+    ("snow", [255, 250, 250]),
+    ("springgreen", [0, 255, 127]),
+    ("steelblue", [70, 130, 180]),
+    ("tan", [210, 180, 140]),
+    ("teal", [0, 128, 128]),
+    ("thistle", [216, 191, 216]),
+    ("tomato", [255, 99, 71]),
+    ("turquoise", [64, 224, 208]),
+    ("violet", [238, 130, 238]),
+    ("wheat", [245, 222, 179]),
+    ("white", [255, 255, 255]),
+    ("whitesmoke", [245, 245, 245]),
+    ("yellow", [255, 255, 0]),
+    ("yellowgreen", [154, 205, 50]),
+];
@@ -0,0 +1,712 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Color space conversions.
+//!
+//! <https://www.w3.org/TR/css-color-4/#color-conversion-code>
+
+#[cfg(not(feature = "std"))]
+use crate::float_funcs::FloatFuncs;
+
+/// A color space.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Space {
+    Srgb,
+    SrgbLinear,
+    DisplayP3,
+    A98Rgb,
+    ProphotoRgb,
+    Rec2020,
+    XyzD50,
+    XyzD65,
+    Lab,
+    Lch,
+    Oklab,
+    Oklch,
+    Hsl,
+    Hwb,
+}
+
+impl Space {
+    /// Returns a predefined color space of the `color()` function.
+    pub(crate) fn from_predefined(name: &str) -> Option<Self> {
+        let spaces = [
+            ("srgb", Space::Srgb),
+            ("srgb-linear", Space::SrgbLinear),
+            ("display-p3", Space::DisplayP3),
+            ("a98-rgb", Space::A98Rgb),
+            ("prophoto-rgb", Space::ProphotoRgb),
+            ("rec2020", Space::Rec2020),
+            ("xyz", Space::XyzD65),
+            ("xyz-d50", Space::XyzD50),
+            ("xyz-d65", Space::XyzD65),
+        ];
+
+        spaces
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, space)| *space)
+    }
+
+    /// Returns an interpolation color space of `color-mix()`.
+    pub(crate) fn from_interpolation(name: &str) -> Option<Self> {
+        let spaces = [
+            ("lab", Space::Lab),
+            ("lch", Space::Lch),
+            ("oklab", Space::Oklab),
+            ("oklch", Space::Oklch),
+            ("hsl", Space::Hsl),
+            ("hwb", Space::Hwb),
+        ];
+
+        spaces
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, space)| *space)
+            .or_else(|| Space::from_predefined(name))
+    }
+
+    /// Returns the hue channel index of a cylindrical color space.
+    pub(crate) fn hue_index(self) -> Option<usize> {
+        match self {
+            Space::Hsl | Space::Hwb => Some(0),
+            Space::Lch | Space::Oklch => Some(2),
+            _ => None,
+        }
+    }
+
+    fn is_srgb_based(self) -> bool {
+        matches!(self, Space::Srgb | Space::Hsl | Space::Hwb)
+    }
+}
+
+/// A color in a specific color space.
+///
+/// Missing components, ie. `none`, are NaN.
+/// Channels use the units of the color functions, like 0..100 for HSL saturation,
+/// except sRGB channels, which are in 0..1.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct SpaceColor {
+    pub(crate) space: Space,
+    pub(crate) channels: [f64; 3],
+    pub(crate) alpha: f64,
+}
+
+impl SpaceColor {
+    /// Converts the color into another color space.
+    ///
+    /// Missing components are preserved only when the color space is the same.
+    pub(crate) fn convert(self, space: Space) -> Self {
+        if self.space == space {
+            return self;
+        }
+
+        let channels = or_zero(self.channels);
+        let channels = if self.space.is_srgb_based() && space.is_srgb_based() {
+            // Avoid round-trip errors.
+            from_srgb(space, to_srgb(self.space, channels))
+        } else {
+            from_xyz(space, to_xyz(self.space, channels))
+        };
+
+        SpaceColor {
+            space,
+            channels,
+            alpha: self.alpha,
+        }
+    }
+
+    /// Returns gamma encoded sRGB channels in 0..1 and alpha.
+    ///
+    /// sRGB-based colors are clipped, other colors are gamut mapped.
+    pub(crate) fn to_srgb(self) -> ([f64; 3], f64) {
+        let channels = or_zero(self.channels);
+        let rgb = if self.space.is_srgb_based() {
+            to_srgb(self.space, channels)
+        } else {
+            gamut_map(to_xyz(self.space, channels))
+        };
+
+        let alpha = if self.alpha.is_nan() { 0.0 } else { self.alpha };
+        (clip(rgb), alpha.clamp(0.0, 1.0))
+    }
+}
+
+fn or_zero(channels: [f64; 3]) -> [f64; 3] {
+    channels.map(|v| if v.is_nan() { 0.0 } else { v })
+}
+
+fn clip(rgb: [f64; 3]) -> [f64; 3] {
+    rgb.map(|v| v.clamp(0.0, 1.0))
+}
+
+/// Converts an sRGB-based color into sRGB.
+fn to_srgb(space: Space, c: [f64; 3]) -> [f64; 3] {
+    match space {
+        Space::Hsl => hsl_to_rgb(c[0], c[1] / 100.0, c[2] / 100.0),
+        Space::Hwb => hwb_to_rgb(c[0], c[1] / 100.0, c[2] / 100.0),
+        _ => c,
+    }
+}
+
+/// Converts sRGB into an sRGB-based color.
+fn from_srgb(space: Space, c: [f64; 3]) -> [f64; 3] {
+    match space {
+        Space::Hsl => rgb_to_hsl(c),
+        Space::Hwb => rgb_to_hwb(c),
+        _ => c,
+    }
+}
+
+fn to_xyz(space: Space, c: [f64; 3]) -> [f64; 3] {
+    match space {
+        Space::Srgb => rgb_to_xyz(&SRGB, D65, c.map(srgb_to_linear)),
+        Space::SrgbLinear => rgb_to_xyz(&SRGB, D65, c),
+        Space::DisplayP3 => rgb_to_xyz(&DISPLAY_P3, D65, c.map(srgb_to_linear)),
+        Space::A98Rgb => rgb_to_xyz(&A98_RGB, D65, c.map(a98_to_linear)),
+        Space::ProphotoRgb => adapt(
+            D50,
+            D65,
+            rgb_to_xyz(&PROPHOTO_RGB, D50, c.map(prophoto_to_linear)),
+        ),
+        Space::Rec2020 => rgb_to_xyz(&REC2020, D65, c.map(rec2020_to_linear)),
+        Space::XyzD50 => adapt(D50, D65, c),
+        Space::XyzD65 => c,
+        Space::Lab => adapt(D50, D65, lab_to_xyz(c)),
+        Space::Lch => to_xyz(Space::Lab, polar_to_rect(c)),
+        Space::Oklab => rgb_to_xyz(&SRGB, D65, oklab_to_linear_srgb(c)),
+        Space::Oklch => to_xyz(Space::Oklab, polar_to_rect(c)),
+        Space::Hsl | Space::Hwb => to_xyz(Space::Srgb, to_srgb(space, c)),
+    }
+}
+
+fn from_xyz(space: Space, c: [f64; 3]) -> [f64; 3] {
+    match space {
+        Space::Srgb => xyz_to_rgb(&SRGB, D65, c).map(srgb_from_linear),
+        Space::SrgbLinear => xyz_to_rgb(&SRGB, D65, c),
+        Space::DisplayP3 => xyz_to_rgb(&DISPLAY_P3, D65, c).map(srgb_from_linear),
+        Space::A98Rgb => xyz_to_rgb(&A98_RGB, D65, c).map(a98_from_linear),
+        Space::ProphotoRgb => {
+            xyz_to_rgb(&PROPHOTO_RGB, D50, adapt(D65, D50, c)).map(prophoto_from_linear)
+        }
+        Space::Rec2020 => xyz_to_rgb(&REC2020, D65, c).map(rec2020_from_linear),
+        Space::XyzD50 => adapt(D65, D50, c),
+        Space::XyzD65 => c,
+        Space::Lab => xyz_to_lab(adapt(D65, D50, c)),
+        Space::Lch => rect_to_polar(from_xyz(Space::Lab, c), LCH_ACHROMATIC),
+        Space::Oklab => linear_srgb_to_oklab(xyz_to_rgb(&SRGB, D65, c)),
+        Space::Oklch => rect_to_polar(from_xyz(Space::Oklab, c), OKLCH_ACHROMATIC),
+        Space::Hsl | Space::Hwb => from_srgb(space, from_xyz(Space::Srgb, c)),
+    }
+}
+
+/// Maps a color into the sRGB gamut by reducing its `OKLCh` chroma.
+///
+/// <https://www.w3.org/TR/css-color-4/#binsearch>
+fn gamut_map(xyz: [f64; 3]) -> [f64; 3] {
+    const JND: f64 = 0.02;
+    const EPSILON: f64 = 0.0001;
+
+    let rgb = from_xyz(Space::Srgb, xyz);
+    if in_gamut(rgb) {
+        return rgb;
+    }
+
+    let [lightness, origin_chroma, hue] = or_zero(from_xyz(Space::Oklch, xyz));
+    if lightness >= 1.0 {
+        return [1.0; 3];
+    } else if lightness <= 0.0 {
+        return [0.0; 3];
+    }
+
+    let with_chroma = |c: f64| from_xyz(Space::Srgb, to_xyz(Space::Oklch, [lightness, c, hue]));
+
+    let mut clipped = clip(rgb);
+    if delta_eok(clipped, rgb) < JND {
+        return clipped;
+    }
+
+    let mut min = 0.0;
+    let mut max = origin_chroma;
+    let mut min_in_gamut = true;
+    while max - min > EPSILON {
+        let chroma = (min + max) / 2.0;
+        let current = with_chroma(chroma);
+        if min_in_gamut && in_gamut(current) {
+            min = chroma;
+            continue;
+        }
+
+        clipped = clip(current);
+        let e = delta_eok(clipped, current);
+        if e < JND {
+            if JND - e < EPSILON {
+                break;
+            }
+
+            min_in_gamut = false;
+            min = chroma;
+        } else {
+            max = chroma;
+        }
+    }
+
+    clipped
+}
+
+fn in_gamut(rgb: [f64; 3]) -> bool {
+    const EPSILON: f64 = 0.0001;
+    rgb.iter().all(|v| (-EPSILON..=1.0 + EPSILON).contains(v))
+}
+
+/// Returns the distance between two sRGB colors in `OKLab`.
+fn delta_eok(a: [f64; 3], b: [f64; 3]) -> f64 {
+    let a = linear_srgb_to_oklab(a.map(srgb_to_linear));
+    let b = linear_srgb_to_oklab(b.map(srgb_to_linear));
+    let [dl, da, db] = [a[0] - b[0], a[1] - b[1], a[2] - b[2]];
+    (dl * dl + da * da + db * db).sqrt()
+}
+
+/// Chroma below which the LCH hue is powerless.
+const LCH_ACHROMATIC: f64 = 0.0015;
+/// Chroma below which the `OKLCh` hue is powerless.
+const OKLCH_ACHROMATIC: f64 = 0.000004;
+
+fn polar_to_rect([lightness, chroma, hue]: [f64; 3]) -> [f64; 3] {
+    let hue = hue.to_radians();
+    [lightness, chroma * hue.cos(), chroma * hue.sin()]
+}
+
+fn rect_to_polar([lightness, a, b]: [f64; 3], achromatic: f64) -> [f64; 3] {
+    let chroma = (a * a + b * b).sqrt();
+    let hue = if chroma < achromatic {
+        f64::NAN
+    } else {
+        normalize_hue(b.atan2(a).to_degrees())
+    };
+
+    [lightness, chroma, hue]
+}
+
+/// Returns a hue in 0..360 degrees.
+pub(crate) fn normalize_hue(hue: f64) -> f64 {
+    let hue = hue % 360.0;
+    if hue < 0.0 {
+        hue + 360.0
+    } else {
+        hue
+    }
+}
+
+/// <https://www.w3.org/TR/css-color-4/#hsl-to-rgb>
+fn hsl_to_rgb(hue: f64, saturation: f64, lightness: f64) -> [f64; 3] {
+    let hue = normalize_hue(hue);
+    let saturation = saturation.clamp(0.0, 1.0);
+    let lightness = lightness.clamp(0.0, 1.0);
+
+    let f = |n: f64| {
+        let k = (n + hue / 30.0) % 12.0;
+        let a = saturation * lightness.min(1.0 - lightness);
+        lightness - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+    };
+
+    [f(0.0), f(8.0), f(4.0)]
+}
+
+/// <https://www.w3.org/TR/css-color-4/#rgb-to-hsl>
+fn rgb_to_hsl([red, green, blue]: [f64; 3]) -> [f64; 3] {
+    let max = red.max(green).max(blue);
+    let min = red.min(green).min(blue);
+    let lightness = (min + max) / 2.0;
+    let d = max - min;
+
+    let mut hue = f64::NAN;
+    let mut saturation = 0.0;
+    if d != 0.0 {
+        if lightness != 0.0 && lightness != 1.0 {
+            saturation = (max - lightness) / lightness.min(1.0 - lightness);
+        }
+
+        hue = if max == red {
+            (green - blue) / d + if green < blue { 6.0 } else { 0.0 }
+        } else if max == green {
+            (blue - red) / d + 2.0
+        } else {
+            (red - green) / d + 4.0
+        };
+        hue *= 60.0;
+    }
+
+    // Out of gamut colors can have a negative saturation.
+    if saturation < 0.0 {
+        hue += 180.0;
+        saturation = -saturation;
+    }
+
+    if saturation == 0.0 {
+        hue = f64::NAN;
+    }
+
+    [normalize_hue(hue), saturation * 100.0, lightness * 100.0]
+}
+
+/// <https://www.w3.org/TR/css-color-4/#hwb-to-rgb>
+fn hwb_to_rgb(hue: f64, whiteness: f64, blackness: f64) -> [f64; 3] {
+    let whiteness = whiteness.clamp(0.0, 1.0);
+    let blackness = blackness.clamp(0.0, 1.0);
+    if whiteness + blackness >= 1.0 {
+        let gray = whiteness / (whiteness + blackness);
+        return [gray; 3];
+    }
+
+    hsl_to_rgb(hue, 1.0, 0.5).map(|v| v * (1.0 - whiteness - blackness) + whiteness)
+}
+
+fn rgb_to_hwb(rgb: [f64; 3]) -> [f64; 3] {
+    let [mut hue, _, _] = rgb_to_hsl(rgb);
+    let whiteness = rgb[0].min(rgb[1]).min(rgb[2]);
+    let blackness = 1.0 - rgb[0].max(rgb[1]).max(rgb[2]);
+    if whiteness + blackness >= 1.0 {
+        hue = f64::NAN;
+    }
+
+    [hue, whiteness * 100.0, blackness * 100.0]
+}
+
+const LAB_KAPPA: f64 = 24389.0 / 27.0;
+const LAB_EPSILON: f64 = 216.0 / 24389.0;
+
+fn lab_to_xyz([lightness, a, b]: [f64; 3]) -> [f64; 3] {
+    let fy = (lightness + 16.0) / 116.0;
+    let fx = a / 500.0 + fy;
+    let fz = fy - b / 200.0;
+
+    let f_inv = |f: f64| {
+        let cube = f * f * f;
+        if cube > LAB_EPSILON {
+            cube
+        } else {
+            (116.0 * f - 16.0) / LAB_KAPPA
+        }
+    };
+
+    let y = if lightness > LAB_KAPPA * LAB_EPSILON {
+        fy * fy * fy
+    } else {
+        lightness / LAB_KAPPA
+    };
+
+    [f_inv(fx) * D50[0], y * D50[1], f_inv(fz) * D50[2]]
+}
+
+fn xyz_to_lab(xyz: [f64; 3]) -> [f64; 3] {
+    let f = |v: f64| {
+        if v > LAB_EPSILON {
+            v.cbrt()
+        } else {
+            (LAB_KAPPA * v + 16.0) / 116.0
+        }
+    };
+
+    let fx = f(xyz[0] / D50[0]);
+    let fy = f(xyz[1] / D50[1]);
+    let fz = f(xyz[2] / D50[2]);
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// <https://bottosson.github.io/posts/oklab/>
+fn linear_srgb_to_oklab([r, g, b]: [f64; 3]) -> [f64; 3] {
+    let l = (0.412_221_470_8 * r + 0.536_332_536_3 * g + 0.051_445_992_9 * b).cbrt();
+    let m = (0.211_903_498_2 * r + 0.680_699_545_1 * g + 0.107_396_956_6 * b).cbrt();
+    let s = (0.088_302_461_9 * r + 0.281_718_837_6 * g + 0.629_978_700_5 * b).cbrt();
+
+    [
+        0.210_454_255_3 * l + 0.793_617_785 * m - 0.004_072_046_8 * s,
+        1.977_998_495_1 * l - 2.428_592_205 * m + 0.450_593_709_9 * s,
+        0.025_904_037_1 * l + 0.782_771_766_2 * m - 0.808_675_766 * s,
+    ]
+}
+
+fn oklab_to_linear_srgb([lightness, a, b]: [f64; 3]) -> [f64; 3] {
+    let l = lightness + 0.396_337_777_4 * a + 0.215_803_757_3 * b;
+    let m = lightness - 0.105_561_345_8 * a - 0.063_854_172_8 * b;
+    let s = lightness - 0.089_484_177_5 * a - 1.291_485_548 * b;
+    let [l, m, s] = [l * l * l, m * m * m, s * s * s];
+
+    [
+        4.076_741_662_1 * l - 3.307_711_591_3 * m + 0.230_969_929_2 * s,
+        -1.268_438_004_6 * l + 2.609_757_401_1 * m - 0.341_319_396_5 * s,
+        -0.004_196_086_3 * l - 0.703_418_614_7 * m + 1.707_614_701 * s,
+    ]
+}
+
+fn srgb_to_linear(v: f64) -> f64 {
+    let abs = abs(v);
+    if abs <= 0.04045 {
+        v / 12.92
+    } else {
+        signum(v) * ((abs + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn srgb_from_linear(v: f64) -> f64 {
+    let abs = abs(v);
+    if abs > 0.003_130_8 {
+        signum(v) * (1.055 * abs.powf(1.0 / 2.4) - 0.055)
+    } else {
+        12.92 * v
+    }
+}
+
+fn a98_to_linear(v: f64) -> f64 {
+    signum(v) * abs(v).powf(563.0 / 256.0)
+}
+
+fn a98_from_linear(v: f64) -> f64 {
+    signum(v) * abs(v).powf(256.0 / 563.0)
+}
+
+fn prophoto_to_linear(v: f64) -> f64 {
+    let abs = abs(v);
+    if abs <= 16.0 / 512.0 {
+        v / 16.0
+    } else {
+        signum(v) * abs.powf(1.8)
+    }
+}
+
+fn prophoto_from_linear(v: f64) -> f64 {
+    let abs = abs(v);
+    if abs >= 1.0 / 512.0 {
+        signum(v) * abs.powf(1.0 / 1.8)
+    } else {
+        16.0 * v
+    }
+}
+
+const REC2020_ALPHA: f64 = 1.099_296_826_809_44;
+const REC2020_BETA: f64 = 0.018_053_968_510_807;
+
+fn rec2020_to_linear(v: f64) -> f64 {
+    let abs = abs(v);
+    if abs < REC2020_BETA * 4.5 {
+        v / 4.5
+    } else {
+        signum(v) * ((abs + REC2020_ALPHA - 1.0) / REC2020_ALPHA).powf(1.0 / 0.45)
+    }
+}
+
+fn rec2020_from_linear(v: f64) -> f64 {
+    let abs = abs(v);
+    if abs > REC2020_BETA {
+        signum(v) * (REC2020_ALPHA * abs.powf(0.45) - (REC2020_ALPHA - 1.0))
+    } else {
+        4.5 * v
+    }
+}
+
+/// `f64::abs` is not available in `core` on older compilers.
+fn abs(v: f64) -> f64 {
+    signum(v) * v
+}
+
+fn signum(v: f64) -> f64 {
+    if v < 0.0 {
+        -1.0
+    } else {
+        1.0
+    }
+}
+
+type Matrix = [[f64; 3]; 3];
+
+/// Chromaticities of the red, green and blue primaries.
+type Primaries = [[f64; 2]; 3];
+
+const SRGB: Primaries = [[0.64, 0.33], [0.30, 0.60], [0.15, 0.06]];
+const DISPLAY_P3: Primaries = [[0.68, 0.32], [0.265, 0.69], [0.15, 0.06]];
+const A98_RGB: Primaries = [[0.64, 0.33], [0.21, 0.71], [0.15, 0.06]];
+const PROPHOTO_RGB: Primaries = [
+    [0.734_699, 0.265_301],
+    [0.159_597, 0.840_403],
+    [0.036_598, 0.000_105],
+];
+const REC2020: Primaries = [[0.708, 0.292], [0.17, 0.797], [0.131, 0.046]];
+
+const D50: [f64; 3] = [0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585];
+const D65: [f64; 3] = [0.3127 / 0.3290, 1.0, (1.0 - 0.3127 - 0.3290) / 0.3290];
+
+fn rgb_to_xyz(primaries: &Primaries, white: [f64; 3], rgb: [f64; 3]) -> [f64; 3] {
+    mul_vector(&rgb_to_xyz_matrix(primaries, white), rgb)
+}
+
+fn xyz_to_rgb(primaries: &Primaries, white: [f64; 3], xyz: [f64; 3]) -> [f64; 3] {
+    mul_vector(&invert(&rgb_to_xyz_matrix(primaries, white)), xyz)
+}
+
+/// <http://www.brucelindbloom.com/index.html?Eqn_RGB_XYZ_Matrix.html>
+fn rgb_to_xyz_matrix(primaries: &Primaries, white: [f64; 3]) -> Matrix {
+    let columns = primaries.map(|[x, y]| [x / y, 1.0, (1.0 - x - y) / y]);
+    let m = transpose(&columns);
+    let scale = mul_vector(&invert(&m), white);
+    m.map(|row| [row[0] * scale[0], row[1] * scale[1], row[2] * scale[2]])
+}
+
+/// Performs a Bradford chromatic adaptation.
+fn adapt(from: [f64; 3], to: [f64; 3], xyz: [f64; 3]) -> [f64; 3] {
+    const BRADFORD: Matrix = [
+        [0.8951, 0.2664, -0.1614],
+        [-0.7502, 1.7135, 0.0367],
+        [0.0389, -0.0685, 1.0296],
+    ];
+
+    let from = mul_vector(&BRADFORD, from);
+    let to = mul_vector(&BRADFORD, to);
+    let cone = mul_vector(&BRADFORD, xyz);
+    let cone = [
+        cone[0] * to[0] / from[0],
+        cone[1] * to[1] / from[1],
+        cone[2] * to[2] / from[2],
+    ];
+
+    mul_vector(&invert(&BRADFORD), cone)
+}
+
+fn mul_vector(m: &Matrix, v: [f64; 3]) -> [f64; 3] {
+    m.map(|row| row[0] * v[0] + row[1] * v[1] + row[2] * v[2])
+}
+
+fn transpose(m: &Matrix) -> Matrix {
+    [
+        [m[0][0], m[1][0], m[2][0]],
+        [m[0][1], m[1][1], m[2][1]],
+        [m[0][2], m[1][2], m[2][2]],
+    ]
+}
+
+fn invert(m: &Matrix) -> Matrix {
+    let cofactor =
+        |r0: usize, r1: usize, c0: usize, c1: usize| m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0];
+
+    let adjugate = [
+        [
+            cofactor(1, 2, 1, 2),
+            -cofactor(0, 2, 1, 2),
+            cofactor(0, 1, 1, 2),
+        ],
+        [
+            -cofactor(1, 2, 0, 2),
+            cofactor(0, 2, 0, 2),
+            -cofactor(0, 1, 0, 2),
+        ],
+        [
+            cofactor(1, 2, 0, 1),
+            -cofactor(0, 2, 0, 1),
+            cofactor(0, 1, 0, 1),
+        ],
+    ];
+
+    let det = m[0][0] * adjugate[0][0] + m[0][1] * adjugate[1][0] + m[0][2] * adjugate[2][0];
+    adjugate.map(|row| row.map(|v| v / det))
+}
+
+/// A hue interpolation method of `color-mix()`.
+///
+/// <https://www.w3.org/TR/css-color-4/#hue-interpolation>
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum HueInterpolation {
+    Shorter,
+    Longer,
+    Increasing,
+    Decreasing,
+}
+
+impl HueInterpolation {
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        let methods = [
+            ("shorter", HueInterpolation::Shorter),
+            ("longer", HueInterpolation::Longer),
+            ("increasing", HueInterpolation::Increasing),
+            ("decreasing", HueInterpolation::Decreasing),
+        ];
+
+        methods
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, method)| *method)
+    }
+}
+
+impl SpaceColor {
+    /// Interpolates between two colors of the same color space.
+    ///
+    /// `t` is the weight of `other`.
+    ///
+    /// <https://www.w3.org/TR/css-color-4/#interpolation>
+    pub(crate) fn mix(self, other: Self, t: f64, hue: HueInterpolation) -> Self {
+        let mut a = self.channels;
+        let mut b = other.channels;
+        let mut alpha_a = self.alpha;
+        let mut alpha_b = other.alpha;
+
+        // A missing component takes the value of the other color.
+        for i in 0..3 {
+            carry_missing(&mut a[i], &mut b[i]);
+        }
+        carry_missing(&mut alpha_a, &mut alpha_b);
+
+        let hue_index = self.space.hue_index();
+        if let Some(i) = hue_index {
+            fix_hues(&mut a[i], &mut b[i], hue);
+        }
+
+        // Interpolate in premultiplied alpha.
+        let premultiplier = |alpha: f64| if alpha.is_nan() { 1.0 } else { alpha };
+        let (pa, pb) = (premultiplier(alpha_a), premultiplier(alpha_b));
+        let alpha = alpha_a + (alpha_b - alpha_a) * t;
+        let mut channels = [0.0; 3];
+        for i in 0..3 {
+            channels[i] = if Some(i) == hue_index {
+                normalize_hue(a[i] + (b[i] - a[i]) * t)
+            } else {
+                let value = a[i] * pa + (b[i] * pb - a[i] * pa) * t;
+                if alpha != 0.0 && !alpha.is_nan() {
+                    value / alpha
+                } else {
+                    value
+                }
+            };
+        }
+
+        SpaceColor {
+            space: self.space,
+            channels,
+            alpha,
+        }
+    }
+}
+
+fn carry_missing(a: &mut f64, b: &mut f64) {
+    if a.is_nan() {
+        *a = *b;
+    } else if b.is_nan() {
+        *b = *a;
+    }
+}
+
+fn fix_hues(a: &mut f64, b: &mut f64, method: HueInterpolation) {
+    if a.is_nan() || b.is_nan() {
+        return;
+    }
+
+    let d = *b - *a;
+    match method {
+        HueInterpolation::Shorter if d > 180.0 => *a += 360.0,
+        HueInterpolation::Shorter if d < -180.0 => *b += 360.0,
+        HueInterpolation::Longer if 0.0 < d && d < 180.0 => *a += 360.0,
+        HueInterpolation::Longer if -180.0 < d && d <= 0.0 => *b += 360.0,
+        HueInterpolation::Increasing if d < 0.0 => *b += 360.0,
+        HueInterpolation::Decreasing if d > 0.0 => *a += 360.0,
+        _ => {}
+    }
+}
@@ -0,0 +1,145 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::str;
+
+/// A style sheet encoding.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Encoding {
+    /// UTF-8
+    Utf8,
+    /// UTF-16, little endian
+    Utf16Le,
+    /// UTF-16, big endian
+    Utf16Be,
+    /// windows-1252
+    ///
+    /// ISO-8859-1 and ASCII labels mean windows-1252 as well,
+    /// like in the [Encoding Standard](https://encoding.spec.whatwg.org/#names-and-labels).
+    Windows1252,
+}
+
+impl Encoding {
+    /// Returns an encoding by its label, like `utf-8` or `latin1`.
+    ///
+    /// Labels are ASCII case-insensitive. Only labels of the supported encodings are recognized.
+    ///
+    /// <https://encoding.spec.whatwg.org/#concept-encoding-get>
+    pub fn from_label(label: &str) -> Option<Self> {
+        let label = label
+            .trim_matches(|c: char| c.is_ascii_whitespace())
+            .to_ascii_lowercase();
+
+        match label.as_str() {
+            "unicode-1-1-utf-8" | "unicode11utf8" | "unicode20utf8" | "utf-8" | "utf8"
+            | "x-unicode20utf8" => Some(Encoding::Utf8),
+            "csunicode" | "iso-10646-ucs-2" | "ucs-2" | "unicode" | "unicodefeff" | "utf-16"
+            | "utf-16le" => Some(Encoding::Utf16Le),
+            "unicodefffe" | "utf-16be" => Some(Encoding::Utf16Be),
+            "ansi_x3.4-1968" | "ascii" | "cp1252" | "cp819" | "csisolatin1" | "ibm819"
+            | "iso-8859-1" | "iso-ir-100" | "iso8859-1" | "iso88591" | "iso_8859-1"
+            | "iso_8859-1:1987" | "l1" | "latin1" | "us-ascii" | "windows-1252" | "x-cp1252" => {
+                Some(Encoding::Windows1252)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Decodes a style sheet.
+///
+/// The encoding is determined by a byte order mark, then by an `@charset` rule,
+/// then by `environment`, ie. the encoding of the referring document, and finally defaults to UTF-8.
+/// The byte order mark is removed. Invalid sequences are replaced with U+FFFD.
+///
+/// Returns the decoded text and the used encoding.
+///
+/// <https://www.w3.org/TR/css-syntax-3/#input-byte-stream>
+///
+/// # Example
+///
+/// ```
+/// use simplecss::{decode_bytes, Encoding};
+///
+/// let (text, encoding) = decode_bytes(b"@charset \"latin1\"; a::after { content: '\xE9' }", None);
+/// assert_eq!(encoding, Encoding::Windows1252);
+/// assert!(text.ends_with("content: 'é' }"));
+/// ```
+pub fn decode_bytes(bytes: &[u8], environment: Option<Encoding>) -> (Cow<'_, str>, Encoding) {
+    let (encoding, bytes) = if let Some(bytes) = bytes.strip_prefix(b"\xEF\xBB\xBF") {
+        (Encoding::Utf8, bytes)
+    } else if let Some(bytes) = bytes.strip_prefix(b"\xFE\xFF") {
+        (Encoding::Utf16Be, bytes)
+    } else if let Some(bytes) = bytes.strip_prefix(b"\xFF\xFE") {
+        (Encoding::Utf16Le, bytes)
+    } else {
+        let encoding = charset_encoding(bytes)
+            .or(environment)
+            .unwrap_or(Encoding::Utf8);
+        (encoding, bytes)
+    };
+
+    let text = match encoding {
+        Encoding::Utf8 => String::from_utf8_lossy(bytes),
+        Encoding::Utf16Le => Cow::Owned(decode_utf16(bytes, u16::from_le_bytes)),
+        Encoding::Utf16Be => Cow::Owned(decode_utf16(bytes, u16::from_be_bytes)),
+        Encoding::Windows1252 => match str::from_utf8(bytes) {
+            Ok(text) if text.is_ascii() => Cow::Borrowed(text),
+            _ => Cow::Owned(bytes.iter().map(|b| windows_1252_char(*b)).collect()),
+        },
+    };
+
+    (text, encoding)
+}
+
+/// Returns the encoding from a leading `@charset "...";` rule.
+///
+/// The rule must match byte for byte and must be in the first 1024 bytes.
+fn charset_encoding(bytes: &[u8]) -> Option<Encoding> {
+    let prefix = b"@charset \"";
+    let bytes = &bytes[..bytes.len().min(1024)];
+    let rest = bytes.strip_prefix(prefix)?;
+    let end = rest.windows(2).position(|w| w == b"\";")?;
+    let label = str::from_utf8(&rest[..end]).ok()?;
+
+    match Encoding::from_label(label)? {
+        // The rule itself was decoded as an ASCII-compatible encoding, so it can't be UTF-16.
+        Encoding::Utf16Le | Encoding::Utf16Be => Some(Encoding::Utf8),
+        encoding => Some(encoding),
+    }
+}
+
+/// Decodes a windows-1252 byte.
+///
+/// Matches ISO-8859-1, except for the 0x80-0x9F range.
+///
+/// <https://encoding.spec.whatwg.org/index-windows-1252.txt>
+fn windows_1252_char(b: u8) -> char {
+    #[rustfmt::skip]
+    const TABLE: [char; 32] = [
+        '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+        '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{017D}', '\u{008F}',
+        '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+        '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+    ];
+
+    match b {
+        0x80..=0x9F => TABLE[usize::from(b - 0x80)],
+        _ => char::from(b),
+    }
+}
+
+fn decode_utf16(bytes: &[u8], to_u16: fn([u8; 2]) -> u16) -> String {
+    let units = bytes.chunks_exact(2).map(|c| to_u16([c[0], c[1]]));
+    let mut text: String = char::decode_utf16(units)
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect();
+
+    if bytes.len() % 2 != 0 {
+        text.push(char::REPLACEMENT_CHARACTER);
+    }
+
+    text
+}
@@ -0,0 +1,73 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Float methods that are not available in `core`.
+
+/// Float methods backed by `libm`.
+///
+/// Import it only when the `std` feature is disabled,
+/// otherwise inherent methods are used.
+pub(crate) trait FloatFuncs: Sized {
+    fn acos(self) -> Self;
+    fn asin(self) -> Self;
+    fn atan(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn cbrt(self) -> Self;
+    fn ceil(self) -> Self;
+    fn cos(self) -> Self;
+    fn floor(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn sin(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn tan(self) -> Self;
+}
+
+impl FloatFuncs for f64 {
+    fn acos(self) -> Self {
+        libm::acos(self)
+    }
+
+    fn asin(self) -> Self {
+        libm::asin(self)
+    }
+
+    fn atan(self) -> Self {
+        libm::atan(self)
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        libm::atan2(self, other)
+    }
+
+    fn cbrt(self) -> Self {
+        libm::cbrt(self)
+    }
+
+    fn ceil(self) -> Self {
+        libm::ceil(self)
+    }
+
+    fn cos(self) -> Self {
+        libm::cos(self)
+    }
+
+    fn floor(self) -> Self {
+        libm::floor(self)
+    }
+
+    fn powf(self, n: Self) -> Self {
+        libm::pow(self, n)
+    }
+
+    fn sin(self) -> Self {
+        libm::sin(self)
+    }
+
+    fn sqrt(self) -> Self {
+        libm::sqrt(self)
+    }
+
+    fn tan(self) -> Self {
+        libm::tan(self)
+    }
+}
@@ -0,0 +1,736 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::calc::CalcNode;
+use crate::color::angle_to_degrees;
+use crate::length::{Length, LengthUnit, ResolveContext};
+use crate::value::{ComponentValue, Value};
+
+/// A `font-style` property value.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum FontStyle {
+    /// `normal`
+    #[default]
+    Normal,
+    /// `italic`
+    Italic,
+    /// `oblique`, with an angle in degrees. `14` by default.
+    Oblique(f64),
+}
+
+impl FontStyle {
+    /// Parses a font style from a property value.
+    pub fn parse(text: &str) -> Option<Self> {
+        let value = Value::parse(text).ok()?;
+        match value.components.as_slice() {
+            [style] => FontStyle::from_components(style, None),
+            [style, angle] => FontStyle::from_components(style, Some(angle)),
+            _ => None,
+        }
+    }
+
+    fn from_components(
+        style: &ComponentValue<'_>,
+        angle: Option<&ComponentValue<'_>>,
+    ) -> Option<Self> {
+        match angle {
+            None if style.is_ident("normal") => Some(FontStyle::Normal),
+            None if style.is_ident("italic") => Some(FontStyle::Italic),
+            None if style.is_ident("oblique") => Some(FontStyle::Oblique(14.0)),
+            Some(angle) if style.is_ident("oblique") => {
+                let degrees = match angle {
+                    ComponentValue::Dimension(n, unit) => angle_to_degrees(*n, unit)?,
+                    ComponentValue::Function(func) => {
+                        CalcNode::from_function(func)?.to_degrees()?
+                    }
+                    _ => return None,
+                };
+
+                (-90.0..=90.0)
+                    .contains(&degrees)
+                    .then_some(FontStyle::Oblique(degrees))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A `font-weight` property value.
+///
+/// # Example
+///
+/// ```
+/// use simplecss::FontWeight;
+///
+/// assert_eq!(FontWeight::parse("bold"), Some(FontWeight::BOLD));
+/// assert_eq!(FontWeight::parse("350"), Some(FontWeight::Absolute(350.0)));
+/// assert_eq!(FontWeight::parse("bolder").unwrap().resolve(400.0), 700.0);
+/// assert_eq!(FontWeight::parse("1001"), None);
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FontWeight {
+    /// A weight in the 1..=1000 range.
+    Absolute(f64),
+    /// `bolder`, relative to the parent weight.
+    Bolder,
+    /// `lighter`, relative to the parent weight.
+    Lighter,
+}
+
+impl FontWeight {
+    /// `normal`, ie. `400`.
+    pub const NORMAL: FontWeight = FontWeight::Absolute(400.0);
+
+    /// `bold`, ie. `700`.
+    pub const BOLD: FontWeight = FontWeight::Absolute(700.0);
+
+    /// Parses a font weight from a property value.
+    pub fn parse(text: &str) -> Option<Self> {
+        let value = Value::parse(text).ok()?;
+        match value.components.as_slice() {
+            [component] => FontWeight::from_component(component),
+            _ => None,
+        }
+    }
+
+    /// Converts a component value into a font weight.
+    pub fn from_component(component: &ComponentValue<'_>) -> Option<Self> {
+        let weight = match component.as_ident() {
+            Some(name) if name.eq_ignore_ascii_case("normal") => FontWeight::NORMAL,
+            Some(name) if name.eq_ignore_ascii_case("bold") => FontWeight::BOLD,
+            Some(name) if name.eq_ignore_ascii_case("bolder") => FontWeight::Bolder,
+            Some(name) if name.eq_ignore_ascii_case("lighter") => FontWeight::Lighter,
+            Some(_) => return None,
+            None => {
+                let weight = number(component)?;
+                if !(1.0..=1000.0).contains(&weight) {
+                    return None;
+                }
+                FontWeight::Absolute(weight)
+            }
+        };
+
+        Some(weight)
+    }
+
+    /// Resolves the weight relative to the parent weight.
+    ///
+    /// <https://www.w3.org/TR/css-fonts-4/#relative-weights>
+    pub fn resolve(&self, parent: f64) -> f64 {
+        match *self {
+            FontWeight::Absolute(weight) => weight,
+            FontWeight::Bolder if parent < 350.0 => 400.0,
+            FontWeight::Bolder if parent < 550.0 => 700.0,
+            FontWeight::Bolder if parent < 900.0 => 900.0,
+            FontWeight::Lighter if parent >= 750.0 => 700.0,
+            FontWeight::Lighter if parent >= 550.0 => 400.0,
+            FontWeight::Lighter if parent >= 100.0 => 100.0,
+            FontWeight::Bolder | FontWeight::Lighter => parent,
+        }
+    }
+}
+
+impl Default for FontWeight {
+    fn default() -> Self {
+        FontWeight::NORMAL
+    }
+}
+
+/// A `font-size` property value.
+///
+/// Absolute size keywords, like `small`, are converted into pixels,
+/// assuming that `medium` is `16px`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FontSize {
+    /// A length, where percentages and font-relative units refer to the parent font size.
+    Length(Length),
+    /// `larger`
+    Larger,
+    /// `smaller`
+    Smaller,
+}
+
+impl FontSize {
+    /// `medium`
+    pub const MEDIUM: FontSize = FontSize::Length(Length::new(16.0, LengthUnit::Px));
+
+    /// Parses a font size from a property value.
+    pub fn parse(text: &str) -> Option<Self> {
+        let value = Value::parse(text).ok()?;
+        match value.components.as_slice() {
+            [component] => FontSize::from_component(component),
+            _ => None,
+        }
+    }
+
+    /// Converts a component value into a font size.
+    pub fn from_component(component: &ComponentValue<'_>) -> Option<Self> {
+        if let Some(name) = component.as_ident() {
+            if name.eq_ignore_ascii_case("larger") {
+                return Some(FontSize::Larger);
+            } else if name.eq_ignore_ascii_case("smaller") {
+                return Some(FontSize::Smaller);
+            }
+
+            let factor = lookup(FONT_SIZES, name)?;
+            return Some(FontSize::Length(Length::new(16.0 * factor, LengthUnit::Px)));
+        }
+
+        let length = Length::from_component(component)?;
+        if length.value < 0.0 {
+            return None;
+        }
+
+        Some(FontSize::Length(length))
+    }
+
+    /// Resolves the size into pixels.
+    ///
+    /// [`ResolveContext::font_size`] must be the parent font size.
+    pub fn to_px(&self, context: &ResolveContext) -> f64 {
+        match self {
+            FontSize::Length(length) => length.to_px(&ResolveContext {
+                percentage_base: context.font_size,
+                ..*context
+            }),
+            FontSize::Larger => context.font_size * 1.2,
+            FontSize::Smaller => context.font_size / 1.2,
+        }
+    }
+}
+
+impl Default for FontSize {
+    fn default() -> Self {
+        FontSize::MEDIUM
+    }
+}
+
+/// A `line-height` property value.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum LineHeight {
+    /// `normal`
+    #[default]
+    Normal,
+    /// A multiplier of the font size.
+    Number(f64),
+    /// A length, where percentages refer to the font size.
+    Length(Length),
+}
+
+impl LineHeight {
+    /// Parses a line height from a property value.
+    pub fn parse(text: &str) -> Option<Self> {
+        let value = Value::parse(text).ok()?;
+        match value.components.as_slice() {
+            [component] => LineHeight::from_component(component),
+            _ => None,
+        }
+    }
+
+    /// Converts a component value into a line height.
+    pub fn from_component(component: &ComponentValue<'_>) -> Option<Self> {
+        if component.is_ident("normal") {
+            return Some(LineHeight::Normal);
+        }
+
+        let line_height = match number(component) {
+            Some(n) => LineHeight::Number(n),
+            None => LineHeight::Length(Length::from_component(component)?),
+        };
+
+        match line_height {
+            LineHeight::Number(n) | LineHeight::Length(Length { value: n, .. }) if n < 0.0 => None,
+            _ => Some(line_height),
+        }
+    }
+}
+
+/// A generic font family.
+///
+/// <https://www.w3.org/TR/css-fonts-4/#generic-font-families>
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum GenericFamily {
+    /// `serif`
+    Serif,
+    /// `sans-serif`
+    SansSerif,
+    /// `cursive`
+    Cursive,
+    /// `fantasy`
+    Fantasy,
+    /// `monospace`
+    Monospace,
+    /// `system-ui`
+    SystemUi,
+    /// `emoji`
+    Emoji,
+    /// `math`
+    Math,
+    /// `fangsong`
+    Fangsong,
+    /// `ui-serif`
+    UiSerif,
+    /// `ui-sans-serif`
+    UiSansSerif,
+    /// `ui-monospace`
+    UiMonospace,
+    /// `ui-rounded`
+    UiRounded,
+}
+
+impl GenericFamily {
+    /// Returns a generic family by its name, ignoring ASCII case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        GENERIC_FAMILIES
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, family)| *family)
+    }
+
+    /// Returns the family name.
+    pub fn as_str(&self) -> &'static str {
+        GENERIC_FAMILIES
+            .iter()
+            .find(|(_, family)| family == self)
+            .map_or("", |(name, _)| name)
+    }
+}
+
+const GENERIC_FAMILIES: &[(&str, GenericFamily)] = &[
+    ("serif", GenericFamily::Serif),
+    ("sans-serif", GenericFamily::SansSerif),
+    ("cursive", GenericFamily::Cursive),
+    ("fantasy", GenericFamily::Fantasy),
+    ("monospace", GenericFamily::Monospace),
+    ("system-ui", GenericFamily::SystemUi),
+    ("emoji", GenericFamily::Emoji),
+    ("math", GenericFamily::Math),
+    ("fangsong", GenericFamily::Fangsong),
+    ("ui-serif", GenericFamily::UiSerif),
+    ("ui-sans-serif", GenericFamily::UiSansSerif),
+    ("ui-monospace", GenericFamily::UiMonospace),
+    ("ui-rounded", GenericFamily::UiRounded),
+];
+
+/// A font family.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum FontFamily<'a> {
+    /// A family name, with escapes decoded.
+    Named(Cow<'a, str>),
+    /// A generic family.
+    Generic(GenericFamily),
+}
+
+impl<'a> FontFamily<'a> {
+    /// Parses a `font-family` list.
+    ///
+    /// Unquoted names consisting of multiple identifiers are joined with a single space.
+    /// Quoted names are never generic families.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use simplecss::{FontFamily, GenericFamily};
+    ///
+    /// let families = FontFamily::parse_list("Open  Sans, 'serif', sans-serif").unwrap();
+    /// assert_eq!(families, [
+    ///     FontFamily::Named("Open Sans".into()),
+    ///     FontFamily::Named("serif".into()),
+    ///     FontFamily::Generic(GenericFamily::SansSerif),
+    /// ]);
+    ///
+    /// // Unquoted names cannot contain numbers.
+    /// assert_eq!(FontFamily::parse_list("Font 2"), None);
+    /// ```
+    pub fn parse_list(text: &'a str) -> Option<Vec<Self>> {
+        let value = Value::parse(text).ok()?;
+        FontFamily::from_components(&value.components)
+    }
+
+    /// Converts component values into a `font-family` list.
+    pub fn from_components(components: &[ComponentValue<'a>]) -> Option<Vec<Self>> {
+        let mut families = Vec::new();
+        for item in components.split(|c| *c == ComponentValue::Comma) {
+            families.push(FontFamily::from_item(item)?);
+        }
+
+        Some(families)
+    }
+
+    fn from_item(item: &[ComponentValue<'a>]) -> Option<Self> {
+        match item {
+            [ComponentValue::String(name)] => Some(FontFamily::Named(name.clone())),
+            [ComponentValue::Ident(name)] => {
+                if let Some(generic) = GenericFamily::from_name(name) {
+                    Some(FontFamily::Generic(generic))
+                } else if is_keyword(name, RESERVED_NAMES) {
+                    None
+                } else {
+                    Some(FontFamily::Named(name.clone()))
+                }
+            }
+            [_, ..] => {
+                let mut name = String::new();
+                for component in item {
+                    let ComponentValue::Ident(ident) = component else {
+                        return None;
+                    };
+
+                    if !name.is_empty() {
+                        name.push(' ');
+                    }
+                    name.push_str(ident);
+                }
+
+                Some(FontFamily::Named(Cow::Owned(name)))
+            }
+            [] => None,
+        }
+    }
+}
+
+/// Idents that are not allowed as single-ident family names.
+const RESERVED_NAMES: &[&str] = &[
+    "inherit",
+    "initial",
+    "unset",
+    "revert",
+    "revert-layer",
+    "default",
+];
+
+/// A system font keyword of the `font` shorthand.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum SystemFont {
+    /// `caption`
+    Caption,
+    /// `icon`
+    Icon,
+    /// `menu`
+    Menu,
+    /// `message-box`
+    MessageBox,
+    /// `small-caption`
+    SmallCaption,
+    /// `status-bar`
+    StatusBar,
+}
+
+const SYSTEM_FONTS: &[(&str, SystemFont)] = &[
+    ("caption", SystemFont::Caption),
+    ("icon", SystemFont::Icon),
+    ("menu", SystemFont::Menu),
+    ("message-box", SystemFont::MessageBox),
+    ("small-caption", SystemFont::SmallCaption),
+    ("status-bar", SystemFont::StatusBar),
+];
+
+/// A `font` shorthand value.
+///
+/// Omitted properties have their initial values.
+///
+/// # Example
+///
+/// ```
+/// use simplecss::{Font, FontFamily, FontSize, FontStyle, FontWeight, GenericFamily, LineHeight};
+///
+/// let font = Font::parse("italic bold 12px/1.5 \"Helvetica Neue\", sans-serif").unwrap();
+/// assert_eq!(font.style, FontStyle::Italic);
+/// assert_eq!(font.weight, FontWeight::BOLD);
+/// assert_eq!(font.line_height, LineHeight::Number(1.5));
+/// assert_eq!(font.families, [
+///     FontFamily::Named("Helvetica Neue".into()),
+///     FontFamily::Generic(GenericFamily::SansSerif),
+/// ]);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct Font<'a> {
+    /// The font style.
+    pub style: FontStyle,
+    /// Checks that `font-variant` is `small-caps`.
+    pub small_caps: bool,
+    /// The font weight.
+    pub weight: FontWeight,
+    /// The font width as a percentage, ie. `font-stretch`. `100` by default.
+    pub stretch: f64,
+    /// The font size.
+    pub size: FontSize,
+    /// The line height.
+    pub line_height: LineHeight,
+    /// The font families, in the order of preference.
+    pub families: Vec<FontFamily<'a>>,
+    /// A system font keyword, like `caption`.
+    ///
+    /// When set, the other properties have their initial values
+    /// and must be taken from the system font.
+    pub system: Option<SystemFont>,
+}
+
+impl<'a> Font<'a> {
+    /// Parses a `font` shorthand value.
+    ///
+    /// CSS-wide keywords, like `inherit`, are not supported.
+    pub fn parse(text: &'a str) -> Option<Self> {
+        let value = Value::parse(text).ok()?;
+        Font::from_components(&value.components)
+    }
+
+    /// Converts component values into a `font` shorthand value.
+    pub fn from_components(components: &[ComponentValue<'a>]) -> Option<Self> {
+        let mut font = Font {
+            style: FontStyle::Normal,
+            small_caps: false,
+            weight: FontWeight::NORMAL,
+            stretch: 100.0,
+            size: FontSize::MEDIUM,
+            line_height: LineHeight::Normal,
+            families: Vec::new(),
+            system: None,
+        };
+
+        if let [ComponentValue::Ident(name)] = components {
+            if let Some((_, system)) = SYSTEM_FONTS
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            {
+                font.system = Some(*system);
+                return Some(font);
+            }
+        }
+
+        let mut style = None;
+        let mut small_caps = None;
+        let mut weight = None;
+        let mut stretch = None;
+        let mut prefix_len = 0;
+        let mut i = 0;
+        while let Some(c) = components.get(i) {
+            if c.is_ident("normal") {
+                // Sets nothing, since all of the properties are `normal` by default.
+            } else if style.is_none() && (c.is_ident("italic") || c.is_ident("oblique")) {
+                let angle = components.get(i + 1).filter(|_| c.is_ident("oblique"));
+                match angle.and_then(|angle| FontStyle::from_components(c, Some(angle))) {
+                    Some(oblique) => {
+                        style = Some(oblique);
+                        i += 1;
+                    }
+                    None => style = Some(FontStyle::from_components(c, None)?),
+                }
+            } else if small_caps.is_none() && c.is_ident("small-caps") {
+                small_caps = Some(true);
+            } else if let Some(w) = FontWeight::from_component(c).filter(|_| weight.is_none()) {
+                weight = Some(w);
+            } else if let Some(s) = c
+                .as_ident()
+                .and_then(|name| lookup(FONT_STRETCHES, name))
+                .filter(|_| stretch.is_none())
+            {
+                stretch = Some(s);
+            } else {
+                break;
+            }
+
+            prefix_len += 1;
+            i += 1;
+        }
+
+        // Up to 4 values are allowed before the size.
+        if prefix_len > 4 {
+            return None;
+        }
+
+        let size = components.get(i)?;
+        // `larger` and `smaller` are allowed as well.
+        font.size = FontSize::from_component(size)?;
+        i += 1;
+
+        if components.get(i) == Some(&ComponentValue::Slash) {
+            font.line_height = LineHeight::from_component(components.get(i + 1)?)?;
+            i += 2;
+        }
+
+        font.families = FontFamily::from_components(components.get(i..)?)?;
+
+        font.style = style.unwrap_or_default();
+        font.small_caps = small_caps.unwrap_or_default();
+        font.weight = weight.unwrap_or_default();
+        font.stretch = stretch.unwrap_or(100.0);
+        Some(font)
+    }
+}
+
+/// A `font-feature-settings` item.
+///
+/// # Example
+///
+/// ```
+/// use simplecss::FontFeature;
+///
+/// let features = FontFeature::parse_list("\"liga\" off, \"ss01\", 'salt' 3").unwrap();
+/// assert_eq!(features, [
+///     FontFeature { tag: *b"liga", value: 0 },
+///     FontFeature { tag: *b"ss01", value: 1 },
+///     FontFeature { tag: *b"salt", value: 3 },
+/// ]);
+/// assert!(FontFeature::parse_list("normal").unwrap().is_empty());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct FontFeature {
+    /// The OpenType feature tag.
+    pub tag: [u8; 4],
+    /// The feature value. `1` for `on` and `0` for `off`.
+    pub value: u32,
+}
+
+impl FontFeature {
+    /// Parses a `font-feature-settings` list.
+    ///
+    /// Returns an empty list for `normal`.
+    pub fn parse_list(text: &str) -> Option<Vec<Self>> {
+        let list = Value::parse(text).ok()?;
+        if let [component] = list.components.as_slice() {
+            if component.is_ident("normal") {
+                return Some(Vec::new());
+            }
+        }
+
+        let mut features = Vec::new();
+        for item in list.split_commas() {
+            let (tag, value) = match item {
+                [ComponentValue::String(tag)] => (tag, 1),
+                [ComponentValue::String(tag), ComponentValue::Ident(name)] => {
+                    if name.eq_ignore_ascii_case("on") {
+                        (tag, 1)
+                    } else if name.eq_ignore_ascii_case("off") {
+                        (tag, 0)
+                    } else {
+                        return None;
+                    }
+                }
+                [ComponentValue::String(tag), value] => (tag, integer(value)?),
+                _ => return None,
+            };
+
+            features.push(FontFeature {
+                tag: parse_tag(tag)?,
+                value,
+            });
+        }
+
+        (!features.is_empty()).then_some(features)
+    }
+}
+
+/// A `font-variation-settings` item.
+///
+/// # Example
+///
+/// ```
+/// use simplecss::FontVariation;
+///
+/// let variations = FontVariation::parse_list("'wght' 650, \"wdth\" 87.5").unwrap();
+/// assert_eq!(variations, [
+///     FontVariation { tag: *b"wght", value: 650.0 },
+///     FontVariation { tag: *b"wdth", value: 87.5 },
+/// ]);
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct FontVariation {
+    /// The OpenType variation axis tag.
+    pub tag: [u8; 4],
+    /// The axis value.
+    pub value: f64,
+}
+
+impl FontVariation {
+    /// Parses a `font-variation-settings` list.
+    ///
+    /// Returns an empty list for `normal`.
+    pub fn parse_list(text: &str) -> Option<Vec<Self>> {
+        let list = Value::parse(text).ok()?;
+        if let [component] = list.components.as_slice() {
+            if component.is_ident("normal") {
+                return Some(Vec::new());
+            }
+        }
+
+        let mut variations = Vec::new();
+        for item in list.split_commas() {
+            let [ComponentValue::String(tag), value] = item else {
+                return None;
+            };
+
+            variations.push(FontVariation {
+                tag: parse_tag(tag)?,
+                value: number(value)?,
+            });
+        }
+
+        (!variations.is_empty()).then_some(variations)
+    }
+}
+
+/// Parses an OpenType tag, which must consist of 4 printable ASCII chars.
+fn parse_tag(tag: &str) -> Option<[u8; 4]> {
+    let bytes: [u8; 4] = tag.as_bytes().try_into().ok()?;
+    bytes
+        .iter()
+        .all(|b| (0x20..=0x7E).contains(b))
+        .then_some(bytes)
+}
+
+fn number(component: &ComponentValue<'_>) -> Option<f64> {
+    match component {
+        ComponentValue::Number(n) => Some(*n),
+        ComponentValue::Function(func) => CalcNode::from_function(func)?.to_number(),
+        _ => None,
+    }
+}
+
+/// Parses a non-negative integer.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn integer(component: &ComponentValue<'_>) -> Option<u32> {
+    let n = number(component)?;
+    (n >= 0.0 && n <= f64::from(u32::MAX) && n == (n as u32).into()).then_some(n as u32)
+}
+
+fn is_keyword(name: &str, keywords: &[&str]) -> bool {
+    keywords.iter().any(|k| k.eq_ignore_ascii_case(name))
+}
+
+fn lookup(table: &[(&str, f64)], name: &str) -> Option<f64> {
+    table
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+        .map(|(_, value)| *value)
+}
+
+/// Absolute size keywords, as factors of `medium`.
+///
+/// <https://www.w3.org/TR/css-fonts-4/#absolute-size-mapping>
+const FONT_SIZES: &[(&str, f64)] = &[
+    ("xx-small", 3.0 / 5.0),
+    ("x-small", 3.0 / 4.0),
+    ("small", 8.0 / 9.0),
+    ("medium", 1.0),
+    ("large", 6.0 / 5.0),
+    ("x-large", 3.0 / 2.0),
+    ("xx-large", 2.0),
+    ("xxx-large", 3.0),
+];
+
+/// `font-stretch` keywords, as percentages.
+const FONT_STRETCHES: &[(&str, f64)] = &[
+    ("ultra-condensed", 50.0),
+    ("extra-condensed", 62.5),
+    ("condensed", 75.0),
+    ("semi-condensed", 87.5),
+    ("semi-expanded", 112.5),
+    ("expanded", 125.0),
+    ("extra-expanded", 150.0),
+    ("ultra-expanded", 200.0),
+];
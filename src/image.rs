@@ -0,0 +1,791 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+use crate::color::Color;
+#[cfg(not(feature = "std"))]
+use crate::float_funcs::FloatFuncs;
+use crate::length::{Length, LengthUnit, ResolveContext};
+use crate::transform::{angle_or_zero, resolve};
+use crate::value::{ComponentValue, Function, Value};
+
+/// An `<image>` value.
+///
+/// Vendor-prefixed gradients are not supported, see [`normalize_prefixes`](crate::normalize_prefixes).
+///
+/// # Example
+///
+/// ```
+/// use simplecss::{Color, GradientItem, Image, Length, LengthUnit, LineDirection, Rgba};
+///
+/// let Some(Image::LinearGradient(gradient)) = Image::parse("linear-gradient(to right, red, 30%, blue 80%)") else {
+///     panic!()
+/// };
+/// assert_eq!(gradient.direction, LineDirection::Angle(90.0));
+/// assert_eq!(gradient.items, [
+///     GradientItem::ColorStop { color: Color::Rgba(Rgba::new(255, 0, 0, 255)), position: None },
+///     GradientItem::Hint(Length::new(30.0, LengthUnit::Percent)),
+///     GradientItem::ColorStop {
+///         color: Color::Rgba(Rgba::new(0, 0, 255, 255)),
+///         position: Some(Length::new(80.0, LengthUnit::Percent)),
+///     },
+/// ]);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub enum Image<'a> {
+    /// `url()`, unescaped.
+    Url(Cow<'a, str>),
+    /// `linear-gradient()` or `repeating-linear-gradient()`.
+    LinearGradient(LinearGradient),
+    /// `radial-gradient()` or `repeating-radial-gradient()`.
+    RadialGradient(RadialGradient),
+    /// `conic-gradient()` or `repeating-conic-gradient()`.
+    ConicGradient(ConicGradient),
+    /// `image-set()`, also with the `-webkit-` prefix.
+    ImageSet(Vec<ImageSetOption<'a>>),
+}
+
+impl<'a> Image<'a> {
+    /// Parses an image from a property value.
+    ///
+    /// The value must consist of a single image.
+    pub fn parse(text: &'a str) -> Option<Self> {
+        let value = Value::parse(text).ok()?;
+        match value.components.as_slice() {
+            [component] => Image::from_component(component),
+            _ => None,
+        }
+    }
+
+    /// Converts a component value into an image.
+    pub fn from_component(component: &ComponentValue<'a>) -> Option<Self> {
+        match component {
+            ComponentValue::Url(url) => Some(Image::Url(url.clone())),
+            ComponentValue::Function(func) => Image::from_function(func),
+            _ => None,
+        }
+    }
+
+    fn from_function(func: &Function<'a>) -> Option<Self> {
+        let name = func.name.to_ascii_lowercase();
+        let (name, repeating) = match name.strip_prefix("repeating-") {
+            Some(name) => (name, true),
+            None => (name.as_str(), false),
+        };
+
+        let args: Vec<_> = func.split_commas().collect();
+        match name {
+            "linear-gradient" => {
+                LinearGradient::from_arguments(&args, repeating).map(Image::LinearGradient)
+            }
+            "radial-gradient" => {
+                RadialGradient::from_arguments(&args, repeating).map(Image::RadialGradient)
+            }
+            "conic-gradient" => {
+                ConicGradient::from_arguments(&args, repeating).map(Image::ConicGradient)
+            }
+            "image-set" | "-webkit-image-set" if !repeating => {
+                image_set(&args).map(Image::ImageSet)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A gradient color stop or color hint.
+///
+/// `P` is a length for linear and radial gradients and an angle in degrees for conic gradients.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GradientItem<P> {
+    /// A color stop.
+    ///
+    /// A color stop with two positions, like `red 10% 20%`, is stored as two stops.
+    ColorStop {
+        /// The stop color.
+        color: Color,
+        /// The stop position, if set.
+        position: Option<P>,
+    },
+    /// A color hint, ie. a transition midpoint between two color stops.
+    Hint(P),
+}
+
+/// A `linear-gradient()` direction.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LineDirection {
+    /// An angle in degrees, where `0deg` points up and `90deg` points right.
+    ///
+    /// Sides, like `to right`, are stored as angles.
+    Angle(f64),
+    /// `to top left`
+    ///
+    /// Corner angles depend on the box size, see [`LineDirection::to_degrees`].
+    TopLeft,
+    /// `to top right`
+    TopRight,
+    /// `to bottom right`
+    BottomRight,
+    /// `to bottom left`
+    BottomLeft,
+}
+
+impl LineDirection {
+    /// Returns the angle in degrees for a box size.
+    ///
+    /// A corner angle is chosen so the corners next to it have the same color.
+    pub fn to_degrees(&self, width: f64, height: f64) -> f64 {
+        match *self {
+            LineDirection::Angle(angle) => angle,
+            LineDirection::TopRight => corner_angle(width, height),
+            LineDirection::BottomRight => 180.0 - corner_angle(width, height),
+            LineDirection::BottomLeft => 180.0 + corner_angle(width, height),
+            LineDirection::TopLeft => 360.0 - corner_angle(width, height),
+        }
+    }
+
+    fn from_sides(sides: &[ComponentValue<'_>]) -> Option<Self> {
+        let side = |component: &ComponentValue<'_>| {
+            let name = component.as_ident()?.to_ascii_lowercase();
+            match name.as_str() {
+                "top" => Some(Side::Vertical(false)),
+                "bottom" => Some(Side::Vertical(true)),
+                "left" => Some(Side::Horizontal(false)),
+                "right" => Some(Side::Horizontal(true)),
+                _ => None,
+            }
+        };
+
+        let direction = match sides {
+            [first] => match side(first)? {
+                Side::Vertical(false) => LineDirection::Angle(0.0),
+                Side::Horizontal(true) => LineDirection::Angle(90.0),
+                Side::Vertical(true) => LineDirection::Angle(180.0),
+                Side::Horizontal(false) => LineDirection::Angle(270.0),
+            },
+            [first, second] => match (side(first)?, side(second)?) {
+                (Side::Horizontal(right), Side::Vertical(bottom))
+                | (Side::Vertical(bottom), Side::Horizontal(right)) => match (right, bottom) {
+                    (false, false) => LineDirection::TopLeft,
+                    (true, false) => LineDirection::TopRight,
+                    (true, true) => LineDirection::BottomRight,
+                    (false, true) => LineDirection::BottomLeft,
+                },
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        Some(direction)
+    }
+}
+
+/// Returns the `to top right` angle, which is perpendicular to the diagonal
+/// between the top left and the bottom right corners.
+fn corner_angle(width: f64, height: f64) -> f64 {
+    height.atan2(width).to_degrees()
+}
+
+/// The initial direction, ie. `to bottom`.
+impl Default for LineDirection {
+    fn default() -> Self {
+        LineDirection::Angle(180.0)
+    }
+}
+
+/// A side keyword. `true` for the right and the bottom sides.
+enum Side {
+    Horizontal(bool),
+    Vertical(bool),
+}
+
+/// A `linear-gradient()` or `repeating-linear-gradient()` value.
+#[derive(Clone, PartialEq, Debug)]
+pub struct LinearGradient {
+    /// The gradient line direction.
+    pub direction: LineDirection,
+    /// Color stops and hints.
+    pub items: Vec<GradientItem<Length>>,
+    /// Whether the gradient is repeating.
+    pub repeating: bool,
+}
+
+impl LinearGradient {
+    /// Returns the gradient line length for a box size.
+    ///
+    /// Percentages in color stops are resolved against this length.
+    pub fn line_length(&self, width: f64, height: f64) -> f64 {
+        let angle = self.direction.to_degrees(width, height).to_radians();
+        abs(width * angle.sin()) + abs(height * angle.cos())
+    }
+
+    fn from_arguments(args: &[&[ComponentValue<'_>]], repeating: bool) -> Option<Self> {
+        let direction = match *args.first()? {
+            [to, sides @ ..] if to.is_ident("to") => Some(LineDirection::from_sides(sides)?),
+            [angle] => angle_or_zero(angle).map(LineDirection::Angle),
+            _ => None,
+        };
+
+        let stops = if direction.is_some() {
+            &args[1..]
+        } else {
+            args
+        };
+        Some(LinearGradient {
+            direction: direction.unwrap_or_default(),
+            items: color_stops(stops, Length::from_component)?,
+            repeating,
+        })
+    }
+}
+
+/// A `radial-gradient()` ending shape.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum RadialShape {
+    /// `circle`
+    Circle,
+    /// `ellipse`
+    #[default]
+    Ellipse,
+}
+
+/// A `radial-gradient()` ending shape size.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum RadialSize {
+    /// `closest-side`
+    ClosestSide,
+    /// `closest-corner`
+    ClosestCorner,
+    /// `farthest-side`
+    FarthestSide,
+    /// `farthest-corner`
+    #[default]
+    FarthestCorner,
+    /// A circle radius. Never a percentage.
+    Circle(Length),
+    /// Ellipse radii.
+    Ellipse(Length, Length),
+}
+
+static RADIAL_EXTENTS: &[(&str, RadialSize)] = &[
+    ("closest-side", RadialSize::ClosestSide),
+    ("closest-corner", RadialSize::ClosestCorner),
+    ("farthest-side", RadialSize::FarthestSide),
+    ("farthest-corner", RadialSize::FarthestCorner),
+];
+
+/// A `radial-gradient()` or `repeating-radial-gradient()` value.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RadialGradient {
+    /// The ending shape.
+    pub shape: RadialShape,
+    /// The ending shape size.
+    pub size: RadialSize,
+    /// The gradient center.
+    pub position: Position,
+    /// Color stops and hints.
+    ///
+    /// Percentages are relative to the ending shape horizontal radius.
+    pub items: Vec<GradientItem<Length>>,
+    /// Whether the gradient is repeating.
+    pub repeating: bool,
+}
+
+impl RadialGradient {
+    /// Returns the gradient center and the ending shape radii, in pixels, for a box size.
+    ///
+    /// Lengths are resolved using `context`.
+    pub fn resolve(
+        &self,
+        context: &ResolveContext,
+        width: f64,
+        height: f64,
+    ) -> ((f64, f64), (f64, f64)) {
+        let (x, y) = self.position.resolve(context, width, height);
+        let (closest_x, farthest_x) = (abs(x).min(abs(width - x)), abs(x).max(abs(width - x)));
+        let (closest_y, farthest_y) = (abs(y).min(abs(height - y)), abs(y).max(abs(height - y)));
+        let is_circle = self.shape == RadialShape::Circle;
+        let radii = match self.size {
+            RadialSize::ClosestSide if is_circle => {
+                let radius = closest_x.min(closest_y);
+                (radius, radius)
+            }
+            RadialSize::ClosestSide => (closest_x, closest_y),
+            RadialSize::FarthestSide if is_circle => {
+                let radius = farthest_x.max(farthest_y);
+                (radius, radius)
+            }
+            RadialSize::FarthestSide => (farthest_x, farthest_y),
+            // An ellipse keeps the aspect ratio of the matching side size.
+            RadialSize::ClosestCorner if is_circle => {
+                let radius = (closest_x * closest_x + closest_y * closest_y).sqrt();
+                (radius, radius)
+            }
+            RadialSize::ClosestCorner => (closest_x * SQRT_2, closest_y * SQRT_2),
+            RadialSize::FarthestCorner if is_circle => {
+                let radius = (farthest_x * farthest_x + farthest_y * farthest_y).sqrt();
+                (radius, radius)
+            }
+            RadialSize::FarthestCorner => (farthest_x * SQRT_2, farthest_y * SQRT_2),
+            RadialSize::Circle(radius) => {
+                let radius = radius.to_px(context);
+                (radius, radius)
+            }
+            RadialSize::Ellipse(radius_x, radius_y) => (
+                resolve(radius_x, context, width),
+                resolve(radius_y, context, height),
+            ),
+        };
+
+        ((x, y), radii)
+    }
+
+    fn from_arguments(args: &[&[ComponentValue<'_>]], repeating: bool) -> Option<Self> {
+        let prelude = args.first().and_then(|first| radial_prelude(first));
+        let stops = if prelude.is_some() { &args[1..] } else { args };
+        let (shape, size, position) = prelude.unwrap_or_default();
+        Some(RadialGradient {
+            shape,
+            size,
+            position,
+            items: color_stops(stops, Length::from_component)?,
+            repeating,
+        })
+    }
+}
+
+const SQRT_2: f64 = core::f64::consts::SQRT_2;
+
+/// Parses `[<radial-shape> || <radial-size>]? [at <position>]?`.
+fn radial_prelude(
+    components: &[ComponentValue<'_>],
+) -> Option<(RadialShape, RadialSize, Position)> {
+    let (components, position) = match components.iter().position(|c| c.is_ident("at")) {
+        Some(index) => (
+            &components[..index],
+            Position::from_components(&components[index + 1..])?,
+        ),
+        None => (components, Position::CENTER),
+    };
+
+    let mut shape = None;
+    let mut extent = None;
+    let mut lengths = Vec::new();
+    for (index, component) in components.iter().enumerate() {
+        if component.is_ident("circle") || component.is_ident("ellipse") {
+            let new_shape = if component.is_ident("circle") {
+                RadialShape::Circle
+            } else {
+                RadialShape::Ellipse
+            };
+            if shape.replace(new_shape).is_some() {
+                return None;
+            }
+        } else if let Some(size) = component.as_ident().and_then(|name| {
+            RADIAL_EXTENTS
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        }) {
+            if extent.replace(size.1).is_some() {
+                return None;
+            }
+        } else {
+            let length = Length::from_component(component).filter(|length| length.value >= 0.0)?;
+            // Radii must be next to each other.
+            if matches!(lengths.last(), Some((last, _)) if last + 1 != index) {
+                return None;
+            }
+            lengths.push((index, length));
+        }
+    }
+
+    let size = match (shape, extent, lengths.as_slice()) {
+        (_, Some(size), []) => size,
+        (None | Some(RadialShape::Circle), None, [(_, radius)])
+            if radius.unit != LengthUnit::Percent =>
+        {
+            RadialSize::Circle(*radius)
+        }
+        (None | Some(RadialShape::Ellipse), None, [(_, x), (_, y)]) => RadialSize::Ellipse(*x, *y),
+        (_, None, []) => RadialSize::FarthestCorner,
+        _ => return None,
+    };
+
+    let shape = shape.unwrap_or(match size {
+        RadialSize::Circle(_) => RadialShape::Circle,
+        _ => RadialShape::Ellipse,
+    });
+
+    Some((shape, size, position))
+}
+
+/// A `conic-gradient()` or `repeating-conic-gradient()` value.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ConicGradient {
+    /// The starting angle in degrees, where `0deg` points up.
+    pub from: f64,
+    /// The gradient center.
+    pub position: Position,
+    /// Color stops and hints, in degrees.
+    ///
+    /// Percentages are converted into degrees, so `50%` is `180`.
+    pub items: Vec<GradientItem<f64>>,
+    /// Whether the gradient is repeating.
+    pub repeating: bool,
+}
+
+impl ConicGradient {
+    fn from_arguments(args: &[&[ComponentValue<'_>]], repeating: bool) -> Option<Self> {
+        let mut from = 0.0;
+        let mut position = Position::CENTER;
+        let mut has_prelude = false;
+        let mut rest = *args.first()?;
+        if let [keyword, angle, tail @ ..] = rest {
+            if keyword.is_ident("from") {
+                from = angle_or_zero(angle)?;
+                rest = tail;
+                has_prelude = true;
+            }
+        }
+
+        if let [keyword, tail @ ..] = rest {
+            if keyword.is_ident("at") {
+                position = Position::from_components(tail)?;
+                rest = &[];
+                has_prelude = true;
+            }
+        }
+
+        if has_prelude && !rest.is_empty() {
+            return None;
+        }
+
+        let stops = if has_prelude { &args[1..] } else { args };
+        Some(ConicGradient {
+            from,
+            position,
+            items: color_stops(stops, |component| match component {
+                ComponentValue::Percentage(n) => Some(n * 3.6),
+                _ => angle_or_zero(component),
+            })?,
+            repeating,
+        })
+    }
+}
+
+/// Parses a color stop list with at least two color stops.
+fn color_stops<P: Copy>(
+    args: &[&[ComponentValue<'_>]],
+    position: impl Fn(&ComponentValue<'_>) -> Option<P>,
+) -> Option<Vec<GradientItem<P>>> {
+    let mut items = Vec::new();
+    for arg in args {
+        let (first, rest) = arg.split_first()?;
+        match Color::from_component(first) {
+            Some(color) => match rest {
+                [] => items.push(GradientItem::ColorStop {
+                    color,
+                    position: None,
+                }),
+                [_] | [_, _] => {
+                    for component in rest {
+                        items.push(GradientItem::ColorStop {
+                            color,
+                            position: Some(position(component)?),
+                        });
+                    }
+                }
+                _ => return None,
+            },
+            // A hint must be between two color stops.
+            None if rest.is_empty()
+                && matches!(items.last(), Some(GradientItem::ColorStop { .. })) =>
+            {
+                items.push(GradientItem::Hint(position(first)?));
+            }
+            None => return None,
+        }
+    }
+
+    let stops = items
+        .iter()
+        .filter(|item| matches!(item, GradientItem::ColorStop { .. }))
+        .count();
+    (stops >= 2 && matches!(items.last(), Some(GradientItem::ColorStop { .. }))).then_some(items)
+}
+
+/// An `image-set()` option.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ImageSetOption<'a> {
+    /// The image. A string is stored as [`Image::Url`].
+    pub image: Image<'a>,
+    /// The resolution in `dppx`. `1` by default.
+    pub resolution: f64,
+    /// The MIME type from `type()`, unescaped.
+    pub mime_type: Option<Cow<'a, str>>,
+}
+
+fn image_set<'a>(args: &[&[ComponentValue<'a>]]) -> Option<Vec<ImageSetOption<'a>>> {
+    let options = args
+        .iter()
+        .map(|option| {
+            let (first, rest) = option.split_first()?;
+            let image = match first {
+                ComponentValue::String(url) => Image::Url(url.clone()),
+                _ => Image::from_component(first)
+                    .filter(|image| !matches!(image, Image::ImageSet(_)))?,
+            };
+
+            let mut resolution = None;
+            let mut mime_type = None;
+            for component in rest {
+                match component {
+                    ComponentValue::Dimension(value, unit) if resolution.is_none() => {
+                        resolution = Some(resolution_to_dppx(*value, unit)?);
+                    }
+                    ComponentValue::Function(func)
+                        if func.name.eq_ignore_ascii_case("type") && mime_type.is_none() =>
+                    {
+                        match func.arguments.as_slice() {
+                            [ComponentValue::String(mime)] => mime_type = Some(mime.clone()),
+                            _ => return None,
+                        }
+                    }
+                    _ => return None,
+                }
+            }
+
+            Some(ImageSetOption {
+                image,
+                resolution: resolution.unwrap_or(1.0),
+                mime_type,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    (!options.is_empty()).then_some(options)
+}
+
+fn resolution_to_dppx(value: f64, unit: &str) -> Option<f64> {
+    let dppx = match unit.to_ascii_lowercase().as_str() {
+        "x" | "dppx" => value,
+        "dpi" => value / 96.0,
+        "dpcm" => value * 2.54 / 96.0,
+        _ => return None,
+    };
+
+    (dppx >= 0.0).then_some(dppx)
+}
+
+/// A `<position>` offset along one axis.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PositionOffset {
+    /// The offset length. Percentages are relative to the box size.
+    pub offset: Length,
+    /// Whether the offset is from the right or the bottom edge, like in `right 10px`.
+    pub from_end: bool,
+}
+
+impl PositionOffset {
+    const fn percent(value: f64) -> Self {
+        PositionOffset {
+            offset: Length::new(value, LengthUnit::Percent),
+            from_end: false,
+        }
+    }
+}
+
+/// A `<position>` value, like `center` or `right 10px top`.
+///
+/// Keywords without an offset are stored as percentages, so `right` is `100%`.
+///
+/// # Example
+///
+/// ```
+/// use simplecss::{Length, LengthUnit, Position, ResolveContext};
+///
+/// let position = Position::parse("right 10px bottom").unwrap();
+/// assert_eq!(position.x.offset, Length::new(10.0, LengthUnit::Px));
+/// assert!(position.x.from_end);
+/// assert_eq!(position.y.offset, Length::new(100.0, LengthUnit::Percent));
+/// assert_eq!(position.resolve(&ResolveContext::default(), 200.0, 100.0), (190.0, 100.0));
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Position {
+    /// The horizontal offset.
+    pub x: PositionOffset,
+    /// The vertical offset.
+    pub y: PositionOffset,
+}
+
+impl Position {
+    /// `center`, ie. `50% 50%`.
+    pub const CENTER: Position = Position {
+        x: PositionOffset::percent(50.0),
+        y: PositionOffset::percent(50.0),
+    };
+
+    /// Parses a position from a property value.
+    pub fn parse(text: &str) -> Option<Self> {
+        let value = Value::parse(text).ok()?;
+        Position::from_components(&value.components)
+    }
+
+    /// Converts component values into a position.
+    pub fn from_components(components: &[ComponentValue<'_>]) -> Option<Self> {
+        let parts = components
+            .iter()
+            .map(PositionPart::from_component)
+            .collect::<Option<Vec<_>>>()?;
+
+        let (x, y) = match parts.as_slice() {
+            [part] => match *part {
+                PositionPart::Vertical(y) => (PositionPart::CENTER, y),
+                PositionPart::Horizontal(x) | PositionPart::Center(x) | PositionPart::Length(x) => {
+                    (x, PositionPart::CENTER)
+                }
+            },
+            [first, second] => match (*first, *second) {
+                // Keywords can be in any order, like `top left`.
+                (
+                    PositionPart::Horizontal(x) | PositionPart::Center(x) | PositionPart::Length(x),
+                    PositionPart::Vertical(y) | PositionPart::Center(y) | PositionPart::Length(y),
+                )
+                | (
+                    PositionPart::Vertical(y) | PositionPart::Center(y),
+                    PositionPart::Horizontal(x) | PositionPart::Center(x),
+                ) => (x, y),
+                _ => return None,
+            },
+            [_, _, _] | [_, _, _, _] => {
+                let (x, y) = edge_offsets(&parts)?;
+                return Some(Position { x, y });
+            }
+            _ => return None,
+        };
+
+        Some(Position {
+            x: x.into(),
+            y: y.into(),
+        })
+    }
+
+    /// Resolves the position into pixels, relative to the top left corner of a box.
+    ///
+    /// Lengths are resolved using `context` and percentages are resolved against
+    /// the box `width` and `height`.
+    pub fn resolve(&self, context: &ResolveContext, width: f64, height: f64) -> (f64, f64) {
+        let resolve_offset = |offset: &PositionOffset, size: f64| {
+            let length = resolve(offset.offset, context, size);
+            if offset.from_end {
+                size - length
+            } else {
+                length
+            }
+        };
+
+        (
+            resolve_offset(&self.x, width),
+            resolve_offset(&self.y, height),
+        )
+    }
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Position::CENTER
+    }
+}
+
+/// A `<position>` part, like `left` or `10px`.
+///
+/// Keywords are stored as lengths from the start.
+#[derive(Clone, Copy)]
+enum PositionPart {
+    /// `left` or `right`.
+    Horizontal(Length),
+    /// `top` or `bottom`.
+    Vertical(Length),
+    /// `center`.
+    Center(Length),
+    /// A length or a percentage.
+    Length(Length),
+}
+
+impl PositionPart {
+    const CENTER: Length = Length::new(50.0, LengthUnit::Percent);
+
+    fn from_component(component: &ComponentValue<'_>) -> Option<Self> {
+        let percent = |value| Length::new(value, LengthUnit::Percent);
+        let Some(name) = component.as_ident() else {
+            return Length::from_component(component).map(PositionPart::Length);
+        };
+
+        match name.to_ascii_lowercase().as_str() {
+            "left" => Some(PositionPart::Horizontal(percent(0.0))),
+            "right" => Some(PositionPart::Horizontal(percent(100.0))),
+            "top" => Some(PositionPart::Vertical(percent(0.0))),
+            "bottom" => Some(PositionPart::Vertical(percent(100.0))),
+            "center" => Some(PositionPart::Center(PositionPart::CENTER)),
+            _ => None,
+        }
+    }
+}
+
+/// Parses the three and four values syntax, like `right 10px top` or `left 10% bottom 5px`.
+fn edge_offsets(parts: &[PositionPart]) -> Option<(PositionOffset, PositionOffset)> {
+    // An axis, `true` for vertical, and an offset.
+    let mut groups: Vec<(Option<bool>, PositionOffset)> = Vec::new();
+    let mut index = 0;
+    while let Some(part) = parts.get(index) {
+        let (axis, keyword) = match *part {
+            PositionPart::Horizontal(keyword) => (Some(false), keyword),
+            PositionPart::Vertical(keyword) => (Some(true), keyword),
+            PositionPart::Center(keyword) => (None, keyword),
+            PositionPart::Length(_) => return None,
+        };
+
+        let from_end = keyword.value == 100.0;
+        let offset = match parts.get(index + 1) {
+            Some(PositionPart::Length(length)) if axis.is_some() => {
+                index += 1;
+                PositionOffset {
+                    offset: *length,
+                    from_end,
+                }
+            }
+            _ => PositionOffset {
+                offset: keyword,
+                from_end: false,
+            },
+        };
+
+        groups.push((axis, offset));
+        index += 1;
+    }
+
+    match groups.as_slice() {
+        [(Some(false) | None, x), (Some(true) | None, y)]
+        | [(Some(true), y), (Some(false) | None, x)]
+        | [(None, y), (Some(false), x)] => Some((*x, *y)),
+        _ => None,
+    }
+}
+
+impl From<Length> for PositionOffset {
+    fn from(offset: Length) -> Self {
+        PositionOffset {
+            offset,
+            from_end: false,
+        }
+    }
+}
+
+fn abs(value: f64) -> f64 {
+    if value < 0.0 {
+        -value
+    } else {
+        value
+    }
+}
@@ -0,0 +1,209 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::iter::Peekable;
+
+use crate::line_index::LazyLineIndex;
+use crate::stream::Stream;
+use crate::{Declaration, DeclarationSpan, Rule, Span, StyleSheet};
+
+/// A text edit.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct TextEdit {
+    /// The replaced range of the old text.
+    pub range: Span,
+    /// The length of the replacement, in bytes.
+    pub new_len: usize,
+}
+
+impl TextEdit {
+    /// Constructs a new `TextEdit` that replaces `range` with `replacement`.
+    pub fn new(range: Span, replacement: &str) -> Self {
+        TextEdit {
+            range,
+            new_len: replacement.len(),
+        }
+    }
+}
+
+impl StyleSheet<'_> {
+    /// Parses an edited version of the text this style sheet was parsed from.
+    ///
+    /// Only the top-level statements touched by the edit are parsed again.
+    /// The other rules are copied with shifted spans,
+    /// so the result is the same as `StyleSheet::parse(text)`.
+    /// At-rules are always parsed again.
+    ///
+    /// The style sheet must be created by [`StyleSheet::parse`] or by a previous `reparse`.
+    /// When it was parsed from multiple texts by [`StyleSheet::parse_more`]
+    /// or when `edit` doesn't fit into `text`, the whole text is parsed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use simplecss::{Span, StyleSheet, TextEdit};
+    ///
+    /// let sheet = StyleSheet::parse("a { color: red } b { color: red }");
+    ///
+    /// let text = "a { color: red } b { color: green }";
+    /// let sheet = sheet.reparse(text, TextEdit::new(Span::new(28, 31), "green"));
+    /// assert_eq!(sheet.to_string(), "a { color:red; }\nb { color:green; }");
+    /// ```
+    pub fn reparse<'b>(&self, text: &'b str, edit: TextEdit) -> StyleSheet<'b> {
+        reparse(self, text, edit).unwrap_or_else(|| StyleSheet::parse(text))
+    }
+}
+
+fn reparse<'b>(old: &StyleSheet<'_>, text: &'b str, edit: TextEdit) -> Option<StyleSheet<'b>> {
+    // Spans of multiple texts can't be mapped into the new one.
+    if old.texts > 1 {
+        return None;
+    }
+
+    let statements = &old.statements;
+    let old_end = edit.range.end;
+    let new_end = edit.range.start.checked_add(edit.new_len)?;
+    if edit.range.start > old_end || new_end > text.len() {
+        return None;
+    }
+
+    // Rules are sorted by specificity, restore the source order.
+    let mut rules: Vec<_> = old.rules.iter().collect();
+    rules.sort_by_key(|rule| (rule.span.start, rule.selector.span().start));
+    let mut rules = rules.into_iter().peekable();
+
+    let mut sheet = StyleSheet::new();
+    sheet.texts = 1;
+
+    let root = Stream::from(text).defer_positions();
+    let lines = LazyLineIndex::new(text);
+
+    // Statements before the edit are not affected.
+    let prefix = statements.partition_point(|span| span.end < edit.range.start);
+    for span in &statements[..prefix] {
+        copy_statement(
+            &mut sheet,
+            &mut rules,
+            root,
+            #[cfg(feature = "at_rules")]
+            &lines,
+            *span,
+            |pos| pos,
+        )?;
+    }
+
+    // Statements after the edit are reused once the parser gets in sync with them again.
+    let mut suffix = statements.partition_point(|span| span.start < old_end);
+    let mut in_sync = false;
+    let start = statements[..prefix].last().map_or(0, |span| span.end);
+    let mut s = root.sub_stream(start, text.len());
+    sheet.consume_statements(&mut s, &lines, |pos| {
+        if pos < new_end {
+            return false;
+        }
+
+        let old_pos = pos - new_end + old_end;
+        while suffix < statements.len() && statements[suffix].start < old_pos {
+            suffix += 1;
+        }
+
+        in_sync = statements.get(suffix).map(|span| span.start) == Some(old_pos);
+        in_sync
+    });
+
+    if in_sync {
+        let shift = |pos: usize| pos - old_end + new_end;
+        for span in &statements[suffix..] {
+            copy_statement(
+                &mut sheet,
+                &mut rules,
+                root,
+                #[cfg(feature = "at_rules")]
+                &lines,
+                *span,
+                shift,
+            )?;
+        }
+    }
+
+    sheet.finish();
+    Some(sheet)
+}
+
+/// Copies rules of an old statement, remapping them into the new text.
+fn copy_statement<'a, 'b, I, F>(
+    sheet: &mut StyleSheet<'b>,
+    rules: &mut Peekable<I>,
+    root: Stream<'b>,
+    #[cfg(feature = "at_rules")] lines: &LazyLineIndex<'_>,
+    old_span: Span,
+    map: F,
+) -> Option<()>
+where
+    I: Iterator<Item = &'a Rule<'a>>,
+    F: Fn(usize) -> usize,
+{
+    let text = root.slice_tail();
+    let span = Span::new(map(old_span.start), map(old_span.end));
+    if span.end > text.len() {
+        return None;
+    }
+
+    sheet.statements.push(span);
+
+    // Skip rules of the reparsed statements.
+    while rules
+        .next_if(|rule| rule.span.start < old_span.start)
+        .is_some()
+    {}
+
+    while let Some(rule) = rules.next_if(|rule| rule.span.start < old_span.end) {
+        let rule = copy_rule(rule, text, &map)?;
+        sheet.rules.push(rule);
+    }
+
+    #[cfg(feature = "at_rules")]
+    if text.as_bytes()[span.start] == b'@' {
+        let mut s = root.sub_stream(span.start + 1, text.len());
+        let _ = crate::consume_at_rule(&mut s, &mut sheet.at_rules, lines);
+    }
+
+    Some(())
+}
+
+/// Copies a rule with shifted spans, borrowing from the new text.
+fn copy_rule<'b, F>(rule: &Rule<'_>, text: &'b str, map: &F) -> Option<Rule<'b>>
+where
+    F: Fn(usize) -> usize,
+{
+    let map_span = |span: Span| Span::new(map(span.start), map(span.end));
+    let slice = |span: Span| text.get(map(span.start)..map(span.end));
+
+    let selector = rule.selector.rebase(text, map_span(rule.selector.span()))?;
+
+    let mut declarations = Vec::with_capacity(rule.declarations.len());
+    for d in &rule.declarations {
+        let name = match &d.name {
+            Cow::Borrowed(_) => Cow::Borrowed(slice(d.span.name)?),
+            Cow::Owned(name) => Cow::Owned(name.clone()),
+        };
+        declarations.push(Declaration {
+            name,
+            value: slice(d.span.value)?,
+            important: d.important,
+            span: DeclarationSpan {
+                name: map_span(d.span.name),
+                value: map_span(d.span.value),
+                important: d.span.important.map(map_span),
+            },
+        });
+    }
+
+    Some(Rule {
+        selector,
+        declarations,
+        span: map_span(rule.span),
+    })
+}
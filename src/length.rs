@@ -0,0 +1,261 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use core::fmt;
+
+use crate::calc::CalcNode;
+use crate::value::{ComponentValue, Value};
+
+/// A length unit.
+///
+/// <https://www.w3.org/TR/css-values-4/#lengths>
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum LengthUnit {
+    /// `px`
+    Px,
+    /// `pt`, 1/72 of an inch.
+    Pt,
+    /// `pc`, 12 points.
+    Pc,
+    /// `in`
+    In,
+    /// `cm`
+    Cm,
+    /// `mm`
+    Mm,
+    /// `Q`, a quarter of a millimeter.
+    Q,
+    /// `em`, the font size.
+    Em,
+    /// `rem`, the root font size.
+    Rem,
+    /// `ex`, the x-height. Approximated as half of the font size.
+    Ex,
+    /// `ch`, the `0` advance. Approximated as half of the font size.
+    Ch,
+    /// `%`
+    Percent,
+    /// `vw`, 1% of the viewport width.
+    Vw,
+    /// `vh`, 1% of the viewport height.
+    Vh,
+    /// `vmin`, 1% of the smaller viewport dimension.
+    Vmin,
+    /// `vmax`, 1% of the larger viewport dimension.
+    Vmax,
+    /// `svh`, 1% of the small viewport height.
+    Svh,
+    /// `lvh`, 1% of the large viewport height.
+    Lvh,
+    /// `dvh`, 1% of the dynamic viewport height.
+    Dvh,
+    /// `cqw`, 1% of the query container width.
+    Cqw,
+    /// `cqh`, 1% of the query container height.
+    Cqh,
+}
+
+impl LengthUnit {
+    /// Returns a unit by its name, ignoring ASCII case.
+    ///
+    /// `%` is accepted as well.
+    pub fn from_name(name: &str) -> Option<Self> {
+        UNITS
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, unit)| *unit)
+    }
+
+    /// Returns the unit name, like `px`.
+    pub fn as_str(&self) -> &'static str {
+        UNITS
+            .iter()
+            .find(|(_, unit)| unit == self)
+            .map_or("", |(name, _)| name)
+    }
+}
+
+static UNITS: &[(&str, LengthUnit)] = &[
+    ("px", LengthUnit::Px),
+    ("pt", LengthUnit::Pt),
+    ("pc", LengthUnit::Pc),
+    ("in", LengthUnit::In),
+    ("cm", LengthUnit::Cm),
+    ("mm", LengthUnit::Mm),
+    ("Q", LengthUnit::Q),
+    ("em", LengthUnit::Em),
+    ("rem", LengthUnit::Rem),
+    ("ex", LengthUnit::Ex),
+    ("ch", LengthUnit::Ch),
+    ("%", LengthUnit::Percent),
+    ("vw", LengthUnit::Vw),
+    ("vh", LengthUnit::Vh),
+    ("vmin", LengthUnit::Vmin),
+    ("vmax", LengthUnit::Vmax),
+    ("svh", LengthUnit::Svh),
+    ("lvh", LengthUnit::Lvh),
+    ("dvh", LengthUnit::Dvh),
+    ("cqw", LengthUnit::Cqw),
+    ("cqh", LengthUnit::Cqh),
+];
+
+/// A length or a percentage.
+///
+/// # Example
+///
+/// ```
+/// use simplecss::{Length, LengthUnit, ResolveContext};
+///
+/// let length = Length::parse("1.5em").unwrap();
+/// assert_eq!(length, Length::new(1.5, LengthUnit::Em));
+///
+/// let context = ResolveContext {
+///     font_size: 20.0,
+///     ..ResolveContext::default()
+/// };
+/// assert_eq!(length.to_px(&context), 30.0);
+/// assert_eq!(Length::parse("1in").unwrap().to_px(&context), 96.0);
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Length {
+    /// The number.
+    pub value: f64,
+    /// The unit.
+    pub unit: LengthUnit,
+}
+
+impl Length {
+    /// Constructs a new length.
+    pub const fn new(value: f64, unit: LengthUnit) -> Self {
+        Length { value, unit }
+    }
+
+    /// Parses a length from a property value.
+    ///
+    /// The value must consist of a single length or percentage.
+    /// Unitless zero is accepted as `0px`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let value = Value::parse(text).ok()?;
+        match value.components.as_slice() {
+            [component] => Length::from_component(component),
+            _ => None,
+        }
+    }
+
+    /// Converts a component value into a length.
+    ///
+    /// Unitless zero is accepted as `0px`.
+    /// Math functions are accepted when they simplify to a single length, like `calc(1em + 2em)`.
+    /// Use [`CalcNode`] for other math functions.
+    pub fn from_component(component: &ComponentValue<'_>) -> Option<Self> {
+        match component {
+            ComponentValue::Dimension(value, unit) => {
+                Some(Length::new(*value, LengthUnit::from_name(unit)?))
+            }
+            ComponentValue::Percentage(value) => Some(Length::new(*value, LengthUnit::Percent)),
+            ComponentValue::Number(value) if *value == 0.0 => {
+                Some(Length::new(0.0, LengthUnit::Px))
+            }
+            ComponentValue::Function(func) => match CalcNode::from_function(func)? {
+                CalcNode::Length(length) => Some(length),
+                CalcNode::Percentage(value) => Some(Length::new(value, LengthUnit::Percent)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Converts the length into pixels.
+    pub fn to_px(&self, context: &ResolveContext) -> f64 {
+        let c = context;
+        let vw = c.viewport_width / 100.0;
+        let vh = c.viewport_height / 100.0;
+        let svh = c.small_viewport_height.unwrap_or(c.viewport_height) / 100.0;
+        let lvh = c.large_viewport_height.unwrap_or(c.viewport_height) / 100.0;
+
+        let factor = match self.unit {
+            LengthUnit::Px => 1.0,
+            LengthUnit::Pt => c.dpi / 72.0,
+            LengthUnit::Pc => c.dpi / 6.0,
+            LengthUnit::In => c.dpi,
+            LengthUnit::Cm => c.dpi / 2.54,
+            LengthUnit::Mm => c.dpi / 25.4,
+            LengthUnit::Q => c.dpi / 101.6,
+            LengthUnit::Em => c.font_size,
+            LengthUnit::Rem => c.root_font_size,
+            LengthUnit::Ex | LengthUnit::Ch => c.font_size / 2.0,
+            LengthUnit::Percent => c.percentage_base / 100.0,
+            LengthUnit::Vw => vw,
+            LengthUnit::Vh | LengthUnit::Dvh => vh,
+            LengthUnit::Vmin => vw.min(vh),
+            LengthUnit::Vmax => vw.max(vh),
+            LengthUnit::Svh => svh,
+            LengthUnit::Lvh => lvh,
+            // Without a query container, the small viewport is used.
+            LengthUnit::Cqw => c.container_width.map_or(vw, |w| w / 100.0),
+            LengthUnit::Cqh => c.container_height.map_or(svh, |h| h / 100.0),
+        };
+
+        self.value * factor
+    }
+}
+
+impl fmt::Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.value, self.unit.as_str())
+    }
+}
+
+/// A context for resolving relative lengths.
+///
+/// All sizes are in pixels.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ResolveContext {
+    /// The font size of the element. `16` by default.
+    pub font_size: f64,
+    /// The font size of the root element. `16` by default.
+    pub root_font_size: f64,
+    /// The viewport width.
+    pub viewport_width: f64,
+    /// The viewport height. Also used for `dvh`.
+    pub viewport_height: f64,
+    /// The small viewport height, ie. with all browser UI expanded.
+    ///
+    /// Falls back to `viewport_height`.
+    pub small_viewport_height: Option<f64>,
+    /// The large viewport height, ie. with all browser UI retracted.
+    ///
+    /// Falls back to `viewport_height`.
+    pub large_viewport_height: Option<f64>,
+    /// The query container width.
+    ///
+    /// Falls back to the viewport width.
+    pub container_width: Option<f64>,
+    /// The query container height.
+    ///
+    /// Falls back to the small viewport height.
+    pub container_height: Option<f64>,
+    /// The length that `100%` stands for, like the containing block width.
+    pub percentage_base: f64,
+    /// Pixels per inch. `96` by default.
+    ///
+    /// Used for absolute units, like `in` and `pt`.
+    pub dpi: f64,
+}
+
+impl Default for ResolveContext {
+    fn default() -> Self {
+        ResolveContext {
+            font_size: 16.0,
+            root_font_size: 16.0,
+            viewport_width: 0.0,
+            viewport_height: 0.0,
+            small_viewport_height: None,
+            large_viewport_height: None,
+            container_width: None,
+            container_height: None,
+            percentage_base: 0.0,
+            dpi: 96.0,
+        }
+    }
+}
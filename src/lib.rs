@@ -14,17 +14,35 @@ Since it's very simple we will start with limitations:
 
 - [At-rules](https://www.w3.org/TR/CSS21/syndata.html#at-rules) are not supported.
   They will be skipped during parsing.
-- Property values are not parsed.
+- Property values are stored as strings.
   In CSS like `* { width: 5px }` you will get a `width` property with a `5px` value as a string.
-- CDO/CDC comments are not supported.
-- Parser is case sensitive. All keywords must be lowercase.
-- Unicode escape, like `\26`, is not supported.
+  Use [`Declaration::parse_value`] to get component values.
+- CDO/CDC tokens, ie. `<!--` and `-->`, are ignored only between top-level statements.
+- Parser is case insensitive only for keywords. Idents are returned as is.
+- Escapes, like `\26` or `\:`, are decoded in selectors, property names and value components.
+  [`Declaration::value`] keeps the original text, use [`unescape`] to decode a part of it.
 
 ## Features
 
 - Selector matching support.
 - The rules are sorted by specificity.
 - `!important` parsing support.
+- Source spans for rules, selectors and declarations.
+- Push-based parsing of chunked input.
+- Incremental reparsing after text edits.
+- Property values tokenizer and component value parser.
+- Color parsing, including CSS Color 4 color spaces, `color-mix()` and relative colors.
+- Length parsing and resolution to pixels.
+- Math functions evaluation, like `calc()`, `min()`, `max()` and `clamp()`.
+- Shorthand properties expansion into longhands and contraction for minified output.
+- Custom properties with `var()` and `env()` substitution.
+- URL references search and rewriting.
+- Transform functions parsing and composition into 2D and 3D matrices.
+- Font shorthand, font family list and font settings parsing.
+- Property registry with inheritance, initial values and value validation.
+- Vendor prefixes normalization and prefixing.
+- Image values parsing, including gradients and `image-set()`.
+- Animation and transition values parsing, with easing functions evaluation.
 - Has a high-level parsers and low-level, zero-allocation tokenizers.
 - No unsafe.
 */
@@ -58,21 +76,97 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+// Suppress the unused_crate_dependencies lint when both std and libm are specified.
+#[cfg(all(feature = "std", feature = "libm"))]
+use libm as _;
+
+use alloc::borrow::Cow;
+use alloc::string::String;
 use alloc::vec::Vec;
 use core::fmt;
+use core::fmt::Write;
 
 use log::warn;
 
+#[cfg(any(feature = "std", feature = "libm"))]
+mod animation;
 /// Module containing everything needed for @rules
-#[cfg(feature="at_rules")]
+#[cfg(feature = "at_rules")]
 pub mod at_rules;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod calc;
+mod chunked_parser;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod color;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod color_space;
+mod encoding;
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+mod float_funcs;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod font;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod image;
+mod incremental;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod length;
+mod line_index;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod prefixes;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod properties;
 mod selector;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod shorthands;
 mod stream;
-
-pub use selector::*;
-use stream::Stream;
-#[cfg(feature="at_rules")]
+#[cfg(any(feature = "std", feature = "libm"))]
+mod transform;
+mod urls;
+mod value;
+mod variables;
+
+#[cfg(any(feature = "std", feature = "libm"))]
+pub use animation::{
+    Animation, AnimationDirection, EasingFunction, FillMode, IterationCount, LinearPoint,
+    PlayState, StepPosition, Transition, TransitionProperty,
+};
+#[cfg(feature = "at_rules")]
 use at_rules::at_rule::AtRule;
+#[cfg(any(feature = "std", feature = "libm"))]
+pub use calc::{CalcNode, CalcType, MathFunction, RoundingStrategy};
+pub use chunked_parser::{ChunkedParser, OwnedStatement};
+#[cfg(any(feature = "std", feature = "libm"))]
+pub use color::{Color, Rgba};
+pub use encoding::{decode_bytes, Encoding};
+#[cfg(any(feature = "std", feature = "libm"))]
+pub use font::{
+    Font, FontFamily, FontFeature, FontSize, FontStyle, FontVariation, FontWeight, GenericFamily,
+    LineHeight, SystemFont,
+};
+#[cfg(any(feature = "std", feature = "libm"))]
+pub use image::{
+    ConicGradient, GradientItem, Image, ImageSetOption, LineDirection, LinearGradient, Position,
+    PositionOffset, RadialGradient, RadialShape, RadialSize,
+};
+pub use incremental::TextEdit;
+#[cfg(any(feature = "std", feature = "libm"))]
+pub use length::{Length, LengthUnit, ResolveContext};
+use line_index::LazyLineIndex;
+pub use line_index::LineIndex;
+#[cfg(any(feature = "std", feature = "libm"))]
+pub use prefixes::{add_prefixes, normalize_prefixes, PrefixOptions, VendorPrefix};
+#[cfg(any(feature = "std", feature = "libm"))]
+pub use properties::{properties, property, Property, PropertySyntax, ValidationError};
+pub use selector::*;
+#[cfg(any(feature = "std", feature = "libm"))]
+pub use shorthands::{contract, longhands, ContractedDeclaration, Longhand};
+pub use stream::unescape;
+use stream::{write_ident, Stream};
+#[cfg(any(feature = "std", feature = "libm"))]
+pub use transform::{Matrix2D, Matrix3D, Transform, TransformFunction, TransformOrigin};
+pub use urls::{find_urls, rewrite_urls, UrlKind, UrlReference};
+pub use value::{Bracket, ComponentValue, Function, Value, ValueToken, ValueTokenizer};
+pub use variables::{CustomProperties, Environment};
 
 /// A list of possible errors.
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -115,6 +209,32 @@ pub enum Error {
     InvalidLanguagePseudoClass,
 }
 
+impl Error {
+    /// Resolves a byte offset held by an error of a stream with deferred positions.
+    fn resolve(self, lines: &LazyLineIndex<'_>) -> Self {
+        let resolve = |pos: TextPos| match pos.row {
+            0 => lines.text_pos(pos.col as usize),
+            _ => pos,
+        };
+
+        match self {
+            Error::InvalidIdent(pos) => Error::InvalidIdent(resolve(pos)),
+            Error::InvalidComment(pos) => Error::InvalidComment(resolve(pos)),
+            Error::InvalidValue(pos) => Error::InvalidValue(resolve(pos)),
+            Error::InvalidByte {
+                expected,
+                actual,
+                pos,
+            } => Error::InvalidByte {
+                expected,
+                actual,
+                pos: resolve(pos),
+            },
+            _ => self,
+        }
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
@@ -188,32 +308,277 @@ impl fmt::Display for TextPos {
     }
 }
 
+/// A byte range in the source text.
+///
+/// Spans are relative to the text passed to the parser.
+/// They are not taken into account when comparing nodes,
+/// so the same CSS parsed at different positions is still equal.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct Span {
+    /// The start byte offset.
+    pub start: usize,
+    /// The end byte offset, exclusive.
+    pub end: usize,
+}
+
+impl Span {
+    /// Constructs a new `Span`.
+    pub fn new(start: usize, end: usize) -> Span {
+        Span { start, end }
+    }
+
+    /// Returns the span length in bytes.
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Checks that the span is empty.
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Returns the text covered by the span.
+    ///
+    /// # Panics
+    ///
+    /// When the span is out of bounds of `text` or not on a char boundary.
+    pub fn slice<'t>(&self, text: &'t str) -> &'t str {
+        &text[self.start..self.end]
+    }
+
+    /// Returns the position of the span start in `text`.
+    ///
+    /// Scans `text` from the start. Use [`LineIndex`] for repeated lookups.
+    pub fn start_pos(&self, text: &str) -> TextPos {
+        Stream::from(text).gen_text_pos_from(self.start)
+    }
+
+    /// Returns the position of the span end in `text`.
+    ///
+    /// Scans `text` from the start. Use [`LineIndex`] for repeated lookups.
+    pub fn end_pos(&self, text: &str) -> TextPos {
+        Stream::from(text).gen_text_pos_from(self.end)
+    }
+}
+
 /// A declaration.
-#[derive(Clone, Copy, PartialEq, Debug)]
-#[allow(missing_docs)]
+#[derive(Clone, Debug)]
 pub struct Declaration<'a> {
-    pub name: &'a str,
+    /// The property name, unescaped.
+    pub name: Cow<'a, str>,
+    /// The value source text, without `!important`.
+    ///
+    /// Escapes are kept, since decoding them would change the value structure.
+    /// Use [`parse_value`](Declaration::parse_value) to get unescaped components.
     pub value: &'a str,
+    /// Whether the declaration has `!important`.
     pub important: bool,
+    /// The source location of the declaration parts.
+    pub span: DeclarationSpan,
+}
+
+impl Declaration<'_> {
+    /// Returns the span of the whole declaration, from the name to the value
+    /// or to the `!important` when present.
+    ///
+    /// The trailing `;` is not included.
+    pub fn span(&self) -> Span {
+        let end = match self.span.important {
+            Some(important) => important.end,
+            None => self.span.value.end,
+        };
+
+        Span::new(self.span.name.start, end)
+    }
+}
+
+impl<'a> Declaration<'a> {
+    /// Checks that the declaration is a custom property, like `--accent: red`.
+    pub fn is_custom_property(&self) -> bool {
+        self.name.starts_with("--")
+    }
+
+    /// Parses the value into component values.
+    ///
+    /// # Errors
+    ///
+    /// See [`Value::parse`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use simplecss::{ComponentValue, DeclarationTokenizer};
+    ///
+    /// let d = DeclarationTokenizer::from("margin: 0 auto").next().unwrap();
+    /// let value = d.parse_value().unwrap();
+    /// assert_eq!(value.components, [ComponentValue::Number(0.0), ComponentValue::Ident("auto".into())]);
+    /// ```
+    pub fn parse_value(&self) -> Result<Value<'a>, Error> {
+        Value::parse(self.value)
+    }
+
+    /// Parses the value as a color.
+    ///
+    /// See [`Color::parse`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use simplecss::{Color, DeclarationTokenizer, Rgba};
+    ///
+    /// let d = DeclarationTokenizer::from("fill: rgba(255, 0, 0, 0.5)").next().unwrap();
+    /// assert_eq!(d.parse_color(), Some(Color::Rgba(Rgba::new(255, 0, 0, 128))));
+    /// ```
+    #[cfg(any(feature = "std", feature = "libm"))]
+    pub fn parse_color(&self) -> Option<Color> {
+        Color::parse(self.value)
+    }
+
+    /// Parses the value as a length or a percentage.
+    ///
+    /// See [`Length::parse`].
+    #[cfg(any(feature = "std", feature = "libm"))]
+    pub fn parse_length(&self) -> Option<Length> {
+        Length::parse(self.value)
+    }
+
+    /// Expands a shorthand property into its longhands.
+    ///
+    /// Omitted longhands are set to their initial values. CSS-wide keywords,
+    /// like `inherit`, are applied to all longhands.
+    ///
+    /// Returns `None` when the property is not a supported shorthand, when the value is invalid
+    /// or contains `var()`, since such values can be expanded only after substitution.
+    /// See [`longhands`] for the list of supported shorthands.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use simplecss::DeclarationTokenizer;
+    ///
+    /// let d = DeclarationTokenizer::from("margin: 0 auto !important").next().unwrap();
+    /// let longhands = d.expand().unwrap();
+    /// assert_eq!(longhands.len(), 4);
+    /// assert_eq!((longhands[1].name, &*longhands[1].value), ("margin-right", "auto"));
+    /// assert!(longhands[1].important);
+    /// ```
+    #[cfg(any(feature = "std", feature = "libm"))]
+    pub fn expand(&self) -> Option<Vec<Longhand<'a>>> {
+        shorthands::expand(self)
+    }
+
+    /// Validates the declaration using the property registry.
+    ///
+    /// Custom properties are always valid. See [`Property::validate`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the property is unknown or when the value doesn't match
+    /// the property grammar.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use simplecss::{DeclarationTokenizer, ValidationError};
+    ///
+    /// let mut declarations = DeclarationTokenizer::from("fill: red; fill: 10px; colr: red; --x: 1");
+    /// assert_eq!(declarations.next().unwrap().validate(), Ok(()));
+    /// assert_eq!(declarations.next().unwrap().validate(), Err(ValidationError::InvalidValue));
+    /// assert_eq!(declarations.next().unwrap().validate(), Err(ValidationError::UnknownProperty));
+    /// assert_eq!(declarations.next().unwrap().validate(), Ok(()));
+    /// ```
+    #[cfg(any(feature = "std", feature = "libm"))]
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.is_custom_property() {
+            return Ok(());
+        }
+
+        let property = property(&self.name).ok_or(ValidationError::UnknownProperty)?;
+        if property.validate(self.value) {
+            Ok(())
+        } else {
+            Err(ValidationError::InvalidValue)
+        }
+    }
+}
+
+impl PartialEq for Declaration<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.value == other.value && self.important == other.important
+    }
+}
+
+impl fmt::Display for Declaration<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_ident(f, &self.name)?;
+        write!(f, ":{}", self.value)?;
+        if self.important {
+            write!(f, "!important")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A declaration source location.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct DeclarationSpan {
+    /// The span of the property name.
+    pub name: Span,
+    /// The span of the value, without `!important`.
+    pub value: Span,
+    /// The span of `!important`, when present.
+    pub important: Option<Span>,
 }
 
 /// A rule.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 pub struct Rule<'a> {
     /// A rule selector.
     pub selector: Selector<'a>,
     /// A rule declarations.
     pub declarations: Vec<Declaration<'a>>,
+    /// The span of the whole rule set, from the first selector to the closing brace.
+    ///
+    /// Rules produced from a single selector list, like `a, b {}`, share the same span.
+    pub span: Span,
+}
+
+impl PartialEq for Rule<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.selector == other.selector && self.declarations == other.declarations
+    }
 }
 
 /// A style sheet.
-#[derive(Clone, Debug, PartialEq)]
+// `statements` is private so that `incremental::reparse` can rely on it.
+#[allow(clippy::partial_pub_fields)]
+#[derive(Clone, Debug)]
 pub struct StyleSheet<'a> {
     /// A list of rules.
     pub rules: Vec<Rule<'a>>,
     /// A list of At Rules
-    #[cfg(feature="at_rules")]
+    #[cfg(feature = "at_rules")]
     pub at_rules: Vec<AtRule<'a>>,
+    statements: Vec<Span>,
+    /// The number of texts parsed into the style sheet, see [`StyleSheet::parse_more`].
+    texts: usize,
+    /// The index of the text each rule was parsed from, in the `rules` order.
+    rule_texts: Vec<usize>,
+    /// The index of the text each at-rule was parsed from.
+    #[cfg(feature = "at_rules")]
+    at_rule_texts: Vec<usize>,
+}
+
+impl PartialEq for StyleSheet<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        #[cfg(feature = "at_rules")]
+        if self.at_rules != other.at_rules {
+            return false;
+        }
+
+        self.rules == other.rules
+    }
 }
 
 /// ident, pre-block content, block content
@@ -224,8 +589,13 @@ impl<'a> StyleSheet<'a> {
     pub fn new() -> Self {
         StyleSheet {
             rules: Vec::new(),
-            #[cfg(feature="at_rules")]
+            #[cfg(feature = "at_rules")]
             at_rules: Vec::new(),
+            statements: Vec::new(),
+            texts: 0,
+            rule_texts: Vec::new(),
+            #[cfg(feature = "at_rules")]
+            at_rule_texts: Vec::new(),
         }
     }
 
@@ -244,37 +614,222 @@ impl<'a> StyleSheet<'a> {
         sheet
     }
 
+    /// Parses a style sheet from bytes.
+    ///
+    /// The encoding is detected by [`decode_bytes`]. When the text has to be decoded,
+    /// it's stored in `buffer`, otherwise the style sheet borrows `bytes` directly.
+    ///
+    /// Returns the style sheet and the used encoding.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use simplecss::{Encoding, StyleSheet};
+    ///
+    /// let mut buffer = String::new();
+    /// let bytes = b"\xFF\xFEa\0{\0t\0o\0p\0:\x000\0}\0";
+    /// let (sheet, encoding) = StyleSheet::parse_bytes(bytes, None, &mut buffer);
+    /// assert_eq!(encoding, Encoding::Utf16Le);
+    /// assert_eq!(sheet.to_string(), "a { top:0; }");
+    /// ```
+    pub fn parse_bytes(
+        bytes: &'a [u8],
+        environment: Option<Encoding>,
+        buffer: &'a mut String,
+    ) -> (Self, Encoding) {
+        let (text, encoding) = decode_bytes(bytes, environment);
+        let text = match text {
+            Cow::Borrowed(text) => text,
+            Cow::Owned(text) => {
+                *buffer = text;
+                buffer.as_str()
+            }
+        };
+
+        (StyleSheet::parse(text), encoding)
+    }
+
     /// Parses a style sheet from a text to the current style sheet.
+    ///
+    /// Spans of the new nodes are relative to `text`.
     pub fn parse_more(&mut self, text: &'a str) {
-        let mut s = Stream::from(text);
+        self.texts += 1;
+        let lines = LazyLineIndex::new(text);
+        self.consume(Stream::from(text).defer_positions(), &lines);
+    }
+
+    /// Returns spans of the top-level statements, ie. rule sets and at-rules, in source order.
+    ///
+    /// Includes statements that didn't produce any rules. Used by [`StyleSheet::reparse`].
+    ///
+    /// After [`StyleSheet::parse_more`], spans of different texts are mixed.
+    pub fn statements(&self) -> &[Span] {
+        &self.statements
+    }
 
-        if s.skip_spaces_and_comments().is_err() {
+    fn consume(&mut self, mut s: Stream<'a>, lines: &LazyLineIndex<'_>) {
+        self.consume_statements(&mut s, lines, |_| false);
+        self.finish();
+    }
+
+    /// Consumes statements until the end of the stream
+    /// or until `stop` returns `true` for a statement start.
+    fn consume_statements<F>(&mut self, s: &mut Stream<'a>, lines: &LazyLineIndex<'_>, mut stop: F)
+    where
+        F: FnMut(usize) -> bool,
+    {
+        if skip_statement_separators(s).is_err() {
             return;
         }
 
         while !s.at_end() {
-            if s.skip_spaces_and_comments().is_err() {
+            if skip_statement_separators(s).is_err() {
                 break;
             }
 
+            if s.at_end() || stop(s.pos()) {
+                return;
+            }
+
+            let start = s.pos();
             let _ = consume_statement(
-                &mut s, 
-                &mut self.rules, 
-                #[cfg(feature="at_rules")]
-                &mut self.at_rules
+                s,
+                &mut self.rules,
+                #[cfg(feature = "at_rules")]
+                &mut self.at_rules,
+                lines,
             );
+            self.statements.push(Span::new(start, s.pos()));
         }
 
         if !s.at_end() {
             warn!("{} bytes were left.", s.slice_tail().len());
         }
+    }
+
+    /// Validates declarations of all rules.
+    ///
+    /// Returns invalid declarations with their errors, see [`Declaration::validate`].
+    #[cfg(any(feature = "std", feature = "libm"))]
+    pub fn validate(&self) -> Vec<(&Declaration<'a>, ValidationError)> {
+        self.rules
+            .iter()
+            .flat_map(|rule| &rule.declarations)
+            .filter_map(|declaration| Some((declaration, declaration.validate().err()?)))
+            .collect()
+    }
+
+    fn finish(&mut self) {
+        // New rules and at-rules come from the last text.
+        self.rule_texts.resize(self.rules.len(), self.texts);
+        #[cfg(feature = "at_rules")]
+        self.at_rule_texts.resize(self.at_rules.len(), self.texts);
+
+        let mut rules: Vec<_> = self
+            .rule_texts
+            .drain(..)
+            .zip(self.rules.drain(..))
+            .collect();
 
         // Remove empty rules.
-        self.rules.retain(|rule| !rule.declarations.is_empty());
+        rules.retain(|(_, rule)| !rule.declarations.is_empty());
 
         // Sort the rules by specificity.
-        self.rules
-            .sort_by_cached_key(|rule| rule.selector.specificity());
+        rules.sort_by_cached_key(|(_, rule)| rule.selector.specificity());
+
+        (self.rule_texts, self.rules) = rules.into_iter().unzip();
+    }
+}
+
+impl StyleSheet<'_> {
+    /// Serializes the style sheet without whitespace.
+    ///
+    /// Rules and at-rules are written in source order.
+    /// Longhands are merged into shorthands when possible, see [`contract`].
+    /// This requires the `std` or `libm` feature.
+    /// Idents and strings are escaped as needed.
+    ///
+    /// At-rules are parsed, and so written, only with the `at_rules` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use simplecss::StyleSheet;
+    ///
+    /// let style = StyleSheet::parse("
+    ///     a { padding-top: 0; padding-right: 0; padding-bottom: 0; padding-left: 0 }
+    ///     b { color: red !important }
+    /// ");
+    /// # #[cfg(any(feature = "std", feature = "libm"))]
+    /// assert_eq!(style.to_minified_string(), "a{padding:0}b{color:red!important}");
+    /// ```
+    pub fn to_minified_string(&self) -> String {
+        let mut text = String::new();
+        self.write_minified(&mut text);
+        text
+    }
+
+    pub(crate) fn write_minified(&self, text: &mut String) {
+        // Rules are sorted by specificity, so restore the source order
+        // to keep them in place relative to at-rules.
+        // Spans of different texts overlap, so texts are ordered first.
+        let mut rules: Vec<_> = self
+            .rules
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| ((self.rule_texts.get(i).copied(), rule.span.start), rule))
+            .collect();
+        rules.sort_by_key(|(key, _)| *key);
+
+        #[cfg(feature = "at_rules")]
+        let rules = {
+            let mut rules = rules.as_slice();
+            for (i, at_rule) in self.at_rules.iter().enumerate() {
+                let key = (
+                    self.at_rule_texts.get(i).copied(),
+                    at_rule.span().rule.start,
+                );
+                let count = rules.iter().take_while(|(k, _)| *k < key).count();
+                write_minified_rules(text, rules[..count].iter().map(|(_, rule)| *rule));
+                rules = &rules[count..];
+                at_rule.write_minified(text);
+            }
+
+            rules
+        };
+
+        write_minified_rules(text, rules.iter().map(|(_, rule)| *rule));
+    }
+}
+
+/// Writes rules without whitespace.
+///
+/// Consecutive rules with the same declarations, like the ones from `a, b {}`,
+/// are written as a single rule set.
+pub(crate) fn write_minified_rules<'b>(
+    text: &mut String,
+    rules: impl IntoIterator<Item = &'b Rule<'b>>,
+) {
+    let mut rules = rules.into_iter().peekable();
+    while let Some(rule) = rules.next() {
+        let _ = write!(text, "{}", rule.selector);
+        while let Some(next) = rules.next_if(|next| next.declarations == rule.declarations) {
+            let _ = write!(text, ",{}", next.selector);
+        }
+
+        text.push('{');
+        #[cfg(any(feature = "std", feature = "libm"))]
+        let declarations = contract(&rule.declarations);
+        // Shorthands are contracted only with float math available.
+        #[cfg(not(any(feature = "std", feature = "libm")))]
+        let declarations = &rule.declarations;
+        for (i, declaration) in declarations.iter().enumerate() {
+            if i != 0 {
+                text.push(';');
+            }
+            let _ = write!(text, "{}", declaration);
+        }
+        text.push('}');
     }
 }
 
@@ -283,7 +838,8 @@ impl fmt::Display for StyleSheet<'_> {
         for (i, rule) in self.rules.iter().enumerate() {
             write!(f, "{} {{ ", rule.selector)?;
             for dec in &rule.declarations {
-                write!(f, "{}:{}", dec.name, dec.value)?;
+                write_ident(f, &dec.name)?;
+                write!(f, ":{}", dec.value)?;
                 if dec.important {
                     write!(f, " !important")?;
                 }
@@ -306,38 +862,65 @@ impl Default for StyleSheet<'_> {
     }
 }
 
+/// Skips whitespace, comments and CDO/CDC tokens between top-level statements.
+///
+/// <https://www.w3.org/TR/css-syntax-3/#consume-list-of-rules>
+fn skip_statement_separators(s: &mut Stream<'_>) -> Result<(), Error> {
+    loop {
+        s.skip_spaces_and_comments()?;
+
+        if s.starts_with(b"<!--") {
+            s.advance(4);
+        } else if s.starts_with(b"-->") {
+            s.advance(3);
+        } else {
+            return Ok(());
+        }
+    }
+}
+
 fn consume_statement<'a>(
     s: &mut Stream<'a>,
     rules: &mut Vec<Rule<'a>>,
 
-    #[cfg(feature="at_rules")]
-    at_rules: &mut Vec<AtRule<'a>>,
+    #[cfg(feature = "at_rules")] at_rules: &mut Vec<AtRule<'a>>,
+    lines: &LazyLineIndex<'_>,
 ) -> Result<(), Error> {
     if s.curr_byte() == Ok(b'@') {
         s.advance(1);
-        consume_at_rule(s, #[cfg(feature="at_rules")] at_rules)
+        consume_at_rule(
+            s,
+            #[cfg(feature = "at_rules")]
+            at_rules,
+            #[cfg(feature = "at_rules")]
+            lines,
+        )
     } else {
-        consume_rule_set(s, rules)
+        consume_rule_set(s, rules, lines)
     }
 }
 
-#[cfg(feature="at_rules")]
-fn consume_at_rule<'a>(s: &mut Stream<'a>, rules: &mut Vec<AtRule<'a>>) -> Result<(), Error> {
+#[cfg(feature = "at_rules")]
+fn consume_at_rule<'a>(
+    s: &mut Stream<'a>,
+    rules: &mut Vec<AtRule<'a>>,
+    lines: &LazyLineIndex<'_>,
+) -> Result<(), Error> {
     #[cfg(test)]
-    match AtRule::consume(s) {
+    match AtRule::consume(s, lines) {
         Ok(r) => rules.push(r),
-        #[allow(clippy::print_stderr)] 
-        Err(e) => std::eprintln!("{e}"),
+        #[allow(clippy::print_stderr)]
+        Err(e) => std::eprintln!("{}", e.resolve(lines)),
     }
 
     #[cfg(not(test))]
-    rules.push(AtRule::consume(s)?);
+    rules.push(AtRule::consume(s, lines)?);
     Ok(())
 }
 
-#[cfg(not(feature="at_rules"))]
+#[cfg(not(feature = "at_rules"))]
 fn consume_at_rule(s: &mut Stream<'_>) -> Result<(), Error> {
-    s.consume_bytes(|b| b != b'{' && b != b';');
+    skip_component_values(s, |c| c == b'{' || c == b';');
     s.skip_spaces_and_comments()?;
 
     if s.curr_byte()? == b'{' {
@@ -352,45 +935,29 @@ fn consume_at_rule(s: &mut Stream<'_>) -> Result<(), Error> {
     Ok(())
 }
 
-#[cfg(feature="at_rules")]
+#[cfg(feature = "at_rules")]
 fn read_block<'a>(s: &mut Stream<'a>, include_braces: bool) -> &'a str {
-    let mut start = s.pos();
-    let mut end = start;
-
+    let start = s.pos();
     s.try_consume_byte(b'{');
-    if !include_braces {
-        start += 1;
-    }
 
-    let mut braces = 0;
-    while !s.at_end() {
-        match s.curr_byte_unchecked() {
-            b'{' => {
-                end += 1;
-                braces += 1;
-            }
-            b'}' => {
-                if braces == 0 {
-                    if include_braces {
-                        end += 1;
-                    }
-                    break;
-                } else {
-                    braces -= 1;
-                }
-            }
-            _ => {}
-        }
-        end += 1;
-        s.advance(1);
-    }
+    let content_start = s.pos();
+    skip_component_values(s, |c| c == b'}');
+    let content_end = s.pos();
 
     s.try_consume_byte(b'}');
 
-    s.slice_range(start, end)
+    if include_braces {
+        s.slice_back(start)
+    } else {
+        s.slice_range(content_start, content_end)
+    }
 }
 
-fn consume_rule_set<'a>(s: &mut Stream<'a>, rules: &mut Vec<Rule<'a>>) -> Result<(), Error> {
+fn consume_rule_set<'a>(
+    s: &mut Stream<'a>,
+    rules: &mut Vec<Rule<'a>>,
+    lines: &LazyLineIndex<'_>,
+) -> Result<(), Error> {
     let start_rule_idx = rules.len();
 
     while s.curr_byte()? == b',' || start_rule_idx == rules.len() {
@@ -398,14 +965,14 @@ fn consume_rule_set<'a>(s: &mut Stream<'a>, rules: &mut Vec<Rule<'a>>) -> Result
             s.advance(1);
         }
 
-        let (selector, offset) = parse(s.slice_tail());
-        s.advance(offset);
+        let selector = parse(s, lines);
         s.skip_spaces();
 
         if let Some(selector) = selector {
             rules.push(Rule {
                 selector,
                 declarations: Vec::new(),
+                span: Span::default(),
             });
         }
 
@@ -413,7 +980,7 @@ fn consume_rule_set<'a>(s: &mut Stream<'a>, rules: &mut Vec<Rule<'a>>) -> Result
             b'{' => break,
             b',' => {}
             _ => {
-                s.skip_bytes(|c| c != b'{');
+                skip_component_values(s, |c| c == b'{');
                 break;
             }
         }
@@ -422,40 +989,96 @@ fn consume_rule_set<'a>(s: &mut Stream<'a>, rules: &mut Vec<Rule<'a>>) -> Result
     s.try_consume_byte(b'{');
 
     let declarations = consume_declarations(s)?;
+    s.try_consume_byte(b'}');
+
+    // The rule set starts at its first selector.
+    let start = rules.get(start_rule_idx).map(|r| r.selector.span().start);
     for rule in rules.iter_mut().skip(start_rule_idx) {
         rule.declarations = declarations.clone();
+        rule.span = Span::new(start.unwrap_or_default(), s.pos());
     }
 
-    s.try_consume_byte(b'}');
-
     Ok(())
 }
 
 fn consume_until_block_end(s: &mut Stream<'_>) {
-    // Block can have nested blocks, so we have to check for matching braces.
-    // We simply counting the number of opening braces, which is incorrect,
-    // since `{` can be inside a string, but it's fine for majority of the cases.
+    skip_component_values(s, |c| c == b'}');
+    s.try_consume_byte(b'}');
+}
+
+/// Skips component values until a top-level byte matched by `stop`.
+///
+/// Strings, comments, escapes and `()`, `[]`, `{}` blocks are skipped as a whole,
+/// so `stop` is never checked inside of them. The stop byte itself is not consumed.
+///
+/// <https://www.w3.org/TR/css-syntax-3/#consume-component-value>
+fn skip_component_values<F>(s: &mut Stream<'_>, stop: F)
+where
+    F: Fn(u8) -> bool,
+{
+    skip_component_values_in(s, &mut Vec::new(), stop);
+}
+
+/// Skips component values like [`skip_component_values`], starting inside of the blocks
+/// from `closing`, a stack of expected closing brackets.
+///
+/// Blocks that are still open at the stream end are left in `closing`.
+///
+/// Returns the position after the last component value that can't be changed by the text
+/// following the stream, ie. before a trailing string, comment, escape or `/`.
+fn skip_component_values_in<F>(s: &mut Stream<'_>, closing: &mut Vec<u8>, stop: F) -> usize
+where
+    F: Fn(u8) -> bool,
+{
+    let mut complete = s.pos();
 
-    let mut braces = 0;
     while !s.at_end() {
-        match s.curr_byte_unchecked() {
-            b'{' => {
-                braces += 1;
+        let c = s.curr_byte_unchecked();
+        if closing.is_empty() && stop(c) {
+            break;
+        }
+
+        match c {
+            b'"' | b'\'' => {
+                s.skip_string();
+                if !s.at_end() {
+                    complete = s.pos();
+                }
+                continue;
             }
-            b'}' => {
-                if braces == 0 {
+            b'/' if s.next_byte() == Ok(b'*') => {
+                // An unclosed comment lasts until the end of the stream.
+                if s.skip_comment().is_ok() {
+                    complete = s.pos();
+                }
+                continue;
+            }
+            b'\\' => {
+                // Skip the escaped byte, whatever it is.
+                s.advance(1);
+                if s.at_end() {
                     break;
-                } else {
-                    braces -= 1;
                 }
             }
+            b'(' => closing.push(b')'),
+            b'[' => closing.push(b']'),
+            b'{' => closing.push(b'}'),
+            b')' | b']' | b'}' if closing.last() == Some(&c) => {
+                closing.pop();
+            }
+            // Unmatched closing brackets are ignored.
             _ => {}
         }
 
         s.advance(1);
+
+        // A trailing `/` can be a comment start.
+        if c != b'/' || !s.at_end() {
+            complete = s.pos();
+        }
     }
 
-    s.try_consume_byte(b'}');
+    complete
 }
 
 fn consume_declarations<'a>(s: &mut Stream<'a>) -> Result<Vec<Declaration<'a>>, Error> {
@@ -481,11 +1104,17 @@ fn consume_declarations<'a>(s: &mut Stream<'a>) -> Result<Vec<Declaration<'a>>,
 /// # Example
 ///
 /// ```
-/// use simplecss::{DeclarationTokenizer, Declaration};
+/// use simplecss::{DeclarationTokenizer, Span};
 ///
 /// let mut t = DeclarationTokenizer::from("background: url(\"img.png\"); color:red !important");
-/// assert_eq!(t.next().unwrap(), Declaration { name: "background", value: "url(\"img.png\")", important: false });
-/// assert_eq!(t.next().unwrap(), Declaration { name: "color", value: "red", important: true });
+///
+/// let d = t.next().unwrap();
+/// assert_eq!((d.name.as_ref(), d.value, d.important), ("background", "url(\"img.png\")", false));
+///
+/// let d = t.next().unwrap();
+/// assert_eq!((d.name.as_ref(), d.value, d.important), ("color", "red", true));
+/// assert_eq!(d.span.name, Span::new(28, 33));
+/// assert_eq!(d.span.important, Some(Span::new(38, 48)));
 /// ```
 pub struct DeclarationTokenizer<'a> {
     stream: Stream<'a>,
@@ -529,7 +1158,9 @@ fn consume_declaration<'a>(s: &mut Stream<'a>) -> Result<Declaration<'a>, Error>
         s.advance(1);
     }
 
-    let name = s.consume_ident()?;
+    let name_start = s.pos();
+    let name = s.consume_any_ident()?;
+    let name_span = Span::new(name_start, s.pos());
 
     s.skip_spaces_and_comments()?;
     s.consume_byte(b':')?;
@@ -538,23 +1169,19 @@ fn consume_declaration<'a>(s: &mut Stream<'a>) -> Result<Declaration<'a>, Error>
     // Parse value.
     let start = s.pos();
     let value = consume_value(s)?;
-    // let mut end = s.pos();
-    // while consume_term(s).is_ok() {
-    //     end = s.pos();
-    //     s.skip_spaces_and_comments()?;
-    // }
-    // let value = s.slice_range(start, end).trim();
+    let value_span = Span::new(start, start + value.len());
 
     s.skip_spaces_and_comments()?;
 
     // Check for `important`.
-    let mut important = false;
+    let mut important_span = None;
     if s.curr_byte() == Ok(b'!') {
+        let important_start = s.pos();
         s.advance(1);
         s.skip_spaces_and_comments()?;
-        if s.slice_tail().starts_with("important") {
+        if s.starts_with_ignore_case("important") {
             s.advance(9);
-            important = true;
+            important_span = Some(Span::new(important_start, s.pos()));
         }
     }
 
@@ -567,83 +1194,53 @@ fn consume_declaration<'a>(s: &mut Stream<'a>) -> Result<Declaration<'a>, Error>
 
     s.skip_spaces_and_comments()?;
 
-    if value.is_empty() {
+    // Custom properties can be empty.
+    if value.is_empty() && !name.starts_with("--") {
         return Err(Error::InvalidValue(s.gen_text_pos_from(start)));
     }
 
     Ok(Declaration {
         name,
         value,
-        important,
+        important: important_span.is_some(),
+        span: DeclarationSpan {
+            name: name_span,
+            value: value_span,
+            important: important_span,
+        },
     })
 }
 
-fn consume_term(s: &mut Stream<'_>) -> Result<(), Error> {
-    fn consume_digits(s: &mut Stream<'_>) {
-        while let Ok(b'0'..=b'9') = s.curr_byte() {
-            s.advance(1);
-        }
-    }
-
-    match s.curr_byte()? {
-        b'#' => {
-            s.advance(1);
-            match s.consume_ident() {
-                Ok(_) => {}
-                Err(_) => {
-                    // Try consume as a hex color.
-                    while let Ok(c) = s.curr_byte() {
-                        match c {
-                            b'0'..=b'9' | b'a'..=b'f' | b'A'..=b'F' => s.advance(1),
-                            _ => break,
-                        }
-                    }
-                }
-            }
-        }
-        b'+' | b'-' | b'0'..=b'9' | b'.' => {
-            // Consume number.
+/// Consumes a declaration value.
+///
+/// The value lasts until a top-level `;`, `!` or an unmatched closing bracket,
+/// so values nested in blocks, like `@supports (display: grid)`, are supported as well.
+/// Leading and trailing whitespace and comments are not part of the value.
+fn consume_value<'a>(s: &mut Stream<'a>) -> Result<&'a str, Error> {
+    let start = s.pos();
+    let mut end = s.pos();
+    loop {
+        s.skip_spaces_and_comments()?;
 
-            s.advance(1);
-            consume_digits(s);
-            if s.curr_byte() == Ok(b'.') {
-                s.advance(1);
-                consume_digits(s);
-            }
+        let pos = s.pos();
+        skip_component_values(s, |c| {
+            matches!(
+                c,
+                b';' | b'!' | b')' | b']' | b'}' | b'/' | b' ' | b'\t' | b'\n' | b'\r' | b'\x0C'
+            )
+        });
 
-            if s.curr_byte() == Ok(b'%') {
-                s.advance(1);
-            } else {
-                // Consume suffix if any.
-                let _ = s.consume_ident();
-            }
-        }
-        b'\'' | b'"' => {
-            s.consume_string()?;
-        }
-        b',' => {
+        // Stop only before a comment, `/` is a valid value delimiter.
+        if s.curr_byte() == Ok(b'/') && s.next_byte() != Ok(b'*') {
             s.advance(1);
         }
-        _ => {
-            let _ = s.consume_ident()?;
 
-            // Consume function.
-            if s.curr_byte() == Ok(b'(') {
-                s.skip_bytes(|c| c != b')');
-                s.consume_byte(b')')?;
-            }
+        if s.pos() == pos {
+            break;
         }
-    }
-
-    Ok(())
-}
 
-fn consume_value<'a>(s: &mut Stream<'a>) -> Result<&'a str, Error> {
-    let start = s.pos();
-    let mut end = s.pos();
-    while consume_term(s).is_ok() {
         end = s.pos();
-        s.skip_spaces_and_comments()?;
     }
-    Ok(s.slice_range(start, end).trim())
+
+    Ok(s.slice_range(start, end))
 }
@@ -0,0 +1,175 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+use crate::{Span, TextPos};
+
+/// A line index of a text.
+///
+/// Converts byte offsets into [`TextPos`] in `O(log n)`.
+/// Build it once per source when you need more than a couple of lookups,
+/// since [`Span::start_pos`] and friends rescan the text on every call.
+///
+/// # Example
+///
+/// ```
+/// use simplecss::{LineIndex, TextPos};
+///
+/// let text = "a {}\n/* 😀 */ b {}";
+/// let index = LineIndex::new(text);
+///
+/// let offset = text.find('b').unwrap();
+/// assert_eq!(index.text_pos(offset), TextPos::new(2, 9));
+/// assert_eq!(index.utf16_pos(offset), TextPos::new(2, 10));
+/// ```
+#[derive(Clone, Debug)]
+pub struct LineIndex {
+    len: usize,
+    /// Byte offsets of each line start. The first line always starts at 0.
+    line_starts: Vec<usize>,
+    /// All non-ASCII chars, in text order.
+    wide_chars: Vec<WideChar>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct WideChar {
+    offset: usize,
+    len: usize,
+    /// The number of UTF-8 continuation bytes up to and including this char.
+    extra_bytes: usize,
+    /// The number of chars outside the BMP up to and including this char.
+    ///
+    /// Each of them takes two UTF-16 code units.
+    astral_chars: usize,
+}
+
+impl LineIndex {
+    /// Builds an index of `text`.
+    pub fn new(text: &str) -> Self {
+        let mut line_starts = Vec::new();
+        line_starts.push(0);
+
+        let mut wide_chars = Vec::new();
+        let mut extra_bytes = 0;
+        let mut astral_chars = 0;
+        for (offset, c) in text.char_indices() {
+            if c == '\n' {
+                line_starts.push(offset + 1);
+            } else if !c.is_ascii() {
+                let len = c.len_utf8();
+                extra_bytes += len - 1;
+                if len == 4 {
+                    astral_chars += 1;
+                }
+
+                wide_chars.push(WideChar {
+                    offset,
+                    len,
+                    extra_bytes,
+                    astral_chars,
+                });
+            }
+        }
+
+        LineIndex {
+            len: text.len(),
+            line_starts,
+            wide_chars,
+        }
+    }
+
+    /// Returns the number of lines.
+    ///
+    /// Always at least one, even for an empty text.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns the position of a byte offset.
+    ///
+    /// Columns are counted in chars, like in parser errors.
+    /// Offsets past the end are clamped, offsets inside a char point to its start.
+    pub fn text_pos(&self, offset: usize) -> TextPos {
+        let (row, line_start, offset) = self.locate(offset);
+        let (extra, _) = self.count_wide(line_start, offset);
+        TextPos::new(row, saturating_u32(offset - line_start - extra + 1))
+    }
+
+    /// Returns the position of a byte offset, with a column in UTF-16 code units.
+    ///
+    /// Rows and columns start from 1, like in [`text_pos`](Self::text_pos).
+    /// Subtract one from both to get an LSP `Position`.
+    pub fn utf16_pos(&self, offset: usize) -> TextPos {
+        let (row, line_start, offset) = self.locate(offset);
+        let (extra, astral) = self.count_wide(line_start, offset);
+        TextPos::new(
+            row,
+            saturating_u32(offset - line_start - extra + astral + 1),
+        )
+    }
+
+    /// Returns the start and end positions of a span.
+    pub fn span_pos(&self, span: Span) -> (TextPos, TextPos) {
+        (self.text_pos(span.start), self.text_pos(span.end))
+    }
+
+    /// Returns the row, the line start and the offset snapped to a char boundary.
+    fn locate(&self, offset: usize) -> (u32, usize, usize) {
+        let mut offset = offset.min(self.len);
+
+        let idx = self.wide_chars.partition_point(|c| c.offset < offset);
+        if let Some(c) = idx.checked_sub(1).map(|i| self.wide_chars[i]) {
+            if c.offset + c.len > offset {
+                offset = c.offset;
+            }
+        }
+
+        let row = self.line_starts.partition_point(|start| *start <= offset);
+        (saturating_u32(row), self.line_starts[row - 1], offset)
+    }
+
+    /// Returns the number of UTF-8 continuation bytes and astral chars in `start..end`.
+    fn count_wide(&self, start: usize, end: usize) -> (usize, usize) {
+        let (extra_end, astral_end) = self.count_wide_before(end);
+        let (extra_start, astral_start) = self.count_wide_before(start);
+        (extra_end - extra_start, astral_end - astral_start)
+    }
+
+    fn count_wide_before(&self, offset: usize) -> (usize, usize) {
+        let idx = self.wide_chars.partition_point(|c| c.offset < offset);
+        match idx.checked_sub(1).map(|i| self.wide_chars[i]) {
+            Some(c) => (c.extra_bytes, c.astral_chars),
+            None => (0, 0),
+        }
+    }
+}
+
+/// A [`LineIndex`] built on the first lookup.
+///
+/// Used by the style sheet parser to resolve positions of the reported errors.
+pub(crate) struct LazyLineIndex<'a> {
+    text: &'a str,
+    index: RefCell<Option<LineIndex>>,
+}
+
+impl<'a> LazyLineIndex<'a> {
+    pub(crate) fn new(text: &'a str) -> Self {
+        LazyLineIndex {
+            text,
+            index: RefCell::new(None),
+        }
+    }
+
+    pub(crate) fn text_pos(&self, offset: usize) -> TextPos {
+        self.index
+            .borrow_mut()
+            .get_or_insert_with(|| LineIndex::new(self.text))
+            .text_pos(offset)
+    }
+}
+
+fn saturating_u32(n: usize) -> u32 {
+    u32::try_from(n).unwrap_or(u32::MAX)
+}
@@ -0,0 +1,975 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::stream::Stream;
+use crate::value::{ValueToken, ValueTokenizer};
+use crate::{
+    consume_declaration, property, skip_component_values, skip_statement_separators, Declaration,
+    Span,
+};
+
+/// A vendor prefix.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum VendorPrefix {
+    /// `-webkit-`
+    Webkit,
+    /// `-moz-`
+    Moz,
+    /// `-ms-`
+    Ms,
+    /// `-o-`
+    O,
+}
+
+impl VendorPrefix {
+    /// Returns the prefix text, like `-webkit-`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VendorPrefix::Webkit => "-webkit-",
+            VendorPrefix::Moz => "-moz-",
+            VendorPrefix::Ms => "-ms-",
+            VendorPrefix::O => "-o-",
+        }
+    }
+
+    /// Splits a name into a vendor prefix and an unprefixed name.
+    ///
+    /// The prefix is case-insensitive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use simplecss::VendorPrefix;
+    ///
+    /// assert_eq!(VendorPrefix::split("-webkit-transform"), Some((VendorPrefix::Webkit, "transform")));
+    /// assert_eq!(VendorPrefix::split("transform"), None);
+    /// ```
+    pub fn split(name: &str) -> Option<(Self, &str)> {
+        [
+            VendorPrefix::Webkit,
+            VendorPrefix::Moz,
+            VendorPrefix::Ms,
+            VendorPrefix::O,
+        ]
+        .into_iter()
+        .find_map(|prefix| {
+            let len = prefix.as_str().len();
+            let (head, tail) = (name.get(..len)?, &name[len..]);
+            (head.eq_ignore_ascii_case(prefix.as_str()) && !tail.is_empty())
+                .then_some((prefix, tail))
+        })
+    }
+}
+
+/// Vendor prefixes added by [`add_prefixes`].
+///
+/// `-o-` prefixes are never added.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct PrefixOptions {
+    /// Add `-webkit-` prefixes. `true` by default.
+    pub webkit: bool,
+    /// Add `-moz-` prefixes. `true` by default.
+    pub moz: bool,
+    /// Add `-ms-` prefixes. `true` by default.
+    pub ms: bool,
+}
+
+impl PrefixOptions {
+    /// Checks that a prefix should be added.
+    pub fn is_enabled(&self, prefix: VendorPrefix) -> bool {
+        match prefix {
+            VendorPrefix::Webkit => self.webkit,
+            VendorPrefix::Moz => self.moz,
+            VendorPrefix::Ms => self.ms,
+            VendorPrefix::O => false,
+        }
+    }
+}
+
+impl Default for PrefixOptions {
+    fn default() -> Self {
+        PrefixOptions {
+            webkit: true,
+            moz: true,
+            ms: true,
+        }
+    }
+}
+
+/// Properties that need a prefix, with the prefixes added by [`add_prefixes`].
+static PROPERTY_PREFIXES: &[(&str, &[VendorPrefix])] = &[
+    ("appearance", &[VendorPrefix::Webkit, VendorPrefix::Moz]),
+    ("backdrop-filter", &[VendorPrefix::Webkit]),
+    ("box-decoration-break", &[VendorPrefix::Webkit]),
+    ("clip-path", &[VendorPrefix::Webkit]),
+    ("hyphens", &[VendorPrefix::Webkit, VendorPrefix::Ms]),
+    ("mask", &[VendorPrefix::Webkit]),
+    ("mask-clip", &[VendorPrefix::Webkit]),
+    ("mask-composite", &[VendorPrefix::Webkit]),
+    ("mask-image", &[VendorPrefix::Webkit]),
+    ("mask-origin", &[VendorPrefix::Webkit]),
+    ("mask-position", &[VendorPrefix::Webkit]),
+    ("mask-repeat", &[VendorPrefix::Webkit]),
+    ("mask-size", &[VendorPrefix::Webkit]),
+    ("print-color-adjust", &[VendorPrefix::Webkit]),
+    ("tab-size", &[VendorPrefix::Moz]),
+    ("text-emphasis", &[VendorPrefix::Webkit]),
+    ("text-emphasis-color", &[VendorPrefix::Webkit]),
+    ("text-emphasis-position", &[VendorPrefix::Webkit]),
+    ("text-emphasis-style", &[VendorPrefix::Webkit]),
+    (
+        "text-size-adjust",
+        &[VendorPrefix::Webkit, VendorPrefix::Moz, VendorPrefix::Ms],
+    ),
+    (
+        "user-select",
+        &[VendorPrefix::Webkit, VendorPrefix::Moz, VendorPrefix::Ms],
+    ),
+];
+
+/// Keywords and functions that need a prefix, with the prefixes added by [`add_prefixes`].
+static VALUE_PREFIXES: &[(&str, &[VendorPrefix])] = &[
+    ("cross-fade", &[VendorPrefix::Webkit]),
+    ("image-set", &[VendorPrefix::Webkit]),
+    ("sticky", &[VendorPrefix::Webkit]),
+];
+
+/// Prefixed pseudo-classes and pseudo-elements and their standard forms.
+///
+/// Used in both directions.
+static PSEUDO_CLASSES: &[(&str, &str)] = &[
+    (":-moz-any-link", ":any-link"),
+    (":-moz-full-screen", ":fullscreen"),
+    (":-moz-read-only", ":read-only"),
+    (":-moz-read-write", ":read-write"),
+    (":-ms-fullscreen", ":fullscreen"),
+    (":-ms-input-placeholder", "::placeholder"),
+    (":-webkit-any-link", ":any-link"),
+    (":-webkit-autofill", ":autofill"),
+    (":-webkit-full-screen", ":fullscreen"),
+    ("::-moz-placeholder", "::placeholder"),
+    ("::-moz-selection", "::selection"),
+    ("::-ms-backdrop", "::backdrop"),
+    ("::-webkit-backdrop", "::backdrop"),
+    ("::-webkit-file-upload-button", "::file-selector-button"),
+    ("::-webkit-input-placeholder", "::placeholder"),
+];
+
+/// Properties that were shipped with a prefix, in addition to the property registry
+/// and [`PROPERTY_PREFIXES`].
+static PREFIXED_PROPERTIES: &[&str] = &[
+    "align-content",
+    "align-items",
+    "align-self",
+    "backface-visibility",
+    "box-shadow",
+    "column-count",
+    "column-gap",
+    "column-rule",
+    "column-width",
+    "columns",
+    "flex-basis",
+    "flex-direction",
+    "flex-flow",
+    "flex-grow",
+    "flex-shrink",
+    "flex-wrap",
+    "justify-content",
+    "order",
+    "perspective",
+    "perspective-origin",
+];
+
+/// Keywords that were shipped with a prefix.
+static PREFIXED_KEYWORDS: &[&str] = &[
+    "fit-content",
+    "flex",
+    "grab",
+    "grabbing",
+    "inline-flex",
+    "max-content",
+    "min-content",
+    "sticky",
+    "zoom-in",
+    "zoom-out",
+];
+
+/// Prefixed keywords with a different standard name.
+static KEYWORD_ALIASES: &[(&str, &str)] = &[
+    ("-moz-available", "stretch"),
+    ("-ms-flexbox", "flex"),
+    ("-ms-inline-flexbox", "inline-flex"),
+    ("-webkit-fill-available", "stretch"),
+];
+
+/// Functions that were shipped with a prefix.
+static PREFIXED_FUNCTIONS: &[&str] = &[
+    "calc",
+    "cross-fade",
+    "image-set",
+    "linear-gradient",
+    "radial-gradient",
+    "repeating-linear-gradient",
+    "repeating-radial-gradient",
+];
+
+/// At-rules that were shipped with a prefix.
+static PREFIXED_AT_RULES: &[&str] = &["keyframes", "viewport"];
+
+/// Replaces vendor-prefixed properties, values, at-rules and pseudo-classes with their
+/// standard forms.
+///
+/// The old syntax of prefixed `linear-gradient()` and `radial-gradient()` is converted too.
+///
+/// When a declaration, a rule or an at-rule ends up repeated in the same block, the standard one
+/// wins and the prefixed ones are removed. Otherwise, the last prefixed one is kept.
+/// Declarations that become identical, like `display: -ms-flexbox` and `display: flex`,
+/// are merged into the last one.
+/// `@supports` and `@media` conditions are kept as is.
+///
+/// # Example
+///
+/// ```
+/// use simplecss::normalize_prefixes;
+///
+/// let text = "a { -webkit-transform: none; transform: none; \
+///             background: -webkit-linear-gradient(top, red, blue) }";
+/// assert_eq!(
+///     normalize_prefixes(text),
+///     "a { transform: none; background: linear-gradient(to bottom, red, blue) }"
+/// );
+/// ```
+pub fn normalize_prefixes(text: &str) -> String {
+    let items = parse_items(&mut Stream::from(text));
+    let mut edits = Vec::new();
+    normalize_items(text, &items, &mut edits);
+    apply_edits(text, Span::new(0, text.len()), edits)
+}
+
+/// Adds vendor-prefixed copies of properties, values and pseudo-classes
+/// that still need a prefix.
+///
+/// Uses a small built-in table. Prefixed declarations are inserted before the standard one,
+/// unless they are already present, and prefixed rules before the standard rule.
+///
+/// # Example
+///
+/// ```
+/// use simplecss::{add_prefixes, PrefixOptions};
+///
+/// let options = PrefixOptions { moz: false, ..PrefixOptions::default() };
+/// assert_eq!(
+///     add_prefixes("a { user-select: none }", &options),
+///     "a { -webkit-user-select: none; -ms-user-select: none; user-select: none }"
+/// );
+/// ```
+pub fn add_prefixes(text: &str, options: &PrefixOptions) -> String {
+    let items = parse_items(&mut Stream::from(text));
+    let mut edits = Vec::new();
+    prefix_items(text, &items, *options, &mut edits);
+    apply_edits(text, Span::new(0, text.len()), edits)
+}
+
+/// A text replacement.
+#[derive(Clone)]
+struct Edit {
+    span: Span,
+    text: String,
+}
+
+impl Edit {
+    fn new(span: Span, text: impl Into<String>) -> Self {
+        Edit {
+            span,
+            text: text.into(),
+        }
+    }
+}
+
+/// Applies edits to a part of the text.
+///
+/// Edits outside the span and edits overlapping a previous one are ignored,
+/// so the outer edit wins.
+fn apply_edits(text: &str, span: Span, mut edits: Vec<Edit>) -> String {
+    edits.sort_by_key(|edit| (edit.span.start, core::cmp::Reverse(edit.span.end)));
+
+    let mut result = String::with_capacity(span.len());
+    let mut last = span.start;
+    for edit in edits {
+        if edit.span.start < last || edit.span.end > span.end {
+            continue;
+        }
+
+        result.push_str(&text[last..edit.span.start]);
+        result.push_str(&edit.text);
+        last = edit.span.end;
+    }
+
+    result.push_str(&text[last..span.end]);
+    result
+}
+
+/// Tokenizes a part of the text. Spans are relative to the whole text.
+fn tokenize(text: &str, span: Span) -> Vec<(ValueToken<'_>, Span)> {
+    let mut tokenizer = ValueTokenizer::from(span.slice(text));
+    core::iter::from_fn(|| tokenizer.next_with_span())
+        .map(|(token, token_span)| {
+            let start = span.start + token_span.start;
+            (token, Span::new(start, start + token_span.len()))
+        })
+        .collect()
+}
+
+/// A rule set, at-rule or declaration.
+enum Item<'a> {
+    Declaration {
+        declaration: Declaration<'a>,
+        /// From the name to the end of the value or `!important`.
+        span: Span,
+        /// The start of the next item.
+        next: usize,
+    },
+    Statement {
+        /// The name of an at-rule, without `@`.
+        at_rule: Option<Span>,
+        /// The selector or the at-rule prelude, without surrounding whitespace.
+        prelude: Span,
+        block: Option<Vec<Item<'a>>>,
+        /// From the start to the end of the block.
+        span: Span,
+        /// The start of the next item.
+        next: usize,
+    },
+}
+
+/// Parses items up to the end of the current block.
+///
+/// Blocks are parsed the same way at any level, so nested rules and at-rules
+/// are supported anywhere.
+fn parse_items<'a>(s: &mut Stream<'a>) -> Vec<Item<'a>> {
+    let mut items = Vec::new();
+    skip_separators(s);
+    while !s.at_end() && s.curr_byte() != Ok(b'}') {
+        items.extend(parse_item(s));
+    }
+
+    items
+}
+
+/// Skips whitespace, comments, CDO/CDC tokens and semicolons between items.
+fn skip_separators(s: &mut Stream<'_>) {
+    while skip_statement_separators(s).is_ok() && s.curr_byte() == Ok(b';') {
+        s.advance(1);
+    }
+}
+
+fn parse_item<'a>(s: &mut Stream<'a>) -> Option<Item<'a>> {
+    let item_start = *s;
+    let start = s.pos();
+    let at_rule = if s.curr_byte() == Ok(b'@') {
+        s.advance(1);
+        let name_start = s.pos();
+        s.consume_raw_ident()
+            .ok()
+            .map(|_| Span::new(name_start, s.pos()))
+    } else {
+        None
+    };
+
+    let prelude_start = s.pos();
+    skip_component_values(s, |c| matches!(c, b';' | b'{' | b'}'));
+    let prelude = s.slice_range(prelude_start, s.pos());
+    let prelude_start = prelude_start + prelude.len() - prelude.trim_start().len();
+    let prelude = Span::new(prelude_start, prelude_start + prelude.trim().len());
+
+    if s.curr_byte() == Ok(b'{') {
+        s.advance(1);
+        let block = parse_items(s);
+        s.try_consume_byte(b'}');
+        let span = Span::new(start, s.pos());
+        skip_separators(s);
+
+        return Some(Item::Statement {
+            at_rule,
+            prelude,
+            block: Some(block),
+            span,
+            next: s.pos(),
+        });
+    }
+
+    // A declaration or an at-rule statement, ending with `;`, `}` or at the end.
+    let declaration = match at_rule {
+        Some(_) => None,
+        None => {
+            let mut declaration_stream = item_start;
+            consume_declaration(&mut declaration_stream).ok()
+        }
+    };
+    skip_separators(s);
+
+    if at_rule.is_some() {
+        Some(Item::Statement {
+            at_rule,
+            prelude,
+            block: None,
+            span: Span::new(start, prelude.end),
+            next: s.pos(),
+        })
+    } else {
+        let declaration = declaration?;
+        let end = declaration
+            .span
+            .important
+            .unwrap_or(declaration.span.value)
+            .end;
+        Some(Item::Declaration {
+            declaration,
+            span: Span::new(start, end),
+            next: s.pos(),
+        })
+    }
+}
+
+/// Returns the span of a function name, without the parenthesis.
+fn function_name_span(span: Span) -> Span {
+    Span::new(span.start, span.end - 1)
+}
+
+fn find_ignore_case<'t, T>(table: &'t [(&str, T)], name: &str) -> Option<&'t T> {
+    table
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value)
+}
+
+fn contains_ignore_case(list: &[&str], name: &str) -> bool {
+    list.iter().any(|item| item.eq_ignore_ascii_case(name))
+}
+
+fn is_known_property(name: &str) -> bool {
+    property(name).is_some()
+        || contains_ignore_case(PREFIXED_PROPERTIES, name)
+        || find_ignore_case(PROPERTY_PREFIXES, name).is_some()
+}
+
+/// Returns the standard name of a prefixed property.
+fn standard_property(name: &str) -> Option<&str> {
+    let (_, name) = VendorPrefix::split(name)?;
+    is_known_property(name).then_some(name)
+}
+
+/// Returns the standard form of a prefixed keyword.
+fn standard_keyword(name: &str) -> Option<&str> {
+    if let Some(keyword) = find_ignore_case(KEYWORD_ALIASES, name) {
+        return Some(keyword);
+    }
+
+    // Property names are used in `transition-property` and `will-change`.
+    let (_, name) = VendorPrefix::split(name)?;
+    (contains_ignore_case(PREFIXED_KEYWORDS, name) || is_known_property(name)).then_some(name)
+}
+
+/// Returns the start index of the colons before a pseudo-class name.
+fn colons_start(tokens: &[(ValueToken<'_>, Span)], index: usize) -> usize {
+    let mut start = index;
+    while start > 0 && tokens[start - 1].0 == ValueToken::Delim(':') {
+        start -= 1;
+    }
+
+    start
+}
+
+/// Returns pseudo-classes in a selector as the span from the first colon to the end
+/// of the name, and the name with colons.
+fn pseudo_classes(text: &str, selector: Span) -> Vec<(Span, String)> {
+    let tokens = tokenize(text, selector);
+    let mut classes = Vec::new();
+    for index in 0..tokens.len() {
+        let span = match tokens[index] {
+            (ValueToken::Ident(_), span) => span,
+            (ValueToken::Function(_), span) => function_name_span(span),
+            _ => continue,
+        };
+
+        let start = colons_start(&tokens, index);
+        if start == index {
+            continue;
+        }
+
+        // Colons and the name must not be separated by spaces.
+        let span = Span::new(tokens[start].1.start, span.end);
+        let name: String = span
+            .slice(text)
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        if name.len() == span.len() {
+            classes.push((span, name));
+        }
+    }
+
+    classes
+}
+
+/// Collapses whitespace, so the same preludes written differently are the same.
+fn collapse_whitespace(text: &str) -> String {
+    text.split_ascii_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn normalize_items(text: &str, items: &[Item<'_>], edits: &mut Vec<Edit>) {
+    // A key, whether the item was prefixed and the span to remove.
+    let mut keys: Vec<(String, bool, Span)> = Vec::new();
+    // A normalized declaration and the span to remove.
+    let mut declarations: Vec<(String, Span)> = Vec::new();
+    for item in items {
+        match item {
+            Item::Declaration {
+                declaration,
+                span,
+                next,
+            } => {
+                let name = declaration.name.as_ref();
+                if name.starts_with("--") {
+                    continue;
+                }
+
+                let standard = standard_property(name);
+                if let Some(standard) = standard {
+                    edits.push(Edit::new(declaration.span.name, standard));
+                }
+
+                let first_edit = edits.len();
+                normalize_value(text, declaration.span.value, edits);
+                let value = apply_edits(text, declaration.span.value, edits[first_edit..].to_vec());
+
+                let key = standard.unwrap_or(name).to_ascii_lowercase();
+                let remove = Span::new(span.start, *next);
+                declarations.push((
+                    format!(
+                        "{}:{}{}",
+                        key,
+                        collapse_whitespace(&value),
+                        if declaration.important { "!" } else { "" }
+                    ),
+                    remove,
+                ));
+                keys.push((key, standard.is_some(), remove));
+            }
+            Item::Statement {
+                at_rule,
+                prelude,
+                block,
+                span,
+                next,
+            } => {
+                let first_edit = edits.len();
+                let is_prefixed = match at_rule {
+                    Some(name) => {
+                        let standard = VendorPrefix::split(name.slice(text))
+                            .map(|(_, name)| name)
+                            .filter(|name| contains_ignore_case(PREFIXED_AT_RULES, name));
+                        if let Some(standard) = standard {
+                            edits.push(Edit::new(*name, standard));
+                        }
+                        standard.is_some()
+                    }
+                    None => normalize_selector(text, *prelude, edits),
+                };
+
+                let prelude_text = apply_edits(
+                    text,
+                    Span::new(span.start, prelude.end),
+                    edits[first_edit..].to_vec(),
+                );
+
+                if let Some(block) = block {
+                    normalize_items(text, block, edits);
+                }
+
+                keys.push((
+                    collapse_whitespace(&prelude_text),
+                    is_prefixed,
+                    Span::new(span.start, *next),
+                ));
+            }
+        }
+    }
+
+    let mut removed = Vec::new();
+    for (i, (key, is_prefixed, span)) in keys.iter().enumerate() {
+        let is_overridden = keys[i + 1..].iter().any(|(other, _, _)| other == key)
+            || keys[..i]
+                .iter()
+                .any(|(other, other_prefixed, _)| !other_prefixed && other == key);
+        if *is_prefixed && is_overridden {
+            removed.push(*span);
+        }
+    }
+
+    // The last of the identical declarations that are kept wins.
+    for (i, (declaration, span)) in declarations.iter().enumerate() {
+        let is_repeated = declarations[i + 1..]
+            .iter()
+            .any(|(other, other_span)| other == declaration && !removed.contains(other_span));
+        if is_repeated && !removed.contains(span) {
+            removed.push(*span);
+        }
+    }
+
+    edits.extend(removed.into_iter().map(|span| Edit::new(span, "")));
+}
+
+/// Replaces prefixed pseudo-classes. Returns `true` when there were any.
+fn normalize_selector(text: &str, selector: Span, edits: &mut Vec<Edit>) -> bool {
+    let mut is_prefixed = false;
+    for (span, name) in pseudo_classes(text, selector) {
+        if let Some(standard) = find_ignore_case(PSEUDO_CLASSES, &name) {
+            edits.push(Edit::new(span, *standard));
+            is_prefixed = true;
+        }
+    }
+
+    is_prefixed
+}
+
+fn normalize_value(text: &str, value: Span, edits: &mut Vec<Edit>) {
+    let tokens = tokenize(text, value);
+    for (index, token) in tokens.iter().enumerate() {
+        match token {
+            (ValueToken::Ident(name), span) => {
+                if let Some(standard) = standard_keyword(name) {
+                    edits.push(Edit::new(*span, standard));
+                }
+            }
+            (ValueToken::Function(name), span) => {
+                let Some((_, standard)) = VendorPrefix::split(name) else {
+                    continue;
+                };
+
+                if !contains_ignore_case(PREFIXED_FUNCTIONS, standard) {
+                    continue;
+                }
+
+                edits.push(Edit::new(function_name_span(*span), standard));
+
+                let standard = standard.to_ascii_lowercase();
+                let arguments = function_arguments(&tokens, index + 1);
+                if standard.ends_with("linear-gradient") {
+                    normalize_linear_gradient(&tokens, &arguments, edits);
+                } else if standard.ends_with("radial-gradient") {
+                    normalize_radial_gradient(text, &tokens, &arguments, edits);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns token indices of comma-separated function arguments.
+/// `start` must be right after the function token.
+fn function_arguments(tokens: &[(ValueToken<'_>, Span)], start: usize) -> Vec<Range<usize>> {
+    let mut arguments = Vec::new();
+    let mut depth = 0usize;
+    let mut argument_start = start;
+    for (index, (token, _)) in tokens.iter().enumerate().skip(start) {
+        match token {
+            ValueToken::Function(_) | ValueToken::OpenBracket(_) => depth += 1,
+            ValueToken::CloseBracket(_) if depth == 0 => {
+                arguments.push(argument_start..index);
+                return arguments;
+            }
+            ValueToken::CloseBracket(_) => depth -= 1,
+            ValueToken::Comma if depth == 0 => {
+                arguments.push(argument_start..index);
+                argument_start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    arguments.push(argument_start..tokens.len());
+    arguments
+}
+
+fn range_span(tokens: &[(ValueToken<'_>, Span)], range: Range<usize>) -> Span {
+    Span::new(tokens[range.start].1.start, tokens[range.end - 1].1.end)
+}
+
+fn opposite_side(name: &str) -> Option<&'static str> {
+    match name.to_ascii_lowercase().as_str() {
+        "top" => Some("bottom"),
+        "bottom" => Some("top"),
+        "left" => Some("right"),
+        "right" => Some("left"),
+        _ => None,
+    }
+}
+
+/// Converts the old `linear-gradient()` direction, which is the starting point
+/// and where `0deg` points to the right, counterclockwise.
+fn normalize_linear_gradient(
+    tokens: &[(ValueToken<'_>, Span)],
+    arguments: &[Range<usize>],
+    edits: &mut Vec<Edit>,
+) {
+    let Some(first) = arguments.first().filter(|range| !range.is_empty()) else {
+        return;
+    };
+
+    let direction = match &tokens[first.clone()] {
+        [(ValueToken::Dimension(value, unit), _)] => {
+            let degrees = match unit.to_ascii_lowercase().as_str() {
+                "deg" => *value,
+                "grad" => value * 0.9,
+                "rad" => value.to_degrees(),
+                "turn" => value * 360.0,
+                _ => return,
+            };
+
+            let degrees = (90.0 - degrees) % 360.0;
+            let degrees = if degrees < 0.0 {
+                degrees + 360.0
+            } else {
+                degrees
+            };
+            format!("{}deg", degrees)
+        }
+        [(ValueToken::Number(value), _)] if *value == 0.0 => "90deg".to_string(),
+        sides => {
+            let mut direction = String::from("to");
+            for (token, _) in sides {
+                let ValueToken::Ident(name) = token else {
+                    return;
+                };
+                let Some(side) = opposite_side(name) else {
+                    return;
+                };
+                direction.push(' ');
+                direction.push_str(side);
+            }
+            direction
+        }
+    };
+
+    edits.push(Edit::new(range_span(tokens, first.clone()), direction));
+}
+
+fn is_radial_shape(tokens: &[(ValueToken<'_>, Span)], range: Range<usize>) -> bool {
+    !range.is_empty()
+        && tokens[range].iter().all(|(token, _)| match token {
+            ValueToken::Ident(name) => [
+                "circle",
+                "ellipse",
+                "closest-side",
+                "closest-corner",
+                "farthest-side",
+                "farthest-corner",
+                "contain",
+                "cover",
+            ]
+            .iter()
+            .any(|keyword| keyword.eq_ignore_ascii_case(name)),
+            _ => false,
+        })
+}
+
+fn is_position(tokens: &[(ValueToken<'_>, Span)], range: Range<usize>) -> bool {
+    !range.is_empty()
+        && tokens[range].iter().all(|(token, _)| match token {
+            ValueToken::Ident(name) => ["left", "right", "top", "bottom", "center"]
+                .iter()
+                .any(|keyword| keyword.eq_ignore_ascii_case(name)),
+            ValueToken::Number(_) | ValueToken::Percentage(_) | ValueToken::Dimension(..) => true,
+            _ => false,
+        })
+}
+
+/// Converts the old `radial-gradient()` syntax, where the position and the shape
+/// are separate arguments and `contain` and `cover` are allowed.
+fn normalize_radial_gradient(
+    text: &str,
+    tokens: &[(ValueToken<'_>, Span)],
+    arguments: &[Range<usize>],
+    edits: &mut Vec<Edit>,
+) {
+    let shape_text = |range: Range<usize>| {
+        let words: Vec<_> = tokens[range]
+            .iter()
+            .map(|(token, _)| match token {
+                ValueToken::Ident(name) if name.eq_ignore_ascii_case("contain") => "closest-side",
+                ValueToken::Ident(name) if name.eq_ignore_ascii_case("cover") => "farthest-corner",
+                ValueToken::Ident(name) => name,
+                _ => "",
+            })
+            .collect();
+        words.join(" ")
+    };
+
+    let Some(first) = arguments.first() else {
+        return;
+    };
+
+    if is_radial_shape(tokens, first.clone()) {
+        edits.push(Edit::new(
+            range_span(tokens, first.clone()),
+            shape_text(first.clone()),
+        ));
+        return;
+    }
+
+    if !is_position(tokens, first.clone()) {
+        return;
+    }
+
+    let position = range_span(tokens, first.clone());
+    let (shape, end) = match arguments.get(1) {
+        Some(second) if is_radial_shape(tokens, second.clone()) => (
+            format!("{} ", shape_text(second.clone())),
+            range_span(tokens, second.clone()).end,
+        ),
+        _ => (String::new(), position.end),
+    };
+
+    edits.push(Edit::new(
+        Span::new(position.start, end),
+        format!("{}at {}", shape, position.slice(text)),
+    ));
+}
+
+fn prefix_items(text: &str, items: &[Item<'_>], options: PrefixOptions, edits: &mut Vec<Edit>) {
+    let names: Vec<&str> = items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Declaration { declaration, .. } => Some(declaration.name.as_ref()),
+            Item::Statement { .. } => None,
+        })
+        .collect();
+
+    let vendors = [VendorPrefix::Webkit, VendorPrefix::Moz, VendorPrefix::Ms];
+    let vendors = vendors.iter().filter(|prefix| options.is_enabled(**prefix));
+    for item in items {
+        match item {
+            Item::Declaration {
+                declaration, span, ..
+            } => {
+                let name_text = declaration.name.as_ref();
+                if name_text.starts_with("--") {
+                    continue;
+                }
+
+                let value_tokens = tokenize(text, declaration.span.value);
+
+                let separator = format!(";{}", whitespace_before(text, span.start));
+                for prefix in vendors.clone() {
+                    let has_prefix = |table: &[(&str, &[VendorPrefix])], name: &str| {
+                        find_ignore_case(table, name).map_or(false, |list| list.contains(prefix))
+                    };
+
+                    let mut value_edits = Vec::new();
+                    for (token, token_span) in &value_tokens {
+                        let (value_name, value_span) = match token {
+                            ValueToken::Ident(ident) => (ident, *token_span),
+                            ValueToken::Function(function) => {
+                                (function, function_name_span(*token_span))
+                            }
+                            _ => continue,
+                        };
+
+                        if has_prefix(VALUE_PREFIXES, value_name) {
+                            value_edits.push(Edit::new(
+                                value_span,
+                                format!("{}{}", prefix.as_str(), value_name),
+                            ));
+                        }
+                    }
+
+                    let name_prefixed = has_prefix(PROPERTY_PREFIXES, name_text);
+                    if !name_prefixed && value_edits.is_empty() {
+                        continue;
+                    }
+
+                    let new_name = if name_prefixed {
+                        format!("{}{}", prefix.as_str(), name_text)
+                    } else {
+                        name_text.to_string()
+                    };
+
+                    if name_prefixed
+                        && names
+                            .iter()
+                            .any(|other| other.eq_ignore_ascii_case(&new_name))
+                    {
+                        continue;
+                    }
+
+                    let name_end = declaration.span.name.end;
+                    let value_text = apply_edits(text, Span::new(name_end, span.end), value_edits);
+                    edits.push(Edit::new(
+                        Span::new(span.start, span.start),
+                        format!("{}{}{}", new_name, value_text, separator),
+                    ));
+                }
+            }
+            Item::Statement {
+                at_rule,
+                prelude,
+                block,
+                span,
+                ..
+            } => {
+                let first_edit = edits.len();
+                if let Some(block) = block {
+                    prefix_items(text, block, options, edits);
+                }
+
+                if at_rule.is_some() {
+                    continue;
+                }
+
+                let classes = pseudo_classes(text, *prelude);
+                let block_edits = edits[first_edit..].to_vec();
+                let separator = match whitespace_before(text, span.start) {
+                    "" if span.start == 0 => "\n",
+                    whitespace => whitespace,
+                };
+                for prefix in vendors.clone() {
+                    let mut rule_edits = block_edits.clone();
+                    let start = rule_edits.len();
+                    for (class_span, name) in &classes {
+                        let prefixed = PSEUDO_CLASSES.iter().find(|(prefixed, standard)| {
+                            standard.eq_ignore_ascii_case(name)
+                                && VendorPrefix::split(prefixed.trim_start_matches(':'))
+                                    .map_or(false, |(vendor, _)| vendor == *prefix)
+                        });
+
+                        if let Some((prefixed, _)) = prefixed {
+                            rule_edits.push(Edit::new(*class_span, *prefixed));
+                        }
+                    }
+
+                    if rule_edits.len() == start {
+                        continue;
+                    }
+
+                    let copy = apply_edits(text, *span, rule_edits);
+                    edits.push(Edit::new(
+                        Span::new(span.start, span.start),
+                        format!("{}{}", copy, separator),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Returns the whitespace right before a position.
+fn whitespace_before(text: &str, pos: usize) -> &str {
+    let before = &text[..pos];
+    &before[before.trim_end().len()..]
+}
@@ -0,0 +1,1441 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+#[cfg(not(feature = "std"))]
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::fmt;
+use core::ops::Deref;
+
+use crate::animation::EasingFunction;
+use crate::calc::{CalcNode, CalcType};
+use crate::color::{cmp_ignore_ascii_case, Color};
+#[cfg(not(feature = "std"))]
+use crate::float_funcs::FloatFuncs;
+use crate::font::{Font, FontFamily, FontSize, FontWeight};
+use crate::image::Image;
+use crate::length::{Length, LengthUnit};
+use crate::prefixes::VendorPrefix;
+use crate::shorthands;
+use crate::transform::{TransformFunction, TransformOrigin};
+use crate::value::{ComponentValue, Value};
+use crate::variables::has_substitutions;
+use crate::{Declaration, DeclarationSpan};
+
+/// A property value grammar.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum PropertySyntax {
+    /// A grammar in the [value definition syntax](https://www.w3.org/TR/css-values-4/#value-defs),
+    /// like `<length-percentage> | auto`.
+    ///
+    /// Data types can have a numeric range, like `<length [0,∞]>`.
+    /// Math functions are not checked against it, since they are clamped instead.
+    Grammar(&'static str),
+    /// A shorthand property, see [`longhands`](crate::longhands).
+    ///
+    /// The value is valid when it can be expanded into longhands.
+    Shorthand,
+}
+
+/// A known property.
+///
+/// Covers CSS 2.1, SVG 1.1 and SVG 2 presentation properties
+/// and the longhands of the supported shorthands.
+///
+/// # Example
+///
+/// ```
+/// use simplecss::{property, PropertySyntax};
+///
+/// let p = property("Stroke-Width").unwrap();
+/// assert_eq!(p.name, "stroke-width");
+/// assert!(p.inherited);
+/// assert_eq!(p.initial, "1px");
+/// assert_eq!(
+///     p.syntax,
+///     PropertySyntax::Grammar("<length-percentage [0,∞]> | <number [0,∞]>")
+/// );
+///
+/// assert!(p.validate("calc(1px + 5%)"));
+/// assert!(p.validate("inherit"));
+/// assert!(!p.validate("thick"));
+///
+/// assert!(property("margin").unwrap().validate("0 auto"));
+/// assert!(property("-webkit-box-flex").is_none());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Property {
+    /// The lowercase property name.
+    pub name: &'static str,
+    /// Checks that the property is inherited by default.
+    pub inherited: bool,
+    /// The initial value.
+    ///
+    /// Empty for shorthands, which use the initial values of their longhands.
+    pub initial: &'static str,
+    /// The value grammar.
+    pub syntax: PropertySyntax,
+}
+
+impl Property {
+    /// Checks that a value is valid for the property.
+    ///
+    /// CSS-wide keywords, like `inherit`, are always valid.
+    /// So are values with `var()` and `env()`, since they can be validated
+    /// only after substitution.
+    pub fn validate(&self, value: &str) -> bool {
+        if has_substitutions(value) {
+            return true;
+        }
+
+        let Ok(parsed) = Value::parse(value) else {
+            return false;
+        };
+
+        match parsed.components.as_slice() {
+            [] => return false,
+            [c] if is_keyword(c, WIDE_KEYWORDS) => return true,
+            _ => {}
+        }
+
+        match self.syntax {
+            PropertySyntax::Grammar(grammar) => match_grammar(grammar, &parsed.components),
+            // System fonts can't be expanded, since their longhands are not known.
+            PropertySyntax::Shorthand if self.name == "font" => {
+                Font::from_components(&parsed.components).is_some()
+            }
+            PropertySyntax::Shorthand => {
+                let declaration = Declaration {
+                    name: self.name.into(),
+                    value,
+                    important: false,
+                    span: DeclarationSpan::default(),
+                };
+                shorthands::expand(&declaration).map_or(false, |longhands| {
+                    longhands
+                        .iter()
+                        .all(|l| property(l.name).map_or(false, |p| p.validate(&l.value)))
+                })
+            }
+        }
+    }
+}
+
+/// Returns a known property by its name, ignoring ASCII case.
+///
+/// Custom properties, like `--gap`, and vendor-prefixed properties are not known.
+pub fn property(name: &str) -> Option<&'static Property> {
+    let idx = PROPERTIES
+        .binary_search_by(|p| cmp_ignore_ascii_case(p.name, name))
+        .ok()?;
+    Some(&PROPERTIES[idx])
+}
+
+/// Returns all known properties, sorted by name.
+pub fn properties() -> &'static [Property] {
+    PROPERTIES
+}
+
+/// A declaration validation error.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ValidationError {
+    /// The property is not known.
+    UnknownProperty,
+    /// The value doesn't match the property grammar.
+    InvalidValue,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ValidationError::UnknownProperty => {
+                write!(f, "unknown property")
+            }
+            ValidationError::InvalidValue => {
+                write!(f, "invalid property value")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ValidationError {}
+
+/// A grammar node.
+enum Node<'s> {
+    /// A keyword, like `auto`.
+    Keyword(&'s str),
+    /// A data type, like `<length>`, with an optional range, like `<length [0,∞]>`.
+    Type(&'s str, Option<Range>),
+    /// A comma.
+    Comma,
+    /// A slash.
+    Slash,
+    /// Juxtaposed nodes, which must occur in order.
+    Sequence(Vec<Node<'s>>),
+    /// `a && b`, all nodes in any order.
+    AllOf(Vec<Node<'s>>),
+    /// `a || b`, one or more nodes in any order.
+    AnyOf(Vec<Node<'s>>),
+    /// `a | b`, exactly one node.
+    OneOf(Vec<Node<'s>>),
+    /// A node with a multiplier, like `a?` or `a#`.
+    Repeat {
+        node: Box<Node<'s>>,
+        min: usize,
+        max: usize,
+        commas: bool,
+    },
+}
+
+/// A numeric range of a data type, like `[0,∞]`.
+#[derive(Clone, Copy, Debug)]
+struct Range {
+    min: f64,
+    max: f64,
+}
+
+impl Range {
+    fn parse(text: &str) -> Option<Self> {
+        let bound = |s: &str| match s.trim() {
+            "∞" => Some(f64::INFINITY),
+            "-∞" => Some(f64::NEG_INFINITY),
+            n => n.parse().ok(),
+        };
+
+        let text = text.strip_prefix('[')?.strip_suffix(']')?;
+        let (min, max) = text.split_once(',')?;
+        Some(Range {
+            min: bound(min)?,
+            max: bound(max)?,
+        })
+    }
+
+    /// Checks that a component is in the range.
+    ///
+    /// Math functions are clamped to the range instead, so they are always in it.
+    fn contains(self, c: &ComponentValue<'_>) -> bool {
+        match c {
+            ComponentValue::Number(n)
+            | ComponentValue::Percentage(n)
+            | ComponentValue::Dimension(n, _) => (self.min..=self.max).contains(n),
+            _ => true,
+        }
+    }
+}
+
+/// A value definition syntax parser.
+struct GrammarParser<'s> {
+    text: &'s str,
+    pos: usize,
+}
+
+impl<'s> GrammarParser<'s> {
+    fn parse(text: &'s str) -> Option<Node<'s>> {
+        let mut parser = GrammarParser { text, pos: 0 };
+        let node = parser.one_of()?;
+        parser.skip_spaces();
+        (parser.pos == text.len()).then_some(node)
+    }
+
+    fn tail(&self) -> &'s str {
+        &self.text[self.pos..]
+    }
+
+    fn skip_spaces(&mut self) {
+        let tail = self.tail();
+        self.pos += tail.len() - tail.trim_start().len();
+    }
+
+    /// Consumes a combinator.
+    fn eat(&mut self, combinator: &str) -> bool {
+        self.skip_spaces();
+        let tail = self.tail();
+        // `|` must not match the start of `||`.
+        if tail.starts_with(combinator) && !tail[combinator.len()..].starts_with('|') {
+            self.pos += combinator.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn one_of(&mut self) -> Option<Node<'s>> {
+        let mut nodes = Vec::from([self.any_of()?]);
+        while self.eat("|") {
+            nodes.push(self.any_of()?);
+        }
+        Some(collapse(nodes, Node::OneOf))
+    }
+
+    fn any_of(&mut self) -> Option<Node<'s>> {
+        let mut nodes = Vec::from([self.all_of()?]);
+        while self.eat("||") {
+            nodes.push(self.all_of()?);
+        }
+        Some(collapse(nodes, Node::AnyOf))
+    }
+
+    fn all_of(&mut self) -> Option<Node<'s>> {
+        let mut nodes = Vec::from([self.sequence()?]);
+        while self.eat("&&") {
+            nodes.push(self.sequence()?);
+        }
+        Some(collapse(nodes, Node::AllOf))
+    }
+
+    fn sequence(&mut self) -> Option<Node<'s>> {
+        let mut nodes = Vec::new();
+        while let Some(node) = self.term() {
+            nodes.push(node);
+        }
+
+        if nodes.is_empty() {
+            return None;
+        }
+
+        Some(collapse(nodes, Node::Sequence))
+    }
+
+    /// Parses a node with optional multipliers.
+    fn term(&mut self) -> Option<Node<'s>> {
+        self.skip_spaces();
+        let tail = self.tail();
+        let mut node = match tail.bytes().next()? {
+            b'[' => {
+                self.pos += 1;
+                let node = self.one_of()?;
+                self.skip_spaces();
+                if !self.tail().starts_with(']') {
+                    return None;
+                }
+                self.pos += 1;
+                node
+            }
+            b'<' => {
+                let end = tail.find('>')?;
+                self.pos += end + 1;
+                match tail[1..end].split_once(' ') {
+                    Some((name, range)) => Node::Type(name, Some(Range::parse(range)?)),
+                    None => Node::Type(&tail[1..end], None),
+                }
+            }
+            b',' => {
+                self.pos += 1;
+                Node::Comma
+            }
+            b'/' => {
+                self.pos += 1;
+                Node::Slash
+            }
+            c if c.is_ascii_alphabetic() => {
+                let len = tail
+                    .find(|ch: char| !ch.is_ascii_alphanumeric() && ch != '-')
+                    .unwrap_or(tail.len());
+                self.pos += len;
+                Node::Keyword(&tail[..len])
+            }
+            _ => return None,
+        };
+
+        loop {
+            let (min, max, commas) = match self.tail().bytes().next() {
+                Some(b'?') => (0, 1, false),
+                Some(b'*') => (0, usize::MAX, false),
+                Some(b'+') => (1, usize::MAX, false),
+                Some(b'#') => (1, usize::MAX, true),
+                Some(b'{') => {
+                    let end = self.tail().find('}')?;
+                    let range = &self.tail()[1..end];
+                    self.pos += end;
+                    match range.split_once(',') {
+                        None => {
+                            let count = range.parse().ok()?;
+                            (count, count, false)
+                        }
+                        Some((min, "")) => (min.parse().ok()?, usize::MAX, false),
+                        Some((min, max)) => (min.parse().ok()?, max.parse().ok()?, false),
+                    }
+                }
+                _ => break,
+            };
+
+            self.pos += 1;
+            node = Node::Repeat {
+                node: Box::new(node),
+                min,
+                max,
+                commas,
+            };
+        }
+
+        Some(node)
+    }
+}
+
+fn collapse<'s>(mut nodes: Vec<Node<'s>>, f: fn(Vec<Node<'s>>) -> Node<'s>) -> Node<'s> {
+    if nodes.len() == 1 {
+        nodes.remove(0)
+    } else {
+        f(nodes)
+    }
+}
+
+/// Checks that components match a grammar.
+fn match_grammar(grammar: &'static str, components: &[ComponentValue<'_>]) -> bool {
+    let matcher = Matcher::new(components);
+    let Some(node) = matcher.grammar(grammar) else {
+        return false;
+    };
+
+    matcher.ends(&node, 0).contains(&components.len())
+}
+
+/// A parsed grammar.
+///
+/// Grammars are cached for the program lifetime only with `std`, see [`cached_grammar`].
+#[derive(Clone)]
+struct Grammar(
+    #[cfg(feature = "std")] &'static Node<'static>,
+    #[cfg(not(feature = "std"))] Rc<Node<'static>>,
+);
+
+impl Deref for Grammar {
+    type Target = Node<'static>;
+
+    #[cfg(feature = "std")]
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Parses a grammar once and caches it for the program lifetime.
+#[cfg(feature = "std")]
+fn cached_grammar(text: &'static str) -> Option<Grammar> {
+    use std::sync::{PoisonError, RwLock};
+
+    // Sorted by text. Nodes are leaked, like any other lazy static.
+    static GRAMMARS: RwLock<Vec<(&str, Option<Grammar>)>> = RwLock::new(Vec::new());
+
+    let cached = GRAMMARS.read().unwrap_or_else(PoisonError::into_inner);
+    if let Ok(idx) = cached.binary_search_by_key(&text, |(t, _)| t) {
+        return cached[idx].1.clone();
+    }
+    drop(cached);
+
+    // The grammar could have been added while the lock was released.
+    let mut grammars = GRAMMARS.write().unwrap_or_else(PoisonError::into_inner);
+    match grammars.binary_search_by_key(&text, |(t, _)| t) {
+        Ok(idx) => grammars[idx].1.clone(),
+        Err(idx) => {
+            let node = GrammarParser::parse(text).map(|node| Grammar(Box::leak(Box::new(node))));
+            grammars.insert(idx, (text, node.clone()));
+            node
+        }
+    }
+}
+
+/// A backtracking grammar matcher.
+///
+/// Instead of a single match, it returns all positions at which a node match can end.
+struct Matcher<'c, 'a> {
+    components: &'c [ComponentValue<'a>],
+    /// Match ends by node address and position.
+    ///
+    /// Nodes are alive while the matcher is, so their addresses are not reused.
+    memo: RefCell<BTreeMap<(*const Node<'static>, usize), Vec<usize>>>,
+    /// Grammars parsed by the matcher, since they are cached only with `std`.
+    #[cfg(not(feature = "std"))]
+    grammars: RefCell<Vec<(&'static str, Option<Grammar>)>>,
+}
+
+impl<'c, 'a> Matcher<'c, 'a> {
+    fn new(components: &'c [ComponentValue<'a>]) -> Self {
+        Matcher {
+            components,
+            memo: RefCell::new(BTreeMap::new()),
+            #[cfg(not(feature = "std"))]
+            grammars: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl Matcher<'_, '_> {
+    #[cfg(feature = "std")]
+    fn grammar(&self, text: &'static str) -> Option<Grammar> {
+        cached_grammar(text)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn grammar(&self, text: &'static str) -> Option<Grammar> {
+        let mut grammars = self.grammars.borrow_mut();
+        if let Some((_, node)) = grammars.iter().find(|(t, _)| *t == text) {
+            return node.clone();
+        }
+
+        let node = GrammarParser::parse(text).map(|node| Grammar(Rc::new(node)));
+        grammars.push((text, node.clone()));
+        node
+    }
+
+    fn ends(&self, node: &Node<'static>, pos: usize) -> Vec<usize> {
+        let key = (node as *const Node<'static>, pos);
+        if let Some(ends) = self.memo.borrow().get(&key) {
+            return ends.clone();
+        }
+
+        let ends = self.node_ends(node, pos);
+        self.memo.borrow_mut().insert(key, ends.clone());
+        ends
+    }
+
+    fn node_ends(&self, node: &Node<'static>, pos: usize) -> Vec<usize> {
+        let mut ends = Vec::new();
+        let component = self.components.get(pos);
+        match node {
+            Node::Keyword(keyword) => {
+                if component.map_or(false, |c| c.is_ident(keyword)) {
+                    ends.push(pos + 1);
+                }
+            }
+            Node::Type(name, range) => self.type_ends(name, *range, pos, &mut ends),
+            Node::Comma => {
+                if component == Some(&ComponentValue::Comma) {
+                    ends.push(pos + 1);
+                }
+            }
+            Node::Slash => {
+                if component == Some(&ComponentValue::Slash) {
+                    ends.push(pos + 1);
+                }
+            }
+            Node::Sequence(nodes) => {
+                ends.push(pos);
+                for item in nodes {
+                    let mut next = Vec::new();
+                    for start in ends {
+                        extend(&mut next, self.ends(item, start));
+                    }
+                    ends = next;
+                }
+            }
+            Node::AllOf(nodes) => self.combination_ends(nodes, 0, pos, true, &mut ends),
+            Node::AnyOf(nodes) => self.combination_ends(nodes, 0, pos, false, &mut ends),
+            Node::OneOf(nodes) => {
+                for item in nodes {
+                    extend(&mut ends, self.ends(item, pos));
+                }
+            }
+            Node::Repeat {
+                node,
+                min,
+                max,
+                commas,
+            } => {
+                if *min == 0 {
+                    ends.push(pos);
+                }
+
+                let mut current = Vec::from([pos]);
+                let mut count = 0;
+                while count < *max && !current.is_empty() {
+                    count += 1;
+                    let mut next = Vec::new();
+                    for start in current {
+                        let item_start = if *commas && count > 1 {
+                            if self.components.get(start) != Some(&ComponentValue::Comma) {
+                                continue;
+                            }
+                            start + 1
+                        } else {
+                            start
+                        };
+
+                        // Repetitions must consume something, otherwise they never end.
+                        let item_ends = self.ends(node, item_start);
+                        extend(&mut next, item_ends.into_iter().filter(|end| *end > start));
+                    }
+
+                    if count >= *min {
+                        extend(&mut ends, next.iter().copied());
+                    }
+                    current = next;
+                }
+            }
+        }
+
+        ends
+    }
+
+    /// Matches `&&` and `||` nodes, where `used` is a bit set of already matched nodes.
+    fn combination_ends(
+        &self,
+        nodes: &[Node<'static>],
+        used: u64,
+        pos: usize,
+        all: bool,
+        ends: &mut Vec<usize>,
+    ) {
+        let done = if all {
+            used.count_ones() as usize == nodes.len()
+        } else {
+            used != 0
+        };
+        if done && !ends.contains(&pos) {
+            ends.push(pos);
+        }
+
+        for (i, node) in nodes.iter().enumerate() {
+            if used & (1 << i) != 0 {
+                continue;
+            }
+
+            for end in self.ends(node, pos) {
+                self.combination_ends(nodes, used | (1 << i), end, all, ends);
+            }
+        }
+    }
+
+    fn type_ends(&self, name: &str, range: Option<Range>, pos: usize, ends: &mut Vec<usize>) {
+        if let Some((_, grammar)) = FRAGMENTS.iter().find(|(n, _)| *n == name) {
+            if let Some(node) = self.grammar(grammar) {
+                extend(ends, self.ends(&node, pos));
+            }
+            return;
+        }
+
+        // Types that consist of multiple components.
+        let whole: Option<fn(&[ComponentValue<'_>]) -> bool> = match name {
+            "font-family" => Some(|c| FontFamily::from_components(c).is_some()),
+            "transform-origin" => Some(|c| TransformOrigin::from_components(c).is_some()),
+            _ => None,
+        };
+        if let Some(f) = whole {
+            for end in pos + 1..=self.components.len() {
+                if f(&self.components[pos..end]) {
+                    ends.push(end);
+                }
+            }
+            return;
+        }
+
+        if let Some(c) = self.components.get(pos) {
+            if is_type(name, c) && range.map_or(true, |r| r.contains(c)) {
+                ends.push(pos + 1);
+            }
+        }
+    }
+}
+
+fn extend(ends: &mut Vec<usize>, new: impl IntoIterator<Item = usize>) {
+    for end in new {
+        if !ends.contains(&end) {
+            ends.push(end);
+        }
+    }
+}
+
+/// Checks that a component is of a single-component data type.
+fn is_type(name: &str, c: &ComponentValue<'_>) -> bool {
+    match name {
+        "length" => match c {
+            ComponentValue::Function(_) => calc_type(c) == Some(CalcType::Length),
+            _ => Length::from_component(c).map_or(false, |l| l.unit != LengthUnit::Percent),
+        },
+        "length-percentage" => {
+            Length::from_component(c).is_some() || calc_type(c).map_or(false, CalcType::is_length)
+        }
+        "percentage" => {
+            matches!(c, ComponentValue::Percentage(_)) || calc_type(c) == Some(CalcType::Percentage)
+        }
+        "number" => {
+            matches!(c, ComponentValue::Number(_)) || calc_type(c) == Some(CalcType::Number)
+        }
+        "integer" => match c {
+            ComponentValue::Number(n) => n.floor() == *n,
+            // Math functions are rounded to the nearest integer.
+            _ => calc_type(c) == Some(CalcType::Number),
+        },
+        "angle" => match c {
+            ComponentValue::Dimension(_, unit) => is_unit(unit, &["deg", "grad", "rad", "turn"]),
+            _ => calc_type(c) == Some(CalcType::Angle),
+        },
+        "time" => match c {
+            ComponentValue::Dimension(_, unit) => is_unit(unit, &["s", "ms"]),
+            _ => calc_type(c) == Some(CalcType::Time),
+        },
+        "frequency" => {
+            matches!(c, ComponentValue::Dimension(_, unit) if is_unit(unit, &["hz", "khz"]))
+        }
+        "color" => Color::from_component(c).is_some(),
+        "string" => matches!(c, ComponentValue::String(_)),
+        "url" => matches!(c, ComponentValue::Url(_)) || is_function(c, &["url", "src"]),
+        "custom-ident" => match c {
+            ComponentValue::Ident(ident) => {
+                !is_unit(ident, WIDE_KEYWORDS) && !is_unit(ident, &["default"])
+            }
+            _ => false,
+        },
+        "image" => {
+            Image::from_component(c).is_some()
+                || is_function(c, IMAGE_FUNCTIONS)
+                // Prefixed gradients have the old syntax, see `normalize_prefixes`.
+                || matches!(c, ComponentValue::Function(f) if VendorPrefix::split(&f.name)
+                    .map_or(false, |(_, unprefixed)| unprefixed.to_ascii_lowercase().ends_with("gradient")))
+        }
+        "transform-function" => TransformFunction::from_component(c).is_some(),
+        "font-weight" => FontWeight::from_component(c).is_some(),
+        "font-size" => FontSize::from_component(c).is_some(),
+        "easing-function" => EasingFunction::from_component(c).is_some(),
+        "filter-function" => is_function(c, FILTER_FUNCTIONS),
+        "basic-shape" => is_function(
+            c,
+            &[
+                "inset", "circle", "ellipse", "polygon", "path", "rect", "xywh",
+            ],
+        ),
+        "shape" => is_function(c, &["rect"]),
+        "counter" => is_function(c, &["counter", "counters"]),
+        "attr" => is_function(c, &["attr"]),
+        "path" => match c {
+            ComponentValue::Function(f) if f.name.eq_ignore_ascii_case("path") => {
+                matches!(f.arguments.as_slice(), [ComponentValue::String(_)])
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn calc_type(c: &ComponentValue<'_>) -> Option<CalcType> {
+    match c {
+        ComponentValue::Function(func) => CalcNode::from_function(func)?.calc_type(),
+        _ => None,
+    }
+}
+
+fn is_keyword(c: &ComponentValue<'_>, keywords: &[&str]) -> bool {
+    keywords.iter().any(|k| c.is_ident(k))
+}
+
+fn is_unit(unit: &str, units: &[&str]) -> bool {
+    units.iter().any(|u| u.eq_ignore_ascii_case(unit))
+}
+
+fn is_function(c: &ComponentValue<'_>, names: &[&str]) -> bool {
+    matches!(c, ComponentValue::Function(f) if is_unit(&f.name, names))
+}
+
+const WIDE_KEYWORDS: &[&str] = &["inherit", "initial", "unset", "revert", "revert-layer"];
+const IMAGE_FUNCTIONS: &[&str] = &["image", "cross-fade", "element", "paint"];
+const FILTER_FUNCTIONS: &[&str] = &[
+    "blur",
+    "brightness",
+    "contrast",
+    "drop-shadow",
+    "grayscale",
+    "hue-rotate",
+    "invert",
+    "opacity",
+    "saturate",
+    "sepia",
+];
+
+/// Data types defined by a grammar.
+static FRAGMENTS: &[(&str, &str)] = &[
+    ("alpha-value", "<number> | <percentage>"),
+    (
+        "blend-mode",
+        "normal | multiply | screen | overlay | darken | lighten | color-dodge | color-burn | \
+         hard-light | soft-light | difference | exclusion | hue | saturation | color | luminosity",
+    ),
+    ("bg-size", "[<length-percentage [0,∞]> | auto]{1,2} | cover | contain"),
+    ("box", "border-box | padding-box | content-box"),
+    (
+        "geometry-box",
+        "<box> | margin-box | fill-box | stroke-box | view-box",
+    ),
+    ("line-style", "none | hidden | dotted | dashed | solid | double | groove | ridge | inset | outset"),
+    ("line-width", "<length [0,∞]> | thin | medium | thick"),
+    (
+        "paint",
+        "none | <color> | <url> [none | <color>]? | context-fill | context-stroke",
+    ),
+    (
+        "position",
+        "[left | center | right | top | bottom | <length-percentage>] | \
+         [left | center | right | <length-percentage>] [top | center | bottom | <length-percentage>] | \
+         [center | [left | right] <length-percentage>?] && [center | [top | bottom] <length-percentage>?]",
+    ),
+    (
+        "repeat-style",
+        "repeat-x | repeat-y | [repeat | space | round | no-repeat]{1,2}",
+    ),
+];
+
+/// Known properties, sorted by name.
+static PROPERTIES: &[Property] = &[
+    longhand(
+        "alignment-baseline",
+        false,
+        "auto",
+        "auto | baseline | before-edge | text-before-edge | middle | central | after-edge | \
+         text-after-edge | ideographic | alphabetic | hanging | mathematical | top | center | \
+         bottom",
+    ),
+    shorthand("animation", false),
+    longhand("animation-delay", false, "0s", "<time>#"),
+    longhand(
+        "animation-direction",
+        false,
+        "normal",
+        "[normal | reverse | alternate | alternate-reverse]#",
+    ),
+    longhand("animation-duration", false, "0s", "[auto | <time [0,∞]>]#"),
+    longhand(
+        "animation-fill-mode",
+        false,
+        "none",
+        "[none | forwards | backwards | both]#",
+    ),
+    longhand(
+        "animation-iteration-count",
+        false,
+        "1",
+        "[infinite | <number [0,∞]>]#",
+    ),
+    longhand(
+        "animation-name",
+        false,
+        "none",
+        "[none | <custom-ident> | <string>]#",
+    ),
+    longhand(
+        "animation-play-state",
+        false,
+        "running",
+        "[running | paused]#",
+    ),
+    longhand(
+        "animation-timing-function",
+        false,
+        "ease",
+        "<easing-function>#",
+    ),
+    longhand(
+        "azimuth",
+        true,
+        "center",
+        "<angle> | [[left-side | far-left | left | center-left | center | center-right | \
+         right | far-right | right-side] || behind] | leftwards | rightwards",
+    ),
+    shorthand("background", false),
+    longhand(
+        "background-attachment",
+        false,
+        "scroll",
+        "[scroll | fixed | local]#",
+    ),
+    longhand("background-clip", false, "border-box", "<box>#"),
+    longhand("background-color", false, "transparent", "<color>"),
+    longhand("background-image", false, "none", "[<image> | none]#"),
+    longhand("background-origin", false, "padding-box", "<box>#"),
+    longhand("background-position", false, "0% 0%", "<position>#"),
+    longhand("background-repeat", false, "repeat", "<repeat-style>#"),
+    longhand("background-size", false, "auto", "<bg-size>#"),
+    longhand(
+        "baseline-shift",
+        false,
+        "baseline",
+        "baseline | sub | super | <length-percentage>",
+    ),
+    shorthand("border", false),
+    shorthand("border-bottom", false),
+    longhand("border-bottom-color", false, "currentcolor", "<color>"),
+    longhand(
+        "border-bottom-left-radius",
+        false,
+        "0",
+        "<length-percentage [0,∞]>{1,2}",
+    ),
+    longhand(
+        "border-bottom-right-radius",
+        false,
+        "0",
+        "<length-percentage [0,∞]>{1,2}",
+    ),
+    longhand("border-bottom-style", false, "none", "<line-style>"),
+    longhand("border-bottom-width", false, "medium", "<line-width>"),
+    longhand("border-collapse", true, "separate", "collapse | separate"),
+    shorthand("border-color", false),
+    shorthand("border-left", false),
+    longhand("border-left-color", false, "currentcolor", "<color>"),
+    longhand("border-left-style", false, "none", "<line-style>"),
+    longhand("border-left-width", false, "medium", "<line-width>"),
+    shorthand("border-radius", false),
+    shorthand("border-right", false),
+    longhand("border-right-color", false, "currentcolor", "<color>"),
+    longhand("border-right-style", false, "none", "<line-style>"),
+    longhand("border-right-width", false, "medium", "<line-width>"),
+    longhand("border-spacing", true, "0", "<length [0,∞]>{1,2}"),
+    shorthand("border-style", false),
+    shorthand("border-top", false),
+    longhand("border-top-color", false, "currentcolor", "<color>"),
+    longhand(
+        "border-top-left-radius",
+        false,
+        "0",
+        "<length-percentage [0,∞]>{1,2}",
+    ),
+    longhand(
+        "border-top-right-radius",
+        false,
+        "0",
+        "<length-percentage [0,∞]>{1,2}",
+    ),
+    longhand("border-top-style", false, "none", "<line-style>"),
+    longhand("border-top-width", false, "medium", "<line-width>"),
+    shorthand("border-width", false),
+    longhand("bottom", false, "auto", "<length-percentage> | auto"),
+    longhand(
+        "box-sizing",
+        false,
+        "content-box",
+        "content-box | border-box",
+    ),
+    longhand("caption-side", true, "top", "top | bottom"),
+    longhand(
+        "clear",
+        false,
+        "none",
+        "none | left | right | both | inline-start | inline-end",
+    ),
+    longhand("clip", false, "auto", "<shape> | auto"),
+    longhand(
+        "clip-path",
+        false,
+        "none",
+        "none | <url> | [<basic-shape> || <geometry-box>]",
+    ),
+    longhand("clip-rule", true, "nonzero", "nonzero | evenodd"),
+    longhand("color", true, "black", "<color>"),
+    longhand(
+        "color-interpolation",
+        true,
+        "sRGB",
+        "auto | sRGB | linearRGB",
+    ),
+    longhand(
+        "color-interpolation-filters",
+        true,
+        "linearRGB",
+        "auto | sRGB | linearRGB",
+    ),
+    longhand(
+        "color-profile",
+        true,
+        "auto",
+        "auto | sRGB | <url> | <custom-ident>",
+    ),
+    longhand(
+        "color-rendering",
+        true,
+        "auto",
+        "auto | optimizeSpeed | optimizeQuality",
+    ),
+    longhand(
+        "column-gap",
+        false,
+        "normal",
+        "normal | <length-percentage [0,∞]>",
+    ),
+    longhand(
+        "content",
+        false,
+        "normal",
+        "normal | none | [<string> | <url> | <image> | <counter> | <attr> | open-quote | \
+         close-quote | no-open-quote | no-close-quote]+",
+    ),
+    longhand(
+        "counter-increment",
+        false,
+        "none",
+        "none | [<custom-ident> <integer>?]+",
+    ),
+    longhand(
+        "counter-reset",
+        false,
+        "none",
+        "none | [<custom-ident> <integer>?]+",
+    ),
+    longhand("cue", false, "none", "[<url> | none]{1,2}"),
+    longhand("cue-after", false, "none", "<url> | none"),
+    longhand("cue-before", false, "none", "<url> | none"),
+    longhand(
+        "cursor",
+        true,
+        "auto",
+        "[<url> [<number> <number>]? ,]* [auto | default | none | context-menu | help | \
+         pointer | progress | wait | cell | crosshair | text | vertical-text | alias | copy | \
+         move | no-drop | not-allowed | grab | grabbing | e-resize | n-resize | ne-resize | \
+         nw-resize | s-resize | se-resize | sw-resize | w-resize | ew-resize | ns-resize | \
+         nesw-resize | nwse-resize | col-resize | row-resize | all-scroll | zoom-in | \
+         zoom-out]",
+    ),
+    longhand("cx", false, "0", "<length-percentage>"),
+    longhand("cy", false, "0", "<length-percentage>"),
+    longhand("d", false, "none", "none | <path>"),
+    longhand("direction", true, "ltr", "ltr | rtl"),
+    longhand(
+        "display",
+        false,
+        "inline",
+        "inline | block | list-item | inline-block | table | inline-table | table-row-group | \
+         table-header-group | table-footer-group | table-row | table-column-group | \
+         table-column | table-cell | table-caption | none | contents | flex | inline-flex | \
+         grid | inline-grid | flow-root | run-in",
+    ),
+    longhand(
+        "dominant-baseline",
+        true,
+        "auto",
+        "auto | use-script | no-change | reset-size | text-bottom | alphabetic | ideographic \
+         | middle | central | mathematical | hanging | text-top",
+    ),
+    longhand(
+        "elevation",
+        true,
+        "level",
+        "<angle> | below | level | above | higher | lower",
+    ),
+    longhand("empty-cells", true, "show", "show | hide"),
+    longhand(
+        "enable-background",
+        false,
+        "accumulate",
+        "accumulate | new <number>{4}?",
+    ),
+    longhand("fill", true, "black", "<paint>"),
+    longhand("fill-opacity", true, "1", "<alpha-value>"),
+    longhand("fill-rule", true, "nonzero", "nonzero | evenodd"),
+    longhand(
+        "filter",
+        false,
+        "none",
+        "none | [<filter-function> | <url>]+",
+    ),
+    shorthand("flex", false),
+    longhand(
+        "flex-basis",
+        false,
+        "auto",
+        "content | auto | <length-percentage [0,∞]>",
+    ),
+    longhand("flex-grow", false, "0", "<number [0,∞]>"),
+    longhand("flex-shrink", false, "1", "<number [0,∞]>"),
+    longhand(
+        "float",
+        false,
+        "none",
+        "left | right | none | inline-start | inline-end",
+    ),
+    longhand("flood-color", false, "black", "<color>"),
+    longhand("flood-opacity", false, "1", "<alpha-value>"),
+    shorthand("font", true),
+    longhand("font-family", true, "serif", "<font-family>"),
+    longhand(
+        "font-feature-settings",
+        true,
+        "normal",
+        "normal | [<string> [<integer> | on | off]?]#",
+    ),
+    longhand("font-size", true, "medium", "<font-size>"),
+    longhand("font-size-adjust", true, "none", "none | <number [0,∞]>"),
+    longhand(
+        "font-stretch",
+        true,
+        "normal",
+        "normal | <percentage> | ultra-condensed | extra-condensed | condensed | \
+         semi-condensed | semi-expanded | expanded | extra-expanded | ultra-expanded",
+    ),
+    longhand(
+        "font-style",
+        true,
+        "normal",
+        "normal | italic | oblique <angle>?",
+    ),
+    longhand("font-variant", true, "normal", "normal | small-caps"),
+    longhand(
+        "font-variation-settings",
+        true,
+        "normal",
+        "normal | [<string> <number>]#",
+    ),
+    longhand("font-weight", true, "normal", "<font-weight>"),
+    shorthand("gap", false),
+    longhand("glyph-orientation-horizontal", true, "0deg", "<angle>"),
+    longhand(
+        "glyph-orientation-vertical",
+        true,
+        "auto",
+        "auto | <angle> | <number>",
+    ),
+    longhand("height", false, "auto", "<length-percentage [0,∞]> | auto"),
+    longhand(
+        "image-rendering",
+        true,
+        "auto",
+        "auto | optimizeSpeed | optimizeQuality | smooth | high-quality | crisp-edges | \
+         pixelated",
+    ),
+    shorthand("inset", false),
+    longhand("isolation", false, "auto", "auto | isolate"),
+    longhand("kerning", true, "auto", "auto | <length>"),
+    longhand("left", false, "auto", "<length-percentage> | auto"),
+    longhand("letter-spacing", true, "normal", "normal | <length>"),
+    longhand("lighting-color", false, "white", "<color>"),
+    longhand(
+        "line-height",
+        true,
+        "normal",
+        "normal | <number [0,∞]> | <length-percentage [0,∞]>",
+    ),
+    shorthand("list-style", true),
+    longhand("list-style-image", true, "none", "<image> | none"),
+    longhand("list-style-position", true, "outside", "inside | outside"),
+    longhand(
+        "list-style-type",
+        true,
+        "disc",
+        "<custom-ident> | <string> | none",
+    ),
+    shorthand("margin", false),
+    longhand("margin-bottom", false, "0", "<length-percentage> | auto"),
+    longhand("margin-left", false, "0", "<length-percentage> | auto"),
+    longhand("margin-right", false, "0", "<length-percentage> | auto"),
+    longhand("margin-top", false, "0", "<length-percentage> | auto"),
+    longhand("marker", true, "none", "none | <url>"),
+    longhand("marker-end", true, "none", "none | <url>"),
+    longhand("marker-mid", true, "none", "none | <url>"),
+    longhand("marker-start", true, "none", "none | <url>"),
+    longhand("mask", false, "none", "none | <url>"),
+    longhand("mask-type", false, "luminance", "luminance | alpha"),
+    longhand(
+        "max-height",
+        false,
+        "none",
+        "<length-percentage [0,∞]> | none",
+    ),
+    longhand(
+        "max-width",
+        false,
+        "none",
+        "<length-percentage [0,∞]> | none",
+    ),
+    longhand(
+        "min-height",
+        false,
+        "auto",
+        "<length-percentage [0,∞]> | auto",
+    ),
+    longhand(
+        "min-width",
+        false,
+        "auto",
+        "<length-percentage [0,∞]> | auto",
+    ),
+    longhand("mix-blend-mode", false, "normal", "<blend-mode>"),
+    longhand("opacity", false, "1", "<alpha-value>"),
+    longhand("orphans", true, "2", "<integer [1,∞]>"),
+    shorthand("outline", false),
+    longhand(
+        "outline-color",
+        false,
+        "currentcolor",
+        "<color> | invert | auto",
+    ),
+    longhand("outline-style", false, "none", "auto | <line-style>"),
+    longhand("outline-width", false, "medium", "<line-width>"),
+    longhand(
+        "overflow",
+        false,
+        "visible",
+        "[visible | hidden | clip | scroll | auto]{1,2}",
+    ),
+    shorthand("padding", false),
+    longhand("padding-bottom", false, "0", "<length-percentage [0,∞]>"),
+    longhand("padding-left", false, "0", "<length-percentage [0,∞]>"),
+    longhand("padding-right", false, "0", "<length-percentage [0,∞]>"),
+    longhand("padding-top", false, "0", "<length-percentage [0,∞]>"),
+    longhand(
+        "page-break-after",
+        false,
+        "auto",
+        "auto | always | avoid | left | right",
+    ),
+    longhand(
+        "page-break-before",
+        false,
+        "auto",
+        "auto | always | avoid | left | right",
+    ),
+    longhand("page-break-inside", false, "auto", "auto | avoid"),
+    longhand(
+        "paint-order",
+        true,
+        "normal",
+        "normal | [fill || stroke || markers]",
+    ),
+    longhand(
+        "pause",
+        false,
+        "0s",
+        "[<time [0,∞]> | <percentage [0,∞]>]{1,2}",
+    ),
+    longhand(
+        "pause-after",
+        false,
+        "0s",
+        "<time [0,∞]> | <percentage [0,∞]>",
+    ),
+    longhand(
+        "pause-before",
+        false,
+        "0s",
+        "<time [0,∞]> | <percentage [0,∞]>",
+    ),
+    longhand(
+        "pitch",
+        true,
+        "medium",
+        "<frequency> | x-low | low | medium | high | x-high",
+    ),
+    longhand("pitch-range", true, "50", "<number>"),
+    longhand(
+        "play-during",
+        false,
+        "auto",
+        "<url> [mix || repeat]? | auto | none",
+    ),
+    longhand(
+        "pointer-events",
+        true,
+        "auto",
+        "auto | bounding-box | visiblePainted | visibleFill | visibleStroke | visible | \
+         painted | fill | stroke | all | none",
+    ),
+    longhand(
+        "position",
+        false,
+        "static",
+        "static | relative | absolute | fixed | sticky",
+    ),
+    longhand("quotes", true, "auto", "none | auto | [<string> <string>]+"),
+    longhand("r", false, "0", "<length-percentage [0,∞]>"),
+    longhand("richness", true, "50", "<number>"),
+    longhand("right", false, "auto", "<length-percentage> | auto"),
+    longhand(
+        "row-gap",
+        false,
+        "normal",
+        "normal | <length-percentage [0,∞]>",
+    ),
+    longhand("rx", false, "auto", "<length-percentage [0,∞]> | auto"),
+    longhand("ry", false, "auto", "<length-percentage [0,∞]> | auto"),
+    longhand(
+        "shape-rendering",
+        true,
+        "auto",
+        "auto | optimizeSpeed | crispEdges | geometricPrecision",
+    ),
+    longhand("speak", true, "normal", "normal | none | spell-out"),
+    longhand("speak-header", true, "once", "once | always"),
+    longhand("speak-numeral", true, "continuous", "digits | continuous"),
+    longhand("speak-punctuation", true, "none", "code | none"),
+    longhand(
+        "speech-rate",
+        true,
+        "medium",
+        "<number> | x-slow | slow | medium | fast | x-fast | faster | slower",
+    ),
+    longhand("stop-color", false, "black", "<color>"),
+    longhand("stop-opacity", false, "1", "<alpha-value>"),
+    longhand("stress", true, "50", "<number>"),
+    longhand("stroke", true, "none", "<paint>"),
+    longhand(
+        "stroke-dasharray",
+        true,
+        "none",
+        "none | [<length-percentage [0,∞]> | <number [0,∞]>] \
+         [,? [<length-percentage [0,∞]> | <number [0,∞]>]]*",
+    ),
+    longhand(
+        "stroke-dashoffset",
+        true,
+        "0",
+        "<length-percentage> | <number>",
+    ),
+    longhand("stroke-linecap", true, "butt", "butt | round | square"),
+    longhand(
+        "stroke-linejoin",
+        true,
+        "miter",
+        "miter | miter-clip | round | bevel | arcs",
+    ),
+    longhand("stroke-miterlimit", true, "4", "<number [1,∞]>"),
+    longhand("stroke-opacity", true, "1", "<alpha-value>"),
+    longhand(
+        "stroke-width",
+        true,
+        "1px",
+        "<length-percentage [0,∞]> | <number [0,∞]>",
+    ),
+    longhand("table-layout", false, "auto", "auto | fixed"),
+    longhand(
+        "text-align",
+        true,
+        "start",
+        "left | right | center | justify | start | end | match-parent",
+    ),
+    longhand("text-anchor", true, "start", "start | middle | end"),
+    shorthand("text-decoration", false),
+    longhand("text-decoration-color", false, "currentcolor", "<color>"),
+    longhand(
+        "text-decoration-line",
+        false,
+        "none",
+        "none | [underline || overline || line-through || blink]",
+    ),
+    longhand(
+        "text-decoration-style",
+        false,
+        "solid",
+        "solid | double | dotted | dashed | wavy",
+    ),
+    longhand(
+        "text-decoration-thickness",
+        false,
+        "auto",
+        "auto | from-font | <length-percentage>",
+    ),
+    longhand("text-indent", true, "0", "<length-percentage>"),
+    longhand(
+        "text-rendering",
+        true,
+        "auto",
+        "auto | optimizeSpeed | optimizeLegibility | geometricPrecision",
+    ),
+    longhand(
+        "text-transform",
+        true,
+        "none",
+        "none | capitalize | uppercase | lowercase | full-width",
+    ),
+    longhand("top", false, "auto", "<length-percentage> | auto"),
+    longhand("transform", false, "none", "none | <transform-function>+"),
+    longhand(
+        "transform-box",
+        false,
+        "view-box",
+        "content-box | border-box | fill-box | stroke-box | view-box",
+    ),
+    longhand("transform-origin", false, "50% 50% 0", "<transform-origin>"),
+    shorthand("transition", false),
+    longhand(
+        "transition-behavior",
+        false,
+        "normal",
+        "[normal | allow-discrete]#",
+    ),
+    longhand("transition-delay", false, "0s", "<time>#"),
+    longhand("transition-duration", false, "0s", "<time [0,∞]>#"),
+    longhand(
+        "transition-property",
+        false,
+        "all",
+        "none | [all | <custom-ident>]#",
+    ),
+    longhand(
+        "transition-timing-function",
+        false,
+        "ease",
+        "<easing-function>#",
+    ),
+    longhand(
+        "unicode-bidi",
+        false,
+        "normal",
+        "normal | embed | isolate | bidi-override | isolate-override | plaintext",
+    ),
+    longhand(
+        "vector-effect",
+        false,
+        "none",
+        "none | non-scaling-stroke | non-scaling-size | non-rotation | fixed-position",
+    ),
+    longhand(
+        "vertical-align",
+        false,
+        "baseline",
+        "baseline | sub | super | top | text-top | middle | bottom | text-bottom | \
+         <length-percentage>",
+    ),
+    longhand("visibility", true, "visible", "visible | hidden | collapse"),
+    longhand("voice-family", true, "female", "<font-family>"),
+    longhand(
+        "volume",
+        true,
+        "medium",
+        "<number> | <percentage> | silent | x-soft | soft | medium | loud | x-loud",
+    ),
+    longhand(
+        "white-space",
+        true,
+        "normal",
+        "normal | pre | nowrap | pre-wrap | pre-line | break-spaces",
+    ),
+    longhand("widows", true, "2", "<integer [1,∞]>"),
+    longhand("width", false, "auto", "<length-percentage [0,∞]> | auto"),
+    longhand("word-spacing", true, "normal", "normal | <length>"),
+    longhand(
+        "writing-mode",
+        true,
+        "horizontal-tb",
+        "horizontal-tb | vertical-rl | vertical-lr | sideways-rl | sideways-lr | lr-tb | \
+         rl-tb | tb-rl | lr | rl | tb",
+    ),
+    longhand("x", false, "0", "<length-percentage>"),
+    longhand("y", false, "0", "<length-percentage>"),
+    longhand("z-index", false, "auto", "auto | <integer>"),
+];
+
+const fn longhand(
+    name: &'static str,
+    inherited: bool,
+    initial: &'static str,
+    grammar: &'static str,
+) -> Property {
+    Property {
+        name,
+        inherited,
+        initial,
+        syntax: PropertySyntax::Grammar(grammar),
+    }
+}
+
+const fn shorthand(name: &'static str, inherited: bool) -> Property {
+    Property {
+        name,
+        inherited,
+        initial: "",
+        syntax: PropertySyntax::Shorthand,
+    }
+}
@@ -1,35 +1,39 @@
 // Copyright 2019 the SimpleCSS Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use alloc::{vec, vec::Vec};
+use alloc::{borrow::Cow, vec, vec::Vec};
 use core::fmt;
 
 use log::warn;
 
-use crate::stream::Stream;
-use crate::Error;
+use crate::line_index::LazyLineIndex;
+use crate::stream::{write_ident, write_string, Stream};
+use crate::{Error, Span};
 
 /// An attribute selector operator.
-#[derive(Clone, Copy, PartialEq, Debug)]
+///
+/// Values are unescaped.
+#[derive(Clone, PartialEq, Debug)]
 pub enum AttributeOperator<'a> {
     /// `[attr]`
     Exists,
     /// `[attr=value]`
-    Matches(&'a str),
+    Matches(Cow<'a, str>),
     /// `[attr~=value]`
-    Contains(&'a str),
+    Contains(Cow<'a, str>),
     /// `[attr|=value]`
-    StartsWith(&'a str),
+    StartsWith(Cow<'a, str>),
 }
 
 impl AttributeOperator<'_> {
     /// Checks that value is matching the operator.
     pub fn matches(&self, value: &str) -> bool {
-        match *self {
+        match self {
             AttributeOperator::Exists => true,
             AttributeOperator::Matches(v) => value == v,
             AttributeOperator::Contains(v) => value.split(' ').any(|s| s == v),
             AttributeOperator::StartsWith(v) => {
+                let v = v.as_ref();
                 // exactly `v` or beginning with `v` immediately followed by `-`
                 if value == v {
                     true
@@ -88,18 +92,31 @@ pub trait Element: Sized {
     fn pseudo_class_matches(&self, class: PseudoClass<'_>) -> bool;
 }
 
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 enum SimpleSelectorType<'a> {
-    Type(&'a str),
+    Type(Cow<'a, str>),
     Universal,
 }
 
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 enum SubSelector<'a> {
-    Attribute(&'a str, AttributeOperator<'a>),
+    Id(Cow<'a, str>),
+    Class(Cow<'a, str>),
+    Attribute(Cow<'a, str>, AttributeOperator<'a>),
     PseudoClass(PseudoClass<'a>),
 }
 
+impl AttributeOperator<'_> {
+    fn borrowed(&self) -> AttributeOperator<'_> {
+        match self {
+            AttributeOperator::Exists => AttributeOperator::Exists,
+            AttributeOperator::Matches(v) => AttributeOperator::Matches(Cow::Borrowed(v)),
+            AttributeOperator::Contains(v) => AttributeOperator::Contains(Cow::Borrowed(v)),
+            AttributeOperator::StartsWith(v) => AttributeOperator::StartsWith(Cow::Borrowed(v)),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 struct SimpleSelector<'a> {
     kind: SimpleSelectorType<'a>,
@@ -122,9 +139,16 @@ pub(crate) struct Component<'a> {
 }
 
 /// A selector.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 pub struct Selector<'a> {
     pub(crate) components: Vec<Component<'a>>,
+    span: Span,
+}
+
+impl PartialEq for Selector<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.components == other.components
+    }
 }
 
 impl<'a> Selector<'a> {
@@ -134,7 +158,12 @@ impl<'a> Selector<'a> {
     ///
     /// Parsing will be stopped at EOF, `,` or `{`.
     pub fn parse(text: &'a str) -> Option<Self> {
-        parse(text).0
+        parse(&mut Stream::from(text), &LazyLineIndex::new(text))
+    }
+
+    /// Returns the selector span, without surrounding whitespace and comments.
+    pub fn span(&self) -> Span {
+        self.span
     }
 
     /// Compute the selector's specificity.
@@ -150,7 +179,7 @@ impl<'a> Selector<'a> {
 
             for sub in &selector.subselectors {
                 match sub {
-                    SubSelector::Attribute("id", _) => spec[0] = spec[0].saturating_add(1),
+                    SubSelector::Id(_) => spec[0] = spec[0].saturating_add(1),
                     _ => spec[1] = spec[1].saturating_add(1),
                 }
             }
@@ -215,7 +244,7 @@ impl<'a> Selector<'a> {
 }
 
 fn match_selector<E: Element>(selector: &SimpleSelector<'_>, element: &E) -> bool {
-    if let SimpleSelectorType::Type(ident) = selector.kind {
+    if let SimpleSelectorType::Type(ref ident) = selector.kind {
         if !element.has_local_name(ident) {
             return false;
         }
@@ -223,8 +252,20 @@ fn match_selector<E: Element>(selector: &SimpleSelector<'_>, element: &E) -> boo
 
     for sub in &selector.subselectors {
         match sub {
-            SubSelector::Attribute(name, operator) => {
-                if !element.attribute_matches(name, *operator) {
+            SubSelector::Id(id) => {
+                let operator = AttributeOperator::Matches(Cow::Borrowed(id));
+                if !element.attribute_matches("id", operator) {
+                    return false;
+                }
+            }
+            SubSelector::Class(class) => {
+                let operator = AttributeOperator::Contains(Cow::Borrowed(class));
+                if !element.attribute_matches("class", operator) {
+                    return false;
+                }
+            }
+            SubSelector::Attribute(name, value) => {
+                if !element.attribute_matches(name, value.borrowed()) {
                     return false;
                 }
             }
@@ -239,12 +280,31 @@ fn match_selector<E: Element>(selector: &SimpleSelector<'_>, element: &E) -> boo
     true
 }
 
-pub(crate) fn parse(text: &str) -> (Option<Selector<'_>>, usize) {
+/// Parses a selector, advancing the stream to where the parsing has stopped.
+///
+/// Error positions are resolved by `lines`.
+pub(crate) fn parse<'a>(s: &mut Stream<'a>, lines: &LazyLineIndex<'_>) -> Option<Selector<'a>> {
+    let mut tokenizer = SelectorTokenizer::from_stream(*s);
+    let selector = parse_impl(&mut tokenizer, lines);
+    *s = tokenizer.stream;
+    selector
+}
+
+fn parse_impl<'a>(
+    tokenizer: &mut SelectorTokenizer<'a>,
+    lines: &LazyLineIndex<'_>,
+) -> Option<Selector<'a>> {
     let mut components: Vec<Component<'_>> = Vec::new();
     let mut combinator = Combinator::None;
 
-    let mut tokenizer = SelectorTokenizer::from(text);
-    for token in &mut tokenizer {
+    let start = {
+        let mut s = tokenizer.stream;
+        let _ = s.skip_spaces_and_comments();
+        s.pos()
+    };
+    let mut end = start;
+
+    while let Some(token) = tokenizer.next() {
         let mut add_sub = |sub| {
             if combinator == Combinator::None && !components.is_empty() {
                 if let Some(ref mut component) = components.last_mut() {
@@ -266,11 +326,15 @@ pub(crate) fn parse(text: &str) -> (Option<Selector<'_>>, usize) {
         let token = match token {
             Ok(t) => t,
             Err(e) => {
-                warn!("Selector parsing failed cause {}.", e);
-                return (None, tokenizer.stream.pos());
+                warn!("Selector parsing failed cause {}.", e.resolve(lines));
+                return None;
             }
         };
 
+        if !matches!(token, SelectorToken::DescendantCombinator) {
+            end = tokenizer.stream.pos();
+        }
+
         match token {
             SelectorToken::UniversalSelector => {
                 components.push(Component {
@@ -295,19 +359,16 @@ pub(crate) fn parse(text: &str) -> (Option<Selector<'_>>, usize) {
                 combinator = Combinator::None;
             }
             SelectorToken::ClassSelector(ident) => {
-                add_sub(SubSelector::Attribute(
-                    "class",
-                    AttributeOperator::Contains(ident),
-                ));
+                add_sub(SubSelector::Class(ident));
             }
             SelectorToken::IdSelector(id) => {
-                add_sub(SubSelector::Attribute("id", AttributeOperator::Matches(id)));
+                add_sub(SubSelector::Id(id));
             }
             SelectorToken::AttributeSelector(name, op) => {
                 add_sub(SubSelector::Attribute(name, op));
             }
             SelectorToken::PseudoClass(ident) => {
-                let class = match ident {
+                let class = match ident.to_ascii_lowercase().as_str() {
                     "first-child" => PseudoClass::FirstChild,
                     "link" => PseudoClass::Link,
                     "visited" => PseudoClass::Visited,
@@ -316,7 +377,7 @@ pub(crate) fn parse(text: &str) -> (Option<Selector<'_>>, usize) {
                     "focus" => PseudoClass::Focus,
                     _ => {
                         warn!("':{}' is not supported. Selector skipped.", ident);
-                        return (None, tokenizer.stream.pos());
+                        return None;
                     }
                 };
 
@@ -341,7 +402,7 @@ pub(crate) fn parse(text: &str) -> (Option<Selector<'_>>, usize) {
     }
 
     if components.is_empty() {
-        (None, tokenizer.stream.pos())
+        None
     } else if components[0].combinator != Combinator::None {
         debug_assert_eq!(
             components[0].combinator,
@@ -349,12 +410,86 @@ pub(crate) fn parse(text: &str) -> (Option<Selector<'_>>, usize) {
             "the first component must not have a combinator"
         );
 
-        (None, tokenizer.stream.pos())
+        None
     } else {
-        (Some(Selector { components }), tokenizer.stream.pos())
+        Some(Selector {
+            components,
+            span: Span::new(start, end),
+        })
     }
 }
 
+impl Selector<'_> {
+    /// Moves the selector into `text`, where the same selector is at `span`.
+    ///
+    /// Borrowed names are borrowed from `text`, unescaped ones are cloned.
+    pub(crate) fn rebase<'b>(&self, text: &'b str, span: Span) -> Option<Selector<'b>> {
+        let source = text.get(span.start..span.end)?;
+        let mut components = Vec::with_capacity(self.components.len());
+        for component in &self.components {
+            let kind = match &component.selector.kind {
+                SimpleSelectorType::Type(name) => {
+                    SimpleSelectorType::Type(rebase(source, name.clone())?)
+                }
+                SimpleSelectorType::Universal => SimpleSelectorType::Universal,
+            };
+
+            let mut subselectors = Vec::with_capacity(component.selector.subselectors.len());
+            for sub in &component.selector.subselectors {
+                subselectors.push(match sub {
+                    SubSelector::Id(id) => SubSelector::Id(rebase(source, id.clone())?),
+                    SubSelector::Class(class) => SubSelector::Class(rebase(source, class.clone())?),
+                    SubSelector::Attribute(name, operator) => {
+                        let operator = match operator {
+                            AttributeOperator::Exists => AttributeOperator::Exists,
+                            AttributeOperator::Matches(v) => {
+                                AttributeOperator::Matches(rebase(source, v.clone())?)
+                            }
+                            AttributeOperator::Contains(v) => {
+                                AttributeOperator::Contains(rebase(source, v.clone())?)
+                            }
+                            AttributeOperator::StartsWith(v) => {
+                                AttributeOperator::StartsWith(rebase(source, v.clone())?)
+                            }
+                        };
+                        SubSelector::Attribute(rebase(source, name.clone())?, operator)
+                    }
+                    SubSelector::PseudoClass(class) => SubSelector::PseudoClass(match *class {
+                        PseudoClass::FirstChild => PseudoClass::FirstChild,
+                        PseudoClass::Link => PseudoClass::Link,
+                        PseudoClass::Visited => PseudoClass::Visited,
+                        PseudoClass::Hover => PseudoClass::Hover,
+                        PseudoClass::Active => PseudoClass::Active,
+                        PseudoClass::Focus => PseudoClass::Focus,
+                        PseudoClass::Lang(lang) => PseudoClass::Lang(find(source, lang)?),
+                    }),
+                });
+            }
+
+            components.push(Component {
+                combinator: component.combinator,
+                selector: SimpleSelector { kind, subselectors },
+            });
+        }
+
+        Some(Selector { components, span })
+    }
+}
+
+/// Borrows a string equal to `value` from `source` or keeps an owned `value`.
+fn rebase<'b>(source: &'b str, value: Cow<'_, str>) -> Option<Cow<'b, str>> {
+    match value {
+        Cow::Borrowed(v) => find(source, v).map(Cow::Borrowed),
+        Cow::Owned(v) => Some(Cow::Owned(v)),
+    }
+}
+
+/// Finds a string equal to `value` in `source`.
+fn find<'b>(source: &'b str, value: &str) -> Option<&'b str> {
+    let start = source.find(value)?;
+    source.get(start..start + value.len())
+}
+
 impl fmt::Display for Selector<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for component in &self.components {
@@ -366,27 +501,38 @@ impl fmt::Display for Selector<'_> {
             }
 
             match component.selector.kind {
+                // `*` is implied before subselectors.
+                SimpleSelectorType::Universal if !component.selector.subselectors.is_empty() => {}
                 SimpleSelectorType::Universal => write!(f, "*")?,
-                SimpleSelectorType::Type(ident) => write!(f, "{}", ident)?,
+                SimpleSelectorType::Type(ref ident) => write_ident(f, ident)?,
             };
 
             for sel in &component.selector.subselectors {
                 match sel {
+                    SubSelector::Id(id) => {
+                        write!(f, "#")?;
+                        write_ident(f, id)?;
+                    }
+                    SubSelector::Class(class) => {
+                        write!(f, ".")?;
+                        write_ident(f, class)?;
+                    }
                     SubSelector::Attribute(name, operator) => {
-                        match operator {
-                            AttributeOperator::Exists => {
-                                write!(f, "[{}]", name)?;
-                            }
-                            AttributeOperator::Matches(value) => {
-                                write!(f, "[{}='{}']", name, value)?;
-                            }
-                            AttributeOperator::Contains(value) => {
-                                write!(f, "[{}~='{}']", name, value)?;
-                            }
-                            AttributeOperator::StartsWith(value) => {
-                                write!(f, "[{}|='{}']", name, value)?;
-                            }
+                        write!(f, "[")?;
+                        write_ident(f, name)?;
+                        let value = match operator {
+                            AttributeOperator::Exists => None,
+                            AttributeOperator::Matches(value) => Some(("=", value)),
+                            AttributeOperator::Contains(value) => Some(("~=", value)),
+                            AttributeOperator::StartsWith(value) => Some(("|=", value)),
                         };
+
+                        if let Some((op, value)) = value {
+                            write!(f, "{}", op)?;
+                            write_string(f, value)?;
+                        }
+
+                        write!(f, "]")?;
                     }
                     SubSelector::PseudoClass(class) => write!(f, ":{}", class)?,
                 }
@@ -398,25 +544,27 @@ impl fmt::Display for Selector<'_> {
 }
 
 /// A selector token.
-#[derive(Clone, Copy, PartialEq, Debug)]
+///
+/// Idents and strings are unescaped.
+#[derive(Clone, PartialEq, Debug)]
 pub enum SelectorToken<'a> {
     /// `*`
     UniversalSelector,
 
     /// `div`
-    TypeSelector(&'a str),
+    TypeSelector(Cow<'a, str>),
 
     /// `.class`
-    ClassSelector(&'a str),
+    ClassSelector(Cow<'a, str>),
 
     /// `#id`
-    IdSelector(&'a str),
+    IdSelector(Cow<'a, str>),
 
     /// `[color=red]`
-    AttributeSelector(&'a str, AttributeOperator<'a>),
+    AttributeSelector(Cow<'a, str>, AttributeOperator<'a>),
 
     /// `:first-child`
-    PseudoClass(&'a str),
+    PseudoClass(Cow<'a, str>),
 
     /// `:lang(en)`
     LangPseudoClass(&'a str),
@@ -439,10 +587,10 @@ pub enum SelectorToken<'a> {
 /// use simplecss::{SelectorTokenizer, SelectorToken};
 ///
 /// let mut t = SelectorTokenizer::from("div > p:first-child");
-/// assert_eq!(t.next().unwrap().unwrap(), SelectorToken::TypeSelector("div"));
+/// assert_eq!(t.next().unwrap().unwrap(), SelectorToken::TypeSelector("div".into()));
 /// assert_eq!(t.next().unwrap().unwrap(), SelectorToken::ChildCombinator);
-/// assert_eq!(t.next().unwrap().unwrap(), SelectorToken::TypeSelector("p"));
-/// assert_eq!(t.next().unwrap().unwrap(), SelectorToken::PseudoClass("first-child"));
+/// assert_eq!(t.next().unwrap().unwrap(), SelectorToken::TypeSelector("p".into()));
+/// assert_eq!(t.next().unwrap().unwrap(), SelectorToken::PseudoClass("first-child".into()));
 /// assert!(t.next().is_none());
 /// ```
 pub struct SelectorTokenizer<'a> {
@@ -453,8 +601,14 @@ pub struct SelectorTokenizer<'a> {
 
 impl<'a> From<&'a str> for SelectorTokenizer<'a> {
     fn from(text: &'a str) -> Self {
+        SelectorTokenizer::from_stream(Stream::from(text))
+    }
+}
+
+impl<'a> SelectorTokenizer<'a> {
+    pub(crate) fn from_stream(stream: Stream<'a>) -> Self {
         SelectorTokenizer {
-            stream: Stream::from(text),
+            stream,
             after_combinator: true,
             finished: false,
         }
@@ -548,7 +702,7 @@ impl<'a> Iterator for SelectorTokenizer<'a> {
                 self.stream.advance(1);
                 let ident = try2!(self.stream.consume_ident());
 
-                if ident == "lang" {
+                if ident.eq_ignore_ascii_case("lang") {
                     try2!(self.stream.consume_byte(b'('));
                     let lang = self.stream.consume_bytes(|c| c != b')').trim();
                     try2!(self.stream.consume_byte(b')'));
@@ -625,12 +779,11 @@ impl<'a> Iterator for SelectorTokenizer<'a> {
                 self.next()
             }
             _ => {
-
-                // some at rules have weird idents... 
-                #[cfg(feature="at_rules")]
+                // some at rules have weird idents...
+                #[cfg(feature = "at_rules")]
                 let ident = try2!(self.stream.consume_ident_special());
-                
-                #[cfg(not(feature="at_rules"))]
+
+                #[cfg(not(feature = "at_rules"))]
                 let ident = try2!(self.stream.consume_ident());
 
                 if !self.after_combinator {
@@ -0,0 +1,1276 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+use crate::calc::{CalcNode, CalcType};
+use crate::stream::{write_ident, Stream};
+use crate::value::{ComponentValue, Value};
+use crate::variables::has_substitutions;
+use crate::{skip_component_values, Color, Declaration, Length, Span};
+
+/// A longhand declaration produced by [`Declaration::expand`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Longhand<'a> {
+    /// The property name, in lowercase.
+    pub name: &'static str,
+    /// The property value.
+    ///
+    /// Borrowed from the shorthand value when possible.
+    pub value: Cow<'a, str>,
+    /// Whether the shorthand was marked as `!important`.
+    pub important: bool,
+}
+
+/// A declaration produced by [`contract`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ContractedDeclaration<'a> {
+    /// The property name, unescaped.
+    pub name: Cow<'a, str>,
+    /// The property value.
+    ///
+    /// Borrowed from the original declaration, unless it is a new shorthand.
+    pub value: Cow<'a, str>,
+    /// Whether the declaration is marked as `!important`.
+    pub important: bool,
+}
+
+impl fmt::Display for ContractedDeclaration<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_ident(f, &self.name)?;
+        write!(f, ":{}", self.value)?;
+        if self.important {
+            write!(f, "!important")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the longhands of a shorthand property, ignoring ASCII case.
+///
+/// Only shorthands supported by [`Declaration::expand`] are listed.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(simplecss::longhands("gap"), Some(&["row-gap", "column-gap"][..]));
+/// assert_eq!(simplecss::longhands("color"), None);
+/// ```
+pub fn longhands(shorthand: &str) -> Option<&'static [&'static str]> {
+    SHORTHANDS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(shorthand))
+        .map(|(_, longhands)| *longhands)
+}
+
+static SHORTHANDS: &[(&str, &[&str])] = &[
+    (
+        "animation",
+        &[
+            "animation-name",
+            "animation-duration",
+            "animation-timing-function",
+            "animation-delay",
+            "animation-iteration-count",
+            "animation-direction",
+            "animation-fill-mode",
+            "animation-play-state",
+        ],
+    ),
+    (
+        "background",
+        &[
+            "background-image",
+            "background-position",
+            "background-size",
+            "background-repeat",
+            "background-attachment",
+            "background-origin",
+            "background-clip",
+            "background-color",
+        ],
+    ),
+    (
+        "border",
+        &[
+            "border-top-width",
+            "border-right-width",
+            "border-bottom-width",
+            "border-left-width",
+            "border-top-style",
+            "border-right-style",
+            "border-bottom-style",
+            "border-left-style",
+            "border-top-color",
+            "border-right-color",
+            "border-bottom-color",
+            "border-left-color",
+        ],
+    ),
+    (
+        "border-bottom",
+        &[
+            "border-bottom-width",
+            "border-bottom-style",
+            "border-bottom-color",
+        ],
+    ),
+    (
+        "border-color",
+        &[
+            "border-top-color",
+            "border-right-color",
+            "border-bottom-color",
+            "border-left-color",
+        ],
+    ),
+    (
+        "border-left",
+        &[
+            "border-left-width",
+            "border-left-style",
+            "border-left-color",
+        ],
+    ),
+    (
+        "border-radius",
+        &[
+            "border-top-left-radius",
+            "border-top-right-radius",
+            "border-bottom-right-radius",
+            "border-bottom-left-radius",
+        ],
+    ),
+    (
+        "border-right",
+        &[
+            "border-right-width",
+            "border-right-style",
+            "border-right-color",
+        ],
+    ),
+    (
+        "border-style",
+        &[
+            "border-top-style",
+            "border-right-style",
+            "border-bottom-style",
+            "border-left-style",
+        ],
+    ),
+    (
+        "border-top",
+        &["border-top-width", "border-top-style", "border-top-color"],
+    ),
+    (
+        "border-width",
+        &[
+            "border-top-width",
+            "border-right-width",
+            "border-bottom-width",
+            "border-left-width",
+        ],
+    ),
+    ("flex", &["flex-grow", "flex-shrink", "flex-basis"]),
+    (
+        "font",
+        &[
+            "font-style",
+            "font-variant",
+            "font-weight",
+            "font-stretch",
+            "font-size",
+            "line-height",
+            "font-family",
+        ],
+    ),
+    ("gap", &["row-gap", "column-gap"]),
+    ("inset", &["top", "right", "bottom", "left"]),
+    (
+        "list-style",
+        &["list-style-position", "list-style-image", "list-style-type"],
+    ),
+    (
+        "margin",
+        &["margin-top", "margin-right", "margin-bottom", "margin-left"],
+    ),
+    (
+        "outline",
+        &["outline-width", "outline-style", "outline-color"],
+    ),
+    (
+        "padding",
+        &[
+            "padding-top",
+            "padding-right",
+            "padding-bottom",
+            "padding-left",
+        ],
+    ),
+    (
+        "text-decoration",
+        &[
+            "text-decoration-line",
+            "text-decoration-style",
+            "text-decoration-color",
+            "text-decoration-thickness",
+        ],
+    ),
+    (
+        "transition",
+        &[
+            "transition-property",
+            "transition-duration",
+            "transition-timing-function",
+            "transition-delay",
+            "transition-behavior",
+        ],
+    ),
+];
+
+const WIDE_KEYWORDS: &[&str] = &["inherit", "initial", "unset", "revert", "revert-layer"];
+const LINE_STYLES: &[&str] = &[
+    "none", "hidden", "dotted", "dashed", "solid", "double", "groove", "ridge", "inset", "outset",
+];
+const OUTLINE_STYLES: &[&str] = &[
+    "auto", "none", "dotted", "dashed", "solid", "double", "groove", "ridge", "inset", "outset",
+];
+const LINE_WIDTHS: &[&str] = &["thin", "medium", "thick"];
+const EASING_KEYWORDS: &[&str] = &[
+    "linear",
+    "ease",
+    "ease-in",
+    "ease-out",
+    "ease-in-out",
+    "step-start",
+    "step-end",
+];
+const EASING_FUNCTIONS: &[&str] = &["linear", "cubic-bezier", "steps"];
+
+pub(crate) fn expand<'a>(declaration: &Declaration<'a>) -> Option<Vec<Longhand<'a>>> {
+    let (shorthand, names) = SHORTHANDS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(&declaration.name))?;
+
+    let tokens = Tokens::parse(declaration.value)?;
+    if tokens.is_empty() {
+        return None;
+    }
+
+    let values = if tokens.len() == 1 && is_keyword(&tokens.components[0], WIDE_KEYWORDS) {
+        vec![tokens.range(0..1); names.len()]
+    } else {
+        match *shorthand {
+            "margin" | "inset" => tokens.sides(|c| is_length(c) || c.is_ident("auto"))?,
+            "padding" => tokens.sides(is_length)?,
+            "gap" => tokens.gap()?,
+            "border-width" => tokens.sides(is_line_width)?,
+            "border-style" => tokens.sides(|c| is_keyword(c, LINE_STYLES))?,
+            "border-color" => tokens.sides(is_color)?,
+            "border" => {
+                let mut values = Vec::with_capacity(names.len());
+                for value in tokens.border_side(LINE_STYLES, "currentcolor")? {
+                    values.extend([value.clone(), value.clone(), value.clone(), value]);
+                }
+                values
+            }
+            "border-top" | "border-right" | "border-bottom" | "border-left" => {
+                tokens.border_side(LINE_STYLES, "currentcolor")?.to_vec()
+            }
+            "outline" => tokens.border_side(OUTLINE_STYLES, "auto")?.to_vec(),
+            "border-radius" => tokens.border_radius()?,
+            "flex" => tokens.flex()?,
+            "font" => tokens.font()?,
+            "list-style" => tokens.list_style()?,
+            "text-decoration" => tokens.text_decoration()?,
+            "background" => tokens.background()?,
+            "transition" => tokens.layers(names.len(), Tokens::transition)?,
+            "animation" => tokens.layers(names.len(), Tokens::animation)?,
+            _ => return None,
+        }
+    };
+
+    debug_assert_eq!(values.len(), names.len());
+
+    let longhands = names
+        .iter()
+        .zip(values)
+        .map(|(name, value)| Longhand {
+            name,
+            value,
+            important: declaration.important,
+        })
+        .collect();
+
+    Some(longhands)
+}
+
+/// Shorthands in the order they are tried by [`contract`].
+///
+/// Larger shorthands go first, so all of the border longhands become a single `border`.
+const CONTRACTIONS: &[&str] = &[
+    "border",
+    "border-width",
+    "border-style",
+    "border-color",
+    "border-top",
+    "border-right",
+    "border-bottom",
+    "border-left",
+    "border-radius",
+    "margin",
+    "padding",
+    "inset",
+    "gap",
+    "outline",
+];
+
+/// Merges longhands into shorthands, like `margin-top`, `margin-right`, `margin-bottom`
+/// and `margin-left` into `margin`.
+///
+/// Longhands are merged only when all of them are present exactly once, have the same
+/// `!important` flag and no declaration between them affects the same properties.
+/// The shorthand takes the place of the last longhand, so the cascade order is preserved.
+/// The shortest shorthand value is used, like `margin: 0 auto`.
+///
+/// Supports `margin`, `padding`, `inset`, `gap`, `outline`, `border-radius`
+/// and all of the `border` shorthands.
+///
+/// # Example
+///
+/// ```
+/// use simplecss::{contract, DeclarationTokenizer};
+///
+/// let text = "margin-top: 0; color: red; margin-right: 1px; margin-bottom: 0; margin-left: 1px";
+/// let declarations: Vec<_> = DeclarationTokenizer::from(text).collect();
+/// let contracted: Vec<_> = contract(&declarations).iter().map(|d| d.to_string()).collect();
+/// assert_eq!(contracted, ["color:red", "margin:0 1px"]);
+/// ```
+pub fn contract<'a>(declarations: &[Declaration<'a>]) -> Vec<ContractedDeclaration<'a>> {
+    let mut list: Vec<_> = declarations
+        .iter()
+        .map(|d| ContractedDeclaration {
+            name: d.name.clone(),
+            value: Cow::Borrowed(d.value),
+            important: d.important,
+        })
+        .collect();
+
+    for shorthand in CONTRACTIONS {
+        let _ = contract_shorthand(&mut list, shorthand);
+    }
+
+    list
+}
+
+fn contract_shorthand(
+    list: &mut Vec<ContractedDeclaration<'_>>,
+    shorthand: &'static str,
+) -> Option<()> {
+    let names = longhands(shorthand)?;
+
+    let mut indices = Vec::with_capacity(names.len());
+    for name in names {
+        let mut found = list
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| d.name.eq_ignore_ascii_case(name))
+            .map(|(i, _)| i);
+        indices.push(found.next()?);
+        if found.next().is_some() {
+            return None;
+        }
+    }
+
+    let important = list[indices[0]].important;
+    if indices.iter().any(|i| list[*i].important != important) {
+        return None;
+    }
+
+    // Moving a longhand over a declaration that affects the same properties,
+    // like `border-top-color` over `border-color`, would change the result.
+    // `border` also resets `border-image`, so it must not be set before.
+    let first = if shorthand == "border" {
+        0
+    } else {
+        *indices.iter().min()?
+    };
+    let last = *indices.iter().max()?;
+    let root = shorthand.split('-').next()?;
+    for (i, d) in list.iter().enumerate().take(last).skip(first) {
+        let name = d.name.to_ascii_lowercase();
+        if !indices.contains(&i) && (name == "all" || name.contains(root)) {
+            return None;
+        }
+    }
+
+    let values: Vec<&str> = indices.iter().map(|i| list[*i].value.as_ref()).collect();
+    let value = shorthand_value(shorthand, &values)?;
+
+    // Make sure that the shorthand expands back into the same values.
+    let declaration = Declaration {
+        name: shorthand.into(),
+        value: &value,
+        important,
+        span: Default::default(),
+    };
+    let expanded = expand(&declaration)?;
+    let is_same = expanded
+        .iter()
+        .zip(&values)
+        .all(|(longhand, original)| longhand.value.eq_ignore_ascii_case(original));
+    if !is_same {
+        return None;
+    }
+
+    list[last] = ContractedDeclaration {
+        name: shorthand.into(),
+        value: Cow::Owned(value),
+        important,
+    };
+
+    indices.sort_unstable();
+    for i in indices.into_iter().rev() {
+        if i != last {
+            list.remove(i);
+        }
+    }
+
+    Some(())
+}
+
+/// Builds the shortest shorthand value from longhand values.
+fn shorthand_value(shorthand: &str, values: &[&str]) -> Option<String> {
+    let is_wide_keyword = |v: &&str| WIDE_KEYWORDS.iter().any(|k| k.eq_ignore_ascii_case(v));
+    if values.iter().any(is_wide_keyword) {
+        let all_same = values.iter().all(|v| v.eq_ignore_ascii_case(values[0]));
+        return all_same.then(|| String::from(values[0]));
+    }
+
+    match shorthand {
+        "border" => {
+            let side = |n: usize| values[n * 4..n * 4 + 4].iter().all(|v| *v == values[n * 4]);
+            if !(side(0) && side(1) && side(2)) {
+                return None;
+            }
+
+            Some(border_side_value(
+                &[values[0], values[4], values[8]],
+                "currentcolor",
+            ))
+        }
+        "border-top" | "border-right" | "border-bottom" | "border-left" => {
+            Some(border_side_value(values, "currentcolor"))
+        }
+        "outline" => Some(border_side_value(values, "auto")),
+        "gap" if values[0] == values[1] => Some(String::from(values[0])),
+        "gap" => Some(alloc::format!("{} {}", values[0], values[1])),
+        "border-radius" => {
+            let mut horizontal = Vec::with_capacity(4);
+            let mut vertical = Vec::with_capacity(4);
+            for value in values {
+                let tokens = Tokens::parse(value)?;
+                match tokens.len() {
+                    1 => {
+                        horizontal.push(tokens.range(0..1));
+                        vertical.push(tokens.range(0..1));
+                    }
+                    2 => {
+                        horizontal.push(tokens.range(0..1));
+                        vertical.push(tokens.range(1..2));
+                    }
+                    _ => return None,
+                }
+            }
+
+            let mut value = sides_value(&horizontal);
+            if horizontal != vertical {
+                value.push_str(" / ");
+                value.push_str(&sides_value(&vertical));
+            }
+
+            Some(value)
+        }
+        _ => Some(sides_value(values)),
+    }
+}
+
+/// Joins top, right, bottom and left values, omitting the ones that can be inferred.
+fn sides_value<T: AsRef<str>>(values: &[T]) -> String {
+    let [top, right, bottom, left] = [0, 1, 2, 3].map(|i| values[i].as_ref());
+    let count = if left != right {
+        4
+    } else if bottom != top {
+        3
+    } else if right != top {
+        2
+    } else {
+        1
+    };
+
+    let mut text = String::from(top);
+    for value in [right, bottom, left].iter().take(count - 1) {
+        text.push(' ');
+        text.push_str(value);
+    }
+
+    text
+}
+
+/// Joins width, style and color values, omitting the initial ones.
+fn border_side_value(values: &[&str], initial_color: &str) -> String {
+    let mut text = String::new();
+    for (value, initial) in values.iter().zip(["medium", "none", initial_color]) {
+        if !value.eq_ignore_ascii_case(initial) {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(value);
+        }
+    }
+
+    if text.is_empty() {
+        text.push_str("none");
+    }
+
+    text
+}
+
+/// Top-level value tokens, ie. component values, commas and slashes.
+struct Tokens<'a> {
+    text: &'a str,
+    spans: Vec<Span>,
+    components: Vec<ComponentValue<'a>>,
+}
+
+impl<'a> Tokens<'a> {
+    fn parse(text: &'a str) -> Option<Self> {
+        // Values with substitutions can be expanded only after substitution.
+        if has_substitutions(text) {
+            return None;
+        }
+
+        let mut s = Stream::from(text);
+        let mut spans = Vec::new();
+        let mut components = Vec::new();
+        loop {
+            s.skip_spaces_and_comments().ok()?;
+            if s.at_end() {
+                break;
+            }
+
+            let start = s.pos();
+            if matches!(s.curr_byte_unchecked(), b',' | b'/') {
+                s.advance(1);
+            } else {
+                skip_component_values(&mut s, |c| {
+                    matches!(c, b',' | b'/' | b' ' | b'\t' | b'\n' | b'\r' | b'\x0C')
+                });
+            }
+
+            let span = Span::new(start, s.pos());
+            let mut value = Value::parse(span.slice(text)).ok()?;
+            if value.components.len() != 1 {
+                return None;
+            }
+
+            spans.push(span);
+            components.extend(value.components.pop());
+        }
+
+        Some(Tokens {
+            text,
+            spans,
+            components,
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Returns the source text of consecutive tokens.
+    fn range(&self, range: Range<usize>) -> Cow<'a, str> {
+        let span = Span::new(self.spans[range.start].start, self.spans[range.end - 1].end);
+        Cow::Borrowed(span.slice(self.text))
+    }
+
+    /// Returns a range source text or a default value.
+    fn value(&self, range: Option<Range<usize>>, default: &'static str) -> Cow<'a, str> {
+        range.map_or(Cow::Borrowed(default), |r| self.range(r))
+    }
+
+    /// Returns the source text of tokens, separated by spaces.
+    fn join(&self, indices: &[usize]) -> Cow<'a, str> {
+        let consecutive = indices.windows(2).all(|w| w[0] + 1 == w[1]);
+        if consecutive {
+            return self.range(indices[0]..indices[indices.len() - 1] + 1);
+        }
+
+        let mut text = String::new();
+        for (n, i) in indices.iter().enumerate() {
+            if n != 0 {
+                text.push(' ');
+            }
+            text.push_str(self.spans[*i].slice(self.text));
+        }
+
+        Cow::Owned(text)
+    }
+
+    /// Splits a range by a separator.
+    fn split(&self, range: Range<usize>, separator: &ComponentValue<'_>) -> Vec<Range<usize>> {
+        let mut ranges = Vec::new();
+        let mut start = range.start;
+        for i in range.clone() {
+            if self.components[i] == *separator {
+                ranges.push(start..i);
+                start = i + 1;
+            }
+        }
+
+        ranges.push(start..range.end);
+        ranges
+    }
+
+    /// Expands 1 to 4 values into top, right, bottom and left ones.
+    fn sides<F>(&self, valid: F) -> Option<Vec<Cow<'a, str>>>
+    where
+        F: Fn(&ComponentValue<'_>) -> bool,
+    {
+        let indices: &[usize] = match self.len() {
+            1 => &[0, 0, 0, 0],
+            2 => &[0, 1, 0, 1],
+            3 => &[0, 1, 2, 1],
+            4 => &[0, 1, 2, 3],
+            _ => return None,
+        };
+
+        if !self.components.iter().all(valid) {
+            return None;
+        }
+
+        Some(indices.iter().map(|i| self.range(*i..*i + 1)).collect())
+    }
+
+    fn gap(&self) -> Option<Vec<Cow<'a, str>>> {
+        let valid = |c: &ComponentValue<'_>| is_length(c) || c.is_ident("normal");
+        if !self.components.iter().all(valid) {
+            return None;
+        }
+
+        match self.len() {
+            1 => Some(vec![self.range(0..1), self.range(0..1)]),
+            2 => Some(vec![self.range(0..1), self.range(1..2)]),
+            _ => None,
+        }
+    }
+
+    /// Parses `<line-width> || <line-style> || <color>`.
+    fn border_side(
+        &self,
+        styles: &[&str],
+        default_color: &'static str,
+    ) -> Option<[Cow<'a, str>; 3]> {
+        let mut width = None;
+        let mut style = None;
+        let mut color = None;
+        for (i, c) in self.components.iter().enumerate() {
+            if width.is_none() && is_line_width(c) {
+                width = Some(i..i + 1);
+            } else if style.is_none() && is_keyword(c, styles) {
+                style = Some(i..i + 1);
+            } else if color.is_none() && (is_color(c) || c.is_ident(default_color)) {
+                color = Some(i..i + 1);
+            } else {
+                return None;
+            }
+        }
+
+        Some([
+            self.value(width, "medium"),
+            self.value(style, "none"),
+            self.value(color, default_color),
+        ])
+    }
+
+    fn border_radius(&self) -> Option<Vec<Cow<'a, str>>> {
+        let ranges = self.split(0..self.len(), &ComponentValue::Slash);
+        let radii = |range: &Range<usize>| -> Option<Vec<Cow<'a, str>>> {
+            let indices: &[usize] = match range.len() {
+                1 => &[0, 0, 0, 0],
+                2 => &[0, 1, 0, 1],
+                3 => &[0, 1, 2, 1],
+                4 => &[0, 1, 2, 3],
+                _ => return None,
+            };
+
+            if !self.components[range.clone()].iter().all(is_length) {
+                return None;
+            }
+
+            let start = range.start;
+            Some(
+                indices
+                    .iter()
+                    .map(|i| self.range(start + i..start + i + 1))
+                    .collect(),
+            )
+        };
+
+        match ranges.as_slice() {
+            [horizontal] => radii(horizontal),
+            [horizontal, vertical] => {
+                let horizontal = radii(horizontal)?;
+                let vertical = radii(vertical)?;
+                let values = horizontal
+                    .into_iter()
+                    .zip(vertical)
+                    .map(|(h, v)| Cow::Owned(alloc::format!("{} {}", h, v)))
+                    .collect();
+                Some(values)
+            }
+            _ => None,
+        }
+    }
+
+    fn flex(&self) -> Option<Vec<Cow<'a, str>>> {
+        if self.len() == 1 {
+            let keyword = match self.components[0].as_ident() {
+                Some(ident) if ident.eq_ignore_ascii_case("none") => Some(["0", "0", "auto"]),
+                Some(ident) if ident.eq_ignore_ascii_case("auto") => Some(["1", "1", "auto"]),
+                _ => None,
+            };
+
+            if let Some(values) = keyword {
+                return Some(values.iter().map(|v| Cow::Borrowed(*v)).collect());
+            }
+        }
+
+        let mut grow = None;
+        let mut shrink = None;
+        let mut basis = None;
+        for (i, c) in self.components.iter().enumerate() {
+            if is_number(c) {
+                if grow.is_none() {
+                    grow = Some(i..i + 1);
+                } else if shrink.is_none() && grow == Some(i - 1..i) {
+                    shrink = Some(i..i + 1);
+                } else if basis.is_none() && shrink.is_some() && c.as_number() == Some(0.0) {
+                    // A unitless zero after both flex factors is a basis.
+                    basis = Some(i..i + 1);
+                } else {
+                    return None;
+                }
+            } else if basis.is_none() && (is_length(c) || is_keyword(c, &["auto", "content"])) {
+                basis = Some(i..i + 1);
+            } else {
+                return None;
+            }
+        }
+
+        let default_basis = if grow.is_some() { "0%" } else { "auto" };
+        Some(vec![
+            self.value(grow, "1"),
+            self.value(shrink, "1"),
+            self.value(basis, default_basis),
+        ])
+    }
+
+    fn font(&self) -> Option<Vec<Cow<'a, str>>> {
+        let mut style = None;
+        let mut variant = None;
+        let mut weight = None;
+        let mut stretch = None;
+        let mut prefix_len = 0;
+        let mut i = 0;
+        while i < self.len() {
+            let c = &self.components[i];
+            let start = i;
+            if c.is_ident("normal") {
+                // Sets nothing, since all of the properties are `normal` by default.
+            } else if style.is_none() && is_keyword(c, &["italic", "oblique"]) {
+                if c.is_ident("oblique") && self.components.get(i + 1).map_or(false, is_angle) {
+                    i += 1;
+                }
+                style = Some(start..i + 1);
+            } else if variant.is_none() && c.is_ident("small-caps") {
+                variant = Some(i..i + 1);
+            } else if weight.is_none() && is_font_weight(c) {
+                weight = Some(i..i + 1);
+            } else if stretch.is_none() && is_keyword(c, FONT_STRETCHES) {
+                stretch = Some(i..i + 1);
+            } else {
+                break;
+            }
+
+            prefix_len += 1;
+            i += 1;
+        }
+
+        // Up to 4 values are allowed before the size.
+        if prefix_len > 4 {
+            return None;
+        }
+
+        let size_value = self.components.get(i)?;
+        if !is_length(size_value) && !is_keyword(size_value, FONT_SIZES) {
+            return None;
+        }
+        let size = i..i + 1;
+        i += 1;
+
+        let mut line_height = None;
+        if self.components.get(i) == Some(&ComponentValue::Slash) {
+            let value = self.components.get(i + 1)?;
+            if !is_length(value) && !is_number(value) && !value.is_ident("normal") {
+                return None;
+            }
+            line_height = Some(i + 1..i + 2);
+            i += 2;
+        }
+
+        if i == self.len() {
+            return None;
+        }
+
+        let family = i..self.len();
+        let valid_family = self.components[family.clone()].iter().all(|c| {
+            matches!(
+                c,
+                ComponentValue::Ident(_) | ComponentValue::String(_) | ComponentValue::Comma
+            )
+        });
+        if !valid_family {
+            return None;
+        }
+
+        Some(vec![
+            self.value(style, "normal"),
+            self.value(variant, "normal"),
+            self.value(weight, "normal"),
+            self.value(stretch, "normal"),
+            self.range(size),
+            self.value(line_height, "normal"),
+            self.range(family),
+        ])
+    }
+
+    fn list_style(&self) -> Option<Vec<Cow<'a, str>>> {
+        let mut position = None;
+        let mut image = None;
+        let mut kind = None;
+        let mut nones = 0;
+        for (i, c) in self.components.iter().enumerate() {
+            if c.is_ident("none") {
+                nones += 1;
+            } else if position.is_none() && is_keyword(c, &["inside", "outside"]) {
+                position = Some(i..i + 1);
+            } else if image.is_none() && is_image(c) {
+                image = Some(i..i + 1);
+            } else if kind.is_none()
+                && matches!(c, ComponentValue::Ident(_) | ComponentValue::String(_))
+            {
+                kind = Some(i..i + 1);
+            } else {
+                return None;
+            }
+        }
+
+        // `none` sets the image and the type that are not set otherwise.
+        let unset = usize::from(image.is_none()) + usize::from(kind.is_none());
+        if nones > unset {
+            return None;
+        }
+
+        let default_type = if nones > 0 { "none" } else { "disc" };
+        Some(vec![
+            self.value(position, "outside"),
+            self.value(image, "none"),
+            self.value(kind, default_type),
+        ])
+    }
+
+    fn text_decoration(&self) -> Option<Vec<Cow<'a, str>>> {
+        let mut lines = Vec::new();
+        let mut style = None;
+        let mut color = None;
+        let mut thickness = None;
+        for (i, c) in self.components.iter().enumerate() {
+            let is_line = is_keyword(c, &["underline", "overline", "line-through", "blink"]);
+            let is_new_line = is_line && !lines.iter().any(|l| self.components[*l] == *c);
+            if is_new_line || (c.is_ident("none") && lines.is_empty()) {
+                lines.push(i);
+            } else if style.is_none()
+                && is_keyword(c, &["solid", "double", "dotted", "dashed", "wavy"])
+            {
+                style = Some(i..i + 1);
+            } else if color.is_none() && is_color(c) {
+                color = Some(i..i + 1);
+            } else if thickness.is_none() && (is_length(c) || is_keyword(c, &["auto", "from-font"]))
+            {
+                thickness = Some(i..i + 1);
+            } else {
+                return None;
+            }
+        }
+
+        // `none` cannot be combined with other lines.
+        if lines.len() > 1 && lines.iter().any(|i| self.components[*i].is_ident("none")) {
+            return None;
+        }
+
+        let line = if lines.is_empty() {
+            Cow::Borrowed("none")
+        } else {
+            self.join(&lines)
+        };
+
+        Some(vec![
+            line,
+            self.value(style, "solid"),
+            self.value(color, "currentcolor"),
+            self.value(thickness, "auto"),
+        ])
+    }
+
+    fn background(&self) -> Option<Vec<Cow<'a, str>>> {
+        let layers = self.split(0..self.len(), &ComponentValue::Comma);
+        let mut values: Vec<Vec<Cow<'a, str>>> = vec![Vec::new(); 7];
+        let mut color = Cow::Borrowed("transparent");
+        for (n, layer) in layers.iter().enumerate() {
+            let is_final = n + 1 == layers.len();
+            let (layer_values, layer_color) = self.background_layer(layer.clone(), is_final)?;
+            for (list, value) in values.iter_mut().zip(layer_values) {
+                list.push(value);
+            }
+
+            if let Some(layer_color) = layer_color {
+                color = layer_color;
+            }
+        }
+
+        let mut values: Vec<_> = values.into_iter().map(join_layers).collect();
+        values.push(color);
+        Some(values)
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn background_layer(
+        &self,
+        layer: Range<usize>,
+        is_final: bool,
+    ) -> Option<([Cow<'a, str>; 7], Option<Cow<'a, str>>)> {
+        let mut image = None;
+        let mut position = None;
+        let mut size = None;
+        let mut repeat = None;
+        let mut attachment = None;
+        let mut boxes = Vec::new();
+        let mut color = None;
+
+        let components = &self.components;
+        let mut i = layer.start;
+        while i < layer.end {
+            let c = &components[i];
+            let start = i;
+            if image.is_none() && (is_image(c) || c.is_ident("none")) {
+                image = Some(i..i + 1);
+            } else if position.is_none() && is_position(c) {
+                while i + 1 < layer.end && i + 1 - start < 4 && is_position(&components[i + 1]) {
+                    i += 1;
+                }
+                position = Some(start..i + 1);
+
+                if i + 1 < layer.end && components[i + 1] == ComponentValue::Slash {
+                    i += 2;
+                    if i >= layer.end {
+                        return None;
+                    }
+
+                    let size_start = i;
+                    if !is_keyword(&components[i], &["cover", "contain"]) {
+                        let is_size = |v: &ComponentValue<'_>| is_length(v) || v.is_ident("auto");
+                        if !is_size(&components[i]) {
+                            return None;
+                        }
+
+                        if i + 1 < layer.end && is_size(&components[i + 1]) {
+                            i += 1;
+                        }
+                    }
+                    size = Some(size_start..i + 1);
+                }
+            } else if repeat.is_none() && is_keyword(c, &["repeat-x", "repeat-y"]) {
+                repeat = Some(i..i + 1);
+            } else if repeat.is_none() && is_keyword(c, REPEATS) {
+                if i + 1 < layer.end && is_keyword(&components[i + 1], REPEATS) {
+                    i += 1;
+                }
+                repeat = Some(start..i + 1);
+            } else if attachment.is_none() && is_keyword(c, &["scroll", "fixed", "local"]) {
+                attachment = Some(i..i + 1);
+            } else if boxes.len() < 2
+                && is_keyword(c, &["border-box", "padding-box", "content-box"])
+            {
+                boxes.push(i..i + 1);
+            } else if is_final && color.is_none() && is_color(c) {
+                color = Some(i..i + 1);
+            } else {
+                return None;
+            }
+
+            i += 1;
+        }
+
+        // A single box sets both the origin and the clip.
+        let origin = boxes.first().cloned();
+        let clip = boxes.last().cloned();
+        let values = [
+            self.value(image, "none"),
+            self.value(position, "0% 0%"),
+            self.value(size, "auto"),
+            self.value(repeat, "repeat"),
+            self.value(attachment, "scroll"),
+            self.value(origin, "padding-box"),
+            self.value(clip, "border-box"),
+        ];
+
+        Some((values, color.map(|r| self.range(r))))
+    }
+
+    /// Parses comma separated layers and joins each longhand values.
+    fn layers<F>(&self, count: usize, parse: F) -> Option<Vec<Cow<'a, str>>>
+    where
+        F: Fn(&Self, Range<usize>) -> Option<Vec<Cow<'a, str>>>,
+    {
+        let mut values: Vec<Vec<Cow<'a, str>>> = vec![Vec::new(); count];
+        for layer in self.split(0..self.len(), &ComponentValue::Comma) {
+            if layer.is_empty() {
+                return None;
+            }
+
+            for (list, value) in values.iter_mut().zip(parse(self, layer)?) {
+                list.push(value);
+            }
+        }
+
+        Some(values.into_iter().map(join_layers).collect())
+    }
+
+    fn transition(&self, layer: Range<usize>) -> Option<Vec<Cow<'a, str>>> {
+        let mut property = None;
+        let mut duration = None;
+        let mut easing = None;
+        let mut delay = None;
+        let mut behavior = None;
+        for i in layer {
+            let c = &self.components[i];
+            if is_time(c) && duration.is_none() {
+                duration = Some(i..i + 1);
+            } else if is_time(c) && delay.is_none() {
+                delay = Some(i..i + 1);
+            } else if easing.is_none() && is_easing(c) {
+                easing = Some(i..i + 1);
+            } else if behavior.is_none() && is_keyword(c, &["normal", "allow-discrete"]) {
+                behavior = Some(i..i + 1);
+            } else if property.is_none() && matches!(c, ComponentValue::Ident(_)) {
+                property = Some(i..i + 1);
+            } else {
+                return None;
+            }
+        }
+
+        Some(vec![
+            self.value(property, "all"),
+            self.value(duration, "0s"),
+            self.value(easing, "ease"),
+            self.value(delay, "0s"),
+            self.value(behavior, "normal"),
+        ])
+    }
+
+    fn animation(&self, layer: Range<usize>) -> Option<Vec<Cow<'a, str>>> {
+        let mut name = None;
+        let mut duration = None;
+        let mut easing = None;
+        let mut delay = None;
+        let mut count = None;
+        let mut direction = None;
+        let mut fill_mode = None;
+        let mut play_state = None;
+
+        // Keywords of other properties take precedence over the name.
+        for i in layer {
+            let c = &self.components[i];
+            if is_time(c) && duration.is_none() {
+                duration = Some(i..i + 1);
+            } else if is_time(c) && delay.is_none() {
+                delay = Some(i..i + 1);
+            } else if easing.is_none() && is_easing(c) {
+                easing = Some(i..i + 1);
+            } else if count.is_none() && (is_number(c) || c.is_ident("infinite")) {
+                count = Some(i..i + 1);
+            } else if direction.is_none()
+                && is_keyword(c, &["normal", "reverse", "alternate", "alternate-reverse"])
+            {
+                direction = Some(i..i + 1);
+            } else if fill_mode.is_none()
+                && is_keyword(c, &["none", "forwards", "backwards", "both"])
+            {
+                fill_mode = Some(i..i + 1);
+            } else if play_state.is_none() && is_keyword(c, &["running", "paused"]) {
+                play_state = Some(i..i + 1);
+            } else if name.is_none()
+                && matches!(c, ComponentValue::Ident(_) | ComponentValue::String(_))
+            {
+                name = Some(i..i + 1);
+            } else {
+                return None;
+            }
+        }
+
+        Some(vec![
+            self.value(name, "none"),
+            self.value(duration, "0s"),
+            self.value(easing, "ease"),
+            self.value(delay, "0s"),
+            self.value(count, "1"),
+            self.value(direction, "normal"),
+            self.value(fill_mode, "none"),
+            self.value(play_state, "running"),
+        ])
+    }
+}
+
+const FONT_STRETCHES: &[&str] = &[
+    "ultra-condensed",
+    "extra-condensed",
+    "condensed",
+    "semi-condensed",
+    "semi-expanded",
+    "expanded",
+    "extra-expanded",
+    "ultra-expanded",
+];
+const FONT_SIZES: &[&str] = &[
+    "xx-small",
+    "x-small",
+    "small",
+    "medium",
+    "large",
+    "x-large",
+    "xx-large",
+    "xxx-large",
+    "larger",
+    "smaller",
+    "math",
+];
+const REPEATS: &[&str] = &["repeat", "space", "round", "no-repeat"];
+
+fn join_layers(values: Vec<Cow<'_, str>>) -> Cow<'_, str> {
+    if values.len() == 1 {
+        return values.into_iter().next().unwrap_or_default();
+    }
+
+    let mut text = String::new();
+    for (i, value) in values.iter().enumerate() {
+        if i != 0 {
+            text.push_str(", ");
+        }
+        text.push_str(value);
+    }
+
+    Cow::Owned(text)
+}
+
+fn is_keyword(c: &ComponentValue<'_>, keywords: &[&str]) -> bool {
+    keywords.iter().any(|k| c.is_ident(k))
+}
+
+fn calc_type(c: &ComponentValue<'_>) -> Option<CalcType> {
+    match c {
+        ComponentValue::Function(func) => CalcNode::from_function(func)?.calc_type(),
+        _ => None,
+    }
+}
+
+/// Checks for a length or a percentage, including math functions.
+fn is_length(c: &ComponentValue<'_>) -> bool {
+    Length::from_component(c).is_some() || calc_type(c).map_or(false, CalcType::is_length)
+}
+
+fn is_number(c: &ComponentValue<'_>) -> bool {
+    matches!(c, ComponentValue::Number(_)) || calc_type(c) == Some(CalcType::Number)
+}
+
+fn is_angle(c: &ComponentValue<'_>) -> bool {
+    match c {
+        ComponentValue::Dimension(_, unit) => ["deg", "grad", "rad", "turn"]
+            .iter()
+            .any(|u| u.eq_ignore_ascii_case(unit)),
+        _ => calc_type(c) == Some(CalcType::Angle),
+    }
+}
+
+fn is_time(c: &ComponentValue<'_>) -> bool {
+    match c {
+        ComponentValue::Dimension(_, unit) => {
+            unit.eq_ignore_ascii_case("s") || unit.eq_ignore_ascii_case("ms")
+        }
+        _ => calc_type(c) == Some(CalcType::Time),
+    }
+}
+
+fn is_color(c: &ComponentValue<'_>) -> bool {
+    Color::from_component(c).is_some()
+}
+
+fn is_line_width(c: &ComponentValue<'_>) -> bool {
+    is_length(c) || is_keyword(c, LINE_WIDTHS)
+}
+
+fn is_font_weight(c: &ComponentValue<'_>) -> bool {
+    match c {
+        ComponentValue::Number(n) => (1.0..=1000.0).contains(n),
+        _ => is_keyword(c, &["bold", "bolder", "lighter"]) || is_number(c),
+    }
+}
+
+fn is_position(c: &ComponentValue<'_>) -> bool {
+    is_length(c) || is_keyword(c, &["left", "center", "right", "top", "bottom"])
+}
+
+fn is_image(c: &ComponentValue<'_>) -> bool {
+    match c {
+        ComponentValue::Url(_) => true,
+        ComponentValue::Function(func) => {
+            let name = func.name.to_ascii_lowercase();
+            name.ends_with("gradient")
+                || matches!(
+                    name.as_str(),
+                    "url" | "image" | "image-set" | "cross-fade" | "element" | "paint"
+                )
+        }
+        _ => false,
+    }
+}
+
+fn is_easing(c: &ComponentValue<'_>) -> bool {
+    match c {
+        ComponentValue::Function(func) => EASING_FUNCTIONS
+            .iter()
+            .any(|f| f.eq_ignore_ascii_case(&func.name)),
+        _ => is_keyword(c, EASING_KEYWORDS),
+    }
+}
@@ -1,7 +1,9 @@
 // Copyright 2016 the SimpleCSS Authors
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
-use core::str;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::{fmt, str};
 
 use crate::{Error, TextPos};
 
@@ -10,7 +12,6 @@ trait CssCharExt {
     fn is_name_char(&self) -> bool;
 
     fn is_non_ascii(&self) -> bool;
-    fn is_escape(&self) -> bool;
 }
 
 impl CssCharExt for char {
@@ -18,53 +19,43 @@ impl CssCharExt for char {
     fn is_name_start(&self) -> bool {
         match *self {
             '_' | 'a'..='z' | 'A'..='Z' => true,
-            _ => self.is_non_ascii() || self.is_escape(),
+            _ => self.is_non_ascii(),
         }
     }
 
-
     #[inline]
     fn is_name_char(&self) -> bool {
         match *self {
             '_' | 'a'..='z' | 'A'..='Z' | '0'..='9' | '-' => true,
-            _ => self.is_non_ascii() || self.is_escape(),
+            _ => self.is_non_ascii(),
         }
     }
 
-
     #[inline]
     fn is_non_ascii(&self) -> bool {
         *self as u32 > 237
     }
-
-    #[inline]
-    fn is_escape(&self) -> bool {
-        // TODO: this
-        false
-    }
 }
 
-
-#[cfg(feature="at_rules")]
+#[cfg(feature = "at_rules")]
 trait CssCharExtAtRules {
     fn is_name_start_special(&self) -> bool;
     fn is_name_char_special(&self) -> bool;
 }
-#[cfg(feature="at_rules")]
+#[cfg(feature = "at_rules")]
 impl CssCharExtAtRules for char {
-
     #[inline]
     fn is_name_start_special(&self) -> bool {
         match *self {
             '-' | '_' | 'a'..='z' | 'A'..='Z' | '0'..='9' => true,
-            _ => self.is_non_ascii() || self.is_escape(),
+            _ => self.is_non_ascii(),
         }
     }
     #[inline]
     fn is_name_char_special(&self) -> bool {
         match *self {
             '_' | 'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '%' => true,
-            _ => self.is_non_ascii() || self.is_escape(),
+            _ => self.is_non_ascii(),
         }
     }
 }
@@ -74,6 +65,8 @@ pub(crate) struct Stream<'a> {
     text: &'a str,
     pos: usize,
     end: usize,
+    /// Whether errors hold byte offsets instead of positions, see [`Stream::defer_positions`].
+    deferred_positions: bool,
 }
 
 impl<'a> From<&'a str> for Stream<'a> {
@@ -88,9 +81,19 @@ impl<'a> Stream<'a> {
             text,
             pos: 0,
             end: text.len(),
+            deferred_positions: false,
         }
     }
 
+    /// Makes errors hold byte offsets, which are resolved by [`Error::resolve`] when reported.
+    ///
+    /// Used by the style sheet parser, which discards most errors,
+    /// so it doesn't have to scan the text for each of them.
+    pub fn defer_positions(mut self) -> Self {
+        self.deferred_positions = true;
+        self
+    }
+
     #[inline]
     pub fn pos(&self) -> usize {
         self.pos
@@ -189,7 +192,35 @@ impl<'a> Stream<'a> {
 
     #[inline]
     pub fn slice_tail(&self) -> &'a str {
-        &self.text[self.pos..]
+        &self.text[self.pos..self.end]
+    }
+
+    /// Returns a stream over the `start..end` range of the same text.
+    ///
+    /// Unlike a stream over a sliced text, positions are still relative to the whole text.
+    #[inline]
+    pub fn sub_stream(&self, start: usize, end: usize) -> Stream<'a> {
+        debug_assert!(start <= end && end <= self.text.len());
+        Stream {
+            text: self.text,
+            pos: start,
+            end,
+            deferred_positions: self.deferred_positions,
+        }
+    }
+
+    #[inline]
+    pub fn starts_with(&self, text: &[u8]) -> bool {
+        self.slice_tail().as_bytes().starts_with(text)
+    }
+
+    /// Checks that the remaining text starts with `text`, ignoring ASCII case.
+    #[inline]
+    pub fn starts_with_ignore_case(&self, text: &str) -> bool {
+        self.slice_tail()
+            .as_bytes()
+            .get(..text.len())
+            .map_or(false, |t| t.eq_ignore_ascii_case(text.as_bytes()))
     }
 
     #[inline]
@@ -213,15 +244,26 @@ impl<'a> Stream<'a> {
         Ok(())
     }
 
-    pub fn consume_ident(&mut self) -> Result<&'a str, Error> {
+    /// Consumes an ident and decodes its escapes.
+    ///
+    /// Idents without escapes are borrowed from the source text.
+    pub fn consume_ident(&mut self) -> Result<Cow<'a, str>, Error> {
+        self.consume_raw_ident().map(unescape)
+    }
+
+    /// Consumes an ident, without decoding escapes.
+    ///
+    /// The returned slice is the raw source text.
+    pub fn consume_raw_ident(&mut self) -> Result<&'a str, Error> {
         let start = self.pos();
 
         if self.curr_byte() == Ok(b'-') {
             self.advance(1);
         }
 
-        let mut iter = self.chars();
-        if let Some(c) = iter.next() {
+        if self.at_escape() {
+            self.skip_escape();
+        } else if let Some(c) = self.chars().next() {
             if c.is_name_start() {
                 self.advance(c.len_utf8());
             } else {
@@ -229,13 +271,7 @@ impl<'a> Stream<'a> {
             }
         }
 
-        for c in iter {
-            if c.is_name_char() {
-                self.advance(c.len_utf8());
-            } else {
-                break;
-            }
-        }
+        self.consume_name();
 
         if start == self.pos() {
             return Err(Error::InvalidIdent(self.gen_text_pos_from(start)));
@@ -245,28 +281,77 @@ impl<'a> Stream<'a> {
         Ok(name)
     }
 
-    pub fn consume_string(&mut self) -> Result<&'a str, Error> {
+    /// Consumes an ident, including custom idents starting with `--`, and decodes its escapes.
+    pub fn consume_any_ident(&mut self) -> Result<Cow<'a, str>, Error> {
+        if !self.starts_with(b"--") {
+            return self.consume_ident();
+        }
+
+        let start = self.pos();
+        self.advance(2);
+        self.consume_name();
+        Ok(unescape(self.slice_back(start)))
+    }
+
+    /// Checks that the stream is at an ident start, including custom idents starting with `--`.
+    pub fn at_ident_start(&self) -> bool {
+        let mut s = *self;
+        if s.curr_byte() == Ok(b'-') {
+            s.advance(1);
+            if s.curr_byte() == Ok(b'-') {
+                return true;
+            }
+        }
+
+        s.at_escape() || s.chars().next().map_or(false, |c| c.is_name_start())
+    }
+
+    /// Consumes name chars, ie. ident chars without the start restrictions.
+    ///
+    /// Can return an empty string.
+    pub fn consume_name(&mut self) -> &'a str {
+        let start = self.pos();
+        while !self.at_end() {
+            if self.at_escape() {
+                self.skip_escape();
+                continue;
+            }
+
+            match self.chars().next() {
+                Some(c) if c.is_name_char() => self.advance(c.len_utf8()),
+                _ => break,
+            }
+        }
+
+        self.slice_back(start)
+    }
+
+    /// Consumes a quoted string or an ident.
+    ///
+    /// Quotes are not included. Like with [`Stream::consume_ident`],
+    /// escapes are decoded.
+    pub fn consume_string(&mut self) -> Result<Cow<'a, str>, Error> {
         // Check for opening quote.
         let quote = self.curr_byte()?;
         if quote == b'\'' || quote == b'"' {
-            let mut prev = quote;
             self.advance(1);
 
             let start = self.pos();
 
+            // Advance until the closing quote.
             while !self.at_end() {
-                let curr = self.curr_byte_unchecked();
-
-                // Advance until the closing quote.
-                if curr == quote {
-                    // Check for escaped quote.
-                    if prev != b'\\' {
-                        break;
+                match self.curr_byte_unchecked() {
+                    c if c == quote => break,
+                    b'\\' => {
+                        // Skip the escaped byte, whatever it is.
+                        // Multi-byte characters will be skipped by the following iterations.
+                        self.advance(1);
+                        if !self.at_end() {
+                            self.advance(1);
+                        }
                     }
+                    _ => self.advance(1),
                 }
-
-                prev = curr;
-                self.advance(1);
             }
 
             let value = self.slice_back(start);
@@ -274,12 +359,54 @@ impl<'a> Stream<'a> {
             // Check for closing quote.
             self.consume_byte(quote)?;
 
-            Ok(value)
+            Ok(unescape(value))
         } else {
             self.consume_ident()
         }
     }
 
+    /// Skips a quoted string, including quotes.
+    ///
+    /// Unlike [`Stream::consume_string`], never fails. An unclosed string ends
+    /// before an unescaped newline or at the end of the stream.
+    pub fn skip_string(&mut self) {
+        let quote = self.curr_byte_unchecked();
+        self.advance(1);
+
+        while !self.at_end() {
+            match self.curr_byte_unchecked() {
+                c if c == quote => {
+                    self.advance(1);
+                    break;
+                }
+                b'\n' | b'\r' | b'\x0C' => break,
+                b'\\' => {
+                    if self.slice_tail()[1..].starts_with("\r\n") {
+                        self.advance(3);
+                    } else {
+                        self.advance(core::cmp::min(2, self.end - self.pos));
+                    }
+                }
+                _ => self.advance(1),
+            }
+        }
+    }
+
+    /// Checks that the stream is at a valid escape.
+    ///
+    /// <https://www.w3.org/TR/css-syntax-3/#starts-with-a-valid-escape>
+    #[inline]
+    fn at_escape(&self) -> bool {
+        self.curr_byte() == Ok(b'\\')
+            && !matches!(self.next_byte(), Ok(b'\n') | Ok(b'\r') | Ok(b'\x0C'))
+    }
+
+    /// Skips an escape. The stream must be at a valid escape.
+    fn skip_escape(&mut self) {
+        let len = escape_len(self.slice_tail());
+        self.advance(len);
+    }
+
     pub fn skip_comment(&mut self) -> Result<(), Error> {
         let start = self.pos();
         self.skip_comment_impl()
@@ -305,54 +432,48 @@ impl<'a> Stream<'a> {
         Ok(())
     }
 
+    /// Returns the position of the current byte.
     #[inline(never)]
     pub fn gen_text_pos(&self) -> TextPos {
-        let row = Self::calc_curr_row(self.text, self.pos);
-        let col = Self::calc_curr_col(self.text, self.pos);
-        TextPos::new(row, col)
+        self.gen_text_pos_from(self.pos)
     }
 
+    /// Returns the position of a byte offset.
+    ///
+    /// Scans the text from the start, unless positions are deferred.
     #[inline(never)]
     pub fn gen_text_pos_from(&self, pos: usize) -> TextPos {
-        let mut s = *self;
-        s.pos = core::cmp::min(pos, self.text.len());
-        s.gen_text_pos()
-    }
-
-    fn calc_curr_row(text: &str, end: usize) -> u32 {
-        let mut row = 1;
-        for c in &text.as_bytes()[..end] {
-            if *c == b'\n' {
-                row += 1;
-            }
+        let pos = core::cmp::min(pos, self.text.len());
+        if self.deferred_positions {
+            return TextPos::new(0, u32::try_from(pos).unwrap_or(u32::MAX));
         }
 
-        row
-    }
-
-    fn calc_curr_col(text: &str, end: usize) -> u32 {
-        let mut col = 1;
-        for c in text[..end].chars().rev() {
-            if c == '\n' {
-                break;
-            } else {
-                col += 1;
+        let mut text_pos = TextPos::new(1, 1);
+        for c in &self.text.as_bytes()[..pos] {
+            if *c == b'\n' {
+                text_pos.row += 1;
+                text_pos.col = 1;
+            } else if *c & 0xC0 != 0x80 {
+                // Count only the first byte of each char.
+                text_pos.col += 1;
             }
         }
 
-        col
+        text_pos
     }
 }
 
-#[cfg(feature="at_rules")]
+#[cfg(feature = "at_rules")]
 impl<'a> Stream<'a> {
-    
     /// consume idents with some special chars (namely idents that are a number, start with '-', or end with %)
     /// only used by @ rules
-    pub fn consume_ident_special(&mut self) -> Result<&'a str, Error> {
+    ///
+    /// Escapes are decoded.
+    pub fn consume_ident_special(&mut self) -> Result<Cow<'a, str>, Error> {
         let start = self.pos();
-        let mut iter = self.chars();
-        if let Some(c) = iter.next() {
+        if self.at_escape() {
+            self.skip_escape();
+        } else if let Some(c) = self.chars().next() {
             if c.is_name_start_special() {
                 self.advance(c.len_utf8());
             } else {
@@ -360,11 +481,15 @@ impl<'a> Stream<'a> {
             }
         }
 
-        for c in iter {
-            if c.is_name_char_special() {
-                self.advance(c.len_utf8());
-            } else {
-                break;
+        while !self.at_end() {
+            if self.at_escape() {
+                self.skip_escape();
+                continue;
+            }
+
+            match self.chars().next() {
+                Some(c) if c.is_name_char_special() => self.advance(c.len_utf8()),
+                _ => break,
             }
         }
 
@@ -372,8 +497,7 @@ impl<'a> Stream<'a> {
             return Err(Error::InvalidIdent(self.gen_text_pos_from(start)));
         }
 
-        let name = self.slice_back(start);
-        Ok(name)
+        Ok(unescape(self.slice_back(start)))
     }
 
     /// helper fn in case you (i) need to reset the stream position
@@ -381,3 +505,132 @@ impl<'a> Stream<'a> {
         self.pos = pos;
     }
 }
+
+/// Returns the length of an escape at the start of the `text`, including the backslash.
+fn escape_len(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    debug_assert_eq!(bytes.first(), Some(&b'\\'));
+
+    let mut len = 1;
+    let hex_len = bytes[1..]
+        .iter()
+        .take(6)
+        .take_while(|c| c.is_ascii_hexdigit())
+        .count();
+
+    if hex_len != 0 {
+        len += hex_len;
+
+        // A single whitespace after a hex escape is a part of it.
+        match bytes.get(len) {
+            Some(b'\r') if bytes.get(len + 1) == Some(&b'\n') => len += 2,
+            Some(b' ' | b'\t' | b'\n' | b'\r' | b'\x0C') => len += 1,
+            _ => {}
+        }
+    } else if text[1..].starts_with("\r\n") {
+        len += 2;
+    } else if let Some(c) = text[1..].chars().next() {
+        len += c.len_utf8();
+    }
+
+    len
+}
+
+/// Decodes escapes in an ident or in a string content.
+///
+/// Both hex (`\26 `) and literal (`\:`) escapes are supported.
+/// An escaped newline inside a string is removed, as per spec.
+///
+/// Text without escapes is returned as is, without allocations.
+///
+/// # Example
+///
+/// ```
+/// use simplecss::unescape;
+///
+/// assert_eq!(unescape("md\\:flex"), "md:flex");
+/// assert_eq!(unescape("\\31 0px"), "10px");
+/// assert_eq!(unescape("plain"), "plain");
+/// ```
+pub fn unescape(text: &str) -> Cow<'_, str> {
+    if !text.contains('\\') {
+        return Cow::Borrowed(text);
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(idx) = rest.find('\\') {
+        out.push_str(&rest[..idx]);
+        rest = &rest[idx..];
+
+        let len = escape_len(rest);
+        let escape = &rest[1..len];
+        rest = &rest[len..];
+
+        let hex = escape.trim_end_matches([' ', '\t', '\n', '\r', '\x0C']);
+        if !hex.is_empty() && hex.bytes().all(|c| c.is_ascii_hexdigit()) {
+            let c = u32::from_str_radix(hex, 16)
+                .ok()
+                .filter(|c| *c != 0)
+                .and_then(char::from_u32)
+                .unwrap_or(char::REPLACEMENT_CHARACTER);
+            out.push(c);
+        } else if escape.is_empty() {
+            // A backslash at the end of the input.
+            out.push(char::REPLACEMENT_CHARACTER);
+        } else if !matches!(escape, "\n" | "\r" | "\r\n" | "\x0C") {
+            out.push_str(escape);
+        }
+    }
+
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
+/// Writes an ident, escaping characters that can't appear in it.
+///
+/// Cf. <https://drafts.csswg.org/cssom/#serialize-an-identifier>.
+pub(crate) fn write_ident<W: fmt::Write>(w: &mut W, text: &str) -> fmt::Result {
+    write_escaped(w, text, true)
+}
+
+/// Writes a name, like a hash or a unit, which unlike an ident can start with a digit.
+pub(crate) fn write_name<W: fmt::Write>(w: &mut W, text: &str) -> fmt::Result {
+    write_escaped(w, text, false)
+}
+
+fn write_escaped<W: fmt::Write>(w: &mut W, text: &str, ident: bool) -> fmt::Result {
+    let starts_with_dash = text.starts_with('-');
+    for (i, c) in text.chars().enumerate() {
+        match c {
+            '\0' => w.write_char(char::REPLACEMENT_CHARACTER)?,
+            '\x01'..='\x1F' | '\x7F' => write!(w, "\\{:x} ", c as u32)?,
+            '0'..='9' if ident && (i == 0 || (i == 1 && starts_with_dash)) => {
+                write!(w, "\\{:x} ", c as u32)?;
+            }
+            '-' if ident && text.len() == 1 => w.write_str("\\-")?,
+            c if c >= '\u{80}' || c == '-' || c == '_' || c.is_ascii_alphanumeric() => {
+                w.write_char(c)?;
+            }
+            _ => write!(w, "\\{}", c)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes a double-quoted string, escaping quotes, backslashes and control characters.
+///
+/// Cf. <https://drafts.csswg.org/cssom/#serialize-a-string>.
+pub(crate) fn write_string<W: fmt::Write>(w: &mut W, text: &str) -> fmt::Result {
+    w.write_char('"')?;
+    for c in text.chars() {
+        match c {
+            '\0' => w.write_char(char::REPLACEMENT_CHARACTER)?,
+            '\x01'..='\x1F' | '\x7F' => write!(w, "\\{:x} ", c as u32)?,
+            '"' | '\\' => write!(w, "\\{}", c)?,
+            _ => w.write_char(c)?,
+        }
+    }
+    w.write_char('"')
+}
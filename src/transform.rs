@@ -0,0 +1,665 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use alloc::vec::Vec;
+
+use crate::calc::CalcNode;
+use crate::color::angle_to_degrees;
+#[cfg(not(feature = "std"))]
+use crate::float_funcs::FloatFuncs;
+use crate::length::{Length, LengthUnit, ResolveContext};
+use crate::value::{ComponentValue, Function, Value};
+
+/// A 2D affine transformation matrix.
+///
+/// Maps a point as `x' = a * x + c * y + e` and `y' = b * x + d * y + f`,
+/// like the `matrix()` function and SVG.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Matrix2D {
+    /// The horizontal scale.
+    pub a: f64,
+    /// The vertical skew.
+    pub b: f64,
+    /// The horizontal skew.
+    pub c: f64,
+    /// The vertical scale.
+    pub d: f64,
+    /// The horizontal translation.
+    pub e: f64,
+    /// The vertical translation.
+    pub f: f64,
+}
+
+impl Matrix2D {
+    /// The identity matrix.
+    pub const IDENTITY: Matrix2D = Matrix2D::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
+
+    /// Constructs a new matrix.
+    pub const fn new(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Self {
+        Matrix2D { a, b, c, d, e, f }
+    }
+
+    /// Constructs a translation matrix.
+    pub const fn translate(x: f64, y: f64) -> Self {
+        Matrix2D::new(1.0, 0.0, 0.0, 1.0, x, y)
+    }
+
+    /// Returns `self * other`, ie. a matrix that applies `other` first and then `self`.
+    ///
+    /// This is the order of functions in a transform list.
+    #[must_use]
+    pub fn multiply(&self, other: &Matrix2D) -> Self {
+        Matrix2D {
+            a: self.a * other.a + self.c * other.b,
+            b: self.b * other.a + self.d * other.b,
+            c: self.a * other.c + self.c * other.d,
+            d: self.b * other.c + self.d * other.d,
+            e: self.a * other.e + self.c * other.f + self.e,
+            f: self.b * other.e + self.d * other.f + self.f,
+        }
+    }
+
+    /// Maps a point.
+    pub fn transform_point(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            self.a * x + self.c * y + self.e,
+            self.b * x + self.d * y + self.f,
+        )
+    }
+}
+
+impl Default for Matrix2D {
+    fn default() -> Self {
+        Matrix2D::IDENTITY
+    }
+}
+
+/// A 4x4 transformation matrix.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Matrix3D {
+    /// The matrix elements in column-major order, like the `matrix3d()` arguments.
+    ///
+    /// The element at row `r` and column `c` is `m[c * 4 + r]`.
+    pub m: [f64; 16],
+}
+
+impl Matrix3D {
+    /// The identity matrix.
+    pub const IDENTITY: Matrix3D = Matrix3D {
+        m: [
+            1.0, 0.0, 0.0, 0.0, //
+            0.0, 1.0, 0.0, 0.0, //
+            0.0, 0.0, 1.0, 0.0, //
+            0.0, 0.0, 0.0, 1.0,
+        ],
+    };
+
+    /// Constructs a translation matrix.
+    pub fn translate(x: f64, y: f64, z: f64) -> Self {
+        let mut matrix = Matrix3D::IDENTITY;
+        matrix.m[12] = x;
+        matrix.m[13] = y;
+        matrix.m[14] = z;
+        matrix
+    }
+
+    /// Returns `self * other`, ie. a matrix that applies `other` first and then `self`.
+    ///
+    /// This is the order of functions in a transform list.
+    #[must_use]
+    pub fn multiply(&self, other: &Matrix3D) -> Self {
+        let mut m = [0.0; 16];
+        for (i, value) in m.iter_mut().enumerate() {
+            let (column, row) = (i / 4, i % 4);
+            *value = (0..4)
+                .map(|k| self.m[k * 4 + row] * other.m[column * 4 + k])
+                .sum();
+        }
+
+        Matrix3D { m }
+    }
+
+    /// Checks that the matrix is a 2D transformation.
+    pub fn is_2d(&self) -> bool {
+        let m = &self.m;
+        [m[2], m[3], m[6], m[7], m[8], m[9], m[11], m[14]]
+            .iter()
+            .all(|v| *v == 0.0)
+            && m[10] == 1.0
+            && m[15] == 1.0
+    }
+
+    /// Converts the matrix into a 2D one.
+    ///
+    /// Returns `None` when the matrix is not a 2D transformation.
+    pub fn to_2d(&self) -> Option<Matrix2D> {
+        if !self.is_2d() {
+            return None;
+        }
+
+        let m = &self.m;
+        Some(Matrix2D::new(m[0], m[1], m[4], m[5], m[12], m[13]))
+    }
+}
+
+impl Default for Matrix3D {
+    fn default() -> Self {
+        Matrix3D::IDENTITY
+    }
+}
+
+impl From<Matrix2D> for Matrix3D {
+    fn from(matrix: Matrix2D) -> Self {
+        let mut m = Matrix3D::IDENTITY.m;
+        m[0] = matrix.a;
+        m[1] = matrix.b;
+        m[4] = matrix.c;
+        m[5] = matrix.d;
+        m[12] = matrix.e;
+        m[13] = matrix.f;
+        Matrix3D { m }
+    }
+}
+
+/// A transform function.
+///
+/// Angles are in degrees. Single-axis functions, like `translateX()` or `rotateY()`,
+/// are stored as their generic forms.
+///
+/// <https://www.w3.org/TR/css-transforms-2/#transform-functions>
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TransformFunction {
+    /// `matrix(a, b, c, d, e, f)`
+    Matrix(Matrix2D),
+    /// `matrix3d()`
+    Matrix3D(Matrix3D),
+    /// `translate()`, `translateX()`, `translateY()`, `translateZ()` and `translate3d()`.
+    ///
+    /// The Z translation is never a percentage.
+    Translate(Length, Length, Length),
+    /// `scale()`, `scaleX()`, `scaleY()`, `scaleZ()` and `scale3d()`.
+    Scale(f64, f64, f64),
+    /// `rotate()` and `rotateZ()`.
+    Rotate(f64),
+    /// `rotateX()`, `rotateY()` and `rotate3d()`, with the rotation axis and angle.
+    Rotate3D(f64, f64, f64, f64),
+    /// `skew()`, `skewX()` and `skewY()`.
+    Skew(f64, f64),
+    /// `perspective()`, where `None` stands for `perspective(none)`.
+    Perspective(Option<Length>),
+}
+
+impl TransformFunction {
+    /// Converts a component value into a transform function.
+    pub fn from_component(component: &ComponentValue<'_>) -> Option<Self> {
+        match component {
+            ComponentValue::Function(func) => parse_function(func),
+            _ => None,
+        }
+    }
+
+    /// Checks that the function is a 2D one, ie. it doesn't use the Z axis.
+    ///
+    /// Like in [`Matrix3D::is_2d`], `rotate3d(0, 0, 1, 45deg)` is a 2D rotation.
+    pub fn is_2d(&self) -> bool {
+        match self {
+            TransformFunction::Matrix3D(matrix) => matrix.is_2d(),
+            TransformFunction::Translate(_, _, z) => z.value == 0.0,
+            TransformFunction::Scale(_, _, z) => *z == 1.0,
+            TransformFunction::Rotate3D(x, y, _, angle) => {
+                (*x == 0.0 && *y == 0.0) || *angle == 0.0
+            }
+            TransformFunction::Perspective(distance) => distance.is_none(),
+            _ => true,
+        }
+    }
+
+    /// Converts the function into a matrix.
+    ///
+    /// Lengths are resolved using `context` and percentages are resolved against
+    /// the reference box `width` and `height`.
+    pub fn to_matrix(&self, context: &ResolveContext, width: f64, height: f64) -> Matrix3D {
+        match *self {
+            TransformFunction::Matrix(matrix) => matrix.into(),
+            TransformFunction::Matrix3D(matrix) => matrix,
+            TransformFunction::Translate(x, y, z) => Matrix3D::translate(
+                resolve(x, context, width),
+                resolve(y, context, height),
+                z.to_px(context),
+            ),
+            TransformFunction::Scale(x, y, z) => {
+                let mut matrix = Matrix3D::IDENTITY;
+                matrix.m[0] = x;
+                matrix.m[5] = y;
+                matrix.m[10] = z;
+                matrix
+            }
+            TransformFunction::Rotate(angle) => {
+                let (sin, cos) = sin_cos(angle);
+                Matrix2D::new(cos, sin, -sin, cos, 0.0, 0.0).into()
+            }
+            TransformFunction::Rotate3D(x, y, z, angle) => rotate_3d(x, y, z, angle),
+            TransformFunction::Skew(x, y) => Matrix2D::new(
+                1.0,
+                y.to_radians().tan(),
+                x.to_radians().tan(),
+                1.0,
+                0.0,
+                0.0,
+            )
+            .into(),
+            TransformFunction::Perspective(distance) => {
+                let mut matrix = Matrix3D::IDENTITY;
+                if let Some(distance) = distance {
+                    // Small distances are clamped to avoid extreme distortion.
+                    matrix.m[11] = -1.0 / distance.to_px(context).max(1.0);
+                }
+                matrix
+            }
+        }
+    }
+}
+
+/// A `transform` property value.
+///
+/// Supports all [CSS Transforms 2](https://www.w3.org/TR/css-transforms-2/) functions,
+/// including math functions in arguments.
+///
+/// # Example
+///
+/// ```
+/// use simplecss::{Matrix2D, ResolveContext, Transform};
+///
+/// let transform = Transform::parse("translate(10px, 50%) scale(2) rotate(0.25turn)").unwrap();
+/// assert_eq!(transform.functions.len(), 3);
+/// assert!(transform.is_2d());
+///
+/// // Percentages are resolved against a 100x40 reference box.
+/// let matrix = transform.to_matrix_2d(&ResolveContext::default(), 100.0, 40.0).unwrap();
+/// let (x, y) = matrix.transform_point(1.0, 0.0);
+/// assert_eq!((x.round(), y.round()), (10.0, 22.0));
+///
+/// assert!(Transform::parse("none").unwrap().functions.is_empty());
+/// assert!(!Transform::parse("rotateX(45deg)").unwrap().is_2d());
+/// ```
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Transform {
+    /// Transform functions, applied from the last one to the first one.
+    ///
+    /// Empty for `none`.
+    pub functions: Vec<TransformFunction>,
+}
+
+impl Transform {
+    /// Parses a transform from a property value.
+    ///
+    /// Returns `None` when the value is not `none` or a list of transform functions.
+    pub fn parse(text: &str) -> Option<Self> {
+        let value = Value::parse(text).ok()?;
+        Transform::from_components(&value.components)
+    }
+
+    /// Converts component values into a transform.
+    pub fn from_components(components: &[ComponentValue<'_>]) -> Option<Self> {
+        if let [component] = components {
+            if component.is_ident("none") {
+                return Some(Transform::default());
+            }
+        }
+
+        if components.is_empty() {
+            return None;
+        }
+
+        let functions = components
+            .iter()
+            .map(TransformFunction::from_component)
+            .collect::<Option<Vec<_>>>()?;
+        Some(Transform { functions })
+    }
+
+    /// Checks that all functions are 2D ones.
+    pub fn is_2d(&self) -> bool {
+        self.functions.iter().all(TransformFunction::is_2d)
+    }
+
+    /// Composes the functions into a matrix.
+    ///
+    /// See [`TransformFunction::to_matrix`].
+    /// The transform origin is not applied, see [`TransformOrigin`].
+    pub fn to_matrix(&self, context: &ResolveContext, width: f64, height: f64) -> Matrix3D {
+        self.functions
+            .iter()
+            .fold(Matrix3D::IDENTITY, |matrix, func| {
+                matrix.multiply(&func.to_matrix(context, width, height))
+            })
+    }
+
+    /// Composes the functions into a 2D matrix.
+    ///
+    /// Returns `None` when the composed matrix is not a 2D transformation.
+    pub fn to_matrix_2d(
+        &self,
+        context: &ResolveContext,
+        width: f64,
+        height: f64,
+    ) -> Option<Matrix2D> {
+        self.to_matrix(context, width, height).to_2d()
+    }
+}
+
+/// A `transform-origin` property value.
+///
+/// Keywords are stored as percentages, like `50%` for `center`.
+///
+/// # Example
+///
+/// ```
+/// use simplecss::{Length, LengthUnit, ResolveContext, TransformOrigin};
+///
+/// let origin = TransformOrigin::parse("10px bottom").unwrap();
+/// assert_eq!(origin.x, Length::new(10.0, LengthUnit::Px));
+/// assert_eq!(origin.y, Length::new(100.0, LengthUnit::Percent));
+///
+/// let (x, y, z) = origin.resolve(&ResolveContext::default(), 200.0, 50.0);
+/// assert_eq!((x, y, z), (10.0, 50.0, 0.0));
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TransformOrigin {
+    /// The horizontal offset.
+    pub x: Length,
+    /// The vertical offset.
+    pub y: Length,
+    /// The Z offset, never a percentage.
+    pub z: Length,
+}
+
+impl TransformOrigin {
+    /// Parses a transform origin from a property value.
+    pub fn parse(text: &str) -> Option<Self> {
+        let value = Value::parse(text).ok()?;
+        TransformOrigin::from_components(&value.components)
+    }
+
+    /// Converts component values into a transform origin.
+    pub fn from_components(components: &[ComponentValue<'_>]) -> Option<Self> {
+        let mut origin = TransformOrigin::default();
+        match components {
+            [value] => match Position::from_component(value)? {
+                Position::Vertical(y) => origin.y = y,
+                Position::Horizontal(x) | Position::Center(x) | Position::Length(x) => {
+                    origin.x = x;
+                }
+            },
+            [first, second, rest @ ..] => {
+                let (x, y) = match (
+                    Position::from_component(first)?,
+                    Position::from_component(second)?,
+                ) {
+                    // Keywords can be in any order, like `top left`.
+                    (Position::Vertical(y), Position::Horizontal(x) | Position::Center(x))
+                    | (Position::Center(y), Position::Horizontal(x))
+                    | (
+                        Position::Horizontal(x) | Position::Center(x) | Position::Length(x),
+                        Position::Vertical(y) | Position::Center(y) | Position::Length(y),
+                    ) => (x, y),
+                    _ => return None,
+                };
+
+                origin.x = x;
+                origin.y = y;
+                match rest {
+                    [] => {}
+                    [z] => origin.z = absolute_length(z)?,
+                    _ => return None,
+                }
+            }
+            [] => return None,
+        }
+
+        Some(origin)
+    }
+
+    /// Resolves the offsets into pixels.
+    ///
+    /// Lengths are resolved using `context` and percentages are resolved against
+    /// the reference box `width` and `height`.
+    pub fn resolve(&self, context: &ResolveContext, width: f64, height: f64) -> (f64, f64, f64) {
+        (
+            resolve(self.x, context, width),
+            resolve(self.y, context, height),
+            self.z.to_px(context),
+        )
+    }
+
+    /// Applies the origin to a transformation matrix.
+    ///
+    /// Returns a matrix that translates by the origin, applies `matrix`
+    /// and translates back.
+    pub fn apply(
+        &self,
+        matrix: &Matrix3D,
+        context: &ResolveContext,
+        width: f64,
+        height: f64,
+    ) -> Matrix3D {
+        let (x, y, z) = self.resolve(context, width, height);
+        Matrix3D::translate(x, y, z)
+            .multiply(matrix)
+            .multiply(&Matrix3D::translate(-x, -y, -z))
+    }
+}
+
+/// The initial value, ie. `50% 50% 0`.
+impl Default for TransformOrigin {
+    fn default() -> Self {
+        TransformOrigin {
+            x: Length::new(50.0, LengthUnit::Percent),
+            y: Length::new(50.0, LengthUnit::Percent),
+            z: Length::new(0.0, LengthUnit::Px),
+        }
+    }
+}
+
+/// A `transform-origin` offset.
+enum Position {
+    /// `left` or `right`.
+    Horizontal(Length),
+    /// `top` or `bottom`.
+    Vertical(Length),
+    /// `center`.
+    Center(Length),
+    /// A length or a percentage.
+    Length(Length),
+}
+
+impl Position {
+    fn from_component(component: &ComponentValue<'_>) -> Option<Self> {
+        let percent = |value| Length::new(value, LengthUnit::Percent);
+        let position = match component.as_ident() {
+            Some(name) if name.eq_ignore_ascii_case("left") => Position::Horizontal(percent(0.0)),
+            Some(name) if name.eq_ignore_ascii_case("right") => {
+                Position::Horizontal(percent(100.0))
+            }
+            Some(name) if name.eq_ignore_ascii_case("top") => Position::Vertical(percent(0.0)),
+            Some(name) if name.eq_ignore_ascii_case("bottom") => Position::Vertical(percent(100.0)),
+            Some(name) if name.eq_ignore_ascii_case("center") => Position::Center(percent(50.0)),
+            Some(_) => return None,
+            None => Position::Length(Length::from_component(component)?),
+        };
+
+        Some(position)
+    }
+}
+
+fn parse_function(func: &Function<'_>) -> Option<TransformFunction> {
+    let args: Vec<_> = func
+        .split_commas()
+        .map(|arg| match arg {
+            [component] => Some(component),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+
+    let name = func.name.to_ascii_lowercase();
+    let zero = Length::new(0.0, LengthUnit::Px);
+    let f = match (name.as_str(), args.as_slice()) {
+        ("matrix", [a, b, c, d, e, f]) => TransformFunction::Matrix(Matrix2D::new(
+            number(a)?,
+            number(b)?,
+            number(c)?,
+            number(d)?,
+            number(e)?,
+            number(f)?,
+        )),
+        ("matrix3d", args) if args.len() == 16 => {
+            let mut m = [0.0; 16];
+            for (value, arg) in m.iter_mut().zip(args) {
+                *value = number(arg)?;
+            }
+            TransformFunction::Matrix3D(Matrix3D { m })
+        }
+        ("translate" | "translatex", [x]) => {
+            TransformFunction::Translate(Length::from_component(x)?, zero, zero)
+        }
+        ("translate", [x, y]) => TransformFunction::Translate(
+            Length::from_component(x)?,
+            Length::from_component(y)?,
+            zero,
+        ),
+        ("translatey", [y]) => TransformFunction::Translate(zero, Length::from_component(y)?, zero),
+        ("translatez", [z]) => TransformFunction::Translate(zero, zero, absolute_length(z)?),
+        ("translate3d", [x, y, z]) => TransformFunction::Translate(
+            Length::from_component(x)?,
+            Length::from_component(y)?,
+            absolute_length(z)?,
+        ),
+        ("scale", [x]) => {
+            let x = scale(x)?;
+            TransformFunction::Scale(x, x, 1.0)
+        }
+        ("scale", [x, y]) => TransformFunction::Scale(scale(x)?, scale(y)?, 1.0),
+        ("scalex", [x]) => TransformFunction::Scale(scale(x)?, 1.0, 1.0),
+        ("scaley", [y]) => TransformFunction::Scale(1.0, scale(y)?, 1.0),
+        ("scalez", [z]) => TransformFunction::Scale(1.0, 1.0, scale(z)?),
+        ("scale3d", [x, y, z]) => TransformFunction::Scale(scale(x)?, scale(y)?, scale(z)?),
+        ("rotate" | "rotatez", [angle]) => TransformFunction::Rotate(angle_or_zero(angle)?),
+        ("rotatex", [angle]) => TransformFunction::Rotate3D(1.0, 0.0, 0.0, angle_or_zero(angle)?),
+        ("rotatey", [angle]) => TransformFunction::Rotate3D(0.0, 1.0, 0.0, angle_or_zero(angle)?),
+        ("rotate3d", [x, y, z, angle]) => {
+            TransformFunction::Rotate3D(number(x)?, number(y)?, number(z)?, angle_or_zero(angle)?)
+        }
+        ("skew" | "skewx", [x]) => TransformFunction::Skew(angle_or_zero(x)?, 0.0),
+        ("skew", [x, y]) => TransformFunction::Skew(angle_or_zero(x)?, angle_or_zero(y)?),
+        ("skewy", [y]) => TransformFunction::Skew(0.0, angle_or_zero(y)?),
+        ("perspective", [distance]) if distance.is_ident("none") => {
+            TransformFunction::Perspective(None)
+        }
+        ("perspective", [distance]) => {
+            let distance = absolute_length(distance)?;
+            if distance.value < 0.0 {
+                return None;
+            }
+            TransformFunction::Perspective(Some(distance))
+        }
+        _ => return None,
+    };
+
+    Some(f)
+}
+
+fn number(component: &ComponentValue<'_>) -> Option<f64> {
+    match component {
+        ComponentValue::Number(n) => Some(*n),
+        ComponentValue::Function(func) => CalcNode::from_function(func)?.to_number(),
+        _ => None,
+    }
+}
+
+/// Parses `<number> | <percentage>`.
+fn scale(component: &ComponentValue<'_>) -> Option<f64> {
+    match component {
+        ComponentValue::Percentage(n) => Some(n / 100.0),
+        _ => number(component),
+    }
+}
+
+/// Parses `<angle> | <zero>`, in degrees.
+pub(crate) fn angle_or_zero(component: &ComponentValue<'_>) -> Option<f64> {
+    match component {
+        ComponentValue::Number(n) if *n == 0.0 => Some(0.0),
+        ComponentValue::Dimension(n, unit) => angle_to_degrees(*n, unit),
+        ComponentValue::Function(func) => CalcNode::from_function(func)?.to_degrees(),
+        _ => None,
+    }
+}
+
+/// Parses a length that is not a percentage.
+fn absolute_length(component: &ComponentValue<'_>) -> Option<Length> {
+    Length::from_component(component).filter(|length| length.unit != LengthUnit::Percent)
+}
+
+/// Resolves a length, using `base` for percentages.
+pub(crate) fn resolve(length: Length, context: &ResolveContext, base: f64) -> f64 {
+    let context = ResolveContext {
+        percentage_base: base,
+        ..*context
+    };
+    length.to_px(&context)
+}
+
+/// Returns the sine and cosine of an angle in degrees.
+///
+/// Multiples of 90 degrees are exact, so `rotate(90deg)` doesn't produce tiny skews.
+fn sin_cos(degrees: f64) -> (f64, f64) {
+    let remainder = degrees % 90.0;
+    if remainder == 0.0 {
+        #[allow(clippy::cast_possible_truncation)]
+        let quarter = (degrees / 90.0) as i64;
+        return match quarter.rem_euclid(4) {
+            0 => (0.0, 1.0),
+            1 => (1.0, 0.0),
+            2 => (0.0, -1.0),
+            _ => (-1.0, 0.0),
+        };
+    }
+
+    let radians = degrees.to_radians();
+    (radians.sin(), radians.cos())
+}
+
+/// Returns a rotation around a vector.
+///
+/// <https://www.w3.org/TR/css-transforms-2/#Rotate3dDefined>
+fn rotate_3d(x: f64, y: f64, z: f64, angle: f64) -> Matrix3D {
+    let length = (x * x + y * y + z * z).sqrt();
+    if length == 0.0 {
+        return Matrix3D::IDENTITY;
+    }
+
+    let (x, y, z) = (x / length, y / length, z / length);
+    let (sin, cos) = sin_cos(angle);
+    let t = 1.0 - cos;
+
+    // Columns of a rotation matrix that maps the X axis to the Y axis around Z.
+    Matrix3D {
+        m: [
+            cos + x * x * t,
+            y * x * t + z * sin,
+            z * x * t - y * sin,
+            0.0,
+            x * y * t - z * sin,
+            cos + y * y * t,
+            z * y * t + x * sin,
+            0.0,
+            x * z * t + y * sin,
+            y * z * t - x * sin,
+            cos + z * z * t,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            1.0,
+        ],
+    }
+}
@@ -0,0 +1,175 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::stream::write_string;
+
+use crate::value::{ValueToken, ValueTokenizer};
+use crate::Span;
+
+/// A URL reference kind.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum UrlKind {
+    /// `url(image.png)` or `url("image.png")`, anywhere in the style sheet.
+    Url,
+    /// A string in `src("image.png")`.
+    Src,
+    /// A string in `@import "style.css"`.
+    Import,
+    /// A string in `image-set("image.png" 1x)`.
+    ImageSet,
+}
+
+/// A URL reference in a style sheet.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct UrlReference<'a> {
+    /// The unescaped URL.
+    pub url: Cow<'a, str>,
+    /// The reference kind.
+    pub kind: UrlKind,
+    /// The span of the whole `url()` token or of the string.
+    pub span: Span,
+    /// Whether the URL is quoted. Always set for strings.
+    pub quoted: bool,
+}
+
+/// Finds all URL references in a style sheet.
+///
+/// Includes `url()` tokens in declarations and at-rules, like `@font-face` `src`,
+/// `@import` strings and strings in `src()` and `image-set()`.
+/// Comments and other strings are ignored.
+///
+/// # Example
+///
+/// ```
+/// use simplecss::{find_urls, UrlKind};
+///
+/// let text = "@import 'base.css'; a { background: url(\"a\\\\b.png\"), image-set('c.png' 2x) }";
+/// let urls = find_urls(text);
+/// assert_eq!(urls.len(), 3);
+/// assert_eq!((urls[0].kind, &*urls[0].url), (UrlKind::Import, "base.css"));
+/// assert_eq!((urls[1].kind, &*urls[1].url), (UrlKind::Url, "a\\b.png"));
+/// assert_eq!(urls[1].span.slice(text), "url(\"a\\\\b.png\")");
+/// assert_eq!((urls[2].kind, &*urls[2].url), (UrlKind::ImageSet, "c.png"));
+/// ```
+pub fn find_urls(text: &str) -> Vec<UrlReference<'_>> {
+    let mut urls = Vec::new();
+    let mut tokenizer = ValueTokenizer::from(text);
+    // Whether the current blocks are `image-set()` functions.
+    let mut image_sets = Vec::new();
+    let mut prev = [None, None];
+    while let Some((token, span)) = tokenizer.next_with_span() {
+        let kind = match &token {
+            ValueToken::Url(url, quoted) => Some((UrlKind::Url, url, *quoted)),
+            ValueToken::String(string) => match &prev {
+                [_, Some(ValueToken::Function(name))] if name.eq_ignore_ascii_case("src") => {
+                    Some((UrlKind::Src, string, true))
+                }
+                [Some(ValueToken::Delim('@')), Some(ValueToken::Ident(name))]
+                    if name.eq_ignore_ascii_case("import") =>
+                {
+                    Some((UrlKind::Import, string, true))
+                }
+                [_, Some(ValueToken::Function(_) | ValueToken::Comma)]
+                    if image_sets.last() == Some(&true) =>
+                {
+                    Some((UrlKind::ImageSet, string, true))
+                }
+                _ => None,
+            },
+            ValueToken::Function(name) => {
+                image_sets.push(is_image_set(name));
+                None
+            }
+            ValueToken::OpenBracket(_) => {
+                image_sets.push(false);
+                None
+            }
+            ValueToken::CloseBracket(_) => {
+                image_sets.pop();
+                None
+            }
+            _ => None,
+        };
+
+        if let Some((kind, url, quoted)) = kind {
+            urls.push(UrlReference {
+                url: url.clone(),
+                kind,
+                span,
+                quoted,
+            });
+        }
+
+        let [_, last] = prev;
+        prev = [last, Some(token)];
+    }
+
+    urls
+}
+
+/// Replaces URL references in a style sheet.
+///
+/// `map` is called for each reference found by [`find_urls`] and returns a new URL,
+/// or `None` to keep the reference as is. New URLs are quoted and escaped.
+/// The rest of the text is kept as is.
+///
+/// # Example
+///
+/// ```
+/// use simplecss::rewrite_urls;
+///
+/// let text = "@import 'base.css'; a { background: url(a.png) } b { background: url(b.png) }";
+/// let text = rewrite_urls(text, |r| (r.url == "a.png").then(|| "/assets/a \"1\".png".to_string()));
+/// assert_eq!(
+///     text,
+///     "@import 'base.css'; a { background: url(\"/assets/a \\\"1\\\".png\") } b { background: url(b.png) }"
+/// );
+/// ```
+pub fn rewrite_urls<F>(text: &str, mut map: F) -> String
+where
+    F: FnMut(&UrlReference<'_>) -> Option<String>,
+{
+    let mut result = String::with_capacity(text.len());
+    let mut last = 0;
+    for reference in find_urls(text) {
+        let Some(url) = map(&reference) else {
+            continue;
+        };
+
+        result.push_str(&text[last..reference.span.start]);
+        last = reference.span.end;
+
+        if reference.kind == UrlKind::Url {
+            if !reference.quoted && is_safe_unquoted(&url) {
+                let _ = write!(result, "url({})", url);
+            } else {
+                result.push_str("url(");
+                let _ = write_string(&mut result, &url);
+                result.push(')');
+            }
+        } else {
+            let _ = write_string(&mut result, &url);
+        }
+    }
+
+    result.push_str(&text[last..]);
+    result
+}
+
+fn is_image_set(name: &str) -> bool {
+    let name = name.strip_prefix("-webkit-").unwrap_or(name);
+    name.eq_ignore_ascii_case("image-set")
+}
+
+/// Checks that a URL can be written without quotes.
+fn is_safe_unquoted(url: &str) -> bool {
+    !url.is_empty()
+        && !url.chars().any(|c| {
+            matches!(c, '"' | '\'' | '(' | ')' | '\\') || c.is_whitespace() || c.is_control()
+        })
+}
@@ -0,0 +1,550 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::stream::{unescape, write_ident, write_name, write_string, Stream};
+use crate::{Error, Span};
+
+/// A bracket kind.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Bracket {
+    /// `()`
+    Parenthesis,
+    /// `[]`
+    Square,
+    /// `{}`
+    Curly,
+}
+
+impl Bracket {
+    fn from_open(c: u8) -> Option<Self> {
+        match c {
+            b'(' => Some(Bracket::Parenthesis),
+            b'[' => Some(Bracket::Square),
+            b'{' => Some(Bracket::Curly),
+            _ => None,
+        }
+    }
+
+    fn from_close(c: u8) -> Option<Self> {
+        match c {
+            b')' => Some(Bracket::Parenthesis),
+            b']' => Some(Bracket::Square),
+            b'}' => Some(Bracket::Curly),
+            _ => None,
+        }
+    }
+
+    /// Returns the opening char.
+    pub fn open(&self) -> char {
+        match self {
+            Bracket::Parenthesis => '(',
+            Bracket::Square => '[',
+            Bracket::Curly => '{',
+        }
+    }
+
+    /// Returns the closing char.
+    pub fn close(&self) -> char {
+        match self {
+            Bracket::Parenthesis => ')',
+            Bracket::Square => ']',
+            Bracket::Curly => '}',
+        }
+    }
+}
+
+/// A property value token.
+///
+/// Like other tokenizers, idents, strings and urls are unescaped.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ValueToken<'a> {
+    /// `auto`, `--name`
+    Ident(Cow<'a, str>),
+    /// `1.5`
+    Number(f64),
+    /// `50%`
+    ///
+    /// The value is not divided by 100.
+    Percentage(f64),
+    /// `10px`
+    Dimension(f64, Cow<'a, str>),
+    /// `"text"` or `'text'`, without quotes.
+    String(Cow<'a, str>),
+    /// `url(image.png)` or `url("image.png")`, without quotes.
+    ///
+    /// The flag is set when the URL is quoted.
+    Url(Cow<'a, str>, bool),
+    /// `#fff`, without `#`.
+    Hash(Cow<'a, str>),
+    /// `name(`
+    ///
+    /// The arguments are the following tokens, up to the matching
+    /// [`CloseBracket`](ValueToken::CloseBracket).
+    Function(Cow<'a, str>),
+    /// `(`, `[` or `{`
+    OpenBracket(Bracket),
+    /// `)`, `]` or `}`
+    CloseBracket(Bracket),
+    /// `,`
+    Comma,
+    /// `/`
+    Slash,
+    /// Any other char, like `+` or `!`.
+    Delim(char),
+}
+
+/// A property value tokenizer.
+///
+/// Whitespace and comments are skipped.
+///
+/// # Example
+///
+/// ```
+/// use simplecss::{ValueTokenizer, ValueToken};
+///
+/// let mut t = ValueTokenizer::from("1px solid rgb(0, 0, 0)");
+/// assert_eq!(t.next().unwrap().unwrap(), ValueToken::Dimension(1.0, "px".into()));
+/// assert_eq!(t.next().unwrap().unwrap(), ValueToken::Ident("solid".into()));
+/// assert_eq!(t.next().unwrap().unwrap(), ValueToken::Function("rgb".into()));
+/// assert_eq!(t.next().unwrap().unwrap(), ValueToken::Number(0.0));
+/// assert_eq!(t.next().unwrap().unwrap(), ValueToken::Comma);
+/// ```
+pub struct ValueTokenizer<'a> {
+    stream: Stream<'a>,
+    finished: bool,
+}
+
+impl<'a> From<&'a str> for ValueTokenizer<'a> {
+    fn from(text: &'a str) -> Self {
+        ValueTokenizer {
+            stream: Stream::from(text),
+            finished: false,
+        }
+    }
+}
+
+impl<'a> Iterator for ValueTokenizer<'a> {
+    type Item = Result<ValueToken<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        match self.consume_token() {
+            Ok(Some(token)) => Some(Ok(token)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl<'a> ValueTokenizer<'a> {
+    /// Returns the next token and its span.
+    ///
+    /// Unlike [`Iterator::next`], skips invalid tokens instead of stopping.
+    pub(crate) fn next_with_span(&mut self) -> Option<(ValueToken<'a>, Span)> {
+        loop {
+            // An unclosed comment lasts until the end.
+            self.stream.skip_spaces_and_comments().ok()?;
+
+            let start = self.stream;
+            match self.consume_token() {
+                Ok(Some(token)) => {
+                    return Some((token, Span::new(start.pos(), self.stream.pos())));
+                }
+                Ok(None) => return None,
+                Err(_) => {
+                    self.stream = start;
+                    let len = self
+                        .stream
+                        .slice_tail()
+                        .chars()
+                        .next()
+                        .map_or(1, char::len_utf8);
+                    self.stream.advance(len);
+                }
+            }
+        }
+    }
+
+    fn consume_token(&mut self) -> Result<Option<ValueToken<'a>>, Error> {
+        let s = &mut self.stream;
+        s.skip_spaces_and_comments()?;
+        if s.at_end() {
+            return Ok(None);
+        }
+
+        let c = s.curr_byte_unchecked();
+        if let Some(bracket) = Bracket::from_open(c) {
+            s.advance(1);
+            return Ok(Some(ValueToken::OpenBracket(bracket)));
+        }
+
+        if let Some(bracket) = Bracket::from_close(c) {
+            s.advance(1);
+            return Ok(Some(ValueToken::CloseBracket(bracket)));
+        }
+
+        let token = match c {
+            b',' => {
+                s.advance(1);
+                ValueToken::Comma
+            }
+            b'/' => {
+                s.advance(1);
+                ValueToken::Slash
+            }
+            b'"' | b'\'' => ValueToken::String(s.consume_string()?),
+            b'#' => {
+                s.advance(1);
+                match s.consume_name() {
+                    "" => ValueToken::Delim('#'),
+                    name => ValueToken::Hash(unescape(name)),
+                }
+            }
+            _ if at_number_start(s) => consume_numeric(s),
+            _ if s.at_ident_start() => {
+                let name = s.consume_any_ident()?;
+                if s.curr_byte() == Ok(b'(') {
+                    s.advance(1);
+                    if name.eq_ignore_ascii_case("url") {
+                        consume_url(s)?
+                    } else {
+                        ValueToken::Function(name)
+                    }
+                } else {
+                    ValueToken::Ident(name)
+                }
+            }
+            _ => {
+                let delim = s.slice_tail().chars().next().unwrap_or('\u{FFFD}');
+                s.advance(delim.len_utf8());
+                ValueToken::Delim(delim)
+            }
+        };
+
+        Ok(Some(token))
+    }
+}
+
+fn at_number_start(s: &Stream<'_>) -> bool {
+    let bytes = s.slice_tail().as_bytes();
+    let bytes = match bytes.first() {
+        Some(b'+') | Some(b'-') => &bytes[1..],
+        _ => bytes,
+    };
+
+    match bytes {
+        [c, ..] if c.is_ascii_digit() => true,
+        [b'.', c, ..] => c.is_ascii_digit(),
+        _ => false,
+    }
+}
+
+/// Consumes a number, a percentage or a dimension. The stream must be at a number start.
+///
+/// <https://www.w3.org/TR/css-syntax-3/#consume-numeric-token>
+fn consume_numeric<'a>(s: &mut Stream<'a>) -> ValueToken<'a> {
+    let start = s.pos();
+    if matches!(s.curr_byte(), Ok(b'+') | Ok(b'-')) {
+        s.advance(1);
+    }
+
+    s.consume_bytes(|c| c.is_ascii_digit());
+    if s.curr_byte() == Ok(b'.') && s.next_byte().map_or(false, |c| c.is_ascii_digit()) {
+        s.advance(1);
+        s.consume_bytes(|c| c.is_ascii_digit());
+    }
+
+    // An exponent, but not an `em` unit.
+    if matches!(s.curr_byte(), Ok(b'e') | Ok(b'E')) {
+        let tail = s.slice_tail().as_bytes();
+        let digits = match tail.get(1) {
+            Some(b'+') | Some(b'-') => 2,
+            _ => 1,
+        };
+
+        if tail.get(digits).map_or(false, |c| c.is_ascii_digit()) {
+            s.advance(digits);
+            s.consume_bytes(|c| c.is_ascii_digit());
+        }
+    }
+
+    let number = s.slice_back(start).parse().unwrap_or(0.0);
+
+    if s.curr_byte() == Ok(b'%') {
+        s.advance(1);
+        ValueToken::Percentage(number)
+    } else if s.at_ident_start() {
+        match s.consume_any_ident() {
+            Ok(unit) => ValueToken::Dimension(number, unit),
+            Err(_) => ValueToken::Number(number),
+        }
+    } else {
+        ValueToken::Number(number)
+    }
+}
+
+/// Consumes an `url()` content. The stream must be right after `url(`.
+fn consume_url<'a>(s: &mut Stream<'a>) -> Result<ValueToken<'a>, Error> {
+    s.skip_spaces();
+
+    let quoted = matches!(s.curr_byte(), Ok(b'"') | Ok(b'\''));
+    let url = if quoted {
+        let url = s.consume_string()?;
+        s.skip_spaces();
+        url
+    } else {
+        let start = s.pos();
+        while !s.at_end() {
+            match s.curr_byte_unchecked() {
+                b')' => break,
+                // Skip the escaped byte, multi-byte chars are skipped by the following iterations.
+                b'\\' => s.advance(2.min(s.slice_tail().len())),
+                _ => s.advance(1),
+            }
+        }
+
+        let url = s.slice_back(start);
+        unescape(url.trim_end_matches([' ', '\t', '\n', '\r', '\x0C']))
+    };
+
+    if s.curr_byte() == Ok(b')') {
+        s.advance(1);
+        Ok(ValueToken::Url(url, quoted))
+    } else if s.at_end() {
+        Ok(ValueToken::Url(url, quoted))
+    } else {
+        Err(Error::InvalidValue(s.gen_text_pos()))
+    }
+}
+
+/// A component value.
+///
+/// <https://www.w3.org/TR/css-syntax-3/#component-value>
+#[derive(Clone, PartialEq, Debug)]
+pub enum ComponentValue<'a> {
+    /// `auto`, `--name`
+    Ident(Cow<'a, str>),
+    /// `1.5`
+    Number(f64),
+    /// `50%`
+    ///
+    /// The value is not divided by 100.
+    Percentage(f64),
+    /// `10px`
+    Dimension(f64, Cow<'a, str>),
+    /// `"text"` or `'text'`, without quotes.
+    String(Cow<'a, str>),
+    /// `url(image.png)` or `url("image.png")`, without quotes.
+    Url(Cow<'a, str>),
+    /// `#fff`, without `#`.
+    Hash(Cow<'a, str>),
+    /// `name(arguments)`
+    Function(Function<'a>),
+    /// `(...)`, `[...]` or `{...}`
+    Block(Bracket, Vec<ComponentValue<'a>>),
+    /// `,`
+    Comma,
+    /// `/`
+    Slash,
+    /// Any other char, like `+` or `!`.
+    Delim(char),
+}
+
+/// A function component value.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Function<'a> {
+    /// The function name, unescaped.
+    pub name: Cow<'a, str>,
+    /// The function arguments, including separators.
+    pub arguments: Vec<ComponentValue<'a>>,
+}
+
+impl<'a> Function<'a> {
+    /// Splits the arguments by commas.
+    pub fn split_commas(&self) -> impl Iterator<Item = &[ComponentValue<'a>]> {
+        split_commas(&self.arguments)
+    }
+}
+
+impl ComponentValue<'_> {
+    /// Returns the ident, if any.
+    pub fn as_ident(&self) -> Option<&str> {
+        match self {
+            ComponentValue::Ident(ident) => Some(ident),
+            _ => None,
+        }
+    }
+
+    /// Returns the number, if any.
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            ComponentValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Checks that the component is an ident, ignoring ASCII case.
+    pub fn is_ident(&self, name: &str) -> bool {
+        self.as_ident()
+            .map_or(false, |ident| ident.eq_ignore_ascii_case(name))
+    }
+}
+
+/// A parsed property value.
+///
+/// # Example
+///
+/// ```
+/// use simplecss::{ComponentValue, Value};
+///
+/// let value = Value::parse("bold 16px/1.5 \"Open Sans\", serif").unwrap();
+/// assert_eq!(value.components[1], ComponentValue::Dimension(16.0, "px".into()));
+/// assert_eq!(value.split_commas().count(), 2);
+/// assert_eq!(value.to_string(), "bold 16px / 1.5 \"Open Sans\", serif");
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct Value<'a> {
+    /// Top-level component values.
+    pub components: Vec<ComponentValue<'a>>,
+}
+
+impl<'a> Value<'a> {
+    /// Parses a value.
+    ///
+    /// Unclosed functions and blocks are closed at the end of the text,
+    /// unmatched closing brackets are returned as [`ComponentValue::Delim`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on unclosed strings and comments and on malformed `url()`.
+    pub fn parse(text: &'a str) -> Result<Self, Error> {
+        let mut tokenizer = ValueTokenizer::from(text);
+        let components = parse_components(&mut tokenizer, None)?;
+        Ok(Value { components })
+    }
+
+    /// Splits the top-level components by commas.
+    pub fn split_commas(&self) -> impl Iterator<Item = &[ComponentValue<'a>]> {
+        split_commas(&self.components)
+    }
+}
+
+fn split_commas<'a, 'b>(
+    components: &'b [ComponentValue<'a>],
+) -> impl Iterator<Item = &'b [ComponentValue<'a>]> {
+    // An empty value has no items, not a single empty one.
+    let count = if components.is_empty() { 0 } else { usize::MAX };
+    components
+        .split(|c| *c == ComponentValue::Comma)
+        .take(count)
+}
+
+fn parse_components<'a>(
+    tokenizer: &mut ValueTokenizer<'a>,
+    close: Option<Bracket>,
+) -> Result<Vec<ComponentValue<'a>>, Error> {
+    let mut components = Vec::new();
+    while let Some(token) = tokenizer.next() {
+        let component = match token? {
+            ValueToken::Ident(v) => ComponentValue::Ident(v),
+            ValueToken::Number(v) => ComponentValue::Number(v),
+            ValueToken::Percentage(v) => ComponentValue::Percentage(v),
+            ValueToken::Dimension(v, unit) => ComponentValue::Dimension(v, unit),
+            ValueToken::String(v) => ComponentValue::String(v),
+            ValueToken::Url(v, _) => ComponentValue::Url(v),
+            ValueToken::Hash(v) => ComponentValue::Hash(v),
+            ValueToken::Function(name) => ComponentValue::Function(Function {
+                name,
+                arguments: parse_components(tokenizer, Some(Bracket::Parenthesis))?,
+            }),
+            ValueToken::OpenBracket(bracket) => {
+                let content = parse_components(tokenizer, Some(bracket))?;
+                ComponentValue::Block(bracket, content)
+            }
+            ValueToken::CloseBracket(bracket) if Some(bracket) == close => break,
+            ValueToken::CloseBracket(bracket) => ComponentValue::Delim(bracket.close()),
+            ValueToken::Comma => ComponentValue::Comma,
+            ValueToken::Slash => ComponentValue::Slash,
+            ValueToken::Delim(c) => ComponentValue::Delim(c),
+        };
+
+        components.push(component);
+    }
+
+    Ok(components)
+}
+
+impl fmt::Display for Value<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_components(f, &self.components)
+    }
+}
+
+impl fmt::Display for ComponentValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComponentValue::Ident(v) => write_ident(f, v),
+            ComponentValue::Number(v) => write!(f, "{}", v),
+            ComponentValue::Percentage(v) => write!(f, "{}%", v),
+            ComponentValue::Dimension(v, unit) => {
+                write!(f, "{}", v)?;
+                // A unit like `e3` would be read back as an exponent.
+                if unit.starts_with(['e', 'E']) {
+                    write!(f, "\\{:x} ", unit.as_bytes()[0])?;
+                    write_name(f, &unit[1..])
+                } else {
+                    write_ident(f, unit)
+                }
+            }
+            ComponentValue::String(v) => write_string(f, v),
+            ComponentValue::Url(v) => {
+                write!(f, "url(")?;
+                write_string(f, v)?;
+                write!(f, ")")
+            }
+            ComponentValue::Hash(v) => {
+                write!(f, "#")?;
+                write_name(f, v)
+            }
+            ComponentValue::Function(func) => {
+                write_ident(f, &func.name)?;
+                write!(f, "(")?;
+                write_components(f, &func.arguments)?;
+                write!(f, ")")
+            }
+            ComponentValue::Block(bracket, content) => {
+                write!(f, "{}", bracket.open())?;
+                write_components(f, content)?;
+                write!(f, "{}", bracket.close())
+            }
+            ComponentValue::Comma => write!(f, ","),
+            ComponentValue::Slash => write!(f, "/"),
+            ComponentValue::Delim(c) => write!(f, "{}", c),
+        }
+    }
+}
+
+fn write_components(f: &mut fmt::Formatter<'_>, components: &[ComponentValue<'_>]) -> fmt::Result {
+    for (i, component) in components.iter().enumerate() {
+        if i != 0 && *component != ComponentValue::Comma {
+            write!(f, " ")?;
+        }
+
+        write!(f, "{}", component)?;
+    }
+
+    Ok(())
+}
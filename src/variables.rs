@@ -0,0 +1,320 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::stream::{unescape, Stream};
+use crate::{skip_component_values, Declaration};
+
+/// Substituted values longer than this are invalid.
+///
+/// Prevents exponential growth of values like `--b: var(--a) var(--a)`.
+const MAX_VALUE_LEN: usize = 1 << 20;
+
+/// The maximum number of bytes produced by a single substitution, across all values.
+///
+/// Bounds the work when many custom properties are close to [`MAX_VALUE_LEN`].
+const MAX_WORK: usize = 16 * MAX_VALUE_LEN;
+
+/// A trait to provide environment variables for `env()`.
+pub trait Environment {
+    /// Returns an environment variable value, like `safe-area-inset-top`.
+    fn variable(&self, name: &str) -> Option<&str>;
+}
+
+/// An environment without variables.
+impl Environment for () {
+    fn variable(&self, _: &str) -> Option<&str> {
+        None
+    }
+}
+
+/// Computed custom property values, used for `var()` substitution.
+///
+/// Values are stored as specified and substituted on demand.
+///
+/// # Example
+///
+/// ```
+/// use simplecss::{CustomProperties, DeclarationTokenizer};
+///
+/// let declarations: Vec<_> = DeclarationTokenizer::from(
+///     "--gap: 4px; --double: calc(var(--gap) * 2); --a: var(--b); --b: var(--a)",
+/// )
+/// .collect();
+/// let properties = CustomProperties::from_declarations(&declarations);
+///
+/// let value = properties.substitute("var(--double) var(--gap)").unwrap();
+/// assert_eq!(value, "calc(4px * 2) 4px");
+///
+/// // `--a` and `--b` are a cycle, so the fallback is used.
+/// assert_eq!(properties.substitute("var(--a, 0)").unwrap(), "0");
+/// assert_eq!(properties.substitute("var(--a)"), None);
+/// ```
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct CustomProperties<'a> {
+    values: BTreeMap<Cow<'a, str>, &'a str>,
+}
+
+impl<'a> CustomProperties<'a> {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collects custom properties from a declaration block.
+    ///
+    /// Later declarations override earlier ones, unless the earlier ones are `!important`.
+    /// Other properties are ignored.
+    pub fn from_declarations(declarations: &[Declaration<'a>]) -> Self {
+        let mut properties = Self::new();
+        for important in [false, true] {
+            for declaration in declarations {
+                if declaration.important == important && declaration.is_custom_property() {
+                    properties.insert(declaration.name.clone(), declaration.value);
+                }
+            }
+        }
+
+        properties
+    }
+
+    /// Sets a custom property value.
+    ///
+    /// The name must include the leading `--` and must be unescaped.
+    pub fn insert(&mut self, name: impl Into<Cow<'a, str>>, value: &'a str) {
+        self.values.insert(name.into(), value);
+    }
+
+    /// Returns a custom property value, as specified.
+    pub fn get(&self, name: &str) -> Option<&'a str> {
+        self.values.get(name).copied()
+    }
+
+    /// Returns the number of custom properties.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Checks that there are no custom properties.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Substitutes `var()` functions in a value.
+    ///
+    /// `env()` functions resolve to their fallbacks.
+    /// See [`CustomProperties::substitute_with_env`].
+    pub fn substitute<'v>(&self, value: &'v str) -> Option<Cow<'v, str>> {
+        self.substitute_with_env(value, &())
+    }
+
+    /// Substitutes `var()` and `env()` functions in a value.
+    ///
+    /// Returns `None` when the value is invalid at computed-value time, ie. when it references
+    /// a missing or an invalid variable without a fallback. Custom properties that reference
+    /// each other in a cycle are invalid. So are values that grow too long after substitution,
+    /// regardless of fallbacks.
+    ///
+    /// Each custom property is resolved at most once per call.
+    ///
+    /// Values without substitutions are returned as is.
+    pub fn substitute_with_env<'v>(
+        &self,
+        value: &'v str,
+        env: &dyn Environment,
+    ) -> Option<Cow<'v, str>> {
+        if !has_substitutions(value) {
+            return Some(Cow::Borrowed(value));
+        }
+
+        let mut resolver = Resolver {
+            properties: self,
+            env,
+            stack: Vec::new(),
+            resolved: BTreeMap::new(),
+            work: 0,
+        };
+
+        resolver.substitute(value).ok().map(Cow::Owned)
+    }
+}
+
+/// A substitution failure.
+enum Failure {
+    /// The value is invalid.
+    Invalid,
+    /// The value is a part of a cycle, which starts at the specified stack index.
+    Cycle(usize),
+    /// The substitution is too long. Fallbacks are not used, so that the result
+    /// doesn't depend on where the limit was reached.
+    TooLong,
+}
+
+struct Resolver<'p, 'a> {
+    properties: &'p CustomProperties<'a>,
+    env: &'p dyn Environment,
+    /// The custom properties being resolved.
+    stack: Vec<&'p str>,
+    /// Already resolved custom properties, `None` when invalid.
+    resolved: BTreeMap<&'p str, Option<String>>,
+    /// The number of bytes produced so far.
+    work: usize,
+}
+
+impl Resolver<'_, '_> {
+    fn substitute(&mut self, value: &str) -> Result<String, Failure> {
+        let mut result = String::new();
+        let mut s = Stream::from(value);
+        let mut last = 0;
+        while !s.at_end() {
+            let start = s.pos();
+            let kind = match s.curr_byte_unchecked() {
+                b'"' | b'\'' => {
+                    s.skip_string();
+                    continue;
+                }
+                b'/' if s.next_byte() == Ok(b'*') => {
+                    let _ = s.skip_comment();
+                    continue;
+                }
+                c if is_name_byte(c) => {
+                    let name = s.consume_name();
+                    if name.is_empty() {
+                        // An invalid escape or a char that is not allowed in names.
+                        skip_char(&mut s);
+                        continue;
+                    }
+
+                    if s.curr_byte() != Ok(b'(') {
+                        continue;
+                    }
+
+                    if name.eq_ignore_ascii_case("var") {
+                        Function::Var
+                    } else if name.eq_ignore_ascii_case("env") {
+                        Function::Env
+                    } else {
+                        continue;
+                    }
+                }
+                _ => {
+                    skip_char(&mut s);
+                    continue;
+                }
+            };
+
+            // Skip `(`.
+            s.advance(1);
+            let arguments_start = s.pos();
+            skip_component_values(&mut s, |c| c == b')');
+            let arguments = s.slice_range(arguments_start, s.pos());
+            s.try_consume_byte(b')');
+
+            let substituted = self.function(kind, arguments)?;
+            self.work += start - last + substituted.len();
+            result.push_str(&value[last..start]);
+            result.push_str(&substituted);
+            last = s.pos();
+
+            if result.len() > MAX_VALUE_LEN || self.work > MAX_WORK {
+                return Err(Failure::TooLong);
+            }
+        }
+
+        result.push_str(&value[last..]);
+        Ok(result)
+    }
+
+    fn function(&mut self, kind: Function, arguments: &str) -> Result<String, Failure> {
+        let mut s = Stream::from(arguments);
+        skip_component_values(&mut s, |c| c == b',');
+        let name = unescape(s.slice_range(0, s.pos()).trim());
+        let fallback = if s.at_end() {
+            None
+        } else {
+            Some(s.slice_range(s.pos() + 1, arguments.len()).trim())
+        };
+
+        let resolved = match kind {
+            Function::Var => self.variable(&name)?,
+            Function::Env => self.env.variable(&name).map(String::from),
+        };
+
+        match (resolved, fallback) {
+            (Some(value), _) => Ok(value),
+            (None, Some(fallback)) => self.substitute(fallback),
+            (None, None) => Err(Failure::Invalid),
+        }
+    }
+
+    /// Resolves a custom property.
+    ///
+    /// Returns `None` when the property is missing or invalid.
+    fn variable(&mut self, name: &str) -> Result<Option<String>, Failure> {
+        if !name.starts_with("--") {
+            return Err(Failure::Invalid);
+        }
+
+        let Some((name, value)) = self.properties.values.get_key_value(name) else {
+            return Ok(None);
+        };
+        let name = name.as_ref();
+
+        if let Some(resolved) = self.resolved.get(name) {
+            return Ok(resolved.clone());
+        }
+
+        if let Some(index) = self.stack.iter().position(|n| *n == name) {
+            return Err(Failure::Cycle(index));
+        }
+
+        self.stack.push(name);
+        let result = self.substitute(value);
+        self.stack.pop();
+
+        match result {
+            Ok(substituted) => {
+                self.resolved.insert(name, Some(substituted.clone()));
+                Ok(Some(substituted))
+            }
+            Err(Failure::TooLong) => Err(Failure::TooLong),
+            Err(failure) => {
+                // Properties in a cycle are invalid no matter where they are referenced from.
+                self.resolved.insert(name, None);
+                match failure {
+                    Failure::Cycle(index) if index < self.stack.len() => Err(failure),
+                    // The cycle starts at this property, so the referencing value
+                    // is not a part of it and can use a fallback.
+                    _ => Ok(None),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Function {
+    Var,
+    Env,
+}
+
+fn is_name_byte(c: u8) -> bool {
+    matches!(c, b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_' | b'-' | b'\\') || !c.is_ascii()
+}
+
+fn skip_char(s: &mut Stream<'_>) {
+    let len = s.slice_tail().chars().next().map_or(1, char::len_utf8);
+    s.advance(len);
+}
+
+/// Checks that a value contains `var()` or `env()` functions.
+pub(crate) fn has_substitutions(value: &str) -> bool {
+    value
+        .as_bytes()
+        .windows(4)
+        .any(|w| w.eq_ignore_ascii_case(b"var(") || w.eq_ignore_ascii_case(b"env("))
+}
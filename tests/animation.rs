@@ -0,0 +1,393 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Animations, transitions and easing functions
+
+#![cfg(any(feature = "std", feature = "libm"))]
+
+use simplecss::*;
+
+fn easing(text: &str) -> EasingFunction {
+    EasingFunction::parse(text).unwrap()
+}
+
+fn points(text: &str) -> Vec<(f64, f64)> {
+    match easing(text) {
+        EasingFunction::Points(points) => points.iter().map(|p| (p.input, p.output)).collect(),
+        other => panic!("{other:?}"),
+    }
+}
+
+fn declarations(text: &str) -> Vec<Declaration<'_>> {
+    DeclarationTokenizer::from(text).collect()
+}
+
+fn assert_close(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-6, "{a} != {b}");
+}
+
+#[test]
+fn easing_keywords() {
+    assert_eq!(easing("linear"), EasingFunction::Linear);
+    assert_eq!(easing("EASE"), EasingFunction::EASE);
+    assert_eq!(
+        easing("ease-in"),
+        EasingFunction::CubicBezier(0.42, 0.0, 1.0, 1.0)
+    );
+    assert_eq!(easing("ease-out"), EasingFunction::EASE_OUT);
+    assert_eq!(easing("ease-in-out"), EasingFunction::EASE_IN_OUT);
+    assert_eq!(
+        easing("step-start"),
+        EasingFunction::Steps(1, StepPosition::JumpStart)
+    );
+    assert_eq!(
+        easing("step-end"),
+        EasingFunction::Steps(1, StepPosition::JumpEnd)
+    );
+    assert_eq!(EasingFunction::default(), EasingFunction::EASE);
+    assert_eq!(EasingFunction::parse("fast"), None);
+    assert_eq!(EasingFunction::parse("ease ease"), None);
+}
+
+#[test]
+fn cubic_bezier() {
+    assert_eq!(
+        easing("cubic-bezier(0.1, -0.5, 1, 1.5)"),
+        EasingFunction::CubicBezier(0.1, -0.5, 1.0, 1.5)
+    );
+    assert_eq!(
+        easing("cubic-bezier(0, 0, calc(1 / 2), 1)"),
+        EasingFunction::CubicBezier(0.0, 0.0, 0.5, 1.0)
+    );
+    assert_eq!(EasingFunction::parse("cubic-bezier(1.1, 0, 1, 1)"), None);
+    assert_eq!(EasingFunction::parse("cubic-bezier(0, 0, -0.1, 1)"), None);
+    assert_eq!(EasingFunction::parse("cubic-bezier(0, 0, 1)"), None);
+    assert_eq!(EasingFunction::parse("cubic-bezier(0 0 1 1)"), None);
+    assert_eq!(EasingFunction::parse("cubic-bezier(0, 0, 1, 1px)"), None);
+}
+
+#[test]
+fn cubic_bezier_evaluate() {
+    let ease = EasingFunction::EASE;
+    assert_close(ease.evaluate(0.0), 0.0);
+    assert_close(ease.evaluate(1.0), 1.0);
+    assert!((ease.evaluate(0.5) - 0.8024).abs() < 1e-4);
+
+    // `ease-out` is `ease-in` rotated by 180 degrees.
+    for i in 0..=10 {
+        let t = f64::from(i) / 10.0;
+        assert_close(
+            EasingFunction::EASE_IN.evaluate(t),
+            1.0 - EasingFunction::EASE_OUT.evaluate(1.0 - t),
+        );
+    }
+
+    let linear = easing("cubic-bezier(0.3, 0.3, 0.7, 0.7)");
+    assert_close(linear.evaluate(0.25), 0.25);
+
+    // Progress outside of the curve follows the end tangents.
+    let overshoot = easing("cubic-bezier(0.5, -0.5, 0.5, 1.5)");
+    assert_close(overshoot.evaluate(-1.0), 1.0);
+    assert_close(overshoot.evaluate(2.0), 0.0);
+    assert_close(easing("cubic-bezier(0, 0, 1, 1)").evaluate(-1.0), -1.0);
+    assert_close(easing("cubic-bezier(0, 0.5, 1, 1)").evaluate(-1.0), 0.0);
+}
+
+#[test]
+fn steps() {
+    assert_eq!(
+        easing("steps(4)"),
+        EasingFunction::Steps(4, StepPosition::JumpEnd)
+    );
+    assert_eq!(
+        easing("steps(2, start)"),
+        EasingFunction::Steps(2, StepPosition::JumpStart)
+    );
+    assert_eq!(
+        easing("steps(2, jump-both)"),
+        EasingFunction::Steps(2, StepPosition::JumpBoth)
+    );
+    assert_eq!(
+        easing("steps(2, jump-none)"),
+        EasingFunction::Steps(2, StepPosition::JumpNone)
+    );
+    assert_eq!(EasingFunction::parse("steps(0)"), None);
+    assert_eq!(EasingFunction::parse("steps(1, jump-none)"), None);
+    assert_eq!(EasingFunction::parse("steps(1.5)"), None);
+    assert_eq!(EasingFunction::parse("steps(2, middle)"), None);
+    assert_eq!(EasingFunction::parse("steps()"), None);
+}
+
+#[test]
+fn steps_evaluate() {
+    let end = easing("steps(4)");
+    assert_eq!(end.evaluate(0.0), 0.0);
+    assert_eq!(end.evaluate(0.3), 0.25);
+    assert_eq!(end.evaluate(0.99), 0.75);
+    assert_eq!(end.evaluate(1.0), 1.0);
+
+    let start = easing("step-start");
+    assert_eq!(start.evaluate(0.0), 1.0);
+    assert_eq!(start.evaluate(0.5), 1.0);
+    assert_eq!(start.evaluate(-0.5), 0.0);
+
+    let both = easing("steps(3, jump-both)");
+    assert_eq!(both.evaluate(0.0), 0.25);
+    assert_eq!(both.evaluate(0.5), 0.5);
+    assert_eq!(both.evaluate(1.0), 1.0);
+
+    let none = easing("steps(3, jump-none)");
+    assert_eq!(none.evaluate(0.0), 0.0);
+    assert_eq!(none.evaluate(0.5), 0.5);
+    assert_eq!(none.evaluate(1.0), 1.0);
+}
+
+#[test]
+fn linear_points() {
+    assert_eq!(points("linear(0, 1)"), [(0.0, 0.0), (1.0, 1.0)]);
+    assert_eq!(
+        points("linear(0, 0.25, 1)"),
+        [(0.0, 0.0), (0.5, 0.25), (1.0, 1.0)]
+    );
+    assert_eq!(
+        points("linear(0, 0.5 25% 75%, 1)"),
+        [(0.0, 0.0), (0.25, 0.5), (0.75, 0.5), (1.0, 1.0)]
+    );
+    // Percentages can come first.
+    assert_eq!(
+        points("linear(0, 20% 0.5, 1)"),
+        [(0.0, 0.0), (0.2, 0.5), (1.0, 1.0)]
+    );
+    // Inputs cannot decrease.
+    assert_eq!(points("linear(0 50%, 1 20%)"), [(0.5, 0.0), (0.5, 1.0)]);
+    // Missing inputs are spread evenly.
+    assert_eq!(
+        points("linear(0 20%, 0.1, 0.2, 1 80%)"),
+        [
+            (0.2, 0.0),
+            (0.4, 0.1),
+            (0.6000000000000001, 0.2),
+            (0.8, 1.0)
+        ]
+    );
+    assert_eq!(EasingFunction::parse("linear(0)"), None);
+    assert_eq!(EasingFunction::parse("linear()"), None);
+    assert_eq!(EasingFunction::parse("linear(0, 1 10% 20% 30%)"), None);
+    assert_eq!(EasingFunction::parse("linear(0, 10%)"), None);
+    assert_eq!(EasingFunction::parse("linear(0 1, 1)"), None);
+    assert_eq!(EasingFunction::parse("linear(0, 50% 1 60%)"), None);
+}
+
+#[test]
+fn linear_points_evaluate() {
+    let identity = easing("linear(0, 1)");
+    assert_eq!(identity.evaluate(0.5), 0.5);
+    assert_eq!(identity.evaluate(-1.0), -1.0);
+    assert_eq!(identity.evaluate(2.0), 2.0);
+
+    let jump = easing("linear(0, 0 50%, 1 50%, 1)");
+    assert_eq!(jump.evaluate(0.25), 0.0);
+    assert_eq!(jump.evaluate(0.5), 1.0);
+    assert_eq!(jump.evaluate(0.75), 1.0);
+
+    let bounce = easing("linear(0, 1.2 60%, 1)");
+    assert_close(bounce.evaluate(0.8), 1.1);
+    assert_eq!(bounce.evaluate(1.0), 1.0);
+
+    assert_eq!(EasingFunction::Points(Vec::new()).evaluate(0.3), 0.3);
+    assert_eq!(EasingFunction::Linear.evaluate(0.3), 0.3);
+}
+
+#[test]
+fn animation_list() {
+    let list =
+        Animation::parse_list("spin 1s linear 200ms infinite alternate forwards paused").unwrap();
+    assert_eq!(
+        list,
+        [Animation {
+            name: Some("spin".into()),
+            duration: 1.0,
+            timing_function: EasingFunction::Linear,
+            delay: 0.2,
+            iteration_count: IterationCount::Infinite,
+            direction: AnimationDirection::Alternate,
+            fill_mode: FillMode::Forwards,
+            play_state: PlayState::Paused,
+        }]
+    );
+
+    let quoted = Animation::parse_list("'fade' 2.5 reverse, none").unwrap();
+    assert_eq!(quoted[0].name.as_deref(), Some("fade"));
+    assert_eq!(quoted[0].iteration_count, IterationCount::Count(2.5));
+    assert_eq!(quoted[0].direction, AnimationDirection::Reverse);
+    assert_eq!(quoted[1], Animation::default());
+}
+
+#[test]
+fn animation_keywords_before_name() {
+    // The first `none` is the fill mode, the second one is the name.
+    let nones = Animation::parse_list("none none").unwrap();
+    assert_eq!(nones[0].name, None);
+    assert_eq!(nones[0].fill_mode, FillMode::None);
+
+    let reversed = Animation::parse_list("reverse both reverse").unwrap();
+    assert_eq!(reversed[0].name.as_deref(), Some("reverse"));
+    assert_eq!(reversed[0].direction, AnimationDirection::Reverse);
+    assert_eq!(reversed[0].fill_mode, FillMode::Both);
+
+    let auto = Animation::parse_list("auto steps(2) calc(1s / 2)").unwrap();
+    assert_eq!(auto[0].duration, 0.0);
+    assert_eq!(
+        auto[0].timing_function,
+        EasingFunction::Steps(2, StepPosition::JumpEnd)
+    );
+    assert_eq!(auto[0].delay, 0.5);
+}
+
+#[test]
+fn animation_invalid() {
+    assert_eq!(Animation::parse_list(""), None);
+    assert_eq!(Animation::parse_list("a 1s,"), None);
+    assert_eq!(Animation::parse_list("a b"), None);
+    assert_eq!(Animation::parse_list("a -1s"), None);
+    assert_eq!(Animation::parse_list("a 1s 2s 3s"), None);
+    assert_eq!(Animation::parse_list("inherit 1s"), None);
+    assert_eq!(Animation::parse_list("a 10px"), None);
+    assert_eq!(Animation::parse_list("a -1"), None);
+}
+
+#[test]
+fn animation_declarations() {
+    let text = "animation: a 1s, b 2s, c 3s; \
+                animation-delay: 1s, 2s; \
+                animation-timing-function: ease-in !important; \
+                animation-timing-function: linear; \
+                animation-direction: sideways; \
+                animation-iteration-count: 2, 3, 4, 5";
+    let list = Animation::from_declarations(&declarations(text));
+    assert_eq!(list.len(), 3);
+    assert_eq!(
+        list.iter().map(|a| a.delay).collect::<Vec<_>>(),
+        [1.0, 2.0, 1.0]
+    );
+    assert!(list
+        .iter()
+        .all(|a| a.timing_function == EasingFunction::EASE_IN));
+    assert!(list
+        .iter()
+        .all(|a| a.direction == AnimationDirection::Normal));
+    assert_eq!(list[2].iteration_count, IterationCount::Count(4.0));
+
+    // A shorthand resets the previous longhands.
+    let reset = Animation::from_declarations(&declarations(
+        "animation-name: x, y; animation-duration: 1s; animation: z",
+    ));
+    assert_eq!(
+        reset,
+        [Animation {
+            name: Some("z".into()),
+            ..Animation::default()
+        }]
+    );
+
+    // Names define the number of animations.
+    let named = Animation::from_declarations(&declarations(
+        "animation-duration: 1s, 2s; animation-name: none, b; COLOR: red",
+    ));
+    assert_eq!(named.len(), 2);
+    assert_eq!(named[0].name, None);
+    assert_eq!(named[1].name.as_deref(), Some("b"));
+    assert_eq!(named[1].duration, 2.0);
+
+    assert_eq!(Animation::from_declarations(&[]), [Animation::default()]);
+}
+
+#[test]
+#[cfg(feature = "at_rules")]
+fn animation_keyframes() {
+    let style = StyleSheet::parse(
+        "@keyframes spin { to { top: 0 } } \
+         @keyframes fade { from { opacity: 0 } } \
+         @keyframes spin { from { top: 1px } to { top: 2px } }",
+    );
+    let list = Animation::parse_list("spin 1s, fade 1s, Spin, none").unwrap();
+    let spin = list[0].keyframes(&style).unwrap();
+    assert_eq!(spin.len(), 2);
+    assert_eq!(spin[0].key, "from");
+    assert_eq!(
+        list[1].keyframes(&style).unwrap()[0].declarations[0].name,
+        "opacity"
+    );
+    assert!(list[2].keyframes(&style).is_none());
+    assert!(list[3].keyframes(&style).is_none());
+}
+
+#[test]
+fn transition_list() {
+    let list = Transition::parse_list("opacity 1s ease-out -500ms allow-discrete, all").unwrap();
+    assert_eq!(
+        list,
+        [
+            Transition {
+                property: TransitionProperty::Name("opacity".into()),
+                duration: 1.0,
+                timing_function: EasingFunction::EASE_OUT,
+                delay: -0.5,
+                allow_discrete: true,
+            },
+            Transition::default(),
+        ]
+    );
+
+    let duration_only = Transition::parse_list("2s").unwrap();
+    assert_eq!(duration_only[0].property, TransitionProperty::All);
+    assert_eq!(duration_only[0].duration, 2.0);
+
+    assert_eq!(
+        Transition::parse_list("none").unwrap()[0].property,
+        TransitionProperty::None
+    );
+    assert_eq!(Transition::parse_list("none, opacity"), None);
+    assert_eq!(Transition::parse_list("opacity width"), None);
+    assert_eq!(Transition::parse_list("opacity -1s"), None);
+    assert_eq!(Transition::parse_list("initial 1s"), None);
+    assert_eq!(Transition::parse_list("opacity,"), None);
+}
+
+#[test]
+fn transition_property() {
+    assert!(TransitionProperty::All.matches("color"));
+    assert!(!TransitionProperty::None.matches("color"));
+    assert!(TransitionProperty::Name("Color".into()).matches("color"));
+    assert!(!TransitionProperty::Name("color".into()).matches("background-color"));
+}
+
+#[test]
+fn transition_declarations() {
+    let text = "transition: opacity 1s; \
+                transition-property: opacity, transform, width; \
+                transition-duration: 1s, 2s; \
+                transition-behavior: allow-discrete; \
+                transition-delay: 1px";
+    let list = Transition::from_declarations(&declarations(text));
+    assert_eq!(list.len(), 3);
+    assert_eq!(
+        list[1].property,
+        TransitionProperty::Name("transform".into())
+    );
+    assert_eq!(
+        list.iter().map(|t| t.duration).collect::<Vec<_>>(),
+        [1.0, 2.0, 1.0]
+    );
+    assert!(list.iter().all(|t| t.allow_discrete && t.delay == 0.0));
+
+    assert!(Transition::from_declarations(&declarations("transition: none")).is_empty());
+    assert_eq!(
+        Transition::from_declarations(&declarations(
+            "transition-property: opacity; transition-property: none, opacity"
+        ))[0]
+            .property,
+        TransitionProperty::Name("opacity".into())
+    );
+    assert_eq!(Transition::from_declarations(&[]), [Transition::default()]);
+}
@@ -2,16 +2,22 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 //! At Rules
-#[cfg(feature="at_rules")]
-use simplecss::*;
-#[cfg(feature="at_rules")]
-use simplecss::at_rules::*;
-#[cfg(feature="at_rules")]
+#[cfg(feature = "at_rules")]
 use simplecss::at_rules::at_rule::*;
+#[cfg(feature = "at_rules")]
+use simplecss::at_rules::*;
+#[cfg(feature = "at_rules")]
+use simplecss::*;
 
+#[cfg(feature = "at_rules")]
+fn rules_only(rules: Vec<Rule<'_>>) -> StyleSheet<'_> {
+    let mut sheet = StyleSheet::new();
+    sheet.rules = rules;
+    sheet
+}
 
 #[test]
-#[cfg(feature="at_rules")]
+#[cfg(feature = "at_rules")]
 fn container() {
     use at_rule::Comparison;
     use container::*;
@@ -51,198 +57,161 @@ fn container() {
         a { color:red }"#,
     );
     assert_eq!(style.to_string(), "a { color:red; }");
-    
+
     let rule = Rule {
         selector: Selector::parse("div").unwrap(),
-        declarations: vec![
-            Declaration {
-                name: "color",
-                value: "red",
-                important: false
-            }
-        ]
+        declarations: vec![Declaration {
+            name: "color".into(),
+            value: "red",
+            important: false,
+            span: Default::default(),
+        }],
+        span: Default::default(),
     };
 
     let rule1 = AtRule::Container(Container {
-        conditions: vec![
-            ContainerCondition::QueryOnly(ContainerQuery::List { 
-                first: ContainerQueryInParens::Feature(Feature {
-                    key: "width",
-                    comparison: Comparison::Greater,
-                    value: "400px",
-                }), 
-                rest: vec![] 
-            })
-        ],
-        contents: StyleSheet { 
-            rules: vec![ rule.clone() ], 
-            at_rules: vec![]
-        },
+        conditions: vec![ContainerCondition::QueryOnly(ContainerQuery::List {
+            first: ContainerQueryInParens::Feature(Feature {
+                key: "width".into(),
+                comparison: Comparison::Greater,
+                value: "400px",
+            }),
+            rest: vec![],
+        })],
+        contents: rules_only(vec![rule.clone()]),
+        span: Default::default(),
     });
     let rule2 = AtRule::Container(Container {
-        conditions: vec![
-            ContainerCondition::NameAndQuery {
-                name: "tall",
-                query: ContainerQuery::List { 
-                    first: ContainerQueryInParens::Feature(Feature {
-                        key: "height",
-                        comparison: Comparison::Greater,
-                        value: "30rem",
-                    }), 
-                    rest: vec![] 
-                }
-            }
-        ],
-        contents: StyleSheet { 
-            rules: vec![ rule.clone() ], 
-            at_rules: vec![]
-        },
+        conditions: vec![ContainerCondition::NameAndQuery {
+            name: "tall".into(),
+            query: ContainerQuery::List {
+                first: ContainerQueryInParens::Feature(Feature {
+                    key: "height".into(),
+                    comparison: Comparison::Greater,
+                    value: "30rem",
+                }),
+                rest: vec![],
+            },
+        }],
+        contents: rules_only(vec![rule.clone()]),
+        span: Default::default(),
     });
     let rule3 = AtRule::Container(Container {
-        conditions: vec![
-            ContainerCondition::QueryOnly(ContainerQuery::List { 
+        conditions: vec![ContainerCondition::QueryOnly(ContainerQuery::List {
+            first: ContainerQueryInParens::Function(ContainerFunction {
+                name: "scroll-state".into(),
+                query: FunctionQuery::List {
+                    first: FunctionInParens::Feature(Feature {
+                        key: "scrollable".into(),
+                        comparison: Comparison::Equal,
+                        value: "top",
+                    }),
+                    rest: vec![],
+                },
+            }),
+            rest: vec![],
+        })],
+        contents: rules_only(vec![rule.clone()]),
+        span: Default::default(),
+    });
+    let rule4 = AtRule::Container(Container {
+        conditions: vec![ContainerCondition::NameAndQuery {
+            name: "sticky-heading".into(),
+            query: ContainerQuery::List {
                 first: ContainerQueryInParens::Function(ContainerFunction {
-                    name: "scroll-state",
-                    query: FunctionQuery::List { 
+                    name: "scroll-state".into(),
+                    query: FunctionQuery::List {
                         first: FunctionInParens::Feature(Feature {
-                            key: "scrollable",
+                            key: "stuck".into(),
                             comparison: Comparison::Equal,
                             value: "top",
-                        }), 
-                        rest: vec![] 
-                    }
-                }), 
-                rest: vec![] 
-            })
-        ],
-        contents: StyleSheet { 
-            rules: vec![ rule.clone() ], 
-            at_rules: vec![]
-        },
-    });
-    let rule4 = AtRule::Container(Container {
-        conditions: vec![
-            ContainerCondition::NameAndQuery {
-                name: "sticky-heading",
-                query: ContainerQuery::List { 
-                    first: ContainerQueryInParens::Function(ContainerFunction {
-                        name: "scroll-state",
-                        query: FunctionQuery::List { 
-                            first: FunctionInParens::Feature(Feature {
-                                key: "stuck",
-                                comparison: Comparison::Equal,
-                                value: "top",
-                            }), 
-                            rest: vec![] 
-                        }
-                    }), 
-                    rest: vec![] 
-                }
-            }
-        ],
-        contents: StyleSheet { 
-            rules: vec![ rule.clone() ], 
-            at_rules: vec![]
-        },
+                        }),
+                        rest: vec![],
+                    },
+                }),
+                rest: vec![],
+            },
+        }],
+        contents: rules_only(vec![rule.clone()]),
+        span: Default::default(),
     });
     let rule5 = AtRule::Container(Container {
-        conditions: vec![
-            ContainerCondition::QueryOnly(
-                ContainerQuery::List { 
-                    first: ContainerQueryInParens::Feature(Feature {
-                        key: "width", 
-                        comparison: Comparison::Greater, 
-                        value: "400px",
-                    }),  
-                    rest: vec![
-                        ContainerQueryAndOr::And(ContainerQueryInParens::Function(ContainerFunction { 
-                            name: "style", 
-                            query: FunctionQuery::List { 
-                                first: FunctionInParens::Feature(Feature {
-                                    key: "--responsive",
-                                    comparison: Comparison::Equal,
-                                    value: "true",
-                                }), 
-                                rest: vec![] 
-                            }
-                        }))
-                    ] 
-                }
-            )
-        ],
-        contents: StyleSheet { 
-            rules: vec![ rule.clone() ], 
-            at_rules: vec![]
-        },
+        conditions: vec![ContainerCondition::QueryOnly(ContainerQuery::List {
+            first: ContainerQueryInParens::Feature(Feature {
+                key: "width".into(),
+                comparison: Comparison::Greater,
+                value: "400px",
+            }),
+            rest: vec![ContainerQueryAndOr::And(ContainerQueryInParens::Function(
+                ContainerFunction {
+                    name: "style".into(),
+                    query: FunctionQuery::List {
+                        first: FunctionInParens::Feature(Feature {
+                            key: "--responsive".into(),
+                            comparison: Comparison::Equal,
+                            value: "true",
+                        }),
+                        rest: vec![],
+                    },
+                },
+            ))],
+        })],
+        contents: rules_only(vec![rule.clone()]),
+        span: Default::default(),
     });
     let rule6 = AtRule::Container(Container {
         conditions: vec![
-            ContainerCondition::NameAndQuery{
-                name: "card",
-                query: ContainerQuery::List { 
+            ContainerCondition::NameAndQuery {
+                name: "card".into(),
+                query: ContainerQuery::List {
                     first: ContainerQueryInParens::Feature(Feature {
-                        key: "width", 
-                        comparison: Comparison::Greater, 
+                        key: "width".into(),
+                        comparison: Comparison::Greater,
                         value: "400px",
                     }),
-                    rest: vec![] ,
-                }
-            },
-            
-            ContainerCondition::QueryOnly(
-                ContainerQuery::List { 
-                    first: ContainerQueryInParens::Function(ContainerFunction {
-                        name: "style",
-                        query: FunctionQuery::List { 
-                            first: FunctionInParens::Feature(Feature {
-                                key: "--responsive",
-                                comparison: Comparison::Equal,
-                                value: "true",
-                            }), 
-                            rest: vec![]
-                        }
-                    }),
                     rest: vec![],
-                }
-            ),
-
-            ContainerCondition::QueryOnly(
-                ContainerQuery::List { 
-                    first: ContainerQueryInParens::Function(ContainerFunction {
-                        name: "scroll-state",
-                        query: FunctionQuery::List { 
-                            first: FunctionInParens::Feature(Feature {
-                                key: "stuck",
-                                comparison: Comparison::Equal,
-                                value: "top",
-                            }), 
-                            rest: vec![]
-                        }
-                    }),
-                    rest: vec![],
-                }
-            ),
+                },
+            },
+            ContainerCondition::QueryOnly(ContainerQuery::List {
+                first: ContainerQueryInParens::Function(ContainerFunction {
+                    name: "style".into(),
+                    query: FunctionQuery::List {
+                        first: FunctionInParens::Feature(Feature {
+                            key: "--responsive".into(),
+                            comparison: Comparison::Equal,
+                            value: "true",
+                        }),
+                        rest: vec![],
+                    },
+                }),
+                rest: vec![],
+            }),
+            ContainerCondition::QueryOnly(ContainerQuery::List {
+                first: ContainerQueryInParens::Function(ContainerFunction {
+                    name: "scroll-state".into(),
+                    query: FunctionQuery::List {
+                        first: FunctionInParens::Feature(Feature {
+                            key: "stuck".into(),
+                            comparison: Comparison::Equal,
+                            value: "top",
+                        }),
+                        rest: vec![],
+                    },
+                }),
+                rest: vec![],
+            }),
         ],
-        contents: StyleSheet { 
-            rules: vec![ rule.clone() ], 
-            at_rules: vec![]
-        },
+        contents: rules_only(vec![rule.clone()]),
+        span: Default::default(),
     });
 
-    let rules = [
-        rule1,
-        rule2,
-        rule3,
-        rule4,
-        rule5,
-        rule6,
-    ];
-    
+    let rules = [rule1, rule2, rule3, rule4, rule5, rule6];
+
     assert_rules(style, rules);
 }
 
 #[test]
-#[cfg(feature="at_rules")]
+#[cfg(feature = "at_rules")]
 fn font_face() {
     // tests are examples from https://developer.mozilla.org/en-US/docs/Web/CSS/@container
     let style = StyleSheet::parse(
@@ -257,35 +226,37 @@ fn font_face() {
         a { color:red }"#,
     );
 
-    let rule1 = AtRule::FontFace(vec![
-        Declaration {
-            name: "font-family",
-            value: "\"Trickster\"",
-            important: false,
-        },
-        Declaration {
-            name: "src",
-            value: r#"local("Trickster"),
+    let rule1 = AtRule::FontFace {
+        declarations: vec![
+            Declaration {
+                name: "font-family".into(),
+                value: "\"Trickster\"",
+                important: false,
+                span: Default::default(),
+            },
+            Declaration {
+                name: "src".into(),
+                value: r#"local("Trickster"),
     url("trickster-COLRv1.otf") format("opentype") tech(color-COLRv1),
     url("trickster-outline.otf") format("opentype"),
     url("trickster-outline.woff") format("woff")"#,
-            important: false,
-        },
-    ]);
+                important: false,
+                span: Default::default(),
+            },
+        ],
+        span: Default::default(),
+    };
 
     assert_eq!(style.to_string(), "a { color:red; }");
 
-    assert_eq!(style.at_rules, vec![
-        rule1,
-    ]);
+    assert_eq!(style.at_rules, vec![rule1,]);
 }
 
-
 #[test]
-#[cfg(feature="at_rules")]
+#[cfg(feature = "at_rules")]
 fn import() {
-    use crate::media::*;
     use crate::import::*;
+    use crate::media::*;
 
     let style = StyleSheet::parse(
         r#"
@@ -308,126 +279,112 @@ fn import() {
     assert_eq!(style.to_string(), "a { color:red; }");
 
     let rule1 = AtRule::Import(Import {
-        url: ImportUrl::String("custom.css"),
+        url: ImportUrl::String("custom.css".into()),
         layer: None,
         supports: None,
-        media_queries: vec![]
+        media_queries: vec![],
+        span: Default::default(),
     });
     let rule2 = AtRule::Import(Import {
-        url: ImportUrl::Url("chrome://communicator/skin/"),
+        url: ImportUrl::Url("chrome://communicator/skin/".into()),
         layer: None,
         supports: None,
-        media_queries: vec![]
+        media_queries: vec![],
+        span: Default::default(),
     });
     let rule3 = AtRule::Import(Import {
-        url: ImportUrl::Src("some-source"),
+        url: ImportUrl::Src("some-source".into()),
         layer: None,
         supports: None,
-        media_queries: vec![]
+        media_queries: vec![],
+        span: Default::default(),
     });
 
     // layer tests
     let layer1 = AtRule::Import(Import {
-        url: ImportUrl::String("test"),
+        url: ImportUrl::String("test".into()),
         layer: Some(ImportLayer::Layer),
         supports: None,
-        media_queries: vec![]
+        media_queries: vec![],
+        span: Default::default(),
     });
     let layer2 = AtRule::Import(Import {
-        url: ImportUrl::String("test"),
-        layer: Some(ImportLayer::Named("test-layer")),
+        url: ImportUrl::String("test".into()),
+        layer: Some(ImportLayer::Named("test-layer".into())),
         supports: None,
-        media_queries: vec![]
+        media_queries: vec![],
+        span: Default::default(),
     });
-    
+
     // media query tests
     let mq1 = AtRule::Import(Import {
-        url: ImportUrl::Url("fine-print.css"),
+        url: ImportUrl::Url("fine-print.css".into()),
         layer: None,
         supports: None,
-        media_queries: vec![
-            MediaQuery::OtherThing {
-                not_only: None,
-                media_type: "print",
-                conditions: vec![],
-            }
-        ]
+        media_queries: vec![MediaQuery::OtherThing {
+            not_only: None,
+            media_type: "print".into(),
+            conditions: vec![],
+        }],
+        span: Default::default(),
     });
 
     let mq2 = AtRule::Import(Import {
-        url: ImportUrl::Src("bluish.css"),
+        url: ImportUrl::Src("bluish.css".into()),
         layer: None,
         supports: None,
         media_queries: vec![
             MediaQuery::OtherThing {
                 not_only: None,
-                media_type: "print",
+                media_type: "print".into(),
                 conditions: vec![],
             },
             MediaQuery::OtherThing {
                 not_only: None,
-                media_type: "screen",
+                media_type: "screen".into(),
                 conditions: vec![],
-            }
-        ]
+            },
+        ],
+        span: Default::default(),
     });
 
     let mq3 = AtRule::Import(Import {
-        url: ImportUrl::String("common.css"),
+        url: ImportUrl::String("common.css".into()),
         layer: None,
         supports: None,
-        media_queries: vec![
-            MediaQuery::OtherThing {
-                not_only: None,
-                media_type: "screen",
-                conditions: vec![],
-            }
-        ]
+        media_queries: vec![MediaQuery::OtherThing {
+            not_only: None,
+            media_type: "screen".into(),
+            conditions: vec![],
+        }],
+        span: Default::default(),
     });
 
     let mq4 = AtRule::Import(Import {
-        url: ImportUrl::Url("landscape.css"),
+        url: ImportUrl::Url("landscape.css".into()),
         layer: None,
         supports: None,
-        media_queries: vec![
-
-            MediaQuery::OtherThing {
-                not_only: None,
-                media_type: "screen",
-                conditions: vec![
-                    MediaConditionWithoutOr::Media {
-                        media: MediaInParens::Feature(MediaFeature::KeyVal { 
-                            key: "orientation", 
-                            val: "landscape",
-                        }),
-                        conditions: vec![]
-                    }
-                ]
-            }
-            
-        ]
+        media_queries: vec![MediaQuery::OtherThing {
+            not_only: None,
+            media_type: "screen".into(),
+            conditions: vec![MediaConditionWithoutOr::Media {
+                media: MediaInParens::Feature(MediaFeature::KeyVal {
+                    key: "orientation",
+                    val: "landscape",
+                }),
+                conditions: vec![],
+            }],
+        }],
+        span: Default::default(),
     });
 
-    let rules = [
-        rule1,
-        rule2,
-        rule3,
-
-        layer1,
-        layer2,
-
-        mq1,
-        mq2,
-        mq3,
-        mq4
-    ];
+    let rules = [rule1, rule2, rule3, layer1, layer2, mq1, mq2, mq3, mq4];
 
     assert_rules(style, rules);
 }
 
-
 #[test]
-#[cfg(feature="at_rules")]
+#[cfg(feature = "at_rules")]
 fn keyframes() {
     use at_rule::KeyFrame;
     let style = StyleSheet::parse(
@@ -441,48 +398,45 @@ fn keyframes() {
     );
     assert_eq!(style.to_string(), "a { color:red; }");
 
-    let rule = AtRule::Keyframes { 
-        name: "test-anim", 
+    let rule = AtRule::Keyframes {
+        name: "test-anim".into(),
         frames: vec![
             KeyFrame {
-                key: "from",
-                declarations: vec![
-                    Declaration {
-                        name: "color",
-                        value: "red",
-                        important: false,
-                    }
-                ]
+                key: "from".into(),
+                declarations: vec![Declaration {
+                    name: "color".into(),
+                    value: "red",
+                    important: false,
+                    span: Default::default(),
+                }],
             },
             KeyFrame {
-                key: "50%",
-                declarations: vec![
-                    Declaration {
-                        name: "color",
-                        value: "cyan",
-                        important: false,
-                    }
-                ]
+                key: "50%".into(),
+                declarations: vec![Declaration {
+                    name: "color".into(),
+                    value: "cyan",
+                    important: false,
+                    span: Default::default(),
+                }],
             },
             KeyFrame {
-                key: "to",
-                declarations: vec![
-                    Declaration {
-                        name: "color",
-                        value: "blue",
-                        important: false,
-                    }
-                ]
+                key: "to".into(),
+                declarations: vec![Declaration {
+                    name: "color".into(),
+                    value: "blue",
+                    important: false,
+                    span: Default::default(),
+                }],
             },
-        ]
+        ],
+        span: Default::default(),
     };
     println!("{style:?}");
     assert_eq!(style.at_rules[0], rule);
 }
 
-
 #[test]
-#[cfg(feature="at_rules")]
+#[cfg(feature = "at_rules")]
 fn layer() {
     use at_rule::LayerType;
     let style = StyleSheet::parse(
@@ -499,39 +453,43 @@ fn layer() {
         a { color:red }"#,
     );
     assert_eq!(style.to_string(), "a { color:red; }");
-    
+
     let rule = Rule {
         selector: Selector::parse("div").unwrap(),
-        declarations: vec![
-            Declaration {
-                name: "color",
-                value: "red",
-                important: false
-            }
-        ]
+        declarations: vec![Declaration {
+            name: "color".into(),
+            value: "red",
+            important: false,
+            span: Default::default(),
+        }],
+        span: Default::default(),
     };
 
-    let rule1 = AtRule::Layer(LayerType::Statement(vec!["module", "state"]));
-    let rule2 = AtRule::Layer(LayerType::Block {
-        name: Some("state"),
-        rules: vec![ rule.clone() ]
-    });
-    let rule3 = AtRule::Layer(LayerType::Block {
-        name: Some("module"),
-        rules: vec![ rule.clone() ]
-    });
+    let rule1 = AtRule::Layer {
+        layer: LayerType::Statement(vec!["module".into(), "state".into()]),
+        span: Default::default(),
+    };
+    let rule2 = AtRule::Layer {
+        layer: LayerType::Block {
+            name: Some("state".into()),
+            rules: vec![rule.clone()],
+        },
+        span: Default::default(),
+    };
+    let rule3 = AtRule::Layer {
+        layer: LayerType::Block {
+            name: Some("module".into()),
+            rules: vec![rule.clone()],
+        },
+        span: Default::default(),
+    };
 
     println!("{style:?}");
-    assert_eq!(style.at_rules, vec![
-        rule1,
-        rule2,
-        rule3,
-    ]);
+    assert_eq!(style.at_rules, vec![rule1, rule2, rule3,]);
 }
 
-
 #[test]
-#[cfg(feature="at_rules")]
+#[cfg(feature = "at_rules")]
 fn media() {
     use media::*;
     let style = StyleSheet::parse(
@@ -550,86 +508,116 @@ fn media() {
         }
 
         a { color:red }"#,
-    ); 
+    );
     let rule = Rule {
         selector: Selector::parse("div").unwrap(),
-        declarations: vec![
-            Declaration {
-                name: "color",
-                value: "red",
-                important: false
-            }
-        ]
+        declarations: vec![Declaration {
+            name: "color".into(),
+            value: "red",
+            important: false,
+            span: Default::default(),
+        }],
+        span: Default::default(),
     };
 
     let rule1 = AtRule::Media(Media {
-        rules: vec![ rule.clone() ],
-        query: vec![
-            MediaQuery::OtherThing {
-                not_only: None,
-                media_type: "screen",
-                conditions: Vec::new()
-            },
-        ],
+        rules: vec![rule.clone()],
+        query: vec![MediaQuery::OtherThing {
+            not_only: None,
+            media_type: "screen".into(),
+            conditions: Vec::new(),
+        }],
+        span: Default::default(),
     });
     let rule2 = AtRule::Media(Media {
-        rules: vec![ rule.clone() ],
-        query: vec![
-            MediaQuery::OtherThing {
-                not_only: Some(MediaNotOnly::Only),
-                media_type: "screen",
-                conditions: vec![
-                    MediaConditionWithoutOr::Media {
-                        media: MediaInParens::Feature(MediaFeature::KeyVal { key: "orientation", val: "landscape" }),
-                        conditions: vec![]
-                    }
-                ],
-            },
-        ],
+        rules: vec![rule.clone()],
+        query: vec![MediaQuery::OtherThing {
+            not_only: Some(MediaNotOnly::Only),
+            media_type: "screen".into(),
+            conditions: vec![MediaConditionWithoutOr::Media {
+                media: MediaInParens::Feature(MediaFeature::KeyVal {
+                    key: "orientation",
+                    val: "landscape",
+                }),
+                conditions: vec![],
+            }],
+        }],
+        span: Default::default(),
     });
 
     let rule3 = AtRule::Media(Media {
-        rules: vec![ rule.clone() ],
+        rules: vec![rule.clone()],
         query: vec![
             MediaQuery::OtherThing {
                 not_only: None,
-                media_type: "screen",
-                conditions: vec![
-                    MediaConditionWithoutOr::Media {
-                        media: MediaInParens::Feature(MediaFeature::KeyVal { 
-                            key: "max-width", 
-                            val: "900px" 
-                        }),
-                        conditions: vec![
-                            MediaAnd(MediaInParens::Feature(MediaFeature::KeyVal { 
-                                key: "min-width", 
-                                val: "600px"
-                            }))
-                        ]
-                    }
-                ],
+                media_type: "screen".into(),
+                conditions: vec![MediaConditionWithoutOr::Media {
+                    media: MediaInParens::Feature(MediaFeature::KeyVal {
+                        key: "max-width",
+                        val: "900px",
+                    }),
+                    conditions: vec![MediaAnd(MediaInParens::Feature(MediaFeature::KeyVal {
+                        key: "min-width",
+                        val: "600px",
+                    }))],
+                }],
             },
-            MediaQuery::Condition(MediaCondition::List { 
-                first: Box::new(MediaInParens::Feature(MediaFeature::KeyVal { 
-                    key: "min-width", 
-                    val: "1100px"
-                })), 
-                conditions: vec![]
-            })
+            MediaQuery::Condition(MediaCondition::List {
+                first: Box::new(MediaInParens::Feature(MediaFeature::KeyVal {
+                    key: "min-width",
+                    val: "1100px",
+                })),
+                conditions: vec![],
+            }),
         ],
+        span: Default::default(),
     });
 
-
-    let rules = [
-        rule1,
-        rule2,
-        rule3,
-    ];
+    let rules = [rule1, rule2, rule3];
     assert_rules(style, rules);
 }
 
 #[test]
-#[cfg(feature="at_rules")]
+#[cfg(feature = "at_rules")]
+fn media_case_insensitive() {
+    use media::*;
+    let style = StyleSheet::parse(
+        r#"
+        @MEDIA ONLY Screen AND (orientation: landscape) {
+            div { color: red !IMPORTANT; }
+        }"#,
+    );
+
+    let rule = AtRule::Media(Media {
+        rules: vec![Rule {
+            selector: Selector::parse("div").unwrap(),
+            declarations: vec![Declaration {
+                name: "color".into(),
+                value: "red",
+                important: true,
+                span: Default::default(),
+            }],
+            span: Default::default(),
+        }],
+        query: vec![MediaQuery::OtherThing {
+            not_only: Some(MediaNotOnly::Only),
+            media_type: "Screen".into(),
+            conditions: vec![MediaConditionWithoutOr::Media {
+                media: MediaInParens::Feature(MediaFeature::KeyVal {
+                    key: "orientation",
+                    val: "landscape",
+                }),
+                conditions: vec![],
+            }],
+        }],
+        span: Default::default(),
+    });
+
+    assert_rules(style, [rule]);
+}
+
+#[test]
+#[cfg(feature = "at_rules")]
 fn namespace() {
     // https://developer.mozilla.org/en-US/docs/Web/CSS/@namespace
     let style = StyleSheet::parse(
@@ -645,36 +633,33 @@ fn namespace() {
         a { color:red }"#,
     );
     assert_eq!(style.to_string(), "a { color:red; }");
-    
-    let rule1 = AtRule::Namespace { 
-        name: None, 
-        value: "url(XML-namespace-URL)",
+
+    let rule1 = AtRule::Namespace {
+        name: None,
+        value: "url(XML-namespace-URL)".into(),
+        span: Default::default(),
     };
-    let rule2 = AtRule::Namespace { 
-        name: None, 
-        value: "XML-namespace-URL",
+    let rule2 = AtRule::Namespace {
+        name: None,
+        value: "XML-namespace-URL".into(),
+        span: Default::default(),
     };
-    let rule3 = AtRule::Namespace { 
-        name: Some("prefix"), 
-        value: "url(XML-namespace-URL)",
+    let rule3 = AtRule::Namespace {
+        name: Some("prefix".into()),
+        value: "url(XML-namespace-URL)".into(),
+        span: Default::default(),
     };
-    let rule4 = AtRule::Namespace { 
-        name: Some("prefix"), 
-        value: "\"XML-namespace-URL\"",
+    let rule4 = AtRule::Namespace {
+        name: Some("prefix".into()),
+        value: "\"XML-namespace-URL\"".into(),
+        span: Default::default(),
     };
 
-    assert_eq!(style.at_rules, vec![
-        rule1,
-        rule2,
-        rule3,
-        rule4,
-    ]);
+    assert_eq!(style.at_rules, vec![rule1, rule2, rule3, rule4,]);
 }
 
-
-
 #[test]
-#[cfg(feature="at_rules")]
+#[cfg(feature = "at_rules")]
 fn supports() {
     use crate::supports::*;
 
@@ -693,62 +678,174 @@ fn supports() {
         a { color:red }"#,
     );
     assert_eq!(style.to_string(), "a { color:red; }");
-    
+
     let rule = Rule {
         selector: Selector::parse("div").unwrap(),
-        declarations: vec![
-            Declaration {
-                name: "color",
-                value: "red",
-                important: false
-            }
-        ]
+        declarations: vec![Declaration {
+            name: "color".into(),
+            value: "red",
+            important: false,
+            span: Default::default(),
+        }],
+        span: Default::default(),
     };
 
-    let rule1 = AtRule::Supports(Supports { 
-        condition: SupportsCondition::Not(
-            SupportsInParens::Condition(Box::new(
-                SupportsCondition::Not(
-                    SupportsInParens::Feature(Declaration { 
-                        name: "transform-origin", 
-                        value: "2px", 
-                        important: false 
-                    })
-                )
-            ))
-        ),
-        rules: vec![ rule.clone() ]  
+    let rule1 = AtRule::Supports(Supports {
+        condition: SupportsCondition::Not(SupportsInParens::Condition(Box::new(
+            SupportsCondition::Not(SupportsInParens::Feature(Declaration {
+                name: "transform-origin".into(),
+                value: "2px",
+                important: false,
+                span: Default::default(),
+            })),
+        ))),
+        rules: vec![rule.clone()],
+        span: Default::default(),
     });
-    let rule2 = AtRule::Supports(Supports { 
-        condition: SupportsCondition::List { 
-            first: SupportsInParens::Feature(Declaration { name: "display", value: "grid", important: false }), 
-            list: vec![
-                SupportsAndOr::And(SupportsInParens::Condition(Box::new(SupportsCondition::Not(
-                    SupportsInParens::Feature(Declaration { name: "display", value: "inline-grid", important: false })
-                ))))
-            ]
+    let rule2 = AtRule::Supports(Supports {
+        condition: SupportsCondition::List {
+            first: SupportsInParens::Feature(Declaration {
+                name: "display".into(),
+                value: "grid",
+                important: false,
+                span: Default::default(),
+            }),
+            list: vec![SupportsAndOr::And(SupportsInParens::Condition(Box::new(
+                SupportsCondition::Not(SupportsInParens::Feature(Declaration {
+                    name: "display".into(),
+                    value: "inline-grid",
+                    important: false,
+                    span: Default::default(),
+                })),
+            )))],
         },
-        rules: vec![ rule.clone() ]  
+        rules: vec![rule.clone()],
+        span: Default::default(),
     });
 
-    let rules = [
-        rule1,
-        rule2,
-    ];
+    let rules = [rule1, rule2];
 
     assert_rules(style, rules);
 }
 
+#[test]
+#[cfg(all(feature = "at_rules", any(feature = "std", feature = "libm")))]
+fn supports_evaluate() {
+    let style = StyleSheet::parse(
+        r#"
+        @supports not (not (transform-origin: 2px)) { div { color: red; } }
+        @supports (display: grid) and (not (display: inline-grid)) { div { color: red; } }
+        @supports (display: grid) and (display: gird) { div { color: red; } }
+        @supports (displya: grid) or (fill: context-fill) { div { color: red; } }
+        @supports (stroke-width: thick) { div { color: red; } }
+        @supports (font: caption) { div { color: red; } }
+        @supports (width: -1px) or (animation-duration: -1s) { div { color: red; } }
+        "#,
+    );
 
+    let results: Vec<bool> = style
+        .at_rules
+        .iter()
+        .map(|rule| match rule {
+            AtRule::Supports(rule) => rule.evaluate(),
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(results, [true, false, false, true, false, true, false]);
+}
 
-#[cfg(feature="at_rules")]
-fn assert_rules<const N:usize>(style: StyleSheet<'_>, rules: [AtRule<'_>; N]) {
-    #[allow(clippy::needless_range_loop, reason = "indexing two things with i, cleaner code than suggestion")]
+#[cfg(feature = "at_rules")]
+fn assert_rules<const N: usize>(style: StyleSheet<'_>, rules: [AtRule<'_>; N]) {
+    #[allow(
+        clippy::needless_range_loop,
+        reason = "indexing two things with i, cleaner code than suggestion"
+    )]
     for i in 0..rules.len() {
         assert_eq!(
             style.at_rules[i],
             rules[i],
-            "at rule {} did not parse correctly", i+1
+            "at rule {} did not parse correctly",
+            i + 1
         );
     }
 }
+
+#[test]
+#[cfg(feature = "at_rules")]
+fn spans() {
+    let text = "@media screen  { a { color: red } }\n@namespace svg url(x);\n@container (width > 1px) { b {} p { top: 0 } }";
+    let style = StyleSheet::parse(text);
+    assert_eq!(style.at_rules.len(), 3);
+
+    let span = style.at_rules[0].span();
+    assert_eq!(span.rule.slice(text), "@media screen  { a { color: red } }");
+    assert_eq!(span.prelude.slice(text), "screen");
+    assert_eq!(
+        span.block.map(|s| s.slice(text)),
+        Some(" a { color: red } ")
+    );
+
+    let AtRule::Media(media) = &style.at_rules[0] else {
+        panic!()
+    };
+    assert_eq!(media.rules[0].span.slice(text), "a { color: red }");
+
+    let span = style.at_rules[1].span();
+    assert_eq!(span.rule.slice(text), "@namespace svg url(x);");
+    assert_eq!(span.prelude.slice(text), "svg url(x)");
+    assert_eq!(span.block, None);
+    assert_eq!(span.rule.start_pos(text), TextPos::new(2, 1));
+
+    // Nested rules are relative to the whole source too.
+    let AtRule::Container(container) = &style.at_rules[2] else {
+        panic!()
+    };
+    assert_eq!(container.span.prelude.slice(text), "(width > 1px)");
+    let rule = &container.contents.rules[0];
+    assert_eq!(rule.span.slice(text), "p { top: 0 }");
+    assert_eq!(rule.declarations[0].span().slice(text), "top: 0");
+}
+
+#[test]
+#[cfg(feature = "at_rules")]
+fn minified_round_trip() {
+    let text = r#"
+        @import url("a b.css") layer(base) supports(display: grid) screen and (min-width: 100px);
+        @namespace svg url(http://www.w3.org/2000/svg);
+        @namespace "XML-namespace-URL";
+        @layer base, theme;
+        a { color: red }
+        @media only screen and (max-width: 600px), (orientation: landscape) {
+            b, c { color: blue }
+        }
+        @supports (display: grid) and (not (display: inline-grid)) {
+            d { float: none }
+        }
+        @font-face { font-family: "Open \"Sans\""; src: url(a.woff2) }
+        @keyframes sl\:ide { from { left: 0 } 50% { left: 10px } to { left: 20px } }
+        @layer theme { e { color: green } }
+        @container card (width > 400px) { f { gap: 1px } }
+        @page :first { margin: 1cm }
+        g { top: 0 }
+    "#;
+    let style = StyleSheet::parse(text);
+    assert_eq!(style.at_rules.len(), 11);
+
+    let minified = style.to_minified_string();
+    assert!(minified.starts_with("@import url(\"a b.css\") layer(base) supports(display:grid)"));
+    assert!(minified.contains("a{color:red}@media only screen and (max-width:600px)"));
+    assert!(minified.ends_with("@page :first{margin: 1cm}g{top:0}"));
+    assert_eq!(StyleSheet::parse(&minified), style);
+    assert_eq!(StyleSheet::parse(&minified).to_minified_string(), minified);
+}
+
+#[test]
+#[cfg(feature = "at_rules")]
+fn minified_parse_more() {
+    let mut style = StyleSheet::parse("a { color: red } @media print { a { color: green } }");
+    style.parse_more("@media screen { b { top: 0 } } a { color: blue }");
+    assert_eq!(
+        style.to_minified_string(),
+        "a{color:red}@media print{a{color:green}}@media screen{b{top:0}}a{color:blue}"
+    );
+}
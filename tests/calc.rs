@@ -0,0 +1,221 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Math functions
+
+#![cfg(any(feature = "std", feature = "libm"))]
+
+use simplecss::*;
+
+fn px(text: &str) -> Option<f64> {
+    let context = ResolveContext {
+        font_size: 10.0,
+        percentage_base: 200.0,
+        viewport_width: 1000.0,
+        ..ResolveContext::default()
+    };
+
+    CalcNode::parse(text)?.to_px(&context)
+}
+
+fn number(text: &str) -> Option<f64> {
+    CalcNode::parse(text)?.to_number()
+}
+
+fn degrees(text: &str) -> Option<f64> {
+    CalcNode::parse(text)?.to_degrees()
+}
+
+#[test]
+fn calc_01() {
+    assert_eq!(px("calc(1px + 2px)"), Some(3.0));
+    assert_eq!(px("calc(10px - 2px * 3)"), Some(4.0));
+    assert_eq!(px("calc((10px - 2px) * 3)"), Some(24.0));
+    assert_eq!(px("calc(12px / 4)"), Some(3.0));
+    assert_eq!(px("calc(2 * 3px)"), Some(6.0));
+}
+
+#[test]
+fn calc_02() {
+    assert_eq!(px("calc(50% - 2em)"), Some(80.0));
+    assert_eq!(px("calc((100% - 4em) / 4)"), Some(40.0));
+    assert_eq!(px("calc(10vw + 1in - 96px)"), Some(100.0));
+    assert_eq!(px("CALC(-1 * (1em))"), Some(-10.0));
+    assert_eq!(px("calc(calc(1px + 1px) * 2)"), Some(4.0));
+}
+
+#[test]
+fn calc_03() {
+    assert_eq!(number("calc(1 + 2 * 3)"), Some(7.0));
+    assert_eq!(number("calc(1 / 4)"), Some(0.25));
+    assert_eq!(number("calc(2 * pi)"), Some(2.0 * core::f64::consts::PI));
+    assert_eq!(number("calc(infinity)"), Some(f64::INFINITY));
+    assert_eq!(px("calc(1px * 1)"), Some(1.0));
+}
+
+#[test]
+fn type_errors() {
+    assert_eq!(CalcNode::parse("calc(1px + 1)"), None);
+    assert_eq!(CalcNode::parse("calc(1px * 1px)"), None);
+    assert_eq!(CalcNode::parse("calc(1 / 1px)"), None);
+    assert_eq!(CalcNode::parse("calc(1deg + 1px)"), None);
+    assert_eq!(CalcNode::parse("calc(1% + 1)"), None);
+    assert_eq!(CalcNode::parse("calc(1px + 1hz)"), None);
+    assert_eq!(CalcNode::parse("sin(1px)"), None);
+    assert_eq!(CalcNode::parse("round(1.5px)"), None);
+    assert_eq!(number("calc(1px)"), None);
+    assert_eq!(px("calc(1)"), None);
+}
+
+#[test]
+fn syntax_errors() {
+    assert_eq!(CalcNode::parse("calc()"), None);
+    assert_eq!(CalcNode::parse("calc(1px +)"), None);
+    assert_eq!(CalcNode::parse("calc(1px 2px)"), None);
+    // Whitespace is required around `+` and `-`.
+    assert_eq!(CalcNode::parse("calc(1px -2px)"), None);
+    assert_eq!(CalcNode::parse("calc(1px, 2px)"), None);
+    assert_eq!(CalcNode::parse("clamp(1px, 2px)"), None);
+    assert_eq!(CalcNode::parse("var(--x)"), None);
+    assert_eq!(CalcNode::parse("calc(auto)"), None);
+}
+
+#[test]
+fn calc_types() {
+    let calc_type = |text| CalcNode::parse(text).unwrap().calc_type();
+    assert_eq!(calc_type("calc(1)"), Some(CalcType::Number));
+    assert_eq!(calc_type("calc(1em)"), Some(CalcType::Length));
+    assert_eq!(calc_type("calc(1%)"), Some(CalcType::Percentage));
+    assert_eq!(
+        calc_type("calc(1% + 1em)"),
+        Some(CalcType::LengthPercentage)
+    );
+    assert_eq!(calc_type("calc(1turn)"), Some(CalcType::Angle));
+    assert_eq!(calc_type("calc(1s + 1ms)"), Some(CalcType::Time));
+    assert_eq!(calc_type("sign(-1px)"), Some(CalcType::Number));
+    assert_eq!(calc_type("atan2(1px, 1px)"), Some(CalcType::Angle));
+}
+
+#[test]
+fn simplification() {
+    assert_eq!(
+        CalcNode::parse("calc(1em + 2px + 3em - 1px)").unwrap(),
+        CalcNode::Sum(vec![
+            CalcNode::Length(Length::new(4.0, LengthUnit::Em)),
+            CalcNode::Length(Length::new(1.0, LengthUnit::Px)),
+        ])
+    );
+
+    assert_eq!(
+        CalcNode::parse("calc((100% - 20px) / 2)").unwrap(),
+        CalcNode::Sum(vec![
+            CalcNode::Percentage(50.0),
+            CalcNode::Length(Length::new(-10.0, LengthUnit::Px)),
+        ])
+    );
+
+    assert_eq!(
+        CalcNode::parse("min(1em, 2em)").unwrap(),
+        CalcNode::Length(Length::new(1.0, LengthUnit::Em))
+    );
+
+    assert_eq!(
+        CalcNode::parse("calc(1s + 500ms)").unwrap(),
+        CalcNode::Time(1.5)
+    );
+}
+
+#[test]
+fn min_max_clamp() {
+    assert_eq!(px("min(10px, 2em, 50%)"), Some(10.0));
+    assert_eq!(px("max(10px, 2em, 50%)"), Some(100.0));
+    assert_eq!(px("min(10px)"), Some(10.0));
+    assert_eq!(px("clamp(10px, 50%, 2em)"), Some(20.0));
+    assert_eq!(px("clamp(10px, 1px, 2em)"), Some(10.0));
+    assert_eq!(px("calc(min(10px, 5px) + max(1px, 2px))"), Some(7.0));
+    assert_eq!(number("clamp(1, 5, 3)"), Some(3.0));
+}
+
+#[test]
+fn stepped_values() {
+    assert_eq!(number("round(2.5)"), Some(3.0));
+    assert_eq!(number("round(-2.5)"), Some(-2.0));
+    assert_eq!(px("round(17px, 5px)"), Some(15.0));
+    assert_eq!(px("round(up, 11px, 5px)"), Some(15.0));
+    assert_eq!(px("round(down, 14px, 5px)"), Some(10.0));
+    assert_eq!(px("round(to-zero, -14px, 5px)"), Some(-10.0));
+    assert_eq!(px("round(nearest, 1em, 3px)"), Some(9.0));
+    assert!(number("round(1, 0)").unwrap().is_nan());
+    assert_eq!(number("mod(-7, 3)"), Some(2.0));
+    assert_eq!(number("rem(-7, 3)"), Some(-1.0));
+    assert_eq!(px("mod(7px, -3px)"), Some(-2.0));
+}
+
+#[test]
+fn sign_related() {
+    assert_eq!(px("abs(-2em)"), Some(20.0));
+    assert_eq!(number("sign(-2em)"), Some(-1.0));
+    assert_eq!(number("sign(calc(1em - 16px))"), Some(0.0));
+    assert_eq!(number("sign(3)"), Some(1.0));
+}
+
+#[test]
+fn trigonometric() {
+    let close = |a: Option<f64>, b: f64| (a.unwrap() - b).abs() < 1e-9;
+    assert!(close(number("sin(90deg)"), 1.0));
+    assert!(close(number("cos(0.5turn)"), -1.0));
+    assert!(close(number("tan(45deg)"), 1.0));
+    assert!(close(number("sin(pi / 2)"), 1.0));
+    assert!(close(degrees("asin(1)"), 90.0));
+    assert!(close(degrees("acos(-1)"), 180.0));
+    assert!(close(degrees("atan(1)"), 45.0));
+    assert!(close(degrees("atan2(1px, -1px)"), 135.0));
+    assert!(close(
+        degrees("calc(asin(1) + 1rad)"),
+        90.0 + 1.0_f64.to_degrees()
+    ));
+}
+
+#[test]
+fn seconds() {
+    assert_eq!(
+        CalcNode::parse("calc(2s / 4)").unwrap().to_seconds(),
+        Some(0.5)
+    );
+    assert_eq!(CalcNode::parse("calc(2s)").unwrap().to_degrees(), None);
+}
+
+#[test]
+fn length_api() {
+    assert_eq!(
+        Length::parse("calc(1em + 2em)"),
+        Some(Length::new(3.0, LengthUnit::Em))
+    );
+    assert_eq!(
+        Length::parse("max(10%, 20%)"),
+        Some(Length::new(20.0, LengthUnit::Percent))
+    );
+    assert_eq!(Length::parse("calc(1em + 2px)"), None);
+    assert_eq!(Length::parse("calc(1)"), None);
+}
+
+#[test]
+fn color_channels() {
+    assert_eq!(
+        Color::parse("rgb(calc(200 + 55) calc(50% - 50%) 0 / calc(1 / 2))"),
+        Some(Color::Rgba(Rgba::new(255, 0, 0, 128)))
+    );
+    assert_eq!(
+        Color::parse("hsl(from red calc(h + 120) s l)"),
+        Some(Color::Rgba(Rgba::new(0, 255, 0, 255)))
+    );
+    assert_eq!(
+        Color::parse("rgb(from red calc(r / 2) g b / calc(alpha / 2))"),
+        Some(Color::Rgba(Rgba::new(128, 0, 0, 128)))
+    );
+    assert_eq!(
+        Color::parse("hsl(calc(0.5turn) 100% 50%)"),
+        Some(Color::Rgba(Rgba::new(0, 255, 255, 255)))
+    );
+    assert_eq!(Color::parse("rgb(calc(1px) 0 0)"), None);
+}
@@ -0,0 +1,178 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Chunked parser
+
+use simplecss::*;
+
+fn collect(parser: &mut ChunkedParser, statements: &mut Vec<OwnedStatement>) {
+    while let Some(statement) = parser.next_statement() {
+        statements.push(statement);
+    }
+}
+
+/// Feeds `text` split at every possible position and checks
+/// that statements are the same as with a single chunk.
+fn check_splits(text: &str, expected: &[&str]) {
+    for i in 0..=text.len() {
+        let mut parser = ChunkedParser::new();
+        let mut statements = Vec::new();
+        parser.feed_bytes(&text.as_bytes()[..i]);
+        collect(&mut parser, &mut statements);
+        parser.feed_bytes(&text.as_bytes()[i..]);
+        collect(&mut parser, &mut statements);
+        parser.finish();
+        collect(&mut parser, &mut statements);
+
+        let texts: Vec<_> = statements.iter().map(|s| s.text()).collect();
+        assert_eq!(texts, expected, "split at {}", i);
+        for statement in &statements {
+            let start = statement.offset();
+            assert_eq!(
+                &text[start..start + statement.text().len()],
+                statement.text(),
+                "offset"
+            );
+        }
+    }
+}
+
+#[test]
+fn chunked_01() {
+    check_splits("", &[]);
+    check_splits(" /* a {} */ ", &[]);
+}
+
+#[test]
+fn chunked_02() {
+    check_splits(
+        "a { color: red }\nb, c { margin: 0 }",
+        &["a { color: red }", "b, c { margin: 0 }"],
+    );
+}
+
+#[test]
+fn chunked_03() {
+    check_splits(
+        "@import \"a;b.css\"; p::after { content: \"}\" } /* } */ q { top: 0 }",
+        &[
+            "@import \"a;b.css\";",
+            "p::after { content: \"}\" }",
+            "q { top: 0 }",
+        ],
+    );
+}
+
+#[test]
+fn chunked_04() {
+    check_splits(
+        "@media screen { a { color: red } } p { content: 'é😀' }",
+        &["@media screen { a { color: red } }", "p { content: 'é😀' }"],
+    );
+}
+
+#[test]
+fn chunked_05() {
+    // Unclosed statements are returned only after `finish`.
+    let mut parser = ChunkedParser::new();
+    parser.feed("a { color: red");
+    assert_eq!(parser.next_statement(), None);
+
+    parser.finish();
+    let statement = parser.next_statement().unwrap();
+    let sheet = statement.parse();
+    assert_eq!(sheet.rules.len(), 1);
+    assert_eq!(sheet.rules[0].declarations[0].value, "red");
+    assert!(!statement.is_at_rule());
+    assert_eq!(parser.next_statement(), None);
+}
+
+#[test]
+fn chunked_06() {
+    let mut parser = ChunkedParser::new();
+    parser.feed_bytes(b"a { content: '\xF0\x9F");
+    parser.feed_bytes(b"\x98\x80\xFF' }");
+    parser.finish();
+
+    let statement = parser.next_statement().unwrap();
+    assert_eq!(statement.text(), "a { content: '😀\u{FFFD}' }");
+}
+
+#[test]
+fn chunked_07() {
+    let text = "a {}\n  b { color: red }";
+    let mut parser = ChunkedParser::new();
+    parser.feed(text);
+    parser.next_statement().unwrap();
+
+    let statement = parser.next_statement().unwrap();
+    let span = statement.parse().rules[0].span;
+    let span = Span::new(
+        span.start + statement.offset(),
+        span.end + statement.offset(),
+    );
+    assert_eq!(span.slice(text), "b { color: red }");
+}
+
+#[test]
+fn chunked_08() {
+    check_splits(
+        "<!-- a { color: red } -->\n<!--b{}-->",
+        &["a { color: red }", "b{}"],
+    );
+}
+
+#[test]
+fn chunked_09() {
+    // Parsed the same way as by `StyleSheet`.
+    let mut parser = ChunkedParser::new();
+    parser.feed("b, #c { color: red } @import 'a.css';");
+    parser.finish();
+
+    let rule_set = parser.next_statement().unwrap();
+    let at_rule = parser.next_statement().unwrap();
+
+    // Statements are kept while the parser is fed.
+    parser.feed("d { top: 0 }");
+    assert!(parser.next_statement().is_some());
+
+    let sheet = rule_set.parse();
+    let selectors: Vec<_> = sheet.rules.iter().map(|r| r.selector.to_string()).collect();
+    assert_eq!(selectors, ["b", "#c"]);
+
+    assert!(at_rule.is_at_rule());
+    assert!(at_rule.parse().rules.is_empty());
+    #[cfg(feature = "at_rules")]
+    assert_eq!(at_rule.parse().at_rules.len(), 1);
+}
+
+#[test]
+fn chunked_10() {
+    // A long input fed byte by byte, with a statement spanning many chunks.
+    let mut text = String::from("@media screen {");
+    for i in 0..200 {
+        text.push_str(&format!(" a{} {{ content: \"{}\" }}", i, i));
+    }
+    text.push_str(" }");
+    for i in 0..200 {
+        text.push_str(&format!("\nb{} {{ /* {} */ top: 0 }}", i, i));
+    }
+
+    let mut parser = ChunkedParser::new();
+    let mut statements = Vec::new();
+    for c in text.as_bytes().chunks(1) {
+        parser.feed_bytes(c);
+        collect(&mut parser, &mut statements);
+    }
+    parser.finish();
+    collect(&mut parser, &mut statements);
+
+    assert_eq!(statements.len(), 201);
+    for statement in &statements {
+        let start = statement.offset();
+        assert_eq!(
+            &text[start..start + statement.text().len()],
+            statement.text()
+        );
+    }
+}
@@ -0,0 +1,269 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Color parsing
+
+#![cfg(any(feature = "std", feature = "libm"))]
+
+use simplecss::*;
+
+macro_rules! test {
+    ($name:ident, $text:expr, $r:expr, $g:expr, $b:expr, $a:expr) => {
+        #[test]
+        fn $name() {
+            assert_eq!(
+                Color::parse($text),
+                Some(Color::Rgba(Rgba::new($r, $g, $b, $a)))
+            );
+        }
+    };
+}
+
+macro_rules! test_invalid {
+    ($name:ident, $text:expr) => {
+        #[test]
+        fn $name() {
+            assert_eq!(Color::parse($text), None);
+        }
+    };
+}
+
+test!(named_01, "red", 255, 0, 0, 255);
+test!(named_02, "RebeccaPurple", 102, 51, 153, 255);
+test!(named_03, "lightgoldenrodyellow", 250, 250, 210, 255);
+test!(named_04, "  yellowgreen /* comment */ ", 154, 205, 50, 255);
+test!(named_05, "TRANSPARENT", 0, 0, 0, 0);
+test_invalid!(named_06, "redd");
+test_invalid!(named_07, "red blue");
+
+#[test]
+fn current_color() {
+    assert_eq!(Color::parse("currentColor"), Some(Color::CurrentColor));
+    assert_eq!(Color::parse("currentcolor"), Some(Color::CurrentColor));
+}
+
+test!(hex_01, "#f80", 255, 136, 0, 255);
+test!(hex_02, "#F808", 255, 136, 0, 136);
+test!(hex_03, "#1e90ff", 30, 144, 255, 255);
+test!(hex_04, "#1E90FF80", 30, 144, 255, 128);
+test_invalid!(hex_05, "#12345");
+test_invalid!(hex_06, "#ggg");
+test_invalid!(hex_07, "#");
+
+test!(rgb_01, "rgb(255, 128, 0)", 255, 128, 0, 255);
+test!(rgb_02, "rgba(255, 128, 0, 0.5)", 255, 128, 0, 128);
+test!(rgb_03, "rgb(100%, 50%, 0%, 25%)", 255, 128, 0, 64);
+test!(rgb_04, "RGB(300, -20, 127.6)", 255, 0, 128, 255);
+test!(rgb_05, "rgb(255 128 0)", 255, 128, 0, 255);
+test!(rgb_06, "rgba(255 50% 0 / 0.5)", 255, 128, 0, 128);
+test!(rgb_07, "rgb(none 255 none / 10%)", 0, 255, 0, 26);
+test!(rgb_08, "rgb(0, 0, 0, 2)", 0, 0, 0, 255);
+test_invalid!(rgb_09, "rgb(255, 50%, 0)");
+test_invalid!(rgb_10, "rgb(255, 0)");
+test_invalid!(rgb_11, "rgb(255, 0, 0, 1, 1)");
+test_invalid!(rgb_12, "rgb(255 0 0 0.5)");
+test_invalid!(rgb_13, "rgb(255, none, 0)");
+test_invalid!(rgb_14, "rgb(255, 0 0)");
+test_invalid!(rgb_15, "rgb(1px 0 0)");
+test!(rgb_16, "rgb(calc(100 + 155), 0, 0)", 255, 0, 0, 255);
+test!(rgb_17, "rgba(calc(255), 0, 0, .5)", 255, 0, 0, 128);
+test!(
+    rgb_18,
+    "rgb(min(50%, 100%), 0%, 0%, calc(50% / 2))",
+    128,
+    0,
+    0,
+    64
+);
+test_invalid!(rgb_19, "rgb(calc(50%), 0, 0)");
+test_invalid!(rgb_20, "rgb(calc(1px), 0, 0)");
+
+test!(hsl_01, "hsl(120, 100%, 25%)", 0, 128, 0, 255);
+test!(hsl_02, "hsla(0, 100%, 50%, 0.5)", 255, 0, 0, 128);
+test!(hsl_03, "hsl(240deg 100% 50%)", 0, 0, 255, 255);
+test!(hsl_04, "hsl(0.5turn 100 50 / 50%)", 0, 255, 255, 128);
+test!(hsl_05, "hsl(-120, 100%, 50%)", 0, 0, 255, 255);
+test!(hsl_06, "hsl(3.14159rad 100% 50%)", 0, 255, 255, 255);
+test!(hsl_07, "hsl(400grad 0% 100%)", 255, 255, 255, 255);
+test!(hsl_08, "hsl(none 0% 50%)", 128, 128, 128, 255);
+test_invalid!(hsl_09, "hsl(120, 100, 25)");
+test_invalid!(hsl_10, "hsl(120px 100% 25%)");
+test!(
+    hsl_11,
+    "hsl(calc(60 * 2), 100%, calc(50% / 2))",
+    0,
+    128,
+    0,
+    255
+);
+
+test!(hwb_01, "hwb(0 0% 0%)", 255, 0, 0, 255);
+test!(hwb_02, "hwb(120 20% 20%)", 51, 204, 51, 255);
+test!(hwb_03, "hwb(0 60% 60% / 0.5)", 128, 128, 128, 128);
+test_invalid!(hwb_04, "hwb(0, 0%, 0%)");
+
+test_invalid!(other_01, "");
+test_invalid!(other_02, "lab(50% 0)");
+test_invalid!(other_03, "1px");
+
+#[test]
+fn from_component() {
+    let value = Value::parse("1px solid #00f").unwrap();
+    let colors: Vec<_> = value
+        .components
+        .iter()
+        .filter_map(Color::from_component)
+        .collect();
+    assert_eq!(colors, [Color::Rgba(Rgba::new(0, 0, 255, 255))]);
+}
+
+#[test]
+fn declaration() {
+    let sheet = StyleSheet::parse("rect { fill: hsl(0 100% 50%); stroke: none }");
+    let declarations = &sheet.rules[0].declarations;
+    assert_eq!(
+        declarations[0].parse_color(),
+        Some(Color::Rgba(Rgba::new(255, 0, 0, 255)))
+    );
+    assert_eq!(declarations[1].parse_color(), None);
+}
+
+test!(lab_01, "lab(54.29 80.8 69.89)", 255, 0, 0, 255);
+test!(lab_02, "lab(50% 0 0)", 119, 119, 119, 255);
+test!(lab_03, "lab(100 0 0 / 0.5)", 255, 255, 255, 128);
+test!(lab_04, "lab(150 0 0)", 255, 255, 255, 255);
+test!(lch_01, "lch(54.29 106.84 40.85)", 255, 0, 0, 255);
+test!(lch_02, "lch(50% 0 none)", 119, 119, 119, 255);
+test_invalid!(lab_05, "lab(50, 0, 0)");
+
+test!(oklab_01, "oklab(0.62796 0.22486 0.12585)", 255, 0, 0, 255);
+test!(oklab_02, "oklab(100% 0 0)", 255, 255, 255, 255);
+test!(oklch_01, "oklch(0.62796 0.25768 29.2339)", 255, 0, 0, 255);
+test!(oklch_02, "oklch(70% 0.1 200)", 64, 177, 183, 255);
+test!(oklch_03, "OKLCH(0.7 25% 200deg / 50%)", 64, 177, 183, 128);
+// Out of gamut, mapped by reducing chroma.
+test!(oklch_04, "oklch(0.5 0.4 145)", 0, 124, 0, 255);
+
+test!(predefined_01, "color(srgb 1 0.5 0)", 255, 128, 0, 255);
+test!(
+    predefined_02,
+    "color(srgb-linear 0.5 0.5 0.5)",
+    188,
+    188,
+    188,
+    255
+);
+test!(predefined_03, "color(display-p3 1 1 1)", 255, 255, 255, 255);
+test!(predefined_04, "color(display-p3 1 0 0)", 255, 11, 12, 255);
+test!(
+    predefined_05,
+    "color(xyz 0.9505 1 1.089)",
+    255,
+    255,
+    255,
+    255
+);
+test!(
+    predefined_06,
+    "color(xyz-d50 0.9642 1 0.8252)",
+    255,
+    255,
+    255,
+    255
+);
+test!(
+    predefined_07,
+    "color(prophoto-rgb 0.5 0.5 0.5 / 0.5)",
+    146,
+    146,
+    146,
+    128
+);
+test!(
+    predefined_08,
+    "color(a98-rgb 100% 100% 100%)",
+    255,
+    255,
+    255,
+    255
+);
+test!(predefined_09, "color(rec2020 0 0 0)", 0, 0, 0, 255);
+test_invalid!(predefined_10, "color(unknown 1 1 1)");
+test_invalid!(predefined_11, "color(srgb 1 1)");
+
+test!(mix_01, "color-mix(in srgb, red, blue)", 128, 0, 128, 255);
+test!(mix_02, "color-mix(in srgb, red 25%, blue)", 64, 0, 191, 255);
+test!(
+    mix_03,
+    "color-mix(in srgb, 25% red, blue 25%)",
+    128,
+    0,
+    128,
+    128
+);
+test!(mix_04, "color-mix(in oklab, red, blue)", 140, 83, 162, 255);
+test!(mix_05, "color-mix(in hsl, red, blue)", 255, 0, 255, 255);
+test!(
+    mix_06,
+    "color-mix(in hsl longer hue, red, blue)",
+    0,
+    255,
+    0,
+    255
+);
+// The white hue is powerless, so the blue one is used.
+test!(mix_07, "color-mix(in lch, white, blue)", 175, 137, 255, 255);
+test!(
+    mix_08,
+    "color-mix(in srgb, transparent, red)",
+    255,
+    0,
+    0,
+    128
+);
+test!(
+    mix_09,
+    "color-mix(in srgb, color-mix(in srgb, red, blue), blue)",
+    64,
+    0,
+    191,
+    255
+);
+test_invalid!(mix_10, "color-mix(in srgb, red 0%, blue 0%)");
+test_invalid!(mix_11, "color-mix(in srgb longer hue, red, blue)");
+test_invalid!(mix_12, "color-mix(red, blue)");
+test_invalid!(mix_13, "color-mix(in srgb, red 150%, blue)");
+test_invalid!(mix_14, "color-mix(in srgb, red, currentColor)");
+
+test!(relative_01, "rgb(from red r g b / 50%)", 255, 0, 0, 128);
+test!(relative_02, "rgb(from #123456 b g r)", 86, 52, 18, 255);
+test!(relative_03, "hsl(from red 120 s l)", 0, 255, 0, 255);
+test!(relative_04, "hwb(from red h 50% b)", 255, 128, 128, 255);
+test!(relative_05, "oklch(from red l c h)", 255, 0, 0, 255);
+test!(
+    relative_06,
+    "rgb(from rgb(0 0 0 / 0.5) 255 g b)",
+    255,
+    0,
+    0,
+    128
+);
+test!(
+    relative_07,
+    "color(from red display-p3 r g b)",
+    255,
+    0,
+    0,
+    255
+);
+test!(
+    relative_08,
+    "lab(from white l 0 0 / alpha)",
+    255,
+    255,
+    255,
+    255
+);
+test_invalid!(relative_09, "rgb(from red r, g, b)");
+test_invalid!(relative_10, "rgb(from red x y z)");
+test_invalid!(relative_11, "rgb(r g b)");
@@ -21,17 +21,19 @@ macro_rules! tokenize {
 
 fn declare<'a>(name: &'a str, value: &'a str) -> Declaration<'a> {
     Declaration {
-        name,
+        name: name.into(),
         value,
         important: false,
+        span: Default::default(),
     }
 }
 
 fn declare_important<'a>(name: &'a str, value: &'a str) -> Declaration<'a> {
     Declaration {
-        name,
+        name: name.into(),
         value,
         important: true,
+        span: Default::default(),
     }
 }
 
@@ -154,6 +156,63 @@ tokenize!(
 
 tokenize!(tokenize_34, "*zoom:1;", declare("zoom", "1"));
 
+tokenize!(
+    tokenize_35,
+    "content:'\\\\';color:red",
+    declare("content", "'\\\\'"),
+    declare("color", "red")
+);
+
+tokenize!(
+    tokenize_36,
+    "color: red !IMPORTANT; COLOR: Red ! Important",
+    declare_important("color", "red"),
+    declare_important("COLOR", "Red")
+);
+
+tokenize!(
+    tokenize_37,
+    "background:url(\"a)b.png\") no-repeat;color:red",
+    declare("background", "url(\"a)b.png\") no-repeat"),
+    declare("color", "red")
+);
+
+tokenize!(
+    tokenize_38,
+    "width:calc(100% - max(1px, 2px));color:rgb(calc(1) 2 3)",
+    declare("width", "calc(100% - max(1px, 2px))"),
+    declare("color", "rgb(calc(1) 2 3)")
+);
+
+tokenize!(
+    tokenize_39,
+    "fill:var(--x, fn(1;2));grid-area:[a] 1 / span 2",
+    declare("fill", "var(--x, fn(1;2))"),
+    declare("grid-area", "[a] 1 / span 2")
+);
+
+tokenize!(
+    tokenize_40,
+    "x:{a:b;c:d} !important;content:\\\\;font:12px/1.5 serif",
+    declare_important("x", "{a:b;c:d}"),
+    declare("content", "\\\\"),
+    declare("font", "12px/1.5 serif")
+);
+
+tokenize!(
+    tokenize_41,
+    "background:url(data:image/png;base64,iVBO) /* c */ ; color:a/**/b",
+    declare("background", "url(data:image/png;base64,iVBO)"),
+    declare("color", "a/**/b")
+);
+
+tokenize!(
+    tokenize_42,
+    "\\63 olor:red;--\\:a:\\31 0px",
+    declare("color", "red"),
+    declare("--:a", "\\31 0px")
+);
+
 //tokenize!(tokenize_, "@unsupported { splines: reticulating } color: green",
 //    declare("color", "green")
 //);
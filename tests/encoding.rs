@@ -0,0 +1,124 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Encoding detection
+
+use std::borrow::Cow;
+
+use simplecss::*;
+
+fn utf16(text: &str, be: bool) -> Vec<u8> {
+    text.encode_utf16()
+        .flat_map(|c| if be { c.to_be_bytes() } else { c.to_le_bytes() })
+        .collect()
+}
+
+#[test]
+fn encoding_01() {
+    let (text, encoding) = decode_bytes("a { content: 'é' }".as_bytes(), None);
+    assert_eq!(encoding, Encoding::Utf8);
+    assert!(matches!(text, Cow::Borrowed("a { content: 'é' }")));
+}
+
+#[test]
+fn encoding_02() {
+    // BOM wins over @charset and the environment.
+    let bytes = b"\xEF\xBB\xBF@charset \"latin1\"; a { content: '\xC3\xA9' }";
+    let (text, encoding) = decode_bytes(bytes, Some(Encoding::Windows1252));
+    assert_eq!(encoding, Encoding::Utf8);
+    assert_eq!(text, "@charset \"latin1\"; a { content: 'é' }");
+}
+
+#[test]
+fn encoding_03() {
+    let mut be = vec![0xFE, 0xFF];
+    be.extend(utf16("a { content: '😀' }", true));
+    let (text, encoding) = decode_bytes(&be, None);
+    assert_eq!(encoding, Encoding::Utf16Be);
+    assert_eq!(text, "a { content: '😀' }");
+
+    let mut le = vec![0xFF, 0xFE];
+    le.extend(utf16("a { content: '😀' }", false));
+    le.push(b'x');
+    let (text_le, encoding_le) = decode_bytes(&le, None);
+    assert_eq!(encoding_le, Encoding::Utf16Le);
+    assert_eq!(text_le, "a { content: '😀' }\u{FFFD}");
+}
+
+#[test]
+fn encoding_04() {
+    let (text, encoding) = decode_bytes(b"@charset \"ISO-8859-1\"; a{content:'\xE9'}", None);
+    assert_eq!(encoding, Encoding::Windows1252);
+    assert_eq!(text, "@charset \"ISO-8859-1\"; a{content:'é'}");
+
+    // UTF-16 in @charset means UTF-8.
+    let latin1 = Some(Encoding::Windows1252);
+    assert_eq!(
+        decode_bytes(b"@charset \"utf-16\";", latin1).1,
+        Encoding::Utf8
+    );
+
+    // Must match byte for byte.
+    assert_eq!(decode_bytes(b"@charset 'latin1';", None).1, Encoding::Utf8);
+    assert_eq!(
+        decode_bytes(b"@CHARSET \"latin1\";", None).1,
+        Encoding::Utf8
+    );
+
+    // Unknown labels are ignored.
+    assert_eq!(
+        decode_bytes(b"@charset \"koi8-r\";", latin1).1,
+        Encoding::Windows1252
+    );
+}
+
+#[test]
+fn encoding_05() {
+    let (text, encoding) = decode_bytes(b"a{content:'\xE9'}", Some(Encoding::Windows1252));
+    assert_eq!(encoding, Encoding::Windows1252);
+    assert_eq!(text, "a{content:'é'}");
+}
+
+#[test]
+fn encoding_06() {
+    let (text, encoding) = decode_bytes(b"a{content:'\xE9'}", None);
+    assert_eq!(encoding, Encoding::Utf8);
+    assert_eq!(text, "a{content:'\u{FFFD}'}");
+}
+
+#[test]
+fn encoding_07() {
+    assert_eq!(Encoding::from_label(" UTF8 "), Some(Encoding::Utf8));
+    assert_eq!(Encoding::from_label("unicodeFFFE"), Some(Encoding::Utf16Be));
+    assert_eq!(Encoding::from_label("windows-1251"), None);
+    assert_eq!(Encoding::from_label("Latin1"), Some(Encoding::Windows1252));
+    assert_eq!(Encoding::from_label("ascii"), Some(Encoding::Windows1252));
+}
+
+#[test]
+fn encoding_10() {
+    // Not ISO-8859-1 in the 0x80-0x9F range.
+    let bytes =
+        b"@charset \"windows-1252\"; a::after { content: '\x80 \x93a\x94 \x81\x9F\xA0\xFF' }";
+    let (text, encoding) = decode_bytes(bytes, None);
+    assert_eq!(encoding, Encoding::Windows1252);
+    assert!(text.ends_with("content: '€ “a” \u{81}Ÿ\u{A0}ÿ' }"));
+}
+
+#[test]
+fn encoding_08() {
+    let mut buffer = String::new();
+    let (sheet, encoding) = StyleSheet::parse_bytes(b"a { color: red }", None, &mut buffer);
+    assert_eq!(encoding, Encoding::Utf8);
+    assert_eq!(sheet.to_string(), "a { color:red; }");
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn encoding_09() {
+    let mut buffer = String::new();
+    let bytes = b"@charset \"latin1\"; p::after { content: '\xE9' } a { color: red }";
+    let (sheet, encoding) = StyleSheet::parse_bytes(bytes, None, &mut buffer);
+    assert_eq!(encoding, Encoding::Windows1252);
+    assert_eq!(sheet.to_string(), "a { color:red; }");
+}
@@ -0,0 +1,350 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Font parsing
+
+#![cfg(any(feature = "std", feature = "libm"))]
+
+use simplecss::*;
+
+fn px(value: f64) -> FontSize {
+    FontSize::Length(Length::new(value, LengthUnit::Px))
+}
+
+fn named(name: &str) -> FontFamily<'_> {
+    FontFamily::Named(name.into())
+}
+
+fn generic(family: GenericFamily) -> FontFamily<'static> {
+    FontFamily::Generic(family)
+}
+
+#[test]
+fn font_01() {
+    let font = Font::parse("12px serif").unwrap();
+    assert_eq!(font.style, FontStyle::Normal);
+    assert!(!font.small_caps);
+    assert_eq!(font.weight, FontWeight::NORMAL);
+    assert_eq!(font.stretch, 100.0);
+    assert_eq!(font.size, px(12.0));
+    assert_eq!(font.line_height, LineHeight::Normal);
+    assert_eq!(font.families, [generic(GenericFamily::Serif)]);
+    assert_eq!(font.system, None);
+}
+
+#[test]
+fn font_02() {
+    let font = Font::parse("condensed oblique 25deg small-caps 900 50%/120% 'A', B C").unwrap();
+    assert_eq!(font.style, FontStyle::Oblique(25.0));
+    assert!(font.small_caps);
+    assert_eq!(font.weight, FontWeight::Absolute(900.0));
+    assert_eq!(font.stretch, 75.0);
+    assert_eq!(
+        font.size,
+        FontSize::Length(Length::new(50.0, LengthUnit::Percent))
+    );
+    assert_eq!(
+        font.line_height,
+        LineHeight::Length(Length::new(120.0, LengthUnit::Percent))
+    );
+    assert_eq!(font.families, [named("A"), named("B C")]);
+}
+
+#[test]
+fn font_03() {
+    let font = Font::parse("normal normal lighter larger/2 monospace").unwrap();
+    assert_eq!(font.weight, FontWeight::Lighter);
+    assert_eq!(font.size, FontSize::Larger);
+    assert_eq!(font.line_height, LineHeight::Number(2.0));
+}
+
+#[test]
+fn font_04() {
+    let font = Font::parse("oblique x-large/1 cursive").unwrap();
+    assert_eq!(font.style, FontStyle::Oblique(14.0));
+    assert_eq!(font.size, px(24.0));
+}
+
+#[test]
+fn font_05() {
+    let font = Font::parse("bold calc(1em + 1em) system-ui").unwrap();
+    assert_eq!(font.weight, FontWeight::BOLD);
+    assert_eq!(
+        font.size,
+        FontSize::Length(Length::new(2.0, LengthUnit::Em))
+    );
+    assert_eq!(font.families, [generic(GenericFamily::SystemUi)]);
+}
+
+#[test]
+fn font_06() {
+    let font = Font::parse("MESSAGE-BOX").unwrap();
+    assert_eq!(font.system, Some(SystemFont::MessageBox));
+    assert!(font.families.is_empty());
+}
+
+#[test]
+fn font_invalid() {
+    let values = [
+        "",
+        "serif",
+        "12px",
+        "bold serif",
+        "12px/ serif",
+        "12px/1.5",
+        "italic italic 12px serif",
+        "normal normal normal normal normal 12px serif",
+        "12px serif,",
+        "12px 'A' B",
+        "-1px serif",
+        "50% 12px serif",
+        "oblique 100deg 12px serif",
+        "caption serif",
+    ];
+
+    for value in values {
+        assert_eq!(Font::parse(value), None, "{}", value);
+    }
+}
+
+#[test]
+fn font_shorthand_consistency() {
+    // Values accepted by the typed parser and by the shorthand expansion agree.
+    let values = [
+        "12px serif",
+        "italic bold 12px/30px Georgia, serif",
+        "condensed oblique 10deg 12px a",
+        "bold",
+        "12px",
+    ];
+
+    for value in values {
+        let text = format!("font: {}", value);
+        let declaration = DeclarationTokenizer::from(text.as_str()).next().unwrap();
+        assert_eq!(
+            Font::parse(value).is_some(),
+            declaration.expand().is_some(),
+            "{}",
+            value
+        );
+    }
+}
+
+#[test]
+fn family_list_01() {
+    let families =
+        FontFamily::parse_list("\"Times New Roman\", Times,  Liberation   Serif , serif").unwrap();
+    assert_eq!(
+        families,
+        [
+            named("Times New Roman"),
+            named("Times"),
+            named("Liberation Serif"),
+            generic(GenericFamily::Serif),
+        ]
+    );
+}
+
+#[test]
+fn family_list_02() {
+    let families =
+        FontFamily::parse_list(r#"'sans-serif', Sans-Serif, "A\"B", Font\ 1, ui-ROUNDED"#).unwrap();
+    assert_eq!(
+        families,
+        [
+            named("sans-serif"),
+            generic(GenericFamily::SansSerif),
+            named("A\"B"),
+            named("Font 1"),
+            generic(GenericFamily::UiRounded),
+        ]
+    );
+}
+
+#[test]
+fn family_list_03() {
+    // Reserved keywords are allowed only as a part of a longer name.
+    assert_eq!(
+        FontFamily::parse_list("Default Font"),
+        Some(vec![named("Default Font")])
+    );
+    assert_eq!(
+        FontFamily::parse_list("'inherit'"),
+        Some(vec![named("inherit")])
+    );
+    assert_eq!(FontFamily::parse_list("inherit"), None);
+    assert_eq!(FontFamily::parse_list("a, default"), None);
+}
+
+#[test]
+fn family_list_invalid() {
+    for value in ["", "a,", ",a", "a,,b", "'a' b", "a 1", "a()", "a/b"] {
+        assert_eq!(FontFamily::parse_list(value), None, "{}", value);
+    }
+}
+
+#[test]
+fn generic_names() {
+    for name in [
+        "serif",
+        "sans-serif",
+        "monospace",
+        "fangsong",
+        "ui-monospace",
+    ] {
+        assert_eq!(GenericFamily::from_name(name).unwrap().as_str(), name);
+    }
+    assert_eq!(GenericFamily::from_name("arial"), None);
+}
+
+#[test]
+fn weight() {
+    assert_eq!(FontWeight::parse("normal"), Some(FontWeight::NORMAL));
+    assert_eq!(FontWeight::parse("BOLD"), Some(FontWeight::BOLD));
+    assert_eq!(FontWeight::parse("1"), Some(FontWeight::Absolute(1.0)));
+    assert_eq!(
+        FontWeight::parse("1000"),
+        Some(FontWeight::Absolute(1000.0))
+    );
+    assert_eq!(
+        FontWeight::parse("calc(100 * 5)"),
+        Some(FontWeight::Absolute(500.0))
+    );
+    assert_eq!(FontWeight::parse("0"), None);
+    assert_eq!(FontWeight::parse("100px"), None);
+    assert_eq!(FontWeight::parse("heavy"), None);
+}
+
+#[test]
+fn weight_resolve() {
+    let bolder = [
+        (50.0, 400.0),
+        (300.0, 400.0),
+        (400.0, 700.0),
+        (600.0, 900.0),
+        (950.0, 950.0),
+    ];
+    for (parent, weight) in bolder {
+        assert_eq!(FontWeight::Bolder.resolve(parent), weight, "{}", parent);
+    }
+
+    let lighter = [
+        (50.0, 50.0),
+        (300.0, 100.0),
+        (600.0, 400.0),
+        (800.0, 700.0),
+        (950.0, 700.0),
+    ];
+    for (parent, weight) in lighter {
+        assert_eq!(FontWeight::Lighter.resolve(parent), weight, "{}", parent);
+    }
+
+    assert_eq!(FontWeight::BOLD.resolve(100.0), 700.0);
+}
+
+#[test]
+fn style() {
+    assert_eq!(FontStyle::parse("italic"), Some(FontStyle::Italic));
+    assert_eq!(FontStyle::parse("oblique"), Some(FontStyle::Oblique(14.0)));
+    assert_eq!(
+        FontStyle::parse("oblique -0.25turn"),
+        Some(FontStyle::Oblique(-90.0))
+    );
+    assert_eq!(FontStyle::parse("oblique 91deg"), None);
+    assert_eq!(FontStyle::parse("italic 10deg"), None);
+}
+
+#[test]
+fn size() {
+    let context = ResolveContext {
+        font_size: 20.0,
+        ..ResolveContext::default()
+    };
+
+    assert_eq!(FontSize::parse("small"), Some(px(128.0 / 9.0)));
+    assert_eq!(FontSize::parse("xxx-large"), Some(px(48.0)));
+    assert_eq!(FontSize::parse("150%").unwrap().to_px(&context), 30.0);
+    assert_eq!(FontSize::parse("2em").unwrap().to_px(&context), 40.0);
+    assert_eq!(FontSize::parse("2rem").unwrap().to_px(&context), 32.0);
+    assert_eq!(FontSize::parse("larger").unwrap().to_px(&context), 24.0);
+    assert_eq!(FontSize::parse("-1px"), None);
+    assert_eq!(FontSize::parse("big"), None);
+}
+
+#[test]
+fn line_height() {
+    assert_eq!(LineHeight::parse("normal"), Some(LineHeight::Normal));
+    assert_eq!(LineHeight::parse("1.2"), Some(LineHeight::Number(1.2)));
+    assert_eq!(
+        LineHeight::parse("20px"),
+        Some(LineHeight::Length(Length::new(20.0, LengthUnit::Px)))
+    );
+    assert_eq!(LineHeight::parse("-1"), None);
+}
+
+#[test]
+fn features() {
+    assert_eq!(
+        FontFeature::parse_list("'kern', \"smcp\" on, 'c2sc' 0, 'swsh' 2"),
+        Some(vec![
+            FontFeature {
+                tag: *b"kern",
+                value: 1
+            },
+            FontFeature {
+                tag: *b"smcp",
+                value: 1
+            },
+            FontFeature {
+                tag: *b"c2sc",
+                value: 0
+            },
+            FontFeature {
+                tag: *b"swsh",
+                value: 2
+            },
+        ])
+    );
+    assert_eq!(FontFeature::parse_list("NORMAL"), Some(vec![]));
+
+    for value in [
+        "",
+        "kern",
+        "'ker'",
+        "'kerns'",
+        "'kern' -1",
+        "'kern' 1.5",
+        "'kern' yes",
+        "'kern',",
+    ] {
+        assert_eq!(FontFeature::parse_list(value), None, "{}", value);
+    }
+}
+
+#[test]
+fn variations() {
+    assert_eq!(
+        FontVariation::parse_list("'opsz' 16, 'slnt' -12.5"),
+        Some(vec![
+            FontVariation {
+                tag: *b"opsz",
+                value: 16.0
+            },
+            FontVariation {
+                tag: *b"slnt",
+                value: -12.5
+            },
+        ])
+    );
+    assert_eq!(FontVariation::parse_list("normal"), Some(vec![]));
+
+    for value in [
+        "",
+        "'wght'",
+        "wght 400",
+        "'wght' 400px",
+        "'wght' 400 'wdth' 100",
+    ] {
+        assert_eq!(FontVariation::parse_list(value), None, "{}", value);
+    }
+}
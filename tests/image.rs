@@ -0,0 +1,381 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Image values parsing
+
+#![cfg(any(feature = "std", feature = "libm"))]
+
+use simplecss::*;
+
+fn px(value: f64) -> Length {
+    Length::new(value, LengthUnit::Px)
+}
+
+fn percent(value: f64) -> Length {
+    Length::new(value, LengthUnit::Percent)
+}
+
+fn rgb(r: u8, g: u8, b: u8) -> Color {
+    Color::Rgba(Rgba::new(r, g, b, 255))
+}
+
+fn stop<P>(color: Color, position: Option<P>) -> GradientItem<P> {
+    GradientItem::ColorStop { color, position }
+}
+
+fn offset(offset: Length, from_end: bool) -> PositionOffset {
+    PositionOffset { offset, from_end }
+}
+
+fn linear(text: &str) -> LinearGradient {
+    match Image::parse(text) {
+        Some(Image::LinearGradient(gradient)) => gradient,
+        image => panic!("{:?}", image),
+    }
+}
+
+fn radial(text: &str) -> RadialGradient {
+    match Image::parse(text) {
+        Some(Image::RadialGradient(gradient)) => gradient,
+        image => panic!("{:?}", image),
+    }
+}
+
+fn conic(text: &str) -> ConicGradient {
+    match Image::parse(text) {
+        Some(Image::ConicGradient(gradient)) => gradient,
+        image => panic!("{:?}", image),
+    }
+}
+
+macro_rules! test_invalid {
+    ($name:ident, $text:expr) => {
+        #[test]
+        fn $name() {
+            assert_eq!(Image::parse($text), None);
+        }
+    };
+}
+
+#[test]
+fn url() {
+    assert_eq!(Image::parse("url(a.png)"), Some(Image::Url("a.png".into())));
+    assert_eq!(
+        Image::parse("url('a\\\"b.png')"),
+        Some(Image::Url("a\"b.png".into()))
+    );
+}
+
+#[test]
+fn linear_gradient() {
+    let gradient = linear("linear-gradient(red, #00f)");
+    assert_eq!(gradient.direction, LineDirection::Angle(180.0));
+    assert_eq!(
+        gradient.items,
+        [stop(rgb(255, 0, 0), None), stop(rgb(0, 0, 255), None)]
+    );
+    assert!(!gradient.repeating);
+}
+
+#[test]
+fn linear_gradient_directions() {
+    assert_eq!(
+        linear("linear-gradient(45deg, red, blue)").direction,
+        LineDirection::Angle(45.0)
+    );
+    assert_eq!(
+        linear("linear-gradient(0.5turn, red, blue)").direction,
+        LineDirection::Angle(180.0)
+    );
+    assert_eq!(
+        linear("linear-gradient(0, red, blue)").direction,
+        LineDirection::Angle(0.0)
+    );
+    assert_eq!(
+        linear("linear-gradient(calc(90deg / 2), red, blue)").direction,
+        LineDirection::Angle(45.0)
+    );
+    assert_eq!(
+        linear("linear-gradient(to top, red, blue)").direction,
+        LineDirection::Angle(0.0)
+    );
+    assert_eq!(
+        linear("linear-gradient(to LEFT, red, blue)").direction,
+        LineDirection::Angle(270.0)
+    );
+    assert_eq!(
+        linear("linear-gradient(to top right, red, blue)").direction,
+        LineDirection::TopRight
+    );
+    assert_eq!(
+        linear("linear-gradient(to left bottom, red, blue)").direction,
+        LineDirection::BottomLeft
+    );
+}
+
+#[test]
+fn linear_gradient_geometry() {
+    let direction = LineDirection::TopRight;
+    assert!((direction.to_degrees(100.0, 100.0) - 45.0).abs() < 1e-9);
+    assert!((LineDirection::BottomRight.to_degrees(100.0, 100.0) - 135.0).abs() < 1e-9);
+    assert!((LineDirection::TopLeft.to_degrees(200.0, 100.0) - 333.434_948_822_922).abs() < 1e-9);
+
+    assert!((linear("linear-gradient(red, blue)").line_length(200.0, 100.0) - 100.0).abs() < 1e-9);
+    assert!(
+        (linear("linear-gradient(90deg, red, blue)").line_length(200.0, 100.0) - 200.0).abs()
+            < 1e-9
+    );
+    let diagonal = linear("linear-gradient(to bottom right, red, blue)").line_length(100.0, 100.0);
+    assert!((diagonal - 100.0 * std::f64::consts::SQRT_2).abs() < 1e-9);
+}
+
+#[test]
+fn color_stops() {
+    let gradient =
+        linear("linear-gradient(red 10px, 40%, lime 50% 60%, transparent calc(50% + 20%))");
+    assert_eq!(
+        gradient.items,
+        [
+            stop(rgb(255, 0, 0), Some(px(10.0))),
+            GradientItem::Hint(percent(40.0)),
+            stop(rgb(0, 255, 0), Some(percent(50.0))),
+            stop(rgb(0, 255, 0), Some(percent(60.0))),
+            stop(Color::Rgba(Rgba::new(0, 0, 0, 0)), Some(percent(70.0))),
+        ]
+    );
+    assert!(matches!(
+        linear("linear-gradient(currentColor 0, red 1em)").items[..],
+        [
+            GradientItem::ColorStop {
+                color: Color::CurrentColor,
+                position: Some(_)
+            },
+            GradientItem::ColorStop {
+                position: Some(Length {
+                    unit: LengthUnit::Em,
+                    ..
+                }),
+                ..
+            },
+        ]
+    ));
+}
+
+#[test]
+fn repeating() {
+    assert!(linear("repeating-linear-gradient(red, blue 10px)").repeating);
+    assert!(radial("Repeating-Radial-Gradient(red, blue 10px)").repeating);
+    assert!(conic("repeating-conic-gradient(red, blue 10deg)").repeating);
+}
+
+test_invalid!(invalid_01, "linear-gradient(red)");
+test_invalid!(invalid_02, "linear-gradient(to right)");
+test_invalid!(invalid_03, "linear-gradient(to top bottom, red, blue)");
+test_invalid!(invalid_04, "linear-gradient(10%, red, blue)");
+test_invalid!(invalid_05, "linear-gradient(red, 10%, 20%, blue)");
+test_invalid!(invalid_06, "linear-gradient(red, blue, 10%)");
+test_invalid!(invalid_07, "linear-gradient(red 1px 2px 3px, blue)");
+test_invalid!(invalid_08, "linear-gradient(red 10deg, blue)");
+test_invalid!(invalid_09, "linear-gradient(45deg red, blue)");
+test_invalid!(invalid_10, "-webkit-linear-gradient(top, red, blue)");
+test_invalid!(invalid_11, "radial-gradient(circle 10%, red, blue)");
+test_invalid!(invalid_12, "radial-gradient(circle 1px 2px, red, blue)");
+test_invalid!(invalid_13, "radial-gradient(ellipse 1px, red, blue)");
+test_invalid!(invalid_14, "radial-gradient(-1px, red, blue)");
+test_invalid!(invalid_15, "radial-gradient(1px circle 2px, red, blue)");
+test_invalid!(invalid_16, "radial-gradient(at, red, blue)");
+test_invalid!(invalid_17, "conic-gradient(from red, red, blue)");
+test_invalid!(invalid_18, "conic-gradient(red, blue 10px)");
+test_invalid!(invalid_19, "image-set()");
+test_invalid!(invalid_20, "image-set('a.png' 1x 2x)");
+test_invalid!(invalid_21, "image-set(image-set('a.png'))");
+test_invalid!(invalid_22, "url(a.png) url(b.png)");
+test_invalid!(invalid_23, "repeating-image-set('a.png')");
+
+#[test]
+fn radial_gradient() {
+    let gradient = radial("radial-gradient(red, blue)");
+    assert_eq!(gradient.shape, RadialShape::Ellipse);
+    assert_eq!(gradient.size, RadialSize::FarthestCorner);
+    assert_eq!(gradient.position, Position::CENTER);
+    assert_eq!(gradient.items.len(), 2);
+
+    let circle = radial("radial-gradient(circle closest-side at left 10px top, red, blue)");
+    assert_eq!(circle.shape, RadialShape::Circle);
+    assert_eq!(circle.size, RadialSize::ClosestSide);
+    assert_eq!(circle.position.x, offset(px(10.0), false));
+    assert_eq!(circle.position.y, offset(percent(0.0), false));
+}
+
+#[test]
+fn radial_gradient_sizes() {
+    assert_eq!(
+        radial("radial-gradient(10px, red, blue)").shape,
+        RadialShape::Circle
+    );
+    assert_eq!(
+        radial("radial-gradient(10px, red, blue)").size,
+        RadialSize::Circle(px(10.0))
+    );
+    assert_eq!(
+        radial("radial-gradient(10px 20%, red, blue)").size,
+        RadialSize::Ellipse(px(10.0), percent(20.0))
+    );
+    assert_eq!(
+        radial("radial-gradient(ellipse 10px 20%, red, blue)").shape,
+        RadialShape::Ellipse
+    );
+    assert_eq!(
+        radial("radial-gradient(10px circle, red, blue)").size,
+        RadialSize::Circle(px(10.0))
+    );
+    assert_eq!(
+        radial("radial-gradient(farthest-side circle, red, blue)").size,
+        RadialSize::FarthestSide
+    );
+    assert_eq!(
+        radial("radial-gradient(circle, red, blue)").size,
+        RadialSize::FarthestCorner
+    );
+    assert_eq!(
+        radial("radial-gradient(at 0 0, red, blue)").position.x,
+        offset(px(0.0), false)
+    );
+}
+
+#[test]
+fn radial_gradient_resolve() {
+    let context = ResolveContext::default();
+    let resolve = |text| radial(text).resolve(&context, 200.0, 100.0);
+    assert_eq!(
+        resolve("radial-gradient(closest-side, red, blue)"),
+        ((100.0, 50.0), (100.0, 50.0))
+    );
+    assert_eq!(
+        resolve("radial-gradient(circle closest-side, red, blue)"),
+        ((100.0, 50.0), (50.0, 50.0))
+    );
+    assert_eq!(
+        resolve("radial-gradient(circle farthest-side at 20px 10px, red, blue)"),
+        ((20.0, 10.0), (180.0, 180.0))
+    );
+    assert_eq!(
+        resolve("radial-gradient(circle farthest-corner at 0 0, red, blue)")
+            .1
+             .0,
+        200.0_f64.hypot(100.0)
+    );
+    assert_eq!(
+        resolve("radial-gradient(circle closest-corner at 30px 40px, red, blue)").1,
+        (50.0, 50.0)
+    );
+    let ((_, _), (x, y)) = resolve("radial-gradient(farthest-corner, red, blue)");
+    assert!((x - 100.0 * std::f64::consts::SQRT_2).abs() < 1e-9);
+    assert!((y - 50.0 * std::f64::consts::SQRT_2).abs() < 1e-9);
+    assert_eq!(
+        resolve("radial-gradient(2em 50%, red, blue)").1,
+        (32.0, 50.0)
+    );
+}
+
+#[test]
+fn conic_gradient() {
+    let gradient = conic("conic-gradient(red, 25%, blue 50%, lime 0.75turn)");
+    assert_eq!(gradient.from, 0.0);
+    assert_eq!(gradient.position, Position::CENTER);
+    assert_eq!(
+        gradient.items,
+        [
+            stop(rgb(255, 0, 0), None),
+            GradientItem::Hint(90.0),
+            stop(rgb(0, 0, 255), Some(180.0)),
+            stop(rgb(0, 255, 0), Some(270.0)),
+        ]
+    );
+
+    let rotated = conic("conic-gradient(from 45deg at right bottom, red 0 10deg, blue)");
+    assert_eq!(rotated.from, 45.0);
+    assert_eq!(rotated.position.x, offset(percent(100.0), false));
+    assert_eq!(rotated.items.len(), 3);
+    assert_eq!(
+        conic("conic-gradient(at top, red, blue)").position.y,
+        offset(percent(0.0), false)
+    );
+}
+
+#[test]
+fn image_set() {
+    let Some(Image::ImageSet(options)) = Image::parse(
+        "image-set('a.png' 1x, url(b.png) 2dppx type('image/png'), linear-gradient(red, blue) 192dpi, \"c.webp\")",
+    ) else {
+        panic!()
+    };
+
+    assert_eq!(options.len(), 4);
+    assert_eq!(options[0].image, Image::Url("a.png".into()));
+    assert_eq!(options[0].resolution, 1.0);
+    assert_eq!(options[1].image, Image::Url("b.png".into()));
+    assert_eq!(options[1].resolution, 2.0);
+    assert_eq!(options[1].mime_type.as_deref(), Some("image/png"));
+    assert!(matches!(options[2].image, Image::LinearGradient(_)));
+    assert_eq!(options[2].resolution, 2.0);
+    assert_eq!(options[3].resolution, 1.0);
+    assert_eq!(options[3].mime_type, None);
+
+    assert!(matches!(
+        Image::parse("-webkit-image-set(url(a.png) 1x)"),
+        Some(Image::ImageSet(_))
+    ));
+}
+
+#[test]
+fn position() {
+    let position = |text| {
+        let position = Position::parse(text).unwrap();
+        (position.x, position.y)
+    };
+
+    let center = offset(percent(50.0), false);
+    assert_eq!(position("center"), (center, center));
+    assert_eq!(position("left"), (offset(percent(0.0), false), center));
+    assert_eq!(position("bottom"), (center, offset(percent(100.0), false)));
+    assert_eq!(position("10px"), (offset(px(10.0), false), center));
+    assert_eq!(
+        position("top left"),
+        (offset(percent(0.0), false), offset(percent(0.0), false))
+    );
+    assert_eq!(
+        position("center right"),
+        (offset(percent(100.0), false), center)
+    );
+    assert_eq!(
+        position("10px 20%"),
+        (offset(px(10.0), false), offset(percent(20.0), false))
+    );
+    assert_eq!(
+        position("left 10px"),
+        (offset(percent(0.0), false), offset(px(10.0), false))
+    );
+    assert_eq!(
+        position("right 10px top"),
+        (offset(px(10.0), true), offset(percent(0.0), false))
+    );
+    assert_eq!(
+        position("bottom 10px center"),
+        (center, offset(px(10.0), true))
+    );
+    assert_eq!(
+        position("top 1em right 5%"),
+        (
+            offset(percent(5.0), true),
+            offset(Length::new(1.0, LengthUnit::Em), false)
+        )
+    );
+
+    assert_eq!(Position::parse("left right"), None);
+    assert_eq!(Position::parse("10px left"), None);
+    assert_eq!(Position::parse("top 10px"), None);
+    assert_eq!(Position::parse("center 10px top"), None);
+    assert_eq!(Position::parse("left 10px right 5px"), None);
+    assert_eq!(Position::parse("left 10px top 5px 1px"), None);
+}
@@ -0,0 +1,105 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Incremental reparsing
+
+use simplecss::*;
+
+fn rule_spans(sheet: &StyleSheet<'_>) -> Vec<(Span, Span, Vec<DeclarationSpan>)> {
+    sheet
+        .rules
+        .iter()
+        .map(|rule| {
+            let declarations = rule.declarations.iter().map(|d| d.span).collect();
+            (rule.span, rule.selector.span(), declarations)
+        })
+        .collect()
+}
+
+/// Checks that reparsing after an edit matches parsing from scratch.
+fn check(text: &str, range: Span, replacement: &str) {
+    let old = StyleSheet::parse(text);
+
+    let mut new_text = String::from(text);
+    new_text.replace_range(range.start..range.end, replacement);
+
+    let sheet = old.reparse(&new_text, TextEdit::new(range, replacement));
+    let expected = StyleSheet::parse(&new_text);
+    assert_eq!(sheet, expected, "{:?}", new_text);
+    assert_eq!(sheet.statements(), expected.statements(), "{:?}", new_text);
+    assert_eq!(rule_spans(&sheet), rule_spans(&expected), "{:?}", new_text);
+}
+
+fn check_all(text: &str) {
+    for replacement in ["", "x", "}", "{", ";", "/*", "\"", " p { margin: 0 } "] {
+        for start in 0..=text.len() {
+            for end in start..=(start + 2).min(text.len()) {
+                check(text, Span::new(start, end), replacement);
+            }
+        }
+    }
+}
+
+#[test]
+fn incremental_01() {
+    check_all("a { color: red }\nb, c > d { margin: 0 !important; }\n\ne { top: 1px }");
+}
+
+#[test]
+fn incremental_02() {
+    check_all("/* c */ a{color:red}@import \"x.css\";b{}@media print{p{top:0}} c {left:0}");
+}
+
+#[test]
+fn incremental_03() {
+    check_all("a { content: \"}\" } .\\31 x { color: red } [title=\"a b\"] { top: 0 ");
+}
+
+#[test]
+fn incremental_04() {
+    let text = "a { color: red } b { color: red } c { color: red }";
+    let sheet = StyleSheet::parse(text);
+    assert_eq!(sheet.statements().len(), 3);
+
+    let new_text = "a { color: red } b { color: blue; top: 0 } c { color: red }";
+    let sheet = sheet.reparse(new_text, TextEdit::new(Span::new(28, 31), "blue; top: 0"));
+    assert_eq!(
+        sheet.to_string(),
+        "a { color:red; }\nb { color:blue;top:0; }\nc { color:red; }"
+    );
+    assert_eq!(sheet.rules[2].span.slice(new_text), "c { color: red }");
+}
+
+#[test]
+fn incremental_05() {
+    // Out of bounds edits fall back to a full parse.
+    let sheet = StyleSheet::parse("a { color: red }");
+    let sheet = sheet.reparse("b { top: 0 }", TextEdit::new(Span::new(10, 40), ""));
+    assert_eq!(sheet.to_string(), "b { top:0; }");
+}
+
+#[test]
+fn incremental_06() {
+    // Style sheets parsed from multiple texts are parsed again.
+    let mut sheet = StyleSheet::parse("a { color: red }");
+    sheet.parse_more("b { top: 0 } c { top: 0 }");
+    assert_eq!(sheet.statements().len(), 3);
+
+    let text = "a { color: blue }";
+    let sheet = sheet.reparse(text, TextEdit::new(Span::new(11, 14), "blue"));
+    assert_eq!(sheet, StyleSheet::parse(text));
+    assert_eq!(sheet.statements(), [Span::new(0, 17)]);
+
+    // A reparsed style sheet has a single text again.
+    let new_text = "a { color: green }";
+    let sheet = sheet.reparse(new_text, TextEdit::new(Span::new(11, 15), "green"));
+    assert_eq!(sheet.to_string(), "a { color:green; }");
+}
+
+#[test]
+fn incremental_07() {
+    // Copied rules keep escaped names, attributes and pseudo-classes.
+    check_all(
+        "p#x.a:lang(en) { c\\olor: red } [lang|=en] > b:first-child { top: 0 } e\\ f { left: 0 }",
+    );
+}
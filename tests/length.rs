@@ -0,0 +1,143 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Length parsing and resolution
+
+#![cfg(any(feature = "std", feature = "libm"))]
+
+use simplecss::*;
+
+macro_rules! test {
+    ($name:ident, $text:expr, $value:expr, $unit:expr) => {
+        #[test]
+        fn $name() {
+            assert_eq!(Length::parse($text), Some(Length::new($value, $unit)));
+        }
+    };
+}
+
+macro_rules! test_invalid {
+    ($name:ident, $text:expr) => {
+        #[test]
+        fn $name() {
+            assert_eq!(Length::parse($text), None);
+        }
+    };
+}
+
+test!(parse_01, "10px", 10.0, LengthUnit::Px);
+test!(parse_02, "-1.5EM", -1.5, LengthUnit::Em);
+test!(parse_03, "50%", 50.0, LengthUnit::Percent);
+test!(parse_04, "0", 0.0, LengthUnit::Px);
+test!(parse_05, "2Q", 2.0, LengthUnit::Q);
+test!(parse_06, "100dvh", 100.0, LengthUnit::Dvh);
+test!(parse_07, " 1e1cqw ", 10.0, LengthUnit::Cqw);
+test_invalid!(parse_08, "10");
+test_invalid!(parse_09, "10deg");
+test_invalid!(parse_10, "1px 2px");
+test_invalid!(parse_11, "auto");
+
+#[test]
+fn unit_names() {
+    let units = [
+        "px", "pt", "pc", "in", "cm", "mm", "Q", "em", "rem", "ex", "ch", "%", "vw", "vh", "vmin",
+        "vmax", "svh", "lvh", "dvh", "cqw", "cqh",
+    ];
+
+    for name in units {
+        assert_eq!(LengthUnit::from_name(name).unwrap().as_str(), name);
+    }
+}
+
+#[test]
+fn display() {
+    assert_eq!(Length::new(1.5, LengthUnit::Rem).to_string(), "1.5rem");
+    assert_eq!(Length::new(50.0, LengthUnit::Percent).to_string(), "50%");
+}
+
+fn px(text: &str, context: &ResolveContext) -> f64 {
+    Length::parse(text).unwrap().to_px(context)
+}
+
+#[test]
+fn absolute_units() {
+    let context = ResolveContext::default();
+    assert_eq!(px("10px", &context), 10.0);
+    assert_eq!(px("1in", &context), 96.0);
+    assert_eq!(px("72pt", &context), 96.0);
+    assert_eq!(px("6pc", &context), 96.0);
+    assert_eq!(px("2.54cm", &context), 96.0);
+    assert_eq!(px("25.4mm", &context), 96.0);
+    assert_eq!(px("101.6Q", &context), 96.0);
+}
+
+#[test]
+fn dpi() {
+    let context = ResolveContext {
+        dpi: 300.0,
+        ..ResolveContext::default()
+    };
+    assert_eq!(px("1in", &context), 300.0);
+    assert_eq!(px("10px", &context), 10.0);
+}
+
+#[test]
+fn font_relative_units() {
+    let context = ResolveContext {
+        font_size: 20.0,
+        root_font_size: 10.0,
+        ..ResolveContext::default()
+    };
+    assert_eq!(px("2em", &context), 40.0);
+    assert_eq!(px("2rem", &context), 20.0);
+    assert_eq!(px("1ex", &context), 10.0);
+    assert_eq!(px("1ch", &context), 10.0);
+}
+
+#[test]
+fn viewport_units() {
+    let context = ResolveContext {
+        viewport_width: 800.0,
+        viewport_height: 600.0,
+        small_viewport_height: Some(500.0),
+        ..ResolveContext::default()
+    };
+    assert_eq!(px("10vw", &context), 80.0);
+    assert_eq!(px("10vh", &context), 60.0);
+    assert_eq!(px("10vmin", &context), 60.0);
+    assert_eq!(px("10vmax", &context), 80.0);
+    assert_eq!(px("10svh", &context), 50.0);
+    assert_eq!(px("10lvh", &context), 60.0);
+    assert_eq!(px("10dvh", &context), 60.0);
+}
+
+#[test]
+fn container_units() {
+    let mut context = ResolveContext {
+        viewport_width: 800.0,
+        viewport_height: 600.0,
+        ..ResolveContext::default()
+    };
+    assert_eq!(px("10cqw", &context), 80.0);
+    assert_eq!(px("10cqh", &context), 60.0);
+
+    context.container_width = Some(200.0);
+    context.container_height = Some(100.0);
+    assert_eq!(px("10cqw", &context), 20.0);
+    assert_eq!(px("10cqh", &context), 10.0);
+}
+
+#[test]
+fn percentage() {
+    let context = ResolveContext {
+        percentage_base: 300.0,
+        ..ResolveContext::default()
+    };
+    assert_eq!(px("50%", &context), 150.0);
+}
+
+#[test]
+fn declaration() {
+    let d = DeclarationTokenizer::from("width: 12pt").next().unwrap();
+    assert_eq!(d.parse_length(), Some(Length::new(12.0, LengthUnit::Pt)));
+}
@@ -0,0 +1,86 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Line index
+
+use simplecss::*;
+
+#[test]
+fn line_index_01() {
+    let index = LineIndex::new("");
+    assert_eq!(index.line_count(), 1);
+    assert_eq!(index.text_pos(0), TextPos::new(1, 1));
+    assert_eq!(index.text_pos(10), TextPos::new(1, 1));
+}
+
+#[test]
+fn line_index_02() {
+    let text = "a {}\n\nb {}\n";
+    let index = LineIndex::new(text);
+    assert_eq!(index.line_count(), 4);
+    assert_eq!(index.text_pos(3), TextPos::new(1, 4));
+    assert_eq!(index.text_pos(4), TextPos::new(1, 5));
+    assert_eq!(index.text_pos(5), TextPos::new(2, 1));
+    assert_eq!(index.text_pos(6), TextPos::new(3, 1));
+    assert_eq!(index.text_pos(text.len()), TextPos::new(4, 1));
+}
+
+#[test]
+fn line_index_03() {
+    // 'é' is 2 bytes and 1 UTF-16 unit, '😀' is 4 bytes and 2 UTF-16 units.
+    let text = "é😀a\n😀é b";
+    let index = LineIndex::new(text);
+
+    let a = text.find('a').unwrap();
+    assert_eq!(index.text_pos(a), TextPos::new(1, 3));
+    assert_eq!(index.utf16_pos(a), TextPos::new(1, 4));
+
+    let b = text.find('b').unwrap();
+    assert_eq!(index.text_pos(b), TextPos::new(2, 4));
+    assert_eq!(index.utf16_pos(b), TextPos::new(2, 5));
+
+    // Inside the emoji.
+    assert_eq!(index.text_pos(4), TextPos::new(1, 2));
+}
+
+#[test]
+fn line_index_04() {
+    let text = "a { color: red }\n/* ü */ b > c {\n  margin: 0;\n}";
+    let style = StyleSheet::parse(text);
+    let index = LineIndex::new(text);
+
+    for rule in &style.rules {
+        assert_eq!(
+            index.span_pos(rule.span),
+            (rule.span.start_pos(text), rule.span.end_pos(text))
+        );
+        for d in &rule.declarations {
+            assert_eq!(
+                index.text_pos(d.span.value.start),
+                d.span.value.start_pos(text)
+            );
+        }
+    }
+}
+
+#[test]
+fn line_index_05() {
+    // Parser errors use the same positions.
+    let text = "ü red\n  ü /* ü";
+    let index = LineIndex::new(text);
+    let offset = text.find("/*").unwrap();
+    assert_eq!(
+        Value::parse(text),
+        Err(Error::InvalidComment(index.text_pos(offset)))
+    );
+    assert_eq!(index.text_pos(offset), TextPos::new(2, 5));
+}
+
+#[test]
+fn line_index_06() {
+    // Tokenizers can be sent to other threads.
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+    assert_send_sync(&SelectorTokenizer::from("a"));
+    assert_send_sync(&DeclarationTokenizer::from("top: 0"));
+    assert_send_sync(&ValueTokenizer::from("1px"));
+}
@@ -0,0 +1,264 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Vendor prefixes
+
+#![cfg(any(feature = "std", feature = "libm"))]
+
+use simplecss::*;
+
+#[test]
+fn split() {
+    assert_eq!(
+        VendorPrefix::split("-moz-appearance"),
+        Some((VendorPrefix::Moz, "appearance"))
+    );
+    assert_eq!(
+        VendorPrefix::split("-MS-flex"),
+        Some((VendorPrefix::Ms, "flex"))
+    );
+    assert_eq!(
+        VendorPrefix::split("-o-transition"),
+        Some((VendorPrefix::O, "transition"))
+    );
+    assert_eq!(VendorPrefix::split("-webkit-"), None);
+    assert_eq!(VendorPrefix::split("--webkit-a"), None);
+    assert_eq!(VendorPrefix::split("-khtml-a"), None);
+}
+
+#[test]
+fn normalize_properties() {
+    assert_eq!(
+        normalize_prefixes(
+            "a { -webkit-box-shadow: none; -moz-user-select: none; -webkit-box-orient: vertical }"
+        ),
+        "a { box-shadow: none; user-select: none; -webkit-box-orient: vertical }"
+    );
+    assert_eq!(
+        normalize_prefixes("a { --x: -webkit-flex; -unknown-a: b }"),
+        "a { --x: -webkit-flex; -unknown-a: b }"
+    );
+    // Strings, comments and escapes don't break the structure.
+    assert_eq!(
+        normalize_prefixes("/* } */ a[title='}'] { content: '}'; -webkit-\\62 ox-shadow: none }"),
+        "/* } */ a[title='}'] { content: '}'; box-shadow: none }"
+    );
+}
+
+#[test]
+fn standard_wins() {
+    assert_eq!(
+        normalize_prefixes(
+            "a { -webkit-transition: -webkit-transform 1s; transition: transform 2s }"
+        ),
+        "a { transition: transform 2s }"
+    );
+    assert_eq!(
+        normalize_prefixes("a { transform: none; -ms-transform: none }"),
+        "a { transform: none; }"
+    );
+    // The last prefixed declaration wins without a standard one.
+    assert_eq!(
+        normalize_prefixes("a { -webkit-border-radius: 1px; -moz-border-radius: 2px; color: red }"),
+        "a { border-radius: 2px; color: red }"
+    );
+    // Only declarations in the same block.
+    assert_eq!(
+        normalize_prefixes("a { -webkit-transform: none } b { transform: none }"),
+        "a { transform: none } b { transform: none }"
+    );
+}
+
+#[test]
+fn normalize_values() {
+    assert_eq!(
+        normalize_prefixes(
+            "a { display: -webkit-flex; display: -ms-flexbox; width: -webkit-fill-available }"
+        ),
+        "a { display: flex; width: stretch }"
+    );
+    // Declarations that become identical are merged.
+    assert_eq!(
+        normalize_prefixes("a { display: -webkit-box; display: -ms-flexbox; display: flex }"),
+        "a { display: -webkit-box; display: flex }"
+    );
+    assert_eq!(
+        normalize_prefixes(
+            "a { display: -ms-flexbox !important; display: flex; display: -webkit-flex }"
+        ),
+        "a { display: flex !important; display: flex }"
+    );
+    assert_eq!(
+        normalize_prefixes(
+            "a { width: -webkit-calc(100% - 1px); position: -webkit-sticky; display: -webkit-box }"
+        ),
+        "a { width: calc(100% - 1px); position: sticky; display: -webkit-box }"
+    );
+    assert_eq!(
+        normalize_prefixes("a { background: -webkit-image-set(url(a.png) 1x) }"),
+        "a { background: image-set(url(a.png) 1x) }"
+    );
+}
+
+#[test]
+fn linear_gradient() {
+    assert_eq!(
+        normalize_prefixes("a { background: -webkit-linear-gradient(left top, red, blue) }"),
+        "a { background: linear-gradient(to right bottom, red, blue) }"
+    );
+    assert_eq!(
+        normalize_prefixes(
+            "a { background: -moz-repeating-linear-gradient(45deg, red, blue 10px) }"
+        ),
+        "a { background: repeating-linear-gradient(45deg, red, blue 10px) }"
+    );
+    assert_eq!(
+        normalize_prefixes("a { background: -o-linear-gradient(0deg, red, blue), -webkit-linear-gradient(-90deg, red, blue) }"),
+        "a { background: linear-gradient(90deg, red, blue), linear-gradient(180deg, red, blue) }"
+    );
+    assert_eq!(
+        normalize_prefixes("a { background: -webkit-linear-gradient(red, blue) }"),
+        "a { background: linear-gradient(red, blue) }"
+    );
+}
+
+#[test]
+fn radial_gradient() {
+    assert_eq!(
+        normalize_prefixes(
+            "a { background: -webkit-radial-gradient(50% 10px, circle cover, red, blue) }"
+        ),
+        "a { background: radial-gradient(circle farthest-corner at 50% 10px, red, blue) }"
+    );
+    assert_eq!(
+        normalize_prefixes("a { background: -moz-radial-gradient(center, red, blue) }"),
+        "a { background: radial-gradient(at center, red, blue) }"
+    );
+    assert_eq!(
+        normalize_prefixes("a { background: -webkit-radial-gradient(ellipse contain, red, blue) }"),
+        "a { background: radial-gradient(ellipse closest-side, red, blue) }"
+    );
+}
+
+#[test]
+fn keyframes() {
+    assert_eq!(
+        normalize_prefixes("@-webkit-keyframes a { to { -webkit-transform: none } } @keyframes a { to { transform: none } }"),
+        "@keyframes a { to { transform: none } }"
+    );
+    assert_eq!(
+        normalize_prefixes(
+            "@-webkit-keyframes a { to { top: 0 } } @-moz-keyframes b { to { top: 0 } }"
+        ),
+        "@keyframes a { to { top: 0 } } @keyframes b { to { top: 0 } }"
+    );
+    assert_eq!(
+        normalize_prefixes(
+            "@media (-webkit-min-device-pixel-ratio: 2) { a { -webkit-hyphens: auto } }"
+        ),
+        "@media (-webkit-min-device-pixel-ratio: 2) { a { hyphens: auto } }"
+    );
+}
+
+#[test]
+fn pseudo_classes() {
+    assert_eq!(
+        normalize_prefixes(
+            "input::-webkit-input-placeholder { color: gray } a:-webkit-any-link { top: 0 }"
+        ),
+        "input::placeholder { color: gray } a:any-link { top: 0 }"
+    );
+    assert_eq!(
+        normalize_prefixes(
+            "input:-ms-input-placeholder { color: gray }\ninput::placeholder { color: gray }"
+        ),
+        "input::placeholder { color: gray }"
+    );
+    assert_eq!(
+        normalize_prefixes("a: -webkit-any-link {}"),
+        "a: -webkit-any-link {}"
+    );
+}
+
+#[test]
+fn normalized_style_sheet() {
+    let text = normalize_prefixes(
+        "a { -webkit-box-shadow: none; box-shadow: 0 0 1px red; -moz-tab-size: 4 }",
+    );
+    let style = StyleSheet::parse(&text);
+    let declarations: Vec<_> = style.rules[0]
+        .declarations
+        .iter()
+        .map(|d| (d.name.as_ref(), d.value))
+        .collect();
+    assert_eq!(
+        declarations,
+        [("box-shadow", "0 0 1px red"), ("tab-size", "4")]
+    );
+}
+
+#[test]
+fn add_properties() {
+    let options = PrefixOptions::default();
+    assert_eq!(
+        add_prefixes("a {\n  appearance: none;\n  color: red;\n}", &options),
+        "a {\n  -webkit-appearance: none;\n  -moz-appearance: none;\n  appearance: none;\n  color: red;\n}"
+    );
+    assert_eq!(
+        add_prefixes("a{backdrop-filter:blur(2px)!important}", &options),
+        "a{-webkit-backdrop-filter:blur(2px)!important;backdrop-filter:blur(2px)!important}"
+    );
+    // Existing prefixed declarations are kept.
+    assert_eq!(
+        add_prefixes("a { -webkit-appearance: auto; appearance: none }", &options),
+        "a { -webkit-appearance: auto; -moz-appearance: none; appearance: none }"
+    );
+}
+
+#[test]
+fn add_values() {
+    let options = PrefixOptions::default();
+    assert_eq!(
+        add_prefixes("a { position: sticky; background: image-set('a.png' 1x, 'b.png' 2x) }", &options),
+        "a { position: -webkit-sticky; position: sticky; \
+         background: -webkit-image-set('a.png' 1x, 'b.png' 2x); background: image-set('a.png' 1x, 'b.png' 2x) }"
+    );
+    assert_eq!(
+        add_prefixes(
+            "a { position: sticky }",
+            &PrefixOptions {
+                webkit: false,
+                ..options
+            }
+        ),
+        "a { position: sticky }"
+    );
+}
+
+#[test]
+fn add_pseudo_classes() {
+    let options = PrefixOptions {
+        ms: false,
+        ..PrefixOptions::default()
+    };
+    assert_eq!(
+        add_prefixes("input::placeholder, textarea::placeholder { user-select: none }", &options),
+        "input::-webkit-input-placeholder, textarea::-webkit-input-placeholder { -webkit-user-select: none; -moz-user-select: none; user-select: none }\n\
+         input::-moz-placeholder, textarea::-moz-placeholder { -webkit-user-select: none; -moz-user-select: none; user-select: none }\n\
+         input::placeholder, textarea::placeholder { -webkit-user-select: none; -moz-user-select: none; user-select: none }"
+    );
+    assert_eq!(
+        add_prefixes("@media print { ::selection { color: red } }", &options),
+        "@media print { ::-moz-selection { color: red } ::selection { color: red } }"
+    );
+}
+
+#[test]
+fn round_trip() {
+    let text = "a { -webkit-user-select: none; -moz-user-select: none; -ms-user-select: none; user-select: none }";
+    assert_eq!(normalize_prefixes(text), "a { user-select: none }");
+    assert_eq!(
+        add_prefixes(&normalize_prefixes(text), &PrefixOptions::default()),
+        text
+    );
+}
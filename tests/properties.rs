@@ -0,0 +1,355 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Property registry
+
+#![cfg(any(feature = "std", feature = "libm"))]
+
+use simplecss::*;
+
+#[test]
+fn registry_is_sorted() {
+    for pair in properties().windows(2) {
+        assert!(
+            pair[0].name < pair[1].name,
+            "{} {}",
+            pair[0].name,
+            pair[1].name
+        );
+    }
+
+    for p in properties() {
+        assert_eq!(p.name, p.name.to_ascii_lowercase());
+        assert_eq!(property(p.name), Some(p));
+    }
+}
+
+#[test]
+fn initial_values_are_valid() {
+    for p in properties() {
+        if p.syntax == PropertySyntax::Shorthand {
+            assert!(p.initial.is_empty(), "{}", p.name);
+        } else {
+            assert!(p.validate(p.initial), "{}: {}", p.name, p.initial);
+        }
+    }
+}
+
+#[test]
+fn shorthands_are_registered() {
+    for p in properties() {
+        let is_shorthand = p.syntax == PropertySyntax::Shorthand;
+        assert_eq!(longhands(p.name).is_some(), is_shorthand, "{}", p.name);
+
+        for longhand in longhands(p.name).unwrap_or_default() {
+            let longhand = property(longhand).unwrap();
+            assert_ne!(longhand.syntax, PropertySyntax::Shorthand);
+            assert_eq!(longhand.inherited, p.inherited, "{}", longhand.name);
+        }
+    }
+}
+
+#[test]
+fn lookup() {
+    assert_eq!(property("FILL").unwrap().name, "fill");
+    assert!(property("fill").unwrap().inherited);
+    assert!(!property("opacity").unwrap().inherited);
+    assert_eq!(property("display").unwrap().initial, "inline");
+    assert_eq!(property("--custom"), None);
+    assert_eq!(property("-webkit-transform"), None);
+    assert_eq!(property(""), None);
+}
+
+macro_rules! test {
+    ($name:ident, $property:expr, [$($valid:expr),*], [$($invalid:expr),*]) => {
+        #[test]
+        fn $name() {
+            let p = property($property).unwrap();
+            $(assert!(p.validate($valid), "{}", $valid);)*
+            $(assert!(!p.validate($invalid), "{}", $invalid);)*
+        }
+    };
+}
+
+test!(
+    validate_color,
+    "color",
+    [
+        "red",
+        "#fff",
+        "rgb(0 0 0 / 50%)",
+        "currentColor",
+        "inherit",
+        "var(--x)"
+    ],
+    ["", "10px", "red blue", "#ggg", "inherit red"]
+);
+test!(
+    validate_width,
+    "width",
+    ["auto", "10px", "50%", "0", "calc(100% - 2em)", "calc(-1px)"],
+    ["10", "10deg", "auto auto", "none", "-1px", "-0.5%"]
+);
+test!(
+    validate_margin,
+    "margin",
+    ["0", "0 auto", "1px 2px 3px 4px", "INITIAL", "-1px"],
+    ["red", "1px 2px 3px 4px 5px"]
+);
+test!(
+    validate_padding,
+    "padding",
+    ["0", "1px 2%"],
+    ["1px -2px", "auto"]
+);
+test!(
+    validate_display,
+    "display",
+    ["block", "inline-block", "table-cell", "flex", "None"],
+    ["blocky", "block inline"]
+);
+test!(
+    validate_font,
+    "font",
+    [
+        "12px serif",
+        "italic bold 12px/1.5 'Open Sans', sans-serif",
+        "caption",
+        "Status-Bar"
+    ],
+    ["serif", "bold", "caption serif", "-12px serif"]
+);
+test!(
+    validate_font_family,
+    "font-family",
+    ["Times New Roman, serif", "'A', B", "sans-serif"],
+    ["A,", "1px", "inherit, serif"]
+);
+test!(
+    validate_font_weight,
+    "font-weight",
+    ["bold", "lighter", "550"],
+    ["0", "heavy"]
+);
+test!(
+    validate_background_position,
+    "background-position",
+    [
+        "left",
+        "10px 20%",
+        "right 10px top",
+        "center bottom 5px",
+        "top left, 0 0"
+    ],
+    ["left right", "10px top 10px", "1px 2px 3px"]
+);
+test!(
+    validate_background_repeat,
+    "background-repeat",
+    ["repeat-x", "space round", "no-repeat, repeat"],
+    ["repeat-x repeat", "repeat repeat repeat", "repeat,"]
+);
+test!(
+    validate_border_spacing,
+    "border-spacing",
+    ["1px", "1px 2px"],
+    ["1px 2px 3px", "10%"]
+);
+test!(
+    validate_counter_reset,
+    "counter-reset",
+    ["none", "a", "a 1 b", "a -1 b 2"],
+    ["1", "a 1.5", "inherit 1", "a 1 1"]
+);
+test!(
+    validate_content,
+    "content",
+    [
+        "normal",
+        "'a' counter(x) open-quote",
+        "url(a.png) attr(title)"
+    ],
+    ["normal 'a'", "1px"]
+);
+test!(
+    validate_cursor,
+    "cursor",
+    [
+        "pointer",
+        "url(a.cur), auto",
+        "url(a.cur) 1 2, url(b.cur), wait"
+    ],
+    ["url(a.cur)", "url(a.cur) 1, auto", "auto, pointer"]
+);
+test!(
+    validate_fill,
+    "fill",
+    [
+        "none",
+        "red",
+        "url(#gradient)",
+        "url(#gradient) none",
+        "url(#g) #fff",
+        "context-stroke"
+    ],
+    ["red url(#g)", "none none"]
+);
+test!(
+    validate_stroke_dasharray,
+    "stroke-dasharray",
+    ["none", "5", "5 10", "5, 10, 1px", "5,10 20%"],
+    ["none 5", "5,", ", 5", "5,,10", "red"]
+);
+test!(
+    validate_opacity,
+    "opacity",
+    ["0.5", "50%", "calc(1 / 2)"],
+    ["0.5px", "auto"]
+);
+test!(
+    validate_transform,
+    "transform",
+    [
+        "none",
+        "translate(10px) rotate(45deg)",
+        "matrix(1, 0, 0, 1, 0, 0)"
+    ],
+    [
+        "rotate(45)",
+        "none rotate(1deg)",
+        "translate(10px),rotate(1deg)"
+    ]
+);
+test!(
+    validate_transform_origin,
+    "transform-origin",
+    ["center", "left top", "10px 20px 5px"],
+    ["top 10px", "10px 20px 30%"]
+);
+test!(
+    validate_text_decoration_line,
+    "text-decoration-line",
+    ["none", "underline", "overline underline line-through"],
+    ["underline underline", "none underline"]
+);
+test!(
+    validate_paint_order,
+    "paint-order",
+    ["normal", "stroke", "markers stroke fill"],
+    ["stroke stroke", "normal fill"]
+);
+test!(
+    validate_animation_name,
+    "animation-name",
+    ["none", "slide, fade", "'quoted'"],
+    ["slide fade", "inherit, fade", "10px"]
+);
+test!(
+    validate_transition_timing_function,
+    "transition-timing-function",
+    [
+        "ease",
+        "steps(4, end), cubic-bezier(0.1, 0.7, 1, 0.1)",
+        "linear(0, 0.5 25% 75%, 1)"
+    ],
+    [
+        "fast",
+        "ease ease",
+        "steps(0)",
+        "cubic-bezier(2, 0, 1, 1)",
+        "linear(0)"
+    ]
+);
+test!(
+    validate_clip_path,
+    "clip-path",
+    [
+        "none",
+        "url(#clip)",
+        "circle(50%)",
+        "inset(10px) fill-box",
+        "stroke-box"
+    ],
+    ["circle(50%) inset(1px)", "url(#a) circle(1px)"]
+);
+test!(
+    validate_azimuth,
+    "azimuth",
+    ["45deg", "left-side behind", "behind far-left", "leftwards"],
+    ["behind behind", "left right"]
+);
+test!(
+    validate_animation_duration,
+    "animation-duration",
+    ["0s", "1s, 250ms", "auto"],
+    ["-1s", "1s, -1ms"]
+);
+test!(
+    validate_animation,
+    "animation",
+    ["1s slide", "1s -1s slide"],
+    ["-1s slide", "1s slide -2"]
+);
+test!(
+    validate_stroke_miterlimit,
+    "stroke-miterlimit",
+    ["1", "4.5"],
+    ["0.5", "-1"]
+);
+test!(
+    validate_integer,
+    "z-index",
+    ["auto", "-1", "10", "calc(1 + 2)"],
+    ["1.5", "1px"]
+);
+
+#[test]
+fn validate_declarations() {
+    let style = StyleSheet::parse(
+        "
+        a { color: red; colr: red; --custom: 10px; width: red }
+        b { stroke: var(--paint); stroke-width: thin !important; -webkit-box-flex: 1 }
+    ",
+    );
+
+    let invalid: Vec<_> = style
+        .validate()
+        .into_iter()
+        .map(|(d, e)| (d.name.as_ref(), d.value, e))
+        .collect();
+    assert_eq!(
+        invalid,
+        [
+            ("colr", "red", ValidationError::UnknownProperty),
+            ("width", "red", ValidationError::InvalidValue),
+            ("stroke-width", "thin", ValidationError::InvalidValue),
+            ("-webkit-box-flex", "1", ValidationError::UnknownProperty),
+        ]
+    );
+}
+
+#[test]
+fn error_display() {
+    assert_eq!(
+        ValidationError::UnknownProperty.to_string(),
+        "unknown property"
+    );
+    assert_eq!(
+        ValidationError::InvalidValue.to_string(),
+        "invalid property value"
+    );
+}
+
+#[test]
+fn validate_threads() {
+    // Grammars are parsed once and shared between threads.
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            std::thread::spawn(|| {
+                let p = property("background-position").unwrap();
+                p.validate("left 10px top") && !p.validate("left left")
+            })
+        })
+        .collect();
+    assert!(threads.into_iter().all(|t| t.join().unwrap()));
+}
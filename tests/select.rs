@@ -502,8 +502,82 @@ fn select_30() {
     assert_eq!(nodes[1].attribute("id").unwrap(), "p1");
 }
 
+#[test]
+fn select_31() {
+    let doc = roxmltree::Document::parse(
+        "\
+<div id='div1'>
+    <p id='p1' class='md:flex'/>
+    <p id='p2' class='10px'/>
+</div>
+",
+    )
+    .unwrap();
+
+    assert_eq!(match_single!(doc, ".md\\:flex"), "p1");
+    assert_eq!(match_single!(doc, ".\\31 0px"), "p2");
+    assert_eq!(match_single!(doc, ".\\000031 0px"), "p2");
+    match_none!(doc, ".md\\3a flexbox");
+}
+
+#[test]
+fn select_32() {
+    let doc = roxmltree::Document::parse(
+        "\
+<div id='div1'>
+    <p id='p1' title='a&quot;b'/>
+    <p id='p2' title='&amp;'/>
+</div>
+",
+    )
+    .unwrap();
+
+    assert_eq!(match_single!(doc, "[title='a\\\"b']"), "p1");
+    assert_eq!(match_single!(doc, "[title=\"\\26\"]"), "p2");
+    assert_eq!(match_single!(doc, "#\\70 1"), "p1");
+    assert_eq!(match_single!(doc, "\\70#p2"), "p2");
+}
+
+#[test]
+fn select_33() {
+    let doc = roxmltree::Document::parse(
+        "\
+<div id='div1'>
+    <p id='p1'/>
+</div>
+",
+    )
+    .unwrap();
+
+    assert_eq!(match_single!(doc, "p:FIRST-CHILD"), "p1");
+    assert_eq!(match_single!(doc, "p:First-Child"), "p1");
+}
+
 #[test]
 fn to_string() {
     let selectors = Selector::parse("a > b").unwrap();
     assert_eq!(selectors.to_string(), "a > b");
 }
+
+#[test]
+fn to_string_escaped() {
+    for text in [
+        ".a\\'b",
+        "[title=\"a\\\"b\\\\\"]",
+        ".\\31 0px",
+        "#-\\31 x",
+        "d\\ iv > .md\\:flex",
+    ] {
+        let selector = Selector::parse(text).unwrap();
+        let serialized = selector.to_string();
+        assert_eq!(
+            Selector::parse(&serialized).unwrap(),
+            selector,
+            "{}",
+            serialized
+        );
+    }
+
+    let selector = Selector::parse(".a\\'b").unwrap();
+    assert_eq!(selector.to_string(), ".a\\'b");
+}
@@ -21,135 +21,143 @@ macro_rules! tokenize {
 
 tokenize!(tokenize_01, "*", SelectorToken::UniversalSelector);
 
-tokenize!(tokenize_02, "div", SelectorToken::TypeSelector("div"));
+tokenize!(
+    tokenize_02,
+    "div",
+    SelectorToken::TypeSelector("div".into())
+);
 
-tokenize!(tokenize_03, "#div", SelectorToken::IdSelector("div"));
+tokenize!(tokenize_03, "#div", SelectorToken::IdSelector("div".into()));
 
-tokenize!(tokenize_04, ".div", SelectorToken::ClassSelector("div"));
+tokenize!(
+    tokenize_04,
+    ".div",
+    SelectorToken::ClassSelector("div".into())
+);
 
 tokenize!(
     tokenize_05,
     "[id]",
-    SelectorToken::AttributeSelector("id", AttributeOperator::Exists)
+    SelectorToken::AttributeSelector("id".into(), AttributeOperator::Exists)
 );
 
 tokenize!(
     tokenize_06,
     "[id=test]",
-    SelectorToken::AttributeSelector("id", AttributeOperator::Matches("test"))
+    SelectorToken::AttributeSelector("id".into(), AttributeOperator::Matches("test".into()))
 );
 
 tokenize!(
     tokenize_07,
     "[id~=test]",
-    SelectorToken::AttributeSelector("id", AttributeOperator::Contains("test"))
+    SelectorToken::AttributeSelector("id".into(), AttributeOperator::Contains("test".into()))
 );
 
 tokenize!(
     tokenize_08,
     "[id|=test]",
-    SelectorToken::AttributeSelector("id", AttributeOperator::StartsWith("test"))
+    SelectorToken::AttributeSelector("id".into(), AttributeOperator::StartsWith("test".into()))
 );
 
 tokenize!(
     tokenize_09,
     "[id='test']",
-    SelectorToken::AttributeSelector("id", AttributeOperator::Matches("test"))
+    SelectorToken::AttributeSelector("id".into(), AttributeOperator::Matches("test".into()))
 );
 
 tokenize!(
     tokenize_10,
     "[id=\"test\"]",
-    SelectorToken::AttributeSelector("id", AttributeOperator::Matches("test"))
+    SelectorToken::AttributeSelector("id".into(), AttributeOperator::Matches("test".into()))
 );
 
 tokenize!(
     tokenize_11,
     "[id='te\\'st']",
-    SelectorToken::AttributeSelector("id", AttributeOperator::Matches("te\\'st"))
+    SelectorToken::AttributeSelector("id".into(), AttributeOperator::Matches("te'st".into()))
 );
 
 tokenize!(
     tokenize_12,
     "[id=\"te\\\"st\"]",
-    SelectorToken::AttributeSelector("id", AttributeOperator::Matches("te\\\"st"))
+    SelectorToken::AttributeSelector("id".into(), AttributeOperator::Matches("te\"st".into()))
 );
 
 tokenize!(
     tokenize_13,
     "div:first-child",
-    SelectorToken::TypeSelector("div"),
-    SelectorToken::PseudoClass("first-child")
+    SelectorToken::TypeSelector("div".into()),
+    SelectorToken::PseudoClass("first-child".into())
 );
 
 tokenize!(
     tokenize_14,
     ":first-child",
-    SelectorToken::PseudoClass("first-child")
+    SelectorToken::PseudoClass("first-child".into())
 );
 
 tokenize!(
     tokenize_15,
     "div p",
-    SelectorToken::TypeSelector("div"),
+    SelectorToken::TypeSelector("div".into()),
     SelectorToken::DescendantCombinator,
-    SelectorToken::TypeSelector("p")
+    SelectorToken::TypeSelector("p".into())
 );
 
 tokenize!(
     tokenize_16,
     "div p a",
-    SelectorToken::TypeSelector("div"),
+    SelectorToken::TypeSelector("div".into()),
     SelectorToken::DescendantCombinator,
-    SelectorToken::TypeSelector("p"),
+    SelectorToken::TypeSelector("p".into()),
     SelectorToken::DescendantCombinator,
-    SelectorToken::TypeSelector("a")
+    SelectorToken::TypeSelector("a".into())
 );
 
 tokenize!(
     tokenize_17,
     "div>p",
-    SelectorToken::TypeSelector("div"),
+    SelectorToken::TypeSelector("div".into()),
     SelectorToken::ChildCombinator,
-    SelectorToken::TypeSelector("p")
+    SelectorToken::TypeSelector("p".into())
 );
 
 tokenize!(
     tokenize_18,
     "div >p",
-    SelectorToken::TypeSelector("div"),
+    SelectorToken::TypeSelector("div".into()),
     SelectorToken::ChildCombinator,
-    SelectorToken::TypeSelector("p")
+    SelectorToken::TypeSelector("p".into())
 );
 
 tokenize!(
     tokenize_19,
     "div> p",
-    SelectorToken::TypeSelector("div"),
+    SelectorToken::TypeSelector("div".into()),
     SelectorToken::ChildCombinator,
-    SelectorToken::TypeSelector("p")
+    SelectorToken::TypeSelector("p".into())
 );
 
 tokenize!(
     tokenize_20,
     "div > p",
-    SelectorToken::TypeSelector("div"),
+    SelectorToken::TypeSelector("div".into()),
     SelectorToken::ChildCombinator,
-    SelectorToken::TypeSelector("p")
+    SelectorToken::TypeSelector("p".into())
 );
 
 tokenize!(
     tokenize_21,
     "div .p",
-    SelectorToken::TypeSelector("div"),
+    SelectorToken::TypeSelector("div".into()),
     SelectorToken::DescendantCombinator,
-    SelectorToken::ClassSelector("p")
+    SelectorToken::ClassSelector("p".into())
 );
 
 tokenize!(
     tokenize_22,
     "div *",
-    SelectorToken::TypeSelector("div"),
+    SelectorToken::TypeSelector("div".into()),
     SelectorToken::DescendantCombinator,
     SelectorToken::UniversalSelector
 );
@@ -157,134 +165,150 @@ tokenize!(
 tokenize!(
     tokenize_23,
     "div #p",
-    SelectorToken::TypeSelector("div"),
+    SelectorToken::TypeSelector("div".into()),
     SelectorToken::DescendantCombinator,
-    SelectorToken::IdSelector("p")
+    SelectorToken::IdSelector("p".into())
 );
 
 tokenize!(
     tokenize_24,
     "div [id]",
-    SelectorToken::TypeSelector("div"),
+    SelectorToken::TypeSelector("div".into()),
     SelectorToken::DescendantCombinator,
-    SelectorToken::AttributeSelector("id", AttributeOperator::Exists)
+    SelectorToken::AttributeSelector("id".into(), AttributeOperator::Exists)
 );
 
 tokenize!(
     tokenize_25,
     "div :link",
-    SelectorToken::TypeSelector("div"),
+    SelectorToken::TypeSelector("div".into()),
     SelectorToken::DescendantCombinator,
-    SelectorToken::PseudoClass("link")
+    SelectorToken::PseudoClass("link".into())
 );
 
 tokenize!(
     tokenize_26,
     "div+p",
-    SelectorToken::TypeSelector("div"),
+    SelectorToken::TypeSelector("div".into()),
     SelectorToken::AdjacentCombinator,
-    SelectorToken::TypeSelector("p")
+    SelectorToken::TypeSelector("p".into())
 );
 
 tokenize!(
     tokenize_27,
     "div +p",
-    SelectorToken::TypeSelector("div"),
+    SelectorToken::TypeSelector("div".into()),
     SelectorToken::AdjacentCombinator,
-    SelectorToken::TypeSelector("p")
+    SelectorToken::TypeSelector("p".into())
 );
 
 tokenize!(
     tokenize_28,
     "div+ p",
-    SelectorToken::TypeSelector("div"),
+    SelectorToken::TypeSelector("div".into()),
     SelectorToken::AdjacentCombinator,
-    SelectorToken::TypeSelector("p")
+    SelectorToken::TypeSelector("p".into())
 );
 
 tokenize!(
     tokenize_29,
     "div + p",
-    SelectorToken::TypeSelector("div"),
+    SelectorToken::TypeSelector("div".into()),
     SelectorToken::AdjacentCombinator,
-    SelectorToken::TypeSelector("p")
+    SelectorToken::TypeSelector("p".into())
 );
 
-tokenize!(tokenize_30, "div {", SelectorToken::TypeSelector("div"));
+tokenize!(
+    tokenize_30,
+    "div {",
+    SelectorToken::TypeSelector("div".into())
+);
 
-tokenize!(tokenize_31, "div,", SelectorToken::TypeSelector("div"));
+tokenize!(
+    tokenize_31,
+    "div,",
+    SelectorToken::TypeSelector("div".into())
+);
 
-tokenize!(tokenize_32, "div{", SelectorToken::TypeSelector("div"));
+tokenize!(
+    tokenize_32,
+    "div{",
+    SelectorToken::TypeSelector("div".into())
+);
 
-tokenize!(tokenize_33, "div ,", SelectorToken::TypeSelector("div"));
+tokenize!(
+    tokenize_33,
+    "div ,",
+    SelectorToken::TypeSelector("div".into())
+);
 
 tokenize!(
     tokenize_34,
     "div.test",
-    SelectorToken::TypeSelector("div"),
-    SelectorToken::ClassSelector("test")
+    SelectorToken::TypeSelector("div".into()),
+    SelectorToken::ClassSelector("test".into())
 );
 
 tokenize!(
     tokenize_35,
     "div.test.warn",
-    SelectorToken::TypeSelector("div"),
-    SelectorToken::ClassSelector("test"),
-    SelectorToken::ClassSelector("warn")
+    SelectorToken::TypeSelector("div".into()),
+    SelectorToken::ClassSelector("test".into()),
+    SelectorToken::ClassSelector("warn".into())
 );
 
 tokenize!(
     tokenize_36,
     "div#id",
-    SelectorToken::TypeSelector("div"),
-    SelectorToken::IdSelector("id")
+    SelectorToken::TypeSelector("div".into()),
+    SelectorToken::IdSelector("id".into())
 );
 
 tokenize!(
     tokenize_37,
     "*[id]",
     SelectorToken::UniversalSelector,
-    SelectorToken::AttributeSelector("id", AttributeOperator::Exists)
+    SelectorToken::AttributeSelector("id".into(), AttributeOperator::Exists)
 );
 
 tokenize!(
     tokenize_38,
     "*.test",
     SelectorToken::UniversalSelector,
-    SelectorToken::ClassSelector("test")
+    SelectorToken::ClassSelector("test".into())
 );
 
 tokenize!(
     tokenize_39,
     "*#id",
     SelectorToken::UniversalSelector,
-    SelectorToken::IdSelector("id")
+    SelectorToken::IdSelector("id".into())
 );
 
 tokenize!(
     tokenize_40,
     "div * p",
-    SelectorToken::TypeSelector("div"),
+    SelectorToken::TypeSelector("div".into()),
     SelectorToken::DescendantCombinator,
     SelectorToken::UniversalSelector,
     SelectorToken::DescendantCombinator,
-    SelectorToken::TypeSelector("p")
+    SelectorToken::TypeSelector("p".into())
 );
 
 tokenize!(
     tokenize_41,
     "div[id=test][color=red]",
-    SelectorToken::TypeSelector("div"),
-    SelectorToken::AttributeSelector("id", AttributeOperator::Matches("test")),
-    SelectorToken::AttributeSelector("color", AttributeOperator::Matches("red"))
+    SelectorToken::TypeSelector("div".into()),
+    SelectorToken::AttributeSelector("id".into(), AttributeOperator::Matches("test".into())),
+    SelectorToken::AttributeSelector("color".into(), AttributeOperator::Matches("red".into()))
 );
 
 tokenize!(
     tokenize_42,
     "a.external:visited",
-    SelectorToken::TypeSelector("a"),
-    SelectorToken::ClassSelector("external"),
-    SelectorToken::PseudoClass("visited")
+    SelectorToken::TypeSelector("a".into()),
+    SelectorToken::ClassSelector("external".into()),
+    SelectorToken::PseudoClass("visited".into())
 );
 
 tokenize!(
@@ -296,17 +320,38 @@ tokenize!(
 tokenize!(
     tokenize_44,
     "a\nb",
-    SelectorToken::TypeSelector("a"),
+    SelectorToken::TypeSelector("a".into()),
     SelectorToken::DescendantCombinator,
-    SelectorToken::TypeSelector("b")
+    SelectorToken::TypeSelector("b".into())
 );
 
 tokenize!(
     tokenize_45,
     ".warn :first-child",
-    SelectorToken::ClassSelector("warn"),
+    SelectorToken::ClassSelector("warn".into()),
     SelectorToken::DescendantCombinator,
-    SelectorToken::PseudoClass("first-child")
+    SelectorToken::PseudoClass("first-child".into())
+);
+
+tokenize!(
+    tokenize_46,
+    ".md\\:flex.\\31 0px",
+    SelectorToken::ClassSelector("md:flex".into()),
+    SelectorToken::ClassSelector("10px".into())
+);
+
+tokenize!(
+    tokenize_47,
+    "[id='te\\\\']",
+    SelectorToken::AttributeSelector("id".into(), AttributeOperator::Matches("te\\".into()))
+);
+
+tokenize!(
+    tokenize_48,
+    "P:FIRST-CHILD:Lang(en)",
+    SelectorToken::TypeSelector("P".into()),
+    SelectorToken::PseudoClass("FIRST-CHILD".into()),
+    SelectorToken::LangPseudoClass("en")
 );
 
 macro_rules! malformed {
@@ -368,64 +413,76 @@ malformed!(malformed_17, " ", "selector missing");
 
 malformed!(malformed_18, "/**/", "selector missing");
 
-tokenize!(comment_01, "/**/a", SelectorToken::TypeSelector("a"));
+tokenize!(comment_01, "/**/a", SelectorToken::TypeSelector("a".into()));
 
-tokenize!(comment_02, "/* */a", SelectorToken::TypeSelector("a"));
+tokenize!(
+    comment_02,
+    "/* */a",
+    SelectorToken::TypeSelector("a".into())
+);
 
 tokenize!(
     comment_03,
     "/* comment */a",
-    SelectorToken::TypeSelector("a")
+    SelectorToken::TypeSelector("a".into())
 );
 
-tokenize!(comment_04, "/**/ /**/a", SelectorToken::TypeSelector("a"));
+tokenize!(
+    comment_04,
+    "/**/ /**/a",
+    SelectorToken::TypeSelector("a".into())
+);
 
-tokenize!(comment_05, "/**/ a /**/", SelectorToken::TypeSelector("a"));
+tokenize!(
+    comment_05,
+    "/**/ a /**/",
+    SelectorToken::TypeSelector("a".into())
+);
 
 tokenize!(
     comment_06,
     "a /**/ b",
-    SelectorToken::TypeSelector("a"),
+    SelectorToken::TypeSelector("a".into()),
     SelectorToken::DescendantCombinator,
-    SelectorToken::TypeSelector("b")
+    SelectorToken::TypeSelector("b".into())
 );
 
 tokenize!(
     comment_08,
     "a /**/b",
-    SelectorToken::TypeSelector("a"),
+    SelectorToken::TypeSelector("a".into()),
     SelectorToken::DescendantCombinator,
-    SelectorToken::TypeSelector("b")
+    SelectorToken::TypeSelector("b".into())
 );
 
 tokenize!(
     comment_09,
     "a/**/ b",
-    SelectorToken::TypeSelector("a"),
+    SelectorToken::TypeSelector("a".into()),
     SelectorToken::DescendantCombinator,
-    SelectorToken::TypeSelector("b")
+    SelectorToken::TypeSelector("b".into())
 );
 
 tokenize!(
     comment_10,
     "a/**/ /**/b",
-    SelectorToken::TypeSelector("a"),
+    SelectorToken::TypeSelector("a".into()),
     SelectorToken::DescendantCombinator,
-    SelectorToken::TypeSelector("b")
+    SelectorToken::TypeSelector("b".into())
 );
 
 tokenize!(
     comment_11,
     "a /**/ /**/ b",
-    SelectorToken::TypeSelector("a"),
+    SelectorToken::TypeSelector("a".into()),
     SelectorToken::DescendantCombinator,
-    SelectorToken::TypeSelector("b")
+    SelectorToken::TypeSelector("b".into())
 );
 
 tokenize!(
     comment_12,
     "a /**//**/ b",
-    SelectorToken::TypeSelector("a"),
+    SelectorToken::TypeSelector("a".into()),
     SelectorToken::DescendantCombinator,
-    SelectorToken::TypeSelector("b")
+    SelectorToken::TypeSelector("b".into())
 );
@@ -0,0 +1,451 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Shorthand expansion
+
+#![cfg(any(feature = "std", feature = "libm"))]
+
+use simplecss::*;
+
+fn expand(text: &str) -> Option<String> {
+    let declaration = DeclarationTokenizer::from(text).next().unwrap();
+    let longhands = declaration.expand()?;
+    let list: Vec<_> = longhands
+        .iter()
+        .map(|l| format!("{}:{}", l.name, l.value))
+        .collect();
+    Some(list.join("; "))
+}
+
+macro_rules! test {
+    ($name:ident, $text:expr, $expected:expr) => {
+        #[test]
+        fn $name() {
+            assert_eq!(expand($text).as_deref(), Some($expected));
+        }
+    };
+}
+
+macro_rules! test_invalid {
+    ($name:ident, $text:expr) => {
+        #[test]
+        fn $name() {
+            assert_eq!(expand($text), None);
+        }
+    };
+}
+
+test!(
+    margin_01,
+    "margin: 1px",
+    "margin-top:1px; margin-right:1px; margin-bottom:1px; margin-left:1px"
+);
+test!(
+    margin_02,
+    "margin: 0 auto",
+    "margin-top:0; margin-right:auto; margin-bottom:0; margin-left:auto"
+);
+test!(
+    margin_03,
+    "MARGIN: 1px 2% 3em",
+    "margin-top:1px; margin-right:2%; margin-bottom:3em; margin-left:2%"
+);
+test!(
+    margin_04,
+    "margin: 1px 2px 3px calc(1px + 1em)",
+    "margin-top:1px; margin-right:2px; margin-bottom:3px; margin-left:calc(1px + 1em)"
+);
+test_invalid!(margin_05, "margin: 1px 2px 3px 4px 5px");
+test_invalid!(margin_06, "margin: red");
+test_invalid!(margin_07, "margin: var(--x) 0");
+test!(
+    padding_01,
+    "padding: 1px 2px",
+    "padding-top:1px; padding-right:2px; padding-bottom:1px; padding-left:2px"
+);
+test_invalid!(padding_02, "padding: auto");
+test!(
+    inset_01,
+    "inset: 0 auto",
+    "top:0; right:auto; bottom:0; left:auto"
+);
+test!(gap_01, "gap: 1em", "row-gap:1em; column-gap:1em");
+test!(gap_02, "gap: normal 10%", "row-gap:normal; column-gap:10%");
+
+test!(
+    wide_01,
+    "margin: inherit",
+    "margin-top:inherit; margin-right:inherit; margin-bottom:inherit; margin-left:inherit"
+);
+test!(
+    wide_02,
+    "flex: Initial",
+    "flex-grow:Initial; flex-shrink:Initial; flex-basis:Initial"
+);
+test!(wide_03, "gap: unset", "row-gap:unset; column-gap:unset");
+test_invalid!(wide_04, "margin: inherit 0");
+
+test!(border_01, "border: 1px solid red", "border-top-width:1px; border-right-width:1px; border-bottom-width:1px; border-left-width:1px; border-top-style:solid; border-right-style:solid; border-bottom-style:solid; border-left-style:solid; border-top-color:red; border-right-color:red; border-bottom-color:red; border-left-color:red");
+test!(
+    border_02,
+    "border-top: dashed",
+    "border-top-width:medium; border-top-style:dashed; border-top-color:currentcolor"
+);
+test!(
+    border_03,
+    "border-left: #f00 thick",
+    "border-left-width:thick; border-left-style:none; border-left-color:#f00"
+);
+test!(border_04, "border-width: thin 2px", "border-top-width:thin; border-right-width:2px; border-bottom-width:thin; border-left-width:2px");
+test!(border_05, "border-style: solid none dotted", "border-top-style:solid; border-right-style:none; border-bottom-style:dotted; border-left-style:none");
+test!(border_06, "border-color: red rgb(0 0 0 / 50%)", "border-top-color:red; border-right-color:rgb(0 0 0 / 50%); border-bottom-color:red; border-left-color:rgb(0 0 0 / 50%)");
+test_invalid!(border_07, "border: 1px 2px");
+test_invalid!(border_08, "border-bottom: solid solid");
+test_invalid!(border_09, "border-style: wavy");
+
+test!(
+    outline_01,
+    "outline: auto",
+    "outline-width:medium; outline-style:auto; outline-color:auto"
+);
+test!(
+    outline_02,
+    "outline: 2px dotted blue",
+    "outline-width:2px; outline-style:dotted; outline-color:blue"
+);
+test_invalid!(outline_03, "outline: hidden");
+
+test!(radius_01, "border-radius: 5px", "border-top-left-radius:5px; border-top-right-radius:5px; border-bottom-right-radius:5px; border-bottom-left-radius:5px");
+test!(radius_02, "border-radius: 1px 2px 3px", "border-top-left-radius:1px; border-top-right-radius:2px; border-bottom-right-radius:3px; border-bottom-left-radius:2px");
+test!(radius_03, "border-radius: 1px 2px / 3px", "border-top-left-radius:1px 3px; border-top-right-radius:2px 3px; border-bottom-right-radius:1px 3px; border-bottom-left-radius:2px 3px");
+test_invalid!(radius_04, "border-radius: 1px /");
+test_invalid!(radius_05, "border-radius: 1px / 2px / 3px");
+
+test!(font_01, "font: 12px serif", "font-style:normal; font-variant:normal; font-weight:normal; font-stretch:normal; font-size:12px; line-height:normal; font-family:serif");
+test!(font_02, "font: italic bold 16px/1.5 \"Open Sans\", sans-serif", "font-style:italic; font-variant:normal; font-weight:bold; font-stretch:normal; font-size:16px; line-height:1.5; font-family:\"Open Sans\", sans-serif");
+test!(font_03, "font: normal small-caps 600 condensed large/normal Arial", "font-style:normal; font-variant:small-caps; font-weight:600; font-stretch:condensed; font-size:large; line-height:normal; font-family:Arial");
+test!(font_04, "font: oblique 10deg 1em/120% a", "font-style:oblique 10deg; font-variant:normal; font-weight:normal; font-stretch:normal; font-size:1em; line-height:120%; font-family:a");
+test_invalid!(font_05, "font: bold serif");
+test_invalid!(font_06, "font: 12px");
+test_invalid!(font_07, "font: 12px/ serif");
+test_invalid!(font_08, "font: caption");
+test_invalid!(
+    font_09,
+    "font: normal normal normal normal normal 12px serif"
+);
+
+test!(background_01, "background: red", "background-image:none; background-position:0% 0%; background-size:auto; background-repeat:repeat; background-attachment:scroll; background-origin:padding-box; background-clip:border-box; background-color:red");
+test!(background_02, "background: url(a.png) center / cover no-repeat fixed content-box #fff", "background-image:url(a.png); background-position:center; background-size:cover; background-repeat:no-repeat; background-attachment:fixed; background-origin:content-box; background-clip:content-box; background-color:#fff");
+test!(background_03, "background: left 10px top / 50% auto repeat-x padding-box border-box", "background-image:none; background-position:left 10px top; background-size:50% auto; background-repeat:repeat-x; background-attachment:scroll; background-origin:padding-box; background-clip:border-box; background-color:transparent");
+test!(background_04, "background: url(a.png) no-repeat, linear-gradient(red, blue) blue", "background-image:url(a.png), linear-gradient(red, blue); background-position:0% 0%, 0% 0%; background-size:auto, auto; background-repeat:no-repeat, repeat; background-attachment:scroll, scroll; background-origin:padding-box, padding-box; background-clip:border-box, border-box; background-color:blue");
+test_invalid!(background_05, "background: red, url(a.png)");
+test_invalid!(background_06, "background: center /");
+test_invalid!(background_07, "background: none none");
+
+test!(
+    flex_01,
+    "flex: 1",
+    "flex-grow:1; flex-shrink:1; flex-basis:0%"
+);
+test!(
+    flex_02,
+    "flex: none",
+    "flex-grow:0; flex-shrink:0; flex-basis:auto"
+);
+test!(
+    flex_03,
+    "flex: auto",
+    "flex-grow:1; flex-shrink:1; flex-basis:auto"
+);
+test!(
+    flex_04,
+    "flex: 2 3 10px",
+    "flex-grow:2; flex-shrink:3; flex-basis:10px"
+);
+test!(
+    flex_05,
+    "flex: 30%",
+    "flex-grow:1; flex-shrink:1; flex-basis:30%"
+);
+test!(
+    flex_06,
+    "flex: 1 0 0",
+    "flex-grow:1; flex-shrink:0; flex-basis:0"
+);
+test!(
+    flex_07,
+    "flex: content 2",
+    "flex-grow:2; flex-shrink:1; flex-basis:content"
+);
+test_invalid!(flex_08, "flex: 1 2 3");
+test_invalid!(flex_09, "flex: 1px 2px");
+
+test!(
+    list_style_01,
+    "list-style: square inside",
+    "list-style-position:inside; list-style-image:none; list-style-type:square"
+);
+test!(
+    list_style_02,
+    "list-style: none",
+    "list-style-position:outside; list-style-image:none; list-style-type:none"
+);
+test!(
+    list_style_03,
+    "list-style: url(a.png) none",
+    "list-style-position:outside; list-style-image:url(a.png); list-style-type:none"
+);
+test!(
+    list_style_04,
+    "list-style: \"-\" outside",
+    "list-style-position:outside; list-style-image:none; list-style-type:\"-\""
+);
+test_invalid!(list_style_05, "list-style: none disc none");
+
+test!(text_decoration_01, "text-decoration: underline", "text-decoration-line:underline; text-decoration-style:solid; text-decoration-color:currentcolor; text-decoration-thickness:auto");
+test!(text_decoration_02, "text-decoration: underline red overline wavy 2px", "text-decoration-line:underline overline; text-decoration-style:wavy; text-decoration-color:red; text-decoration-thickness:2px");
+test!(text_decoration_03, "text-decoration: none", "text-decoration-line:none; text-decoration-style:solid; text-decoration-color:currentcolor; text-decoration-thickness:auto");
+test_invalid!(text_decoration_04, "text-decoration: underline none");
+test_invalid!(text_decoration_05, "text-decoration: underline underline");
+
+test!(transition_01, "transition: opacity 1s", "transition-property:opacity; transition-duration:1s; transition-timing-function:ease; transition-delay:0s; transition-behavior:normal");
+test!(transition_02, "transition: opacity 1s ease-in 200ms, transform 2s steps(4)", "transition-property:opacity, transform; transition-duration:1s, 2s; transition-timing-function:ease-in, steps(4); transition-delay:200ms, 0s; transition-behavior:normal, normal");
+test!(transition_03, "transition: all cubic-bezier(0, 0, 1, 1) allow-discrete", "transition-property:all; transition-duration:0s; transition-timing-function:cubic-bezier(0, 0, 1, 1); transition-delay:0s; transition-behavior:allow-discrete");
+test_invalid!(transition_04, "transition: opacity 1s 2s 3s");
+test_invalid!(transition_05, "transition: opacity,");
+
+test!(animation_01, "animation: spin 2s linear infinite", "animation-name:spin; animation-duration:2s; animation-timing-function:linear; animation-delay:0s; animation-iteration-count:infinite; animation-direction:normal; animation-fill-mode:none; animation-play-state:running");
+test!(animation_02, "animation: 1s 500ms 3 reverse both paused \"a b\"", "animation-name:\"a b\"; animation-duration:1s; animation-timing-function:ease; animation-delay:500ms; animation-iteration-count:3; animation-direction:reverse; animation-fill-mode:both; animation-play-state:paused");
+// The first `forwards` is a fill mode, the second one is a name.
+test!(animation_03, "animation: forwards forwards 1s, x", "animation-name:forwards, x; animation-duration:1s, 0s; animation-timing-function:ease, ease; animation-delay:0s, 0s; animation-iteration-count:1, 1; animation-direction:normal, normal; animation-fill-mode:forwards, none; animation-play-state:running, running");
+test_invalid!(animation_04, "animation: a b");
+
+test_invalid!(other_01, "color: red");
+test_invalid!(other_02, "margin-top: 1px");
+
+#[test]
+fn important() {
+    let declaration = DeclarationTokenizer::from("border: 1px solid !important")
+        .next()
+        .unwrap();
+    let longhands = declaration.expand().unwrap();
+    assert_eq!(longhands.len(), 12);
+    assert!(longhands.iter().all(|l| l.important));
+
+    let other = DeclarationTokenizer::from("gap: 1px").next().unwrap();
+    assert!(other.expand().unwrap().iter().all(|l| !l.important));
+}
+
+#[test]
+fn borrowed() {
+    use std::borrow::Cow;
+
+    let declaration = DeclarationTokenizer::from("font: bold 12px/2 a, b")
+        .next()
+        .unwrap();
+    let longhands = declaration.expand().unwrap();
+    assert!(matches!(longhands[2].value, Cow::Borrowed("bold")));
+    assert!(matches!(longhands[6].value, Cow::Borrowed("a, b")));
+}
+
+#[test]
+fn cascade() {
+    let sheet = StyleSheet::parse("a { margin: 1px; margin-top: 2px }");
+    let mut computed: Vec<(&str, String)> = Vec::new();
+    for declaration in &sheet.rules[0].declarations {
+        let longhands = match declaration.expand() {
+            Some(longhands) => longhands
+                .into_iter()
+                .map(|l| (l.name, l.value.into_owned()))
+                .collect(),
+            None => vec![(declaration.name.as_ref(), declaration.value.to_string())],
+        };
+
+        for (name, value) in longhands {
+            computed.retain(|(n, _)| *n != name);
+            computed.push((name, value));
+        }
+    }
+
+    assert_eq!(computed.len(), 4);
+    assert_eq!(computed[3], ("margin-top", "2px".to_string()));
+}
+
+#[test]
+fn longhands_list() {
+    assert_eq!(
+        longhands("Flex"),
+        Some(&["flex-grow", "flex-shrink", "flex-basis"][..])
+    );
+    assert_eq!(longhands("border").map(|l| l.len()), Some(12));
+    assert_eq!(longhands("margin-top"), None);
+}
+
+fn contract_text(text: &str) -> String {
+    let declarations: Vec<_> = DeclarationTokenizer::from(text).collect();
+    let list: Vec<_> = contract(&declarations)
+        .iter()
+        .map(|d| d.to_string())
+        .collect();
+    list.join("; ")
+}
+
+macro_rules! test_contract {
+    ($name:ident, $text:expr, $expected:expr) => {
+        #[test]
+        fn $name() {
+            assert_eq!(contract_text($text), $expected);
+        }
+    };
+}
+
+test_contract!(
+    contract_01,
+    "margin-top:1px; margin-right:1px; margin-bottom:1px; margin-left:1px",
+    "margin:1px"
+);
+test_contract!(
+    contract_02,
+    "margin-top:0; margin-right:auto; margin-bottom:0; margin-left:auto",
+    "margin:0 auto"
+);
+test_contract!(
+    contract_03,
+    "margin-left:4px; margin-top:1px; margin-right:2px; margin-bottom:3px",
+    "margin:1px 2px 3px 4px"
+);
+test_contract!(
+    contract_04,
+    "padding-top:1px; padding-right:2px; padding-bottom:3px; padding-left:2px",
+    "padding:1px 2px 3px"
+);
+// Not a full set.
+test_contract!(
+    contract_05,
+    "margin-top:1px; margin-right:1px; margin-bottom:1px",
+    "margin-top:1px; margin-right:1px; margin-bottom:1px"
+);
+// Different `!important` flags.
+test_contract!(
+    contract_06,
+    "margin-top:1px; margin-right:1px; margin-bottom:1px; margin-left:1px !important",
+    "margin-top:1px; margin-right:1px; margin-bottom:1px; margin-left:1px!important"
+);
+test_contract!(
+    contract_07,
+    "gap-x:0; row-gap:1px !important; column-gap:1px !important",
+    "gap-x:0; gap:1px!important"
+);
+// A declaration in between affects the same properties.
+test_contract!(
+    contract_08,
+    "margin-top:0; margin:5px; margin-right:0; margin-bottom:0; margin-left:0",
+    "margin-top:0; margin:5px; margin-right:0; margin-bottom:0; margin-left:0"
+);
+// The shorthand takes the place of the last longhand.
+test_contract!(
+    contract_09,
+    "margin-top:0; color:red; margin-right:0; margin-bottom:0; margin-left:0; top:0",
+    "color:red; margin:0; top:0"
+);
+// Duplicated longhands.
+test_contract!(
+    contract_10,
+    "margin-top:0; margin-top:1px; margin-right:0; margin-bottom:0; margin-left:0",
+    "margin-top:0; margin-top:1px; margin-right:0; margin-bottom:0; margin-left:0"
+);
+test_contract!(contract_11, "border-top-width:1px; border-right-width:1px; border-bottom-width:1px; border-left-width:1px; border-top-style:solid; border-right-style:solid; border-bottom-style:solid; border-left-style:solid; border-top-color:red; border-right-color:red; border-bottom-color:red; border-left-color:red", "border:1px solid red");
+test_contract!(contract_12, "border-top-width:1px; border-right-width:2px; border-bottom-width:1px; border-left-width:2px; border-top-style:solid; border-right-style:solid; border-bottom-style:solid; border-left-style:solid; border-top-color:currentcolor; border-right-color:currentcolor; border-bottom-color:currentcolor; border-left-color:currentcolor", "border-width:1px 2px; border-style:solid; border-color:currentcolor");
+test_contract!(
+    contract_13,
+    "border-left-width:medium; border-left-style:dashed; border-left-color:currentColor",
+    "border-left:dashed"
+);
+test_contract!(
+    contract_14,
+    "outline-width:medium; outline-style:none; outline-color:auto",
+    "outline:none"
+);
+test_contract!(contract_15, "border-top-left-radius:1px; border-top-right-radius:2px; border-bottom-right-radius:1px; border-bottom-left-radius:2px", "border-radius:1px 2px");
+test_contract!(contract_16, "border-top-left-radius:1px 3px; border-top-right-radius:1px 3px; border-bottom-right-radius:1px 3px; border-bottom-left-radius:1px 3px", "border-radius:1px / 3px");
+test_contract!(
+    contract_17,
+    "top:inherit; right:inherit; bottom:inherit; left:inherit",
+    "inset:inherit"
+);
+test_contract!(
+    contract_18,
+    "top:inherit; right:0; bottom:0; left:0",
+    "top:inherit; right:0; bottom:0; left:0"
+);
+test_contract!(
+    contract_19,
+    "margin-top:var(--x); margin-right:0; margin-bottom:0; margin-left:0",
+    "margin-top:var(--x); margin-right:0; margin-bottom:0; margin-left:0"
+);
+// `border` would reset `border-image`.
+test_contract!(contract_20, "border-image:none; border-top-width:1px; border-right-width:1px; border-bottom-width:1px; border-left-width:1px; border-top-style:solid; border-right-style:solid; border-bottom-style:solid; border-left-style:solid; border-top-color:red; border-right-color:red; border-bottom-color:red; border-left-color:red", "border-image:none; border-width:1px; border-style:solid; border-color:red");
+test_contract!(
+    contract_21,
+    "margin-top:1px; margin-right:0; margin-bottom:0; margin-left:0; MARGIN-TOP:1px",
+    "margin-top:1px; margin-right:0; margin-bottom:0; margin-left:0; MARGIN-TOP:1px"
+);
+
+#[test]
+fn minified() {
+    let style = StyleSheet::parse(
+        "a { margin-top: 0; margin-right: 0; margin-bottom: 0; margin-left: 0; color: red }\n\
+         b > c { gap: 1px; font-size: 10px !important }",
+    );
+    assert_eq!(
+        style.to_minified_string(),
+        "a{margin:0;color:red}b > c{gap:1px;font-size:10px!important}"
+    );
+}
+
+#[test]
+fn minified_round_trip() {
+    let text = "#x, .a\\'b { color: red } [title=\"a\\\"b\"] { content: \"\\\"\" } \
+                d\\ iv > p { --\\:a: 1 } a, b { top: 0 } c { margin-top: 0 }";
+    let style = StyleSheet::parse(text);
+    let minified = style.to_minified_string();
+    assert_eq!(
+        minified,
+        ".a\\'b,#x{color:red}[title=\"a\\\"b\"]{content:\"\\\"\"}\
+         d\\ iv > p{--\\:a:1}a,b{top:0}c{margin-top:0}"
+    );
+    assert_eq!(StyleSheet::parse(&minified), style);
+}
+
+#[test]
+fn minified_parse_more() {
+    // Rules of the second text come after the first text, whatever their offsets.
+    let mut style = StyleSheet::parse("a { color: red } b { top: 0 }");
+    style.parse_more("a { color: blue }");
+    assert_eq!(
+        style.to_minified_string(),
+        "a{color:red}b{top:0}a{color:blue}"
+    );
+}
+
+#[test]
+fn minified_selectors() {
+    // Ids and classes keep their syntax and specificity.
+    for text in ["#x{top:0}", ".a{top:0}", "p#x.a.b{top:0}", "*{top:0}"] {
+        let style = StyleSheet::parse(text);
+        let minified = style.to_minified_string();
+        assert_eq!(minified, text);
+
+        let expected: Vec<_> = style
+            .rules
+            .iter()
+            .map(|r| r.selector.specificity())
+            .collect();
+        let specificity: Vec<_> = StyleSheet::parse(&minified)
+            .rules
+            .iter()
+            .map(|r| r.selector.specificity())
+            .collect();
+        assert_eq!(specificity, expected, "{}", text);
+    }
+}
@@ -145,3 +145,98 @@ fn style_21() {
     let style = StyleSheet::parse(":le>*");
     assert_eq!(style.to_string(), "");
 }
+
+#[test]
+fn style_22() {
+    let style = StyleSheet::parse(
+        "\
+@media screen {
+    p:before { content: '}'; }
+}
+a { color:red }",
+    );
+    assert_eq!(style.to_string(), "a { color:red; }");
+}
+
+#[test]
+fn style_23() {
+    let style = StyleSheet::parse("@font-face { /* { */ src: url(x) } a { color:red }");
+    assert_eq!(style.to_string(), "a { color:red; }");
+}
+
+#[test]
+fn style_24() {
+    let style = StyleSheet::parse("@unknown \"{;\" ( { ; } ) [ } ; ]; a { color:red }");
+    assert_eq!(style.to_string(), "a { color:red; }");
+}
+
+#[test]
+fn style_25() {
+    let style = StyleSheet::parse("p { color:green; {\"}\" '\\'}'} } a { color:red }");
+    assert_eq!(style.to_string(), "p { color:green; }\na { color:red; }");
+}
+
+#[test]
+fn style_26() {
+    let style = StyleSheet::parse("p::after { content: \"}\" } a { color:red }");
+    assert_eq!(style.to_string(), "a { color:red; }");
+}
+
+#[test]
+fn style_27() {
+    let text = "a, b > c { color: red; }\n  p { margin: 0 !important }";
+    let style = StyleSheet::parse(text);
+    // Rules are sorted by specificity: `a`, `p`, `b > c`.
+    assert_eq!(style.rules.len(), 3);
+
+    assert_eq!(style.rules[0].span.slice(text), "a, b > c { color: red; }");
+    assert_eq!(style.rules[0].span, style.rules[2].span);
+    assert_eq!(style.rules[0].selector.span().slice(text), "a");
+    assert_eq!(style.rules[2].selector.span().slice(text), "b > c");
+
+    let color = &style.rules[0].declarations[0];
+    assert_eq!(color.span.name.slice(text), "color");
+    assert_eq!(color.span.value.slice(text), "red");
+    assert_eq!(color.span.important, None);
+    assert_eq!(color.span().slice(text), "color: red");
+
+    let rule = &style.rules[1];
+    assert_eq!(rule.span.slice(text), "p { margin: 0 !important }");
+    assert_eq!(rule.span.start_pos(text), TextPos::new(2, 3));
+
+    let margin = &rule.declarations[0];
+    assert_eq!(margin.span.value.slice(text), "0");
+    assert_eq!(
+        margin.span.important.map(|s| s.slice(text)),
+        Some("!important")
+    );
+    assert_eq!(margin.span().slice(text), "margin: 0 !important");
+    assert_eq!(margin.span.name.start_pos(text), TextPos::new(2, 7));
+}
+
+#[test]
+fn style_28() {
+    let style = StyleSheet::parse("<!-- a { color:red } --> <!--\n--> b { color:green }\n-->");
+    assert_eq!(style.to_string(), "a { color:red; }\nb { color:green; }");
+}
+
+#[test]
+fn style_29() {
+    // CDO/CDC are ordinary tokens inside blocks and selectors.
+    let style = StyleSheet::parse("a { <!-- color:red } b <!-- c { color:red } d { top:0 }");
+    assert_eq!(style.to_string(), "d { top:0; }");
+}
+
+#[test]
+fn style_30() {
+    let text = "a { background: url(\"a)b.png\"); fill: var(--x, rgb(0 0 0)); color: red }";
+    let style = StyleSheet::parse(text);
+    let declarations = &style.rules[0].declarations;
+    assert_eq!(declarations.len(), 3);
+    assert_eq!(declarations[0].span.value.slice(text), "url(\"a)b.png\")");
+    assert_eq!(
+        declarations[1].span.value.slice(text),
+        "var(--x, rgb(0 0 0))"
+    );
+    assert_eq!(declarations[2].value, "red");
+}
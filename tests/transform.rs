@@ -0,0 +1,376 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Transform parsing and composition
+
+#![cfg(any(feature = "std", feature = "libm"))]
+
+use simplecss::*;
+
+fn px(value: f64) -> Length {
+    Length::new(value, LengthUnit::Px)
+}
+
+fn percent(value: f64) -> Length {
+    Length::new(value, LengthUnit::Percent)
+}
+
+fn matrix_2d(text: &str) -> Matrix2D {
+    let transform = Transform::parse(text).unwrap();
+    transform
+        .to_matrix_2d(&ResolveContext::default(), 200.0, 100.0)
+        .unwrap()
+}
+
+fn assert_near(a: Matrix2D, b: Matrix2D) {
+    let a = [a.a, a.b, a.c, a.d, a.e, a.f];
+    let b = [b.a, b.b, b.c, b.d, b.e, b.f];
+    for (x, y) in a.iter().zip(&b) {
+        assert!((x - y).abs() < 1e-9, "{:?} != {:?}", a, b);
+    }
+}
+
+macro_rules! test {
+    ($name:ident, $text:expr, $func:expr) => {
+        #[test]
+        fn $name() {
+            assert_eq!(Transform::parse($text).unwrap().functions, [$func]);
+        }
+    };
+}
+
+macro_rules! test_invalid {
+    ($name:ident, $text:expr) => {
+        #[test]
+        fn $name() {
+            assert_eq!(Transform::parse($text), None);
+        }
+    };
+}
+
+test!(
+    parse_01,
+    "matrix(1, 2, 3, 4, 5, 6)",
+    TransformFunction::Matrix(Matrix2D::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0))
+);
+test!(
+    parse_02,
+    "translate(10px)",
+    TransformFunction::Translate(px(10.0), px(0.0), px(0.0))
+);
+test!(
+    parse_03,
+    "translate(10px, 50%)",
+    TransformFunction::Translate(px(10.0), percent(50.0), px(0.0))
+);
+test!(
+    parse_04,
+    "translateY(2em)",
+    TransformFunction::Translate(px(0.0), Length::new(2.0, LengthUnit::Em), px(0.0))
+);
+test!(
+    parse_05,
+    "translateZ(5px)",
+    TransformFunction::Translate(px(0.0), px(0.0), px(5.0))
+);
+test!(
+    parse_06,
+    "translate3d(1px, 2px, 3px)",
+    TransformFunction::Translate(px(1.0), px(2.0), px(3.0))
+);
+test!(
+    parse_07,
+    "scale(2)",
+    TransformFunction::Scale(2.0, 2.0, 1.0)
+);
+test!(
+    parse_08,
+    "scale(2, 50%)",
+    TransformFunction::Scale(2.0, 0.5, 1.0)
+);
+test!(
+    parse_09,
+    "SCALEX(3)",
+    TransformFunction::Scale(3.0, 1.0, 1.0)
+);
+test!(
+    parse_10,
+    "scale3d(1, 2, 3)",
+    TransformFunction::Scale(1.0, 2.0, 3.0)
+);
+test!(
+    parse_11,
+    "rotate(0.5turn)",
+    TransformFunction::Rotate(180.0)
+);
+test!(
+    parse_12,
+    "rotateZ(100grad)",
+    TransformFunction::Rotate(90.0)
+);
+test!(parse_13, "rotate(0)", TransformFunction::Rotate(0.0));
+test!(
+    parse_14,
+    "rotateX(45deg)",
+    TransformFunction::Rotate3D(1.0, 0.0, 0.0, 45.0)
+);
+test!(
+    parse_15,
+    "rotate3d(1, 1, 0, 90deg)",
+    TransformFunction::Rotate3D(1.0, 1.0, 0.0, 90.0)
+);
+test!(parse_16, "skew(10deg)", TransformFunction::Skew(10.0, 0.0));
+test!(parse_17, "skewY(20deg)", TransformFunction::Skew(0.0, 20.0));
+test!(
+    parse_18,
+    "perspective(100px)",
+    TransformFunction::Perspective(Some(px(100.0)))
+);
+test!(
+    parse_19,
+    "perspective(none)",
+    TransformFunction::Perspective(None)
+);
+test!(
+    parse_20,
+    "translate(calc(1em + 1em))",
+    TransformFunction::Translate(Length::new(2.0, LengthUnit::Em), px(0.0), px(0.0))
+);
+test!(
+    parse_21,
+    "rotate(calc(45deg * 2))",
+    TransformFunction::Rotate(90.0)
+);
+test!(
+    parse_22,
+    "scale(calc(1 / 2))",
+    TransformFunction::Scale(0.5, 0.5, 1.0)
+);
+test_invalid!(parse_23, "");
+test_invalid!(parse_24, "rotate(45)");
+test_invalid!(parse_25, "translate(10px, 20px, 30px)");
+test_invalid!(parse_26, "translateZ(10%)");
+test_invalid!(parse_27, "scale(2px)");
+test_invalid!(parse_28, "perspective(-1px)");
+test_invalid!(parse_29, "matrix(1, 0, 0, 1, 0)");
+test_invalid!(parse_30, "rotate(45deg) none");
+test_invalid!(parse_31, "translate(10px 20px)");
+test_invalid!(parse_32, "foo(1)");
+
+#[test]
+fn none() {
+    let transform = Transform::parse("none").unwrap();
+    assert!(transform.functions.is_empty());
+    assert_eq!(
+        transform.to_matrix(&ResolveContext::default(), 0.0, 0.0),
+        Matrix3D::IDENTITY
+    );
+}
+
+#[test]
+fn matrix_3d() {
+    let text = "matrix3d(1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1, 0, 10, 20, 0, 1)";
+    let transform = Transform::parse(text).unwrap();
+    assert!(transform.is_2d());
+    assert_eq!(matrix_2d(text), Matrix2D::translate(10.0, 20.0));
+}
+
+#[test]
+fn compose_01() {
+    assert_eq!(
+        matrix_2d("translate(10px, 20px)"),
+        Matrix2D::translate(10.0, 20.0)
+    );
+    assert_eq!(
+        matrix_2d("translate(50%, 50%)"),
+        Matrix2D::translate(100.0, 50.0)
+    );
+    assert_eq!(
+        matrix_2d("rotate(90deg)"),
+        Matrix2D::new(0.0, 1.0, -1.0, 0.0, 0.0, 0.0)
+    );
+    assert_eq!(
+        matrix_2d("rotate(-0.5turn)"),
+        Matrix2D::new(-1.0, 0.0, 0.0, -1.0, 0.0, 0.0)
+    );
+}
+
+#[test]
+fn compose_02() {
+    // Functions are applied from right to left.
+    let matrix = matrix_2d("translate(10px) scale(2)");
+    assert_eq!(matrix, Matrix2D::new(2.0, 0.0, 0.0, 2.0, 10.0, 0.0));
+    assert_eq!(matrix.transform_point(1.0, 1.0), (12.0, 2.0));
+
+    assert_eq!(
+        matrix_2d("scale(2) translate(10px)"),
+        Matrix2D::new(2.0, 0.0, 0.0, 2.0, 20.0, 0.0)
+    );
+}
+
+#[test]
+fn compose_03() {
+    assert_near(
+        matrix_2d("skew(45deg, 0)"),
+        Matrix2D::new(1.0, 0.0, 1.0, 1.0, 0.0, 0.0),
+    );
+    assert_near(
+        matrix_2d("rotate(30deg) rotate(60deg)"),
+        Matrix2D::new(0.0, 1.0, -1.0, 0.0, 0.0, 0.0),
+    );
+
+    // A rotation around the Z axis is a 2D rotation.
+    assert_near(
+        matrix_2d("rotate3d(0, 0, 2, 30deg) rotateZ(60deg)"),
+        Matrix2D::new(0.0, 1.0, -1.0, 0.0, 0.0, 0.0),
+    );
+}
+
+#[test]
+fn compose_3d() {
+    let context = ResolveContext::default();
+    let transform = Transform::parse("rotateX(90deg)").unwrap();
+    assert!(!transform.is_2d());
+
+    let matrix = transform.to_matrix(&context, 0.0, 0.0);
+    assert_eq!(matrix.to_2d(), None);
+    assert_eq!(
+        matrix.m,
+        [
+            1.0, 0.0, 0.0, 0.0, //
+            0.0, 0.0, 1.0, 0.0, //
+            0.0, -1.0, 0.0, 0.0, //
+            0.0, 0.0, 0.0, 1.0,
+        ]
+    );
+
+    // The Z translation is rotated around the X axis.
+    let text = "rotateX(30deg) translateZ(10px) rotate3d(-1, 0, 0, 30deg)";
+    let m = Transform::parse(text)
+        .unwrap()
+        .to_matrix(&context, 0.0, 0.0)
+        .m;
+    assert!((m[13] + 5.0).abs() < 1e-9);
+    assert!((m[14] - 75.0_f64.sqrt()).abs() < 1e-9);
+}
+
+#[test]
+fn perspective() {
+    let matrix = |text| {
+        let transform = Transform::parse(text).unwrap();
+        transform.to_matrix(&ResolveContext::default(), 0.0, 0.0)
+    };
+
+    assert!(!Transform::parse("perspective(1px)").unwrap().is_2d());
+    assert!(Transform::parse("perspective(none)").unwrap().is_2d());
+
+    assert_eq!(matrix("perspective(200px)").m[11], -0.005);
+    // Small distances are clamped to 1px.
+    assert_eq!(matrix("perspective(0)").m[11], -1.0);
+    assert_eq!(matrix("perspective(none)"), Matrix3D::IDENTITY);
+}
+
+#[test]
+fn matrix_conversion() {
+    let matrix = Matrix2D::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0);
+    assert_eq!(Matrix3D::from(matrix).to_2d(), Some(matrix));
+    assert_eq!(
+        Matrix3D::from(matrix)
+            .multiply(&Matrix3D::from(matrix))
+            .to_2d(),
+        Some(matrix.multiply(&matrix))
+    );
+}
+
+macro_rules! test_origin {
+    ($name:ident, $text:expr, $x:expr, $y:expr, $z:expr) => {
+        #[test]
+        fn $name() {
+            let origin = TransformOrigin {
+                x: $x,
+                y: $y,
+                z: $z,
+            };
+            assert_eq!(TransformOrigin::parse($text), Some(origin));
+        }
+    };
+}
+
+macro_rules! test_origin_invalid {
+    ($name:ident, $text:expr) => {
+        #[test]
+        fn $name() {
+            assert_eq!(TransformOrigin::parse($text), None);
+        }
+    };
+}
+
+test_origin!(origin_01, "left", percent(0.0), percent(50.0), px(0.0));
+test_origin!(origin_02, "bottom", percent(50.0), percent(100.0), px(0.0));
+test_origin!(origin_03, "10px", px(10.0), percent(50.0), px(0.0));
+test_origin!(
+    origin_04,
+    "right top",
+    percent(100.0),
+    percent(0.0),
+    px(0.0)
+);
+test_origin!(
+    origin_05,
+    "top right",
+    percent(100.0),
+    percent(0.0),
+    px(0.0)
+);
+test_origin!(
+    origin_06,
+    "center left",
+    percent(0.0),
+    percent(50.0),
+    px(0.0)
+);
+test_origin!(
+    origin_07,
+    "bottom center 5px",
+    percent(50.0),
+    percent(100.0),
+    px(5.0)
+);
+test_origin!(
+    origin_08,
+    "10% 2em",
+    percent(10.0),
+    Length::new(2.0, LengthUnit::Em),
+    px(0.0)
+);
+test_origin!(
+    origin_09,
+    "CENTER center",
+    percent(50.0),
+    percent(50.0),
+    px(0.0)
+);
+test_origin_invalid!(origin_10, "");
+test_origin_invalid!(origin_11, "top 10px");
+test_origin_invalid!(origin_12, "left right");
+test_origin_invalid!(origin_13, "top bottom");
+test_origin_invalid!(origin_14, "left top 10%");
+test_origin_invalid!(origin_15, "1px 2px 3px 4px");
+test_origin_invalid!(origin_16, "auto");
+
+#[test]
+fn origin_default() {
+    let origin = TransformOrigin::default();
+    let context = ResolveContext::default();
+    assert_eq!(origin.resolve(&context, 200.0, 100.0), (100.0, 50.0, 0.0));
+
+    // Rotate around the center of a 200x100 box.
+    let transform = Transform::parse("rotate(180deg)").unwrap();
+    let matrix = transform.to_matrix(&context, 200.0, 100.0);
+    let matrix = origin
+        .apply(&matrix, &context, 200.0, 100.0)
+        .to_2d()
+        .unwrap();
+    assert_eq!(matrix.transform_point(0.0, 0.0), (200.0, 100.0));
+    assert_eq!(matrix.transform_point(100.0, 50.0), (100.0, 50.0));
+}
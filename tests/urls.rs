@@ -0,0 +1,138 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! URL references
+
+use simplecss::*;
+
+fn urls(text: &str) -> Vec<(UrlKind, String, &str)> {
+    find_urls(text)
+        .into_iter()
+        .map(|r| (r.kind, r.url.into_owned(), r.span.slice(text)))
+        .collect()
+}
+
+#[test]
+fn import() {
+    let text = "@import url(a.css); @import \"b.css\" screen; @IMPORT src('c.css'); @import url( 'd.css' ) layer;";
+    assert_eq!(
+        urls(text),
+        [
+            (UrlKind::Url, "a.css".to_string(), "url(a.css)"),
+            (UrlKind::Import, "b.css".to_string(), "\"b.css\""),
+            (UrlKind::Src, "c.css".to_string(), "'c.css'"),
+            (UrlKind::Url, "d.css".to_string(), "url( 'd.css' )"),
+        ]
+    );
+}
+
+#[test]
+fn font_face() {
+    let text =
+        "@font-face { font-family: a; src: local(a), url(a.woff2) format('woff2'), url('a.ttf') }";
+    assert_eq!(
+        urls(text),
+        [
+            (UrlKind::Url, "a.woff2".to_string(), "url(a.woff2)"),
+            (UrlKind::Url, "a.ttf".to_string(), "url('a.ttf')"),
+        ]
+    );
+}
+
+#[test]
+fn image_set() {
+    let text = "a { background: -webkit-image-set('a.png' 1x, url(b.png) 2x, \"c.png\" type('image/png')) }";
+    assert_eq!(
+        urls(text),
+        [
+            (UrlKind::ImageSet, "a.png".to_string(), "'a.png'"),
+            (UrlKind::Url, "b.png".to_string(), "url(b.png)"),
+            (UrlKind::ImageSet, "c.png".to_string(), "\"c.png\""),
+        ]
+    );
+}
+
+#[test]
+fn ignored() {
+    let text =
+        "/* url(a.png) */ a { content: 'url(b.png)'; font-family: \"c.png\"; background: none }";
+    assert!(urls(text).is_empty());
+}
+
+#[test]
+fn escapes() {
+    let text = r#"a { background: url(a\(1\).png), url("b\"c.png") }"#;
+    assert_eq!(
+        urls(text),
+        [
+            (UrlKind::Url, "a(1).png".to_string(), r#"url(a\(1\).png)"#),
+            (UrlKind::Url, "b\"c.png".to_string(), r#"url("b\"c.png")"#),
+        ]
+    );
+}
+
+#[test]
+fn invalid() {
+    // Invalid tokens are skipped.
+    let text = "a { background: url('a.png' b) } b { background: url(c.png) }";
+    assert_eq!(
+        urls(text),
+        [(UrlKind::Url, "c.png".to_string(), "url(c.png)")]
+    );
+}
+
+#[test]
+fn rewrite_01() {
+    let text = "@import 'a.css'; @import url(b.css); a { background: url(c.png) no-repeat }";
+    let result = rewrite_urls(text, |r| Some(format!("/cdn/{}", r.url)));
+    assert_eq!(
+        result,
+        "@import \"/cdn/a.css\"; @import url(/cdn/b.css); a { background: url(/cdn/c.png) no-repeat }"
+    );
+}
+
+#[test]
+fn rewrite_02() {
+    let text = "a { background: url(a.png), url('b.png'), image-set('c.png' 1x) }";
+    let result = rewrite_urls(text, |r| match &*r.url {
+        "a.png" => Some("a b.png".to_string()),
+        "b.png" => Some("b.png".to_string()),
+        "c.png" => Some("c\\\n.png".to_string()),
+        _ => None,
+    });
+    assert_eq!(
+        result,
+        "a { background: url(\"a b.png\"), url(\"b.png\"), image-set(\"c\\\\\\a .png\" 1x) }"
+    );
+
+    // Rewritten URLs are found again.
+    let found: Vec<_> = find_urls(&result)
+        .into_iter()
+        .map(|r| r.url.into_owned())
+        .collect();
+    assert_eq!(found, ["a b.png", "b.png", "c\\\n.png"]);
+}
+
+#[test]
+fn rewrite_03() {
+    let text = "a { background: url(a.png) }";
+    assert_eq!(rewrite_urls(text, |_| None), text);
+}
+
+#[test]
+fn rewrite_04() {
+    // The function name can be escaped.
+    let text = "a { background: u\\72l(a.png), U\\52L( 'b.png' ) }";
+    let found: Vec<_> = find_urls(text).into_iter().map(|r| r.quoted).collect();
+    assert_eq!(found, [false, true]);
+
+    let result = rewrite_urls(text, |r| Some(format!("/{}", r.url)));
+    assert_eq!(result, "a { background: url(/a.png), url(\"/b.png\") }");
+}
+
+#[test]
+fn rewrite_05() {
+    let text = "a { background: url(a.png) }";
+    let result = rewrite_urls(text, |_| Some("a\0\u{1}.png".to_string()));
+    assert_eq!(result, "a { background: url(\"a\u{FFFD}\\1 .png\") }");
+}
@@ -0,0 +1,232 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Value tokenizer and parser
+
+use simplecss::*;
+
+macro_rules! tokenize {
+    ($name:ident, $text:expr, $( $token:expr ),*) => (
+        #[test]
+        fn $name() {
+            let mut t = ValueTokenizer::from($text);
+            $(
+                assert_eq!(t.next().unwrap().unwrap(), $token);
+            )*
+
+            assert!(t.next().is_none());
+        }
+    )
+}
+
+tokenize!(tokenize_01, "",);
+
+tokenize!(tokenize_02, "  /* comment */ ",);
+
+tokenize!(
+    tokenize_03,
+    "auto -webkit-box --main-color",
+    ValueToken::Ident("auto".into()),
+    ValueToken::Ident("-webkit-box".into()),
+    ValueToken::Ident("--main-color".into())
+);
+
+tokenize!(
+    tokenize_04,
+    "1 -2.5 +.5 1e3 1.5E-2 10e",
+    ValueToken::Number(1.0),
+    ValueToken::Number(-2.5),
+    ValueToken::Number(0.5),
+    ValueToken::Number(1000.0),
+    ValueToken::Number(0.015),
+    ValueToken::Dimension(10.0, "e".into())
+);
+
+tokenize!(
+    tokenize_05,
+    "50% 10px -1.5em 2e1px",
+    ValueToken::Percentage(50.0),
+    ValueToken::Dimension(10.0, "px".into()),
+    ValueToken::Dimension(-1.5, "em".into()),
+    ValueToken::Dimension(20.0, "px".into())
+);
+
+tokenize!(
+    tokenize_06,
+    "\"a b\" 'c' #fff #",
+    ValueToken::String("a b".into()),
+    ValueToken::String("c".into()),
+    ValueToken::Hash("fff".into()),
+    ValueToken::Delim('#')
+);
+
+tokenize!(
+    tokenize_07,
+    "url(a.png) URL( \"b c.png\" ) url()",
+    ValueToken::Url("a.png".into(), false),
+    ValueToken::Url("b c.png".into(), true),
+    ValueToken::Url("".into(), false)
+);
+
+tokenize!(
+    tokenize_08,
+    "rgb(0,0 ,0) / [a] {b}",
+    ValueToken::Function("rgb".into()),
+    ValueToken::Number(0.0),
+    ValueToken::Comma,
+    ValueToken::Number(0.0),
+    ValueToken::Comma,
+    ValueToken::Number(0.0),
+    ValueToken::CloseBracket(Bracket::Parenthesis),
+    ValueToken::Slash,
+    ValueToken::OpenBracket(Bracket::Square),
+    ValueToken::Ident("a".into()),
+    ValueToken::CloseBracket(Bracket::Square),
+    ValueToken::OpenBracket(Bracket::Curly),
+    ValueToken::Ident("b".into()),
+    ValueToken::CloseBracket(Bracket::Curly)
+);
+
+tokenize!(
+    tokenize_09,
+    "calc(1px + 2px) a (b)",
+    ValueToken::Function("calc".into()),
+    ValueToken::Dimension(1.0, "px".into()),
+    ValueToken::Delim('+'),
+    ValueToken::Dimension(2.0, "px".into()),
+    ValueToken::CloseBracket(Bracket::Parenthesis),
+    ValueToken::Ident("a".into()),
+    ValueToken::OpenBracket(Bracket::Parenthesis),
+    ValueToken::Ident("b".into()),
+    ValueToken::CloseBracket(Bracket::Parenthesis)
+);
+
+tokenize!(
+    tokenize_10,
+    "a\\:b 1\\70 x ! \u{fc}ber",
+    ValueToken::Ident("a:b".into()),
+    ValueToken::Dimension(1.0, "px".into()),
+    ValueToken::Delim('!'),
+    ValueToken::Ident("\u{fc}ber".into())
+);
+
+#[test]
+fn tokenize_11() {
+    let mut t = ValueTokenizer::from("a 'b");
+    assert_eq!(t.next().unwrap().unwrap(), ValueToken::Ident("a".into()));
+    assert!(t.next().unwrap().is_err());
+    assert!(t.next().is_none());
+}
+
+#[test]
+fn value_01() {
+    let value = Value::parse("1px solid rgba(0, 0, 0, 50%)").unwrap();
+    assert_eq!(
+        value.components,
+        [
+            ComponentValue::Dimension(1.0, "px".into()),
+            ComponentValue::Ident("solid".into()),
+            ComponentValue::Function(Function {
+                name: "rgba".into(),
+                arguments: vec![
+                    ComponentValue::Number(0.0),
+                    ComponentValue::Comma,
+                    ComponentValue::Number(0.0),
+                    ComponentValue::Comma,
+                    ComponentValue::Number(0.0),
+                    ComponentValue::Comma,
+                    ComponentValue::Percentage(50.0),
+                ],
+            }),
+        ]
+    );
+}
+
+#[test]
+fn value_02() {
+    // Unclosed functions are closed at the end, unmatched brackets are delimiters.
+    let value = Value::parse("a ] f(b [c").unwrap();
+    assert_eq!(
+        value.components,
+        [
+            ComponentValue::Ident("a".into()),
+            ComponentValue::Delim(']'),
+            ComponentValue::Function(Function {
+                name: "f".into(),
+                arguments: vec![
+                    ComponentValue::Ident("b".into()),
+                    ComponentValue::Block(Bracket::Square, vec![ComponentValue::Ident("c".into())]),
+                ],
+            }),
+        ]
+    );
+}
+
+#[test]
+fn value_03() {
+    let value = Value::parse("a b, c, f(d, e)").unwrap();
+    let items: Vec<_> = value.split_commas().collect();
+    assert_eq!(items.len(), 3);
+    assert_eq!(
+        items[0],
+        [
+            ComponentValue::Ident("a".into()),
+            ComponentValue::Ident("b".into())
+        ]
+    );
+
+    let ComponentValue::Function(f) = &items[2][0] else {
+        panic!()
+    };
+    assert_eq!(f.split_commas().count(), 2);
+
+    assert_eq!(Value::parse("").unwrap().split_commas().count(), 0);
+}
+
+#[test]
+fn value_04() {
+    let value =
+        Value::parse("italic  bold 12px/30px Georgia,'Times \"New\" Roman' ,serif").unwrap();
+    assert_eq!(
+        value.to_string(),
+        "italic bold 12px / 30px Georgia, \"Times \\\"New\\\" Roman\", serif"
+    );
+
+    let other = Value::parse("url(a.png) #FFF calc(100% - (2 * 3px)) [a b]").unwrap();
+    assert_eq!(
+        other.to_string(),
+        "url(\"a.png\") #FFF calc(100% - (2 * 3px)) [a b]"
+    );
+}
+
+#[test]
+fn value_05() {
+    let style = StyleSheet::parse("a { margin: 0 auto !important }");
+    let value = style.rules[0].declarations[0].parse_value().unwrap();
+    assert_eq!(value.components[1].as_ident(), Some("auto"));
+    assert!(value.components[1].is_ident("AUTO"));
+    assert_eq!(value.components[0].as_number(), Some(0.0));
+}
+
+#[test]
+fn value_06() {
+    let text = "a\\:b 1\\65 3 #\\31 a \"q\\\"\\\\\" url(a\\)b) \\31 0 f\\(x(1) -\\31";
+    let value = Value::parse(text).unwrap();
+    assert_eq!(
+        value.components[..4],
+        [
+            ComponentValue::Ident("a:b".into()),
+            ComponentValue::Dimension(1.0, "e3".into()),
+            ComponentValue::Hash("1a".into()),
+            ComponentValue::String("q\"\\".into()),
+        ]
+    );
+    assert_eq!(value.components[4], ComponentValue::Url("a)b".into()));
+
+    let serialized = value.to_string();
+    assert_eq!(
+        serialized,
+        "a\\:b 1\\65 3 #1a \"q\\\"\\\\\" url(\"a)b\") \\31 0 f\\(x(1) -\\31 "
+    );
+    assert_eq!(Value::parse(&serialized).unwrap(), value);
+}
@@ -0,0 +1,195 @@
+// Copyright 2025 the SimpleCSS Authors
+// SPDX-License-Identifier: Apache-2.0 OR MIT
+
+//! Custom properties and `var()` substitution
+
+use simplecss::*;
+
+fn properties(text: &str) -> Vec<Declaration<'_>> {
+    DeclarationTokenizer::from(text).collect()
+}
+
+#[test]
+fn parse_01() {
+    let declarations = properties("--accent: #f00; color: var(--accent)");
+    assert_eq!(declarations.len(), 2);
+    assert_eq!(declarations[0].name, "--accent");
+    assert_eq!(declarations[0].value, "#f00");
+    assert!(declarations[0].is_custom_property());
+    assert!(!declarations[1].is_custom_property());
+}
+
+#[test]
+fn parse_02() {
+    let declarations = properties("--empty:; --block: { a: b; c: [d] } !important; --x: 1");
+    assert_eq!(declarations.len(), 3);
+    assert_eq!(declarations[0].value, "");
+    assert_eq!(declarations[1].value, "{ a: b; c: [d] }");
+    assert!(declarations[1].important);
+    assert_eq!(declarations[2].value, "1");
+}
+
+#[test]
+fn parse_03() {
+    let style = StyleSheet::parse("a { --My-Var: 'a;b' (c) ; color: red }");
+    let declarations = &style.rules[0].declarations;
+    assert_eq!(declarations.len(), 2);
+    assert_eq!(declarations[0].name, "--My-Var");
+    assert_eq!(declarations[0].value, "'a;b' (c)");
+    assert_eq!(declarations[1].value, "red");
+}
+
+#[test]
+fn parse_04() {
+    // Regular properties still cannot be empty.
+    let style = StyleSheet::parse("a { color:; top: 0 }");
+    assert_eq!(style.to_string(), "");
+}
+
+#[test]
+fn cascade() {
+    let declarations = properties("--a: 1 !important; --a: 2; --b: 1; --b: 2; color: red");
+    let map = CustomProperties::from_declarations(&declarations);
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get("--a"), Some("1"));
+    assert_eq!(map.get("--b"), Some("2"));
+    assert_eq!(map.get("color"), None);
+}
+
+#[test]
+fn substitute_01() {
+    let declarations = properties("--x: 10px; --y: var(--x) var(--x)");
+    let map = CustomProperties::from_declarations(&declarations);
+    assert_eq!(
+        map.substitute("calc(var(--x) + 1px)").as_deref(),
+        Some("calc(10px + 1px)")
+    );
+    assert_eq!(map.substitute("VAR( --y )").as_deref(), Some("10px 10px"));
+    assert_eq!(
+        map.substitute("1px solid red").as_deref(),
+        Some("1px solid red")
+    );
+}
+
+#[test]
+fn substitute_02() {
+    let map = CustomProperties::new();
+    assert_eq!(map.substitute("var(--x)"), None);
+    assert_eq!(map.substitute("var(--x, 1px)").as_deref(), Some("1px"));
+    assert_eq!(map.substitute("var(--x,)").as_deref(), Some(""));
+    assert_eq!(map.substitute("var(--x, a, b)").as_deref(), Some("a, b"));
+    assert_eq!(
+        map.substitute("var(--x, var(--y, (1)))").as_deref(),
+        Some("(1)")
+    );
+    assert_eq!(map.substitute("var(x, 1)"), None);
+}
+
+#[test]
+fn substitute_03() {
+    let mut map = CustomProperties::new();
+    map.insert("--x", "1");
+    // Strings, comments and other functions are kept as is.
+    assert_eq!(
+        map.substitute("'var(--x)' /* var(--x) */ myvar(--x) var(--x)")
+            .as_deref(),
+        Some("'var(--x)' /* var(--x) */ myvar(--x) 1")
+    );
+    assert_eq!(map.substitute("é var(--x)").as_deref(), Some("é 1"));
+}
+
+#[test]
+fn cycles() {
+    let declarations = properties(
+        "--a: var(--b); --b: var(--a, 1); --c: var(--c); --d: var(--a, 2); --e: var(--f, 3); --f: 4",
+    );
+    let map = CustomProperties::from_declarations(&declarations);
+    assert_eq!(map.substitute("var(--a)"), None);
+    assert_eq!(map.substitute("var(--b)"), None);
+    assert_eq!(map.substitute("var(--c, 0)").as_deref(), Some("0"));
+    // `--d` references the cycle, but is not a part of it.
+    assert_eq!(map.substitute("var(--d)").as_deref(), Some("2"));
+    assert_eq!(map.substitute("var(--e)").as_deref(), Some("4"));
+}
+
+#[test]
+fn growth() {
+    let declarations = properties(
+        "--a: xxxxxxxxxxxxxxxx; --b: var(--a) var(--a) var(--a) var(--a); \
+         --c: var(--b) var(--b) var(--b) var(--b); --d: var(--c) var(--c) var(--c) var(--c); \
+         --e: var(--d) var(--d) var(--d) var(--d); --f: var(--e) var(--e) var(--e) var(--e); \
+         --g: var(--f) var(--f) var(--f) var(--f); --h: var(--g) var(--g) var(--g) var(--g); \
+         --i: var(--h) var(--h) var(--h) var(--h); --j: var(--i) var(--i) var(--i) var(--i)",
+    );
+    let map = CustomProperties::from_declarations(&declarations);
+    assert!(map.substitute("var(--e)").is_some());
+    assert_eq!(map.substitute("var(--j)"), None);
+}
+
+#[test]
+fn fan_out() {
+    // Each property references the previous one twice, so resolving it without caching
+    // would take exponential time.
+    let mut text = String::from("--v0: x;");
+    for i in 1..=40 {
+        text.push_str(&format!(
+            "--v{i}: var(--v{}, a) var(--v{}, a);",
+            i - 1,
+            i - 1
+        ));
+    }
+
+    let declarations = properties(&text);
+    let map = CustomProperties::from_declarations(&declarations);
+    assert_eq!(map.substitute("var(--v2)").as_deref(), Some("x x x x"));
+    assert_eq!(map.substitute("var(--v10)").map(|v| v.len()), Some(2047));
+
+    // Once a value is too long, the fallbacks are not used, so longer chains stay invalid.
+    let valid: Vec<_> = (0..=40)
+        .map(|i| map.substitute(&format!("var(--v{i})")).is_some())
+        .collect();
+    assert_eq!(valid.iter().position(|v| !v), Some(20));
+    assert!(valid[20..].iter().all(|v| !v));
+    assert_eq!(map.substitute("var(--v40, fallback)"), None);
+}
+
+struct Env;
+
+impl Environment for Env {
+    fn variable(&self, name: &str) -> Option<&str> {
+        match name {
+            "safe-area-inset-top" => Some("20px"),
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn env() {
+    let mut map = CustomProperties::new();
+    map.insert("--top", "env(safe-area-inset-top, 0px)");
+    assert_eq!(
+        map.substitute_with_env("var(--top)", &Env).as_deref(),
+        Some("20px")
+    );
+    assert_eq!(map.substitute("var(--top)").as_deref(), Some("0px"));
+    assert_eq!(map.substitute_with_env("env(unknown)", &Env), None);
+    assert_eq!(
+        map.substitute_with_env("env(unknown, var(--top))", &Env)
+            .as_deref(),
+        Some("20px")
+    );
+}
+
+#[test]
+#[cfg(any(feature = "std", feature = "libm"))]
+fn declaration_values() {
+    let style = StyleSheet::parse("a { --fg: rgb(0 0 255); color: var(--fg) }");
+    let declarations = &style.rules[0].declarations;
+    let map = CustomProperties::from_declarations(declarations);
+    let value = map.substitute(declarations[1].value).unwrap();
+    assert_eq!(
+        Color::parse(&value),
+        Some(Color::Rgba(Rgba::new(0, 0, 255, 255)))
+    );
+}